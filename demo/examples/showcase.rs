@@ -0,0 +1,6 @@
+//! Examples are shipped to users as well, so they desrve a spellcheck.
+
+/// Call all the things the libary provides.
+fn main() {
+    println!("Showcase");
+}
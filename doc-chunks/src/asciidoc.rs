@@ -0,0 +1,539 @@
+//! AsciiDoc documents.
+//!
+//! Paragraphs, section titles, block titles and table cells are extracted line
+//! based, there is no full AsciiDoc parser involved. Source, listing, literal,
+//! passthrough and comment blocks, indented literal paragraphs, attribute
+//! entries, block attributes and block macros like `include::` are skipped.
+//!
+//! The inline markup of the extracted paragraphs is erased by
+//! [`erase_inline`], only the text of links and cross references is kept.
+
+use crate::markdown::SourceRange;
+use crate::{Range, TextLine};
+
+use indexmap::IndexMap;
+
+/// Block delimiter characters, a delimiter line consists of at least four.
+const DELIMITERS: &str = "-.+/=*_";
+
+/// Delimiter characters of the blocks which do not contain prose, listing,
+/// literal, passthrough and comment blocks.
+const VERBATIM_DELIMITERS: &str = "-.+/";
+
+/// Determine the character of a block delimiter line, i.e. `-` for `----`.
+fn delimiter(line: &str) -> Option<char> {
+    let c = line.chars().next()?;
+    if line.len() >= 4 && DELIMITERS.contains(c) && line.chars().all(|x| x == c) {
+        Some(c)
+    } else {
+        None
+    }
+}
+
+/// An attribute entry, i.e. `:toc: left` or `:!sectnums:`.
+fn is_attribute_entry(line: &str) -> bool {
+    line.strip_prefix(':')
+        .and_then(|rest| rest.split_once(':'))
+        .map_or(false, |(name, _value)| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '!')
+        })
+}
+
+/// A block attribute list or anchor, i.e. `[source,rust]` or `[[anchor]]`.
+fn is_block_attribute(line: &str) -> bool {
+    line.starts_with('[') && line.ends_with(']')
+}
+
+/// A block macro, i.e. `include::chapter.adoc[]` or `ifdef::env-github[]`.
+fn is_block_macro(line: &str) -> bool {
+    line.split_once("::").map_or(false, |(name, _target)| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            && line.ends_with(']')
+    })
+}
+
+/// A section title, i.e. `== Usage` or `## Usage`, returns the level and the
+/// byte offset the title starts at.
+fn section_title(line: &str) -> Option<(usize, usize)> {
+    let marker = line.chars().next().filter(|c| *c == '=' || *c == '#')?;
+    let level = line.chars().take_while(|c| *c == marker).count();
+    let rest = &line[level..];
+    let title = rest.trim_start();
+    if level <= 6 && rest.starts_with(' ') && !title.is_empty() {
+        Some((level, line.len() - title.len()))
+    } else {
+        None
+    }
+}
+
+/// A cell specifier, i.e. `2+`, `.3+^` or `a`, which precedes the `|` of a
+/// cell.
+fn is_cell_spec(spec: &str) -> bool {
+    let operators = spec
+        .strip_suffix(|c: char| "adehlmsv".contains(c))
+        .unwrap_or(spec);
+    operators
+        .chars()
+        .all(|c| c.is_ascii_digit() || ".*+<>^".contains(c))
+}
+
+/// The cells of a table row, i.e. `| Name 2+| Description`, each with the
+/// column it starts at. A row may be split across multiple lines, a leading
+/// part without a `|` continues the cell of the previous line.
+fn table_cells(lineno: usize, line: &str) -> Vec<TextLine<'_>> {
+    let mut acc = Vec::new();
+    let mut start = 0;
+    let separators = line.match_indices('|').map(|(idx, _)| idx);
+    for end in separators.chain(std::iter::once(line.len())) {
+        let mut cell = &line[start..end];
+        if end < line.len() {
+            // the specifier of the following cell
+            let spec = cell.rsplit(char::is_whitespace).next().unwrap_or("");
+            if is_cell_spec(spec) {
+                cell = &cell[..(cell.len() - spec.len())];
+            }
+        }
+        let text = cell.trim();
+        if !text.is_empty() {
+            let offset = start + cell.len() - cell.trim_start().len();
+            acc.push((lineno, line[..offset].chars().count(), text));
+        }
+        start = end + 1;
+    }
+    acc
+}
+
+/// Extract the paragraphs of `content`, section and block titles as well as
+/// table cells are paragraphs on their own.
+pub(crate) fn extract_paragraphs(content: &str) -> Vec<Vec<TextLine<'_>>> {
+    fn flush<'a>(acc: &mut Vec<Vec<TextLine<'a>>>, paragraph: &mut Vec<TextLine<'a>>) {
+        if !paragraph.is_empty() {
+            acc.push(std::mem::take(paragraph));
+        }
+    }
+
+    let mut acc = Vec::new();
+    let mut paragraph = Vec::new();
+    // the closing delimiter of the verbatim block the current line is part of
+    let mut verbatim = None;
+    // the author and revision lines following the document title
+    let mut header = false;
+    let mut table = false;
+    for (idx, line) in content.lines().enumerate() {
+        let lineno = idx + 1;
+        let trimmed = line.trim_end();
+        if let Some(closing) = verbatim {
+            if trimmed == closing {
+                verbatim = None;
+            }
+            continue;
+        }
+        if table {
+            if trimmed == "|===" {
+                table = false;
+            } else {
+                acc.extend(
+                    table_cells(lineno, trimmed)
+                        .into_iter()
+                        .map(|cell| vec![cell]),
+                );
+            }
+            continue;
+        }
+        if trimmed.is_empty() {
+            header = false;
+            flush(&mut acc, &mut paragraph);
+            continue;
+        }
+        if header {
+            continue;
+        }
+        if let Some(c) = delimiter(trimmed) {
+            flush(&mut acc, &mut paragraph);
+            if VERBATIM_DELIMITERS.contains(c) {
+                verbatim = Some(trimmed);
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            flush(&mut acc, &mut paragraph);
+            verbatim = Some("```");
+            continue;
+        }
+        if trimmed == "|===" {
+            flush(&mut acc, &mut paragraph);
+            table = true;
+            continue;
+        }
+        if trimmed.starts_with("//")
+            || trimmed == "+"
+            || trimmed == "--"
+            || line.starts_with(char::is_whitespace)
+            || is_attribute_entry(trimmed)
+            || is_block_attribute(trimmed)
+            || is_block_macro(trimmed)
+        {
+            flush(&mut acc, &mut paragraph);
+            continue;
+        }
+        if let Some((level, offset)) = section_title(trimmed) {
+            flush(&mut acc, &mut paragraph);
+            header = level == 1 && acc.is_empty();
+            acc.push(vec![(
+                lineno,
+                trimmed[..offset].chars().count(),
+                &trimmed[offset..],
+            )]);
+            continue;
+        }
+        match trimmed.strip_prefix('.') {
+            Some(title) if title.starts_with(|c: char| c != '.' && !c.is_whitespace()) => {
+                flush(&mut acc, &mut paragraph);
+                acc.push(vec![(lineno, 1, title)]);
+            }
+            _ => paragraph.push((lineno, 0, trimmed)),
+        }
+    }
+    flush(&mut acc, &mut paragraph);
+    acc
+}
+
+/// Inline macros and URL schemes whose text is prose, i.e. the `text` of
+/// `link:https://example.com[text]`. The text of all other inline macros, i.e.
+/// `kbd:[Ctrl+C]` or `image:logo.png[Logo]`, is erased.
+const PROSE_MACROS: &[&str] = &[
+    "link", "xref", "mailto", "footnote", "http", "https", "ftp", "irc",
+];
+
+/// Find the first position at or after `from` where `needle` starts.
+fn find(chars: &[char], from: usize, needle: &[char]) -> Option<usize> {
+    (from..chars.len()).find(|idx| chars[*idx..].starts_with(needle))
+}
+
+/// Match the inline element starting at `idx`, returns the char range of the
+/// text to keep, if any, and the position after the element.
+fn inline_element(chars: &[char], idx: usize) -> Option<(Option<Range>, usize)> {
+    let is_name = |c: &char| c.is_ascii_alphanumeric() || *c == '_' || *c == '-';
+    match chars[idx] {
+        // attribute references, i.e. `{project-name}`
+        '{' => {
+            let end = idx + 1 + chars[(idx + 1)..].iter().take_while(|c| is_name(c)).count();
+            if end > idx + 1 && chars.get(end) == Some(&'}') {
+                Some((None, end + 1))
+            } else {
+                None
+            }
+        }
+        // cross references, i.e. `<<anchor>>` or `<<anchor,text>>`
+        '<' if chars.get(idx + 1) == Some(&'<') => {
+            let end = find(chars, idx + 2, &['>', '>'])?;
+            let text = (idx + 2..end)
+                .find(|idx| chars[*idx] == ',')
+                .map(|comma| (comma + 1)..end);
+            Some((text, end + 2))
+        }
+        // monospace, i.e. `cargo spellcheck`
+        '`' => {
+            let end = find(chars, idx + 1, &['`'])?;
+            Some((None, end + 1))
+        }
+        c if c.is_ascii_alphabetic() => {
+            if idx > 0 && (chars[idx - 1].is_alphanumeric() || chars[idx - 1] == '_') {
+                return None;
+            }
+            let colon = idx + chars[idx..].iter().take_while(|c| is_name(c)).count();
+            if chars.get(colon) != Some(&':') {
+                return None;
+            }
+            let name = String::from_iter(&chars[idx..colon]);
+            let target = colon
+                + 1
+                + chars[(colon + 1)..]
+                    .iter()
+                    .take_while(|c| !c.is_whitespace() && **c != '[')
+                    .count();
+            if chars.get(target) == Some(&'[') {
+                // inline macros, i.e. `kbd:[F11]`, and URLs with a text
+                let end = find(chars, target + 1, &[']'])?;
+                let text = if PROSE_MACROS.contains(&name.as_str()) {
+                    Some((target + 1)..end)
+                } else {
+                    None
+                };
+                Some((text, end + 1))
+            } else if chars[(colon + 1)..].starts_with(&['/', '/']) {
+                // bare URLs, without trailing punctuation
+                let end = (colon..target)
+                    .rev()
+                    .find(|idx| !".,;:!?)".contains(chars[*idx]))
+                    .map_or(target, |idx| idx + 1);
+                Some((None, end))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Erase the inline markup of an AsciiDoc paragraph, i.e. attribute
+/// references, inline macros, URLs and monospace text.
+///
+/// Returns the plain text and the mapping of its char ranges to those of
+/// `content`.
+pub(crate) fn erase_inline(content: &str) -> (String, IndexMap<Range, SourceRange>) {
+    let chars = Vec::from_iter(content.chars());
+    // the char ranges of `content` which are kept
+    let mut kept = Vec::<Range>::new();
+    let keep = |range: Range, kept: &mut Vec<Range>| {
+        // split at line breaks, which are not covered by any span
+        let mut start = range.start;
+        for idx in range.clone() {
+            if chars[idx] == '\n' {
+                kept.push(start..idx);
+                kept.push(idx..(idx + 1));
+                start = idx + 1;
+            }
+        }
+        kept.push(start..range.end);
+    };
+    let mut idx = 0;
+    let mut start = 0;
+    while idx < chars.len() {
+        match inline_element(&chars, idx) {
+            Some((text, end)) => {
+                keep(start..idx, &mut kept);
+                if let Some(text) = text {
+                    keep(text, &mut kept);
+                }
+                idx = end;
+                start = end;
+            }
+            None => idx += 1,
+        }
+    }
+    keep(start..chars.len(), &mut kept);
+
+    let mut plain = String::with_capacity(content.len());
+    let mut mapping = IndexMap::with_capacity(kept.len());
+    let mut cursor = 0;
+    for range in kept.into_iter().filter(|range| !range.is_empty()) {
+        plain.extend(&chars[range.clone()]);
+        if chars[range.start] != '\n' {
+            mapping.insert(
+                cursor..(cursor + range.len()),
+                SourceRange::Direct(range.clone()),
+            );
+        }
+        cursor += range.len();
+    }
+    (plain, mapping)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paragraphs() {
+        const CONTENT: &str = r#"= Document Title
+Jane Doe <jane@example.com>
+v1.0, 2024-01-01
+:toc: left
+
+// a comment
+== First Section
+
+A paragraph
+spanning two lines.
+
+[source,rust]
+----
+fn main() {}
+----
+
+.A block title
+include::other.adoc[]
+
+  an indented literal
+
+|===
+| Cell | Cell
+|===
+
+====
+An example.
+====
+"#;
+        assert_eq!(
+            extract_paragraphs(CONTENT),
+            vec![
+                vec![(1, 2, "Document Title")],
+                vec![(7, 3, "First Section")],
+                vec![(9, 0, "A paragraph"), (10, 0, "spanning two lines.")],
+                vec![(17, 1, "A block title")],
+                vec![(23, 2, "Cell")],
+                vec![(23, 9, "Cell")],
+                vec![(27, 0, "An example.")],
+            ]
+        );
+    }
+
+    #[test]
+    fn tables() {
+        const CONTENT: &str = r#"[cols="1,2"]
+|===
+| Name | Description
+
+| `foo` 2+a| Does a thing,
+spanning two lines.
+|===
+"#;
+        assert_eq!(
+            extract_paragraphs(CONTENT),
+            vec![
+                vec![(3, 2, "Name")],
+                vec![(3, 9, "Description")],
+                vec![(5, 2, "`foo`")],
+                vec![(5, 13, "Does a thing,")],
+                vec![(6, 0, "spanning two lines.")],
+            ]
+        );
+    }
+
+    /// The plain text of `content` and the raw text each fragment of it maps
+    /// to.
+    fn erased(content: &str) -> (String, Vec<String>) {
+        let (plain, mapping) = erase_inline(content);
+        let fragments = Vec::from_iter(mapping.into_iter().map(|(plain_range, raw)| {
+            let raw = String::from_iter(content.chars().skip(raw.start).take(raw.len()));
+            assert_eq!(
+                String::from_iter(
+                    plain
+                        .chars()
+                        .skip(plain_range.start)
+                        .take(plain_range.len())
+                ),
+                raw
+            );
+            raw
+        }));
+        (plain, fragments)
+    }
+
+    #[test]
+    fn erase_link_macro() {
+        assert_eq!(
+            erased("See link:https://example.com[the homepage] for more."),
+            (
+                "See the homepage for more.".to_owned(),
+                vec![
+                    "See ".to_owned(),
+                    "the homepage".to_owned(),
+                    " for more.".to_owned()
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn erase_url() {
+        assert_eq!(
+            erased("See https://example.com[the homepage] or https://example.com."),
+            (
+                "See the homepage or .".to_owned(),
+                vec![
+                    "See ".to_owned(),
+                    "the homepage".to_owned(),
+                    " or ".to_owned(),
+                    ".".to_owned()
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn erase_cross_reference() {
+        assert_eq!(
+            erased("Read <<usage,the usage>> and <<install>>."),
+            (
+                "Read the usage and .".to_owned(),
+                vec![
+                    "Read ".to_owned(),
+                    "the usage".to_owned(),
+                    " and ".to_owned(),
+                    ".".to_owned()
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn erase_attribute_reference() {
+        assert_eq!(
+            erased("Install {project-name} {version}\nfrom {url}."),
+            (
+                "Install  \nfrom .".to_owned(),
+                vec![
+                    "Install ".to_owned(),
+                    " ".to_owned(),
+                    "from ".to_owned(),
+                    ".".to_owned()
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn erase_passthrough_macro() {
+        assert_eq!(
+            erased("Keep pass:[<u>undrline</u>] as is."),
+            (
+                "Keep  as is.".to_owned(),
+                vec!["Keep ".to_owned(), " as is.".to_owned()]
+            )
+        );
+    }
+
+    #[test]
+    fn erase_keyboard_macro() {
+        assert_eq!(
+            erased("Press kbd:[Ctrl+Shft+F] to search."),
+            (
+                "Press  to search.".to_owned(),
+                vec!["Press ".to_owned(), " to search.".to_owned()]
+            )
+        );
+    }
+
+    #[test]
+    fn erase_image_macro() {
+        assert_eq!(
+            erased("The logo image:logo.png[] and image:icon.png[Icn, 16]."),
+            (
+                "The logo  and .".to_owned(),
+                vec!["The logo ".to_owned(), " and ".to_owned(), ".".to_owned()]
+            )
+        );
+    }
+
+    #[test]
+    fn keep_prose() {
+        assert_eq!(
+            erased("Note: a ratio of 1:2, key:value and `code`."),
+            (
+                "Note: a ratio of 1:2, key:value and .".to_owned(),
+                vec![
+                    "Note: a ratio of 1:2, key:value and ".to_owned(),
+                    ".".to_owned()
+                ]
+            )
+        );
+    }
+}
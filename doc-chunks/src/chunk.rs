@@ -16,12 +16,21 @@ use crate::{
 use crate::{Ignores, PlainOverlay};
 
 /// Definition of the source of a checkable chunk
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum ContentOrigin {
     /// A `Cargo.toml` manifest that contains a `description` field.
     CargoManifestDescription(PathBuf),
+    /// The comments documenting the features of a `Cargo.toml` manifest.
+    CargoManifestFeatures(PathBuf),
     /// A common mark file at given path.
     CommonMarkFile(PathBuf),
+    /// A plain text file at given path, i.e. a `LICENSE` or `CHANGELOG`.
+    PlainTextFile(PathBuf),
+    /// An AsciiDoc file at given path.
+    AsciiDocFile(PathBuf),
+    /// A source file in a language other than rust, i.e. a C header, of
+    /// which only the comments are checked.
+    ForeignSourceFile(PathBuf),
     /// A rustdoc comment, part of file reference by path in span.
     RustDocTest(PathBuf, Span),
     /// Full rust source file.
@@ -42,7 +51,11 @@ impl ContentOrigin {
     pub fn as_path(&self) -> &Path {
         match self {
             Self::CargoManifestDescription(path) => path.as_path(),
+            Self::CargoManifestFeatures(path) => path.as_path(),
             Self::CommonMarkFile(path) => path.as_path(),
+            Self::PlainTextFile(path) => path.as_path(),
+            Self::AsciiDocFile(path) => path.as_path(),
+            Self::ForeignSourceFile(path) => path.as_path(),
             Self::RustDocTest(path, _) => path.as_path(),
             Self::RustSourceFile(path) => path.as_path(),
             Self::TestEntityCommonMark => {
@@ -122,6 +135,16 @@ impl CheckableChunk {
         }
     }
 
+    /// Move all spans down by `lines`, used for content embedded in another
+    /// document.
+    pub(crate) fn offset_lines(mut self, lines: usize) -> Self {
+        for span in self.source_mapping.values_mut() {
+            span.start.line += lines;
+            span.end.line += lines;
+        }
+        self
+    }
+
     /// Find which part of the range maps to which span. Note that Range can
     /// very well be split into multiple fragments where each of them can be
     /// mapped to a potentially non-continuous span.
@@ -386,6 +409,7 @@ impl From<Clusters> for Vec<CheckableChunk> {
             .set
             .into_iter()
             .map(CheckableChunk::from_literalset)
+            .chain(clusters.synthetic)
             .collect::<Vec<_>>()
     }
 }
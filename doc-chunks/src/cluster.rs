@@ -9,7 +9,369 @@ use super::{LiteralSet, TokenTree, TrimmedLiteral};
 use crate::developer::extract_developer_comments;
 
 use crate::errors::*;
-use crate::Span;
+use crate::util::load_span_from;
+use crate::{CheckableChunk, CommentVariant, LineColumn, Span};
+use indexmap::IndexMap;
+
+/// Macros with a user facing message, and the index of the argument carrying
+/// the message.
+const MESSAGE_MACROS: &[(&str, usize)] = &[
+    ("panic", 0),
+    ("unreachable", 0),
+    ("todo", 0),
+    ("unimplemented", 0),
+    ("assert", 1),
+    ("debug_assert", 1),
+    ("assert_eq", 2),
+    ("assert_ne", 2),
+    ("debug_assert_eq", 2),
+    ("debug_assert_ne", 2),
+    ("error", 0),
+    ("warn", 0),
+    ("info", 0),
+    ("debug", 0),
+    ("trace", 0),
+];
+
+/// Attributes of `clap` derived command line interfaces, which carry help
+/// texts.
+const HELP_ATTRIBUTES: &[&str] = &["command", "arg", "clap", "structopt"];
+
+/// Keys of [`HELP_ATTRIBUTES`] whose string literal value is a help text.
+const HELP_KEYS: &[&str] = &[
+    "about",
+    "long_about",
+    "help",
+    "long_help",
+    "before_help",
+    "after_help",
+    "before_long_help",
+    "after_long_help",
+];
+
+/// Marker comment, placed on the line above a `const` or `static` item, to
+/// check the string literals of its value.
+///
+/// ```rust
+/// // spellcheck: check
+/// const USAGE: &str = "Checks all the things.";
+/// ```
+pub const STRING_CONSTANT_MARKER: &str = "spellcheck: check";
+
+/// Check if the item starting at 1-based `line` is preceded by the
+/// [`STRING_CONSTANT_MARKER`], attributes and doc comments in between are
+/// skipped.
+fn is_marked_item(source: &str, line: usize) -> bool {
+    let preceding = Vec::from_iter(source.lines().take(line.saturating_sub(1)));
+    preceding
+        .into_iter()
+        .rev()
+        .map(str::trim)
+        .find(|line| !(line.starts_with("#[") || line.starts_with("///")))
+        .and_then(|line| line.strip_prefix("//"))
+        .map(|comment| comment.trim() == STRING_CONSTANT_MARKER)
+        .unwrap_or_default()
+}
+
+/// Check if the literal is a plain or raw string literal.
+fn is_string_literal(literal: &proc_macro2::Literal) -> bool {
+    let repr = literal.to_string();
+    repr.starts_with('"') || repr.starts_with("r\"") || repr.starts_with("r#")
+}
+
+/// Collect all string literals of a stream, including those of nested groups.
+fn string_literals(stream: proc_macro2::TokenStream, acc: &mut Vec<proc_macro2::Literal>) {
+    for tree in stream {
+        match tree {
+            TokenTree::Group(group) => string_literals(group.stream(), acc),
+            TokenTree::Literal(literal) if is_string_literal(&literal) => acc.push(literal),
+            _ => {}
+        }
+    }
+}
+
+/// Collect the help text literals of the content of an attribute, i.e.
+/// `arg(long, help = "Foo")`.
+fn help_literals(attribute: proc_macro2::TokenStream, acc: &mut Vec<proc_macro2::Literal>) {
+    let trees = Vec::from_iter(attribute);
+    let arguments = match (trees.first(), trees.get(1)) {
+        (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(group)))
+            if HELP_ATTRIBUTES.iter().any(|name| ident == name)
+                && group.delimiter() == proc_macro2::Delimiter::Parenthesis =>
+        {
+            Vec::from_iter(group.stream())
+        }
+        _ => return,
+    };
+    for window in arguments.windows(3) {
+        match window {
+            [TokenTree::Ident(key), TokenTree::Punct(eq), TokenTree::Literal(literal)]
+                if eq.as_char() == '='
+                    && HELP_KEYS.iter().any(|name| key == name)
+                    && is_string_literal(literal) =>
+            {
+                acc.push(literal.clone())
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Check if the item following its attributes `rest` is public, `inherited`
+/// applies to items without any visibility, i.e. enum variants.
+///
+/// Restricted visibilities such as `pub(crate)` are not considered public.
+fn is_public_item(rest: &[TokenTree], inherited: bool) -> bool {
+    let mut iter = rest.iter().peekable();
+    // skip further attributes
+    while let Some(TokenTree::Punct(punct)) = iter.peek() {
+        if punct.as_char() != '#' {
+            break;
+        }
+        iter.next();
+        iter.next();
+    }
+    match (iter.next(), iter.next()) {
+        (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(group))) if ident == "pub" => {
+            group.delimiter() != proc_macro2::Delimiter::Parenthesis
+        }
+        (Some(TokenTree::Ident(ident)), _) if ident == "pub" => true,
+        _ => inherited,
+    }
+}
+
+/// Check if the items of the group following `header` inherit their
+/// visibility from the surrounding item, as it is the case for variants of a
+/// public enum or the items of a trait implementation.
+fn is_inherited_visibility(header: &[TokenTree], inherited: bool) -> bool {
+    // the header starts after the previous item
+    let start = header
+        .iter()
+        .rposition(|tree| match tree {
+            TokenTree::Punct(punct) => punct.as_char() == ';',
+            TokenTree::Group(group) => group.delimiter() == proc_macro2::Delimiter::Brace,
+            _ => false,
+        })
+        .map(|idx| idx + 1)
+        .unwrap_or_default();
+    let header = &header[start..];
+    let has = |keyword: &str| {
+        header
+            .iter()
+            .any(|tree| matches!(tree, TokenTree::Ident(ident) if ident == keyword))
+    };
+    if has("enum") || has("trait") {
+        let pub_idx = header
+            .iter()
+            .position(|tree| matches!(tree, TokenTree::Ident(ident) if ident == "pub"));
+        pub_idx
+            .map(|idx| is_public_item(&header[idx..], false))
+            .unwrap_or_default()
+    } else if has("impl") {
+        has("for")
+    } else if has("struct") || has("union") || has("mod") || has("fn") || has("extern") {
+        false
+    } else {
+        inherited
+    }
+}
+
+/// Split macro arguments at the top level `,`.
+///
+/// The commas of turbofish generics, i.e. `foo::<A, B>()`, are not top level,
+/// while a `<` without a preceding `::` is a comparison, i.e. the two
+/// arguments of `assert!(a < b, c > d)`.
+fn macro_arguments(stream: proc_macro2::TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut args = vec![Vec::new()];
+    // the nesting depth of turbofish generics
+    let mut generics = 0_usize;
+    for tree in stream {
+        let arg = args
+            .last_mut()
+            .expect("There is always at least one argument. qed");
+        if let TokenTree::Punct(ref punct) = tree {
+            let after =
+                |c: char| matches!(arg.last(), Some(TokenTree::Punct(prev)) if prev.as_char() == c);
+            match punct.as_char() {
+                ',' if generics == 0 => {
+                    args.push(Vec::new());
+                    continue;
+                }
+                '<' if generics > 0 || after(':') => generics += 1,
+                // but not the `->` of a function pointer type
+                '>' if generics > 0 && !after('-') => generics -= 1,
+                _ => {}
+            }
+        }
+        arg.push(tree);
+    }
+    args
+}
+
+/// Extract the string literal of the macro arguments at position `argument`.
+///
+/// Leading `key: value` arguments, i.e. `target: "foo"` of the `log` macros, are
+/// not counted.
+fn message_literal(
+    stream: proc_macro2::TokenStream,
+    argument: usize,
+) -> Option<proc_macro2::Literal> {
+    let arg = macro_arguments(stream)
+        .into_iter()
+        .skip_while(|arg| {
+            matches!(arg.as_slice(), [TokenTree::Ident(_), TokenTree::Punct(punct), ..]
+                if punct.as_char() == ':' && punct.spacing() == proc_macro2::Spacing::Alone)
+        })
+        .nth(argument)?;
+    match arg.as_slice() {
+        [TokenTree::Literal(literal)] => {
+            let repr = literal.to_string();
+            if repr.starts_with('"') || repr.starts_with("r\"") || repr.starts_with("r#") {
+                Some(literal.clone())
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// A part of the content of a doc attribute built by a macro, along with the
+/// span of the source it appears in verbatim, if any.
+type Piece = (String, Option<Span>);
+
+/// The span from `start` to the exclusive `end`, if it is on a single line and
+/// the source text equals `content`.
+fn verbatim_span(source: &str, start: LineColumn, end: LineColumn, content: &str) -> Option<Span> {
+    if start.line != end.line || content.is_empty() || end.column <= start.column {
+        return None;
+    }
+    let span = Span {
+        start,
+        end: LineColumn {
+            line: end.line,
+            column: end.column - 1,
+        },
+    };
+    load_span_from(source.as_bytes(), span)
+        .ok()
+        .filter(|verbatim| verbatim == content)
+        .map(|_| span)
+}
+
+/// The value of a literal argument of `concat!(..)`.
+fn literal_piece(source: &str, literal: &proc_macro2::Literal) -> Option<Piece> {
+    let piece = match syn::Lit::new(literal.clone()) {
+        syn::Lit::Str(s) => {
+            let repr = literal.to_string();
+            // `"` or `r#"` and alike
+            let hashes = repr
+                .strip_prefix('r')
+                .map(|rest| rest.chars().take_while(|&c| c == '#').count() + 1)
+                .unwrap_or_default();
+            let (start, end) = (literal.span().start(), literal.span().end());
+            let value = s.value();
+            let span = verbatim_span(
+                source,
+                LineColumn {
+                    line: start.line,
+                    column: start.column + 1 + hashes,
+                },
+                LineColumn {
+                    line: end.line,
+                    column: end.column.saturating_sub(1 + hashes.saturating_sub(1)),
+                },
+                &value,
+            );
+            (value, span)
+        }
+        syn::Lit::Char(c) => (c.value().to_string(), None),
+        syn::Lit::Int(i) => (i.base10_digits().to_owned(), None),
+        syn::Lit::Float(f) => (f.base10_digits().to_owned(), None),
+        _ => return None,
+    };
+    Some(piece)
+}
+
+/// Best-effort evaluation of a `concat!(..)` or `stringify!(..)` invocation
+/// building a doc attribute. `None` if any argument is neither a literal nor
+/// one of those macros, i.e. `env!("CARGO_PKG_NAME")`.
+fn evaluate_doc_macro(
+    source: &str,
+    name: &str,
+    tokens: proc_macro2::TokenStream,
+    acc: &mut Vec<Piece>,
+) -> Option<()> {
+    match name {
+        "stringify" => {
+            let trees = Vec::from_iter(tokens.clone());
+            let content = tokens.to_string();
+            let span = match (trees.first(), trees.last()) {
+                (Some(first), Some(last)) => {
+                    verbatim_span(source, first.span().start(), last.span().end(), &content)
+                }
+                _ => None,
+            };
+            acc.push((content, span));
+        }
+        "concat" => {
+            let trees = Vec::from_iter(tokens);
+            for arg in trees
+                .split(|tree| matches!(tree, TokenTree::Punct(punct) if punct.as_char() == ','))
+            {
+                match arg {
+                    [TokenTree::Literal(literal)] => acc.push(literal_piece(source, literal)?),
+                    [TokenTree::Ident(ident)] if ident == "true" || ident == "false" => {
+                        acc.push((ident.to_string(), None))
+                    }
+                    // path qualified ones like `core::stringify!` included
+                    [.., TokenTree::Ident(mac), TokenTree::Punct(bang), TokenTree::Group(args)]
+                        if bang.as_char() == '!' =>
+                    {
+                        evaluate_doc_macro(source, &mac.to_string(), args.stream(), acc)?
+                    }
+                    // trailing comma
+                    [] => {}
+                    _ => return None,
+                }
+            }
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Evaluate the macro building a doc attribute into a chunk of
+/// [`CommentVariant::Synthetic`], the parts appearing verbatim in the source
+/// are mapped to their spans.
+///
+/// `None` if the macro can not be evaluated or no part can be mapped.
+fn synthetic_chunk(source: &str, mac: &Macro) -> Option<CheckableChunk> {
+    let name = mac.path.segments.last()?.ident.to_string();
+    let mut pieces = Vec::new();
+    evaluate_doc_macro(source, &name, mac.tokens.clone(), &mut pieces)?;
+
+    let mut content = String::new();
+    let mut source_mapping = IndexMap::with_capacity(pieces.len());
+    let mut cursor = 0usize;
+    for (piece, span) in pieces {
+        let len = piece.chars().count();
+        if let Some(span) = span {
+            source_mapping.insert(cursor..(cursor + len), span);
+        }
+        content.push_str(&piece);
+        cursor += len;
+    }
+    if source_mapping.is_empty() {
+        log::debug!("Skipping doc attribute {name}!(..), no part of it appears verbatim");
+        return None;
+    }
+    Some(CheckableChunk::from_string(
+        content,
+        source_mapping,
+        CommentVariant::Synthetic,
+    ))
+}
 
 mod kw {
     syn::custom_keyword!(doc);
@@ -55,16 +417,42 @@ impl syn::parse::Parse for DocComment {
     }
 }
 
+/// The doc attributes of a `cfg_attr`, i.e. `doc = "Text"` of
+/// `#[cfg_attr(feature = "foo", doc = "Text")]`, which are rendered if the
+/// predicate holds, like the feature docs shown with `doc_cfg`.
+fn cfg_attr_doc_comments(group: &proc_macro2::Group) -> Vec<DocComment> {
+    let mut trees = group.stream().into_iter();
+    match (trees.next(), trees.next(), trees.next()) {
+        (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(args)), None)
+            if ident == "cfg_attr" =>
+        {
+            let args = Vec::from_iter(args.stream());
+            args.split(|tree| matches!(tree, TokenTree::Punct(punct) if punct.as_char() == ','))
+                // the first one is the predicate
+                .skip(1)
+                .filter_map(|attr| syn::parse2::<DocComment>(attr.iter().cloned().collect()).ok())
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
 /// Cluster comments together, such they appear as continuous text blocks.
 #[derive(Debug)]
 pub struct Clusters {
     pub(crate) set: Vec<LiteralSet>,
+    /// Chunks of the doc attributes built by macros, see
+    /// [`CommentVariant::Synthetic`].
+    pub(crate) synthetic: Vec<CheckableChunk>,
 }
 
 impl Clusters {
     /// Only works if the file is processed line by line, otherwise requires a
     /// adjacency list.
     fn process_literal(&mut self, source: &str, comment: DocComment) -> Result<()> {
+        if let DocContent::Macro(ref mac) = comment.content {
+            self.synthetic.extend(synthetic_chunk(source, mac));
+        }
         let span = Span::from(comment.content.span());
         let trimmed_literal = match comment.content {
             DocContent::LitStr(_s) => TrimmedLiteral::load_from(source, span)?,
@@ -93,22 +481,173 @@ impl Clusters {
         source: &str,
         stream: proc_macro2::TokenStream,
     ) -> Result<()> {
-        let iter = stream.into_iter();
-        for tree in iter {
+        self.parse_token_tree_filtered(source, stream, None)
+    }
+
+    /// Parse a stream and associate the found literals, with `public` set only
+    /// the doc comments of public items are considered. The inner value tells
+    /// if items without visibility within the stream are public.
+    ///
+    /// Inner doc comments, `//!`, are always considered.
+    fn parse_token_tree_filtered(
+        &mut self,
+        source: &str,
+        stream: proc_macro2::TokenStream,
+        public: Option<bool>,
+    ) -> Result<()> {
+        let trees = Vec::from_iter(stream);
+        for (idx, tree) in trees.iter().enumerate() {
             if let TokenTree::Group(group) = tree {
-                if let Ok(comment) = syn::parse2::<DocComment>(group.stream()) {
-                    if let Err(e) = self.process_literal(source, comment) {
-                        log::error!("BUG: Failed to guarantee literal content/span integrity: {e}");
-                        continue;
+                let comments = match syn::parse2::<DocComment>(group.stream()) {
+                    Ok(comment) => vec![comment],
+                    Err(_) => cfg_attr_doc_comments(group),
+                };
+                if !comments.is_empty() {
+                    let inner = matches!(idx.checked_sub(1).map(|idx| &trees[idx]),
+                        Some(TokenTree::Punct(punct)) if punct.as_char() == '!');
+                    if let Some(inherited) = public {
+                        if !inner && !is_public_item(&trees[idx + 1..], inherited) {
+                            continue;
+                        }
+                    }
+                    for comment in comments {
+                        if let Err(e) = self.process_literal(source, comment) {
+                            log::error!(
+                                "BUG: Failed to guarantee literal content/span integrity: {e}"
+                            );
+                        }
                     }
                 } else {
-                    self.parse_token_tree(source, group.stream())?;
+                    let public =
+                        public.map(|inherited| is_inherited_visibility(&trees[..idx], inherited));
+                    self.parse_token_tree_filtered(source, group.stream(), public)?;
                 }
             };
         }
         Ok(())
     }
 
+    /// Helper function to find the message string literals of macro invocations
+    /// in a stream.
+    fn parse_string_literals(&mut self, source: &str, stream: proc_macro2::TokenStream) {
+        let trees = Vec::from_iter(stream);
+        for (idx, tree) in trees.iter().enumerate() {
+            let ident = match tree {
+                TokenTree::Group(group) => {
+                    self.parse_string_literals(source, group.stream());
+                    continue;
+                }
+                TokenTree::Ident(ident) => ident,
+                _ => continue,
+            };
+            let argument = match MESSAGE_MACROS.iter().find(|(name, _)| ident == name) {
+                Some((_, argument)) => *argument,
+                None => continue,
+            };
+            let group = match (trees.get(idx + 1), trees.get(idx + 2)) {
+                (Some(TokenTree::Punct(bang)), Some(TokenTree::Group(group)))
+                    if bang.as_char() == '!' =>
+                {
+                    group
+                }
+                _ => continue,
+            };
+            if let Some(literal) = message_literal(group.stream(), argument) {
+                let span = Span::from(literal.span());
+                match TrimmedLiteral::load_string_literal_from(source, span) {
+                    Ok(literal) if literal.len() > 0 => self.set.push(LiteralSet::from(literal)),
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::error!("BUG: Failed to guarantee literal content/span integrity: {e}")
+                    }
+                }
+            }
+        }
+    }
+
+    /// Helper function to find the help texts of [`HELP_ATTRIBUTES`] in a
+    /// stream.
+    fn parse_help_texts(&mut self, source: &str, stream: proc_macro2::TokenStream) {
+        let mut literals = Vec::new();
+        let trees = Vec::from_iter(stream);
+        for (idx, tree) in trees.iter().enumerate() {
+            let group = match tree {
+                TokenTree::Group(group) => group,
+                _ => continue,
+            };
+            // `#[..]` and `#![..]` alike
+            let attribute = group.delimiter() == proc_macro2::Delimiter::Bracket
+                && matches!(
+                    idx.checked_sub(1).map(|prev| &trees[prev]),
+                    Some(TokenTree::Punct(punct)) if punct.as_char() == '#' || punct.as_char() == '!'
+                );
+            if attribute {
+                help_literals(group.stream(), &mut literals);
+            } else {
+                self.parse_help_texts(source, group.stream());
+            }
+        }
+        for literal in literals {
+            let span = Span::from(literal.span());
+            match TrimmedLiteral::load_string_literal_from(source, span) {
+                Ok(literal) if literal.len() > 0 => self.set.push(LiteralSet::from(literal)),
+                Ok(_) => {}
+                Err(e) => {
+                    log::error!("BUG: Failed to guarantee literal content/span integrity: {e}")
+                }
+            }
+        }
+    }
+
+    /// Helper function to find the string literals of `const` and `static`
+    /// items marked with [`STRING_CONSTANT_MARKER`] in a stream.
+    fn parse_marked_constants(&mut self, source: &str, stream: proc_macro2::TokenStream) {
+        let trees = Vec::from_iter(stream);
+        for (idx, tree) in trees.iter().enumerate() {
+            let ident = match tree {
+                TokenTree::Group(group) => {
+                    self.parse_marked_constants(source, group.stream());
+                    continue;
+                }
+                TokenTree::Ident(ident) if ident == "const" || ident == "static" => ident,
+                _ => continue,
+            };
+            // skip `const fn` and alike, an item has a name followed by `:`
+            let name = match trees.get(idx + 1) {
+                Some(TokenTree::Ident(next)) if next == "mut" => idx + 2,
+                _ => idx + 1,
+            };
+            match (trees.get(name), trees.get(name + 1)) {
+                (Some(TokenTree::Ident(_)), Some(TokenTree::Punct(colon)))
+                    if colon.as_char() == ':' => {}
+                _ => continue,
+            }
+            // a visibility qualifier is on the same line as the keyword
+            if !is_marked_item(source, ident.span().start().line) {
+                continue;
+            }
+            let value = trees[name..]
+                .iter()
+                .skip_while(|tree| !matches!(tree, TokenTree::Punct(eq) if eq.as_char() == '='))
+                .skip(1)
+                .take_while(|tree| !matches!(tree, TokenTree::Punct(semi) if semi.as_char() == ';'))
+                .cloned()
+                .collect::<proc_macro2::TokenStream>();
+            let mut literals = Vec::new();
+            string_literals(value, &mut literals);
+            for literal in literals {
+                let span = Span::from(literal.span());
+                match TrimmedLiteral::load_string_literal_from(source, span) {
+                    Ok(literal) if literal.len() > 0 => self.set.push(LiteralSet::from(literal)),
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::error!("BUG: Failed to guarantee literal content/span integrity: {e}")
+                    }
+                }
+            }
+        }
+    }
+
     /// From the given source text, extracts developer comments to `LiteralSet`s
     /// and adds them to this `Clusters`
     fn parse_developer_comments(&mut self, source: &str) {
@@ -124,15 +663,38 @@ impl Clusters {
     }
 
     /// Load clusters from a `&str`. Optionally loads developer comments as
-    /// well.
+    /// well. String constants marked with [`STRING_CONSTANT_MARKER`] are
+    /// loaded along with the doc comments.
     pub fn load_from_str(source: &str, doc_comments: bool, dev_comments: bool) -> Result<Self> {
+        Self::load(source, doc_comments, false, dev_comments)
+    }
+
+    /// Load clusters from a `&str`, like [`Self::load_from_str`] but only
+    /// considers the doc comments of public items.
+    ///
+    /// The visibility is determined per item, the visibility of the
+    /// surrounding modules is not taken into account.
+    pub fn load_public_from_str(source: &str, dev_comments: bool) -> Result<Self> {
+        Self::load(source, true, true, dev_comments)
+    }
+
+    fn load(
+        source: &str,
+        doc_comments: bool,
+        public_only: bool,
+        dev_comments: bool,
+    ) -> Result<Self> {
         let mut chunk = Self {
             set: Vec::with_capacity(64),
+            synthetic: Vec::new(),
         };
         if doc_comments {
             let stream =
                 syn::parse_str::<proc_macro2::TokenStream>(source).map_err(Error::ParserFailure)?;
-            chunk.parse_token_tree(source, stream)?;
+            // the top level of a file is the module itself
+            let public = if public_only { Some(false) } else { None };
+            chunk.parse_token_tree_filtered(source, stream.clone(), public)?;
+            chunk.parse_marked_constants(source, stream);
         }
         if dev_comments {
             chunk.parse_developer_comments(source);
@@ -140,6 +702,34 @@ impl Clusters {
         chunk.ensure_sorted();
         Ok(chunk)
     }
+
+    /// Load clusters of the message string literals of macros like `panic!`,
+    /// `assert!` or `log::warn!` from a `&str`.
+    pub fn load_string_literals_from_str(source: &str) -> Result<Self> {
+        let mut chunk = Self {
+            set: Vec::with_capacity(16),
+            synthetic: Vec::new(),
+        };
+        let stream =
+            syn::parse_str::<proc_macro2::TokenStream>(source).map_err(Error::ParserFailure)?;
+        chunk.parse_string_literals(source, stream);
+        chunk.ensure_sorted();
+        Ok(chunk)
+    }
+
+    /// Load clusters of the help texts of `clap` derived command line
+    /// interfaces, i.e. `#[arg(help = "..")]`, from a `&str`.
+    pub fn load_help_texts_from_str(source: &str) -> Result<Self> {
+        let mut chunk = Self {
+            set: Vec::with_capacity(16),
+            synthetic: Vec::new(),
+        };
+        let stream =
+            syn::parse_str::<proc_macro2::TokenStream>(source).map_err(Error::ParserFailure)?;
+        chunk.parse_help_texts(source, stream);
+        chunk.ensure_sorted();
+        Ok(chunk)
+    }
 }
 
 #[cfg(test)]
@@ -201,4 +791,296 @@ struct VeryWellThanks;
         assert_eq!(clusters.set.len(), 1);
         dbg!(&clusters.set[0]);
     }
+
+    #[test]
+    fn string_literals() {
+        static CONTENT: &str = r#####"
+fn main() {
+    assert!(x == "nope", "Assertoin failed");
+    assert_eq!(a, b, r#"Not "equal""#);
+    log::warn!(target: "nope", "Warnnig {}", 7);
+    let _ = Some(7).unwrap_or_else(|| panic!("Paniced"));
+    println!("nope");
+    unreachable!();
+}
+"#####;
+        let clusters = Clusters::load_string_literals_from_str(CONTENT).unwrap();
+        let literals = Vec::from_iter(
+            clusters
+                .set
+                .iter()
+                .map(|set| set.literals()[0].as_str().to_owned()),
+        );
+        assert_eq!(
+            literals,
+            vec![
+                "Assertoin failed",
+                r#"Not "equal""#,
+                "Warnnig {}",
+                "Paniced"
+            ]
+        );
+        let span = clusters.set[0].literals()[0].span();
+        assert_eq!(
+            span.start,
+            crate::LineColumn {
+                line: 3,
+                column: 26
+            }
+        );
+        assert_eq!(
+            span.end,
+            crate::LineColumn {
+                line: 3,
+                column: 41
+            }
+        );
+    }
+    #[test]
+    fn string_literal_arguments() {
+        static CONTENT: &str = r#####"
+fn main() {
+    assert!(a < b, c > d);
+    assert!(a < b, "Frist {}", c > d);
+    assert!(parse::<A, B>(x), "Secnod");
+    assert!(call::<fn(A) -> B, C>(x), "Thrid");
+}
+"#####;
+        let clusters = Clusters::load_string_literals_from_str(CONTENT).unwrap();
+        let literals = Vec::from_iter(
+            clusters
+                .set
+                .iter()
+                .map(|set| set.literals()[0].as_str().to_owned()),
+        );
+        assert_eq!(literals, vec!["Frist {}", "Secnod", "Thrid"]);
+    }
+
+    #[test]
+    fn string_literal_escapes_erased() {
+        static CONTENT: &str = r#####"
+fn main() {
+    panic!("Lien\none \"{name:?}\" {{braces}}");
+}
+"#####;
+        let clusters = Clusters::load_string_literals_from_str(CONTENT).unwrap();
+        let chunk = clusters.set[0].clone().into_chunk();
+        let plain = chunk.erase_cmark(&Default::default());
+        assert_eq!(plain.as_str(), "Lien one  braces");
+        // `one` maps back to the source, past the erased escape
+        let spans = Vec::from_iter(plain.find_spans(5..8).into_values());
+        assert_eq!(
+            spans,
+            vec![Span {
+                start: crate::LineColumn {
+                    line: 3,
+                    column: 18
+                },
+                end: crate::LineColumn {
+                    line: 3,
+                    column: 20
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn help_texts() {
+        static CONTENT: &str = r#####"
+#[derive(clap::Parser)]
+#[command(about = "Checks teh things", version)]
+struct Args {
+    #[arg(short, long, help = "Be verbsoe")]
+    verbose: bool,
+    #[arg(long, default_value = "nope")]
+    mode: String,
+    #[serde(rename = "nope")]
+    other: bool,
+}
+
+#[derive(clap::Subcommand)]
+enum Sub {
+    #[clap(about = r#"Fix "all""#, long_about = "Fixes everything")]
+    Fix,
+}
+"#####;
+        let clusters = Clusters::load_help_texts_from_str(CONTENT).unwrap();
+        let literals = Vec::from_iter(
+            clusters
+                .set
+                .iter()
+                .map(|set| set.literals()[0].as_str().to_owned()),
+        );
+        assert_eq!(
+            literals,
+            vec![
+                "Checks teh things",
+                "Be verbsoe",
+                r#"Fix "all""#,
+                "Fixes everything"
+            ]
+        );
+        let span = clusters.set[1].literals()[0].span();
+        assert_eq!(
+            span.start,
+            crate::LineColumn {
+                line: 5,
+                column: 31
+            }
+        );
+    }
+
+    #[test]
+    fn public_only() {
+        static CONTENT: &str = r#####"
+//! Crate
+
+/// Public
+pub struct A {
+    /// Field
+    pub a: u8,
+    /// Private field
+    b: u8,
+}
+
+/// Private
+#[derive(Debug)]
+pub(crate) fn b() {
+    /// Nope
+    struct C;
+}
+
+/// Enum
+#[non_exhaustive]
+pub enum D {
+    /// Variant
+    E,
+}
+
+impl Trait for A {
+    /// Trait method
+    fn f() {}
+}
+
+impl A {
+    /// Private method
+    fn g() {}
+    /// Method
+    pub fn h() {}
+}
+"#####;
+        let clusters = Clusters::load_public_from_str(CONTENT, false).unwrap();
+        let literals = Vec::from_iter(
+            clusters
+                .set
+                .iter()
+                .map(|set| set.literals()[0].as_str().trim().to_owned()),
+        );
+        assert_eq!(
+            literals,
+            vec![
+                "Crate",
+                "Public",
+                "Field",
+                "Enum",
+                "Variant",
+                "Trait method",
+                "Method"
+            ]
+        );
+    }
+
+    #[test]
+    fn synthetic_doc_attributes() {
+        const SOURCE: &str = r#"
+#[doc = concat!("Documentd ", "with a ", stringify!(macro), '.')]
+struct A;
+#[doc = concat!("Version ", env!("CARGO_PKG_VERSION"))]
+struct B;
+"#;
+        let clusters = Clusters::load_from_str(SOURCE, true, false).unwrap();
+        let chunks = Vec::<CheckableChunk>::from(clusters);
+        let synthetic = Vec::from_iter(
+            chunks
+                .iter()
+                .filter(|chunk| chunk.variant() == CommentVariant::Synthetic),
+        );
+        assert_eq!(synthetic.len(), 1);
+        assert_eq!(synthetic[0].as_str(), "Documentd with a macro.");
+        assert_eq!(
+            synthetic[0].find_spans(0..9).values().next(),
+            Some(&Span {
+                start: LineColumn {
+                    line: 2,
+                    column: 17
+                },
+                end: LineColumn {
+                    line: 2,
+                    column: 25
+                },
+            })
+        );
+        // the character is not mapped
+        assert!(synthetic[0].find_spans(22..23).is_empty());
+    }
+
+    #[test]
+    fn cfg_attr_doc_strings() {
+        const SOURCE: &str = r#"
+#[cfg_attr(feature = "serde", doc = "Serializable with `serde`.")]
+#[cfg_attr(all(docsrs, feature = "std"), doc = "Requires the `std` featur.")]
+#[cfg_attr(test, derive(Debug))]
+struct A;
+"#;
+        let clusters = Clusters::load_from_str(SOURCE, true, false).unwrap();
+        let chunks = Vec::<CheckableChunk>::from(clusters);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(
+            chunks[0].as_str(),
+            "Serializable with `serde`.\nRequires the `std` featur."
+        );
+        assert_eq!(
+            chunks[0].find_spans(46..52).values().next(),
+            Some(&Span {
+                start: LineColumn {
+                    line: 3,
+                    column: 67
+                },
+                end: LineColumn {
+                    line: 3,
+                    column: 72
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn marked_constants() {
+        static CONTENT: &str = r#####"
+// spellcheck: check
+/// Usage.
+pub const USAGE: &str = "Chekcs all the things.";
+
+const UNMARKED: &str = "nope";
+
+mod errors {
+    // spellcheck: check
+    #[allow(dead_code)]
+    pub(crate) static MESSAGES: &[(u8, &str)] = &[(1, "Frist"), (2, r#"Second"#)];
+
+    // spellcheck: check
+    const fn nope() -> &'static str {
+        "nope"
+    }
+}
+"#####;
+        let clusters = Clusters::load_from_str(CONTENT, true, false).unwrap();
+        let literals = Vec::from_iter(clusters.set.iter().flat_map(|set| {
+            set.literals()
+                .into_iter()
+                .filter(|literal| literal.variant() == crate::CommentVariant::StringLiteral)
+                .map(|literal| literal.as_str().to_owned())
+        }));
+        assert_eq!(literals, vec!["Chekcs all the things.", "Frist", "Second"]);
+    }
 }
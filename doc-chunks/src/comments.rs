@@ -0,0 +1,273 @@
+//! Comments of source files in languages other than rust.
+//!
+//! Comments are found line based by their configured markers, without any
+//! knowledge of the language itself. A marker only starts a comment at the
+//! beginning of a line or after whitespace, so `https://` within a string
+//! literal is not mistaken for one, and never within a string literal which
+//! starts and ends on the same line, i.e. `"see // here"`. Markers within
+//! string literals spanning multiple lines are not recognized as such.
+
+use crate::{Range, TextLine};
+
+/// The comment markers of a language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentSyntax {
+    /// Prefixes of line comments, i.e. `//` or `#`.
+    pub line: Vec<String>,
+    /// Opening and closing delimiters of block comments, i.e. `/*` and `*/`.
+    pub block: Vec<(String, String)>,
+}
+
+impl Default for CommentSyntax {
+    /// The comments of C and C++.
+    fn default() -> Self {
+        Self {
+            line: vec!["//".to_owned()],
+            block: vec![("/*".to_owned(), "*/".to_owned())],
+        }
+    }
+}
+
+/// A comment marker found within a line.
+enum Marker<'s> {
+    Line(&'s str),
+    Block(&'s str, &'s str),
+}
+
+impl CommentSyntax {
+    /// Find the first marker in `line` at or after the byte `offset`, the
+    /// longest one if multiple match at the same position.
+    fn next_marker(&self, line: &str, offset: usize) -> Option<(usize, Marker<'_>)> {
+        let mut preceding = line[..offset].chars().next_back();
+        let mut idx = offset;
+        while let Some(c) = line[idx..].chars().next() {
+            let rest = &line[idx..];
+            if preceding.map_or(true, char::is_whitespace) {
+                let block = self
+                    .block
+                    .iter()
+                    .filter(|(open, close)| {
+                        !open.is_empty() && !close.is_empty() && rest.starts_with(open.as_str())
+                    })
+                    .map(|(open, close)| (open.len(), Marker::Block(open, close)));
+                let marker = self
+                    .line
+                    .iter()
+                    .filter(|prefix| !prefix.is_empty() && rest.starts_with(prefix.as_str()))
+                    .map(|prefix| (prefix.len(), Marker::Line(prefix)))
+                    .chain(block)
+                    .max_by_key(|(len, _marker)| *len);
+                if let Some((_len, marker)) = marker {
+                    return Some((idx, marker));
+                }
+            }
+            // skip string literals, which are closed on the same line
+            let literal = if c == '"' || c == '\'' {
+                string_literal_len(rest, c)
+            } else {
+                None
+            };
+            idx += literal.unwrap_or(c.len_utf8());
+            preceding = Some(c);
+        }
+        None
+    }
+
+    /// Extract the comments of `content`, consecutive line comments starting
+    /// at the same column are joined, just like the lines of a block comment.
+    pub(crate) fn extract<'a>(&self, content: &'a str) -> Vec<Vec<TextLine<'a>>> {
+        let mut acc = Vec::new();
+        let mut comment = Vec::new();
+        // the closing delimiter of the block comment the current line is part of
+        let mut block_end = None::<&str>;
+        // the column of the consecutive line comments collected in `comment`
+        let mut line_comments = None::<usize>;
+        for (idx, line) in content.lines().enumerate() {
+            let lineno = idx + 1;
+            // a shebang is not a comment, even though it looks like one
+            if idx == 0 && line.starts_with("#!") {
+                continue;
+            }
+            let mut offset = 0;
+            let mut continued = false;
+            while offset <= line.len() {
+                if let Some(close) = block_end {
+                    let rest = &line[offset..];
+                    let end = rest.find(close).map(|end| offset + end);
+                    let body = &line[offset..end.unwrap_or(line.len())];
+                    // the decoration of continued lines, i.e. ` * `
+                    let trimmed = body.trim_start();
+                    let skip = body.len() - trimmed.len() + usize::from(trimmed.starts_with('*'));
+                    push(
+                        &mut comment,
+                        lineno,
+                        line,
+                        (offset + skip)..(offset + body.len()),
+                    );
+                    let end = match end {
+                        Some(end) => end,
+                        None => break,
+                    };
+                    flush(&mut acc, &mut comment);
+                    block_end = None;
+                    offset = end + close.len();
+                    continue;
+                }
+                let (start, marker) = match self.next_marker(line, offset) {
+                    Some(found) => found,
+                    None => break,
+                };
+                match marker {
+                    Marker::Line(prefix) => {
+                        let own_line = line[..start].trim().is_empty();
+                        let column = line[..start].chars().count();
+                        if !own_line || line_comments != Some(column) {
+                            flush(&mut acc, &mut comment);
+                        }
+                        let body = start + prefix.len();
+                        let body = body + decoration(&line[body..], prefix);
+                        push(&mut comment, lineno, line, body..line.len());
+                        if own_line {
+                            line_comments = Some(column);
+                            continued = true;
+                        } else {
+                            // trailing comments stand on their own
+                            flush(&mut acc, &mut comment);
+                        }
+                        break;
+                    }
+                    Marker::Block(open, close) => {
+                        flush(&mut acc, &mut comment);
+                        line_comments = None;
+                        block_end = Some(close);
+                        let body = start + open.len();
+                        // only up to the closing delimiter, i.e. `/**/`
+                        let rest = &line[body..];
+                        let rest = &rest[..rest.find(close).unwrap_or(rest.len())];
+                        offset = body + decoration(rest, open);
+                    }
+                }
+            }
+            if !continued && line_comments.take().is_some() {
+                flush(&mut acc, &mut comment);
+            }
+        }
+        flush(&mut acc, &mut comment);
+        acc
+    }
+}
+
+/// The byte length of the string literal at the start of `rest`, delimited
+/// by `quote`, including both quotes. `None` if it is not closed.
+fn string_literal_len(rest: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (idx, c) in rest.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return Some(idx + c.len_utf8());
+        }
+    }
+    None
+}
+
+/// The length of the decoration at the start of `body`, made of the
+/// characters of the `marker`, i.e. `/**` or `///`, and `!` as used by
+/// doxygen.
+fn decoration(body: &str, marker: &str) -> usize {
+    body.len()
+        - body
+            .trim_start_matches(|c| c == '!' || marker.contains(c))
+            .len()
+}
+
+/// Add the text of `line` within the byte `range` to the `comment`, without
+/// surrounding whitespace. Empty lines are only kept in between.
+fn push<'a>(comment: &mut Vec<TextLine<'a>>, lineno: usize, line: &'a str, range: Range) {
+    let body = &line[range.clone()];
+    let text = body.trim();
+    if text.is_empty() && comment.is_empty() {
+        return;
+    }
+    let column = line[..(range.start + body.len() - body.trim_start().len())]
+        .chars()
+        .count();
+    comment.push((lineno, column, text));
+}
+
+fn flush<'a>(acc: &mut Vec<Vec<TextLine<'a>>>, comment: &mut Vec<TextLine<'a>>) {
+    while comment
+        .last()
+        .map_or(false, |(_lineno, _column, text)| text.is_empty())
+    {
+        comment.pop();
+    }
+    if !comment.is_empty() {
+        acc.push(std::mem::take(comment));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn c_header() {
+        const CONTENT: &str = r#"/**
+ * Initialize the contxt.
+ *
+ * Returns zero on success.
+ */
+int init(void); // trailing remark
+/* single line */
+static const char* URL = "https://example.com"; /**/
+printf("see // here, \" /* */"); puts('"'); // trailing
+
+// first line
+// second line
+int x;
+"#;
+        assert_eq!(
+            CommentSyntax::default().extract(CONTENT),
+            vec![
+                vec![
+                    (2, 3, "Initialize the contxt."),
+                    (3, 2, ""),
+                    (4, 3, "Returns zero on success."),
+                ],
+                vec![(6, 19, "trailing remark")],
+                vec![(7, 3, "single line")],
+                vec![(9, 47, "trailing")],
+                vec![(11, 3, "first line"), (12, 3, "second line")],
+            ]
+        );
+    }
+
+    #[test]
+    fn python() {
+        const CONTENT: &str = r#"#!/usr/bin/env python
+# A scirpt.
+x = 1  # the answer
+print('# no comment')
+    # indented
+"""
+Docstring.
+"""
+"#;
+        let syntax = CommentSyntax {
+            line: vec!["#".to_owned()],
+            block: vec![(r#"""""#.to_owned(), r#"""""#.to_owned())],
+        };
+        assert_eq!(
+            syntax.extract(CONTENT),
+            vec![
+                vec![(2, 2, "A scirpt.")],
+                vec![(3, 9, "the answer")],
+                vec![(5, 6, "indented")],
+                vec![(7, 0, "Docstring.")],
+            ]
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! Rust code embedded in fenced code blocks of markdown files.
+//!
+//! The comments within are extracted just like the ones of a rust source file,
+//! the spans are then moved to the location of the fence within the markdown
+//! file.
+
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag};
+
+/// A fenced rust code block, represented by the 0-based line of the opening
+/// fence and the code in between the fences.
+pub(crate) type RustFence<'a> = (usize, &'a str);
+
+/// Check if the info string of a fence declares rust code, i.e. `rust` or
+/// `rust,ignore`.
+fn is_rust_info(info: &str) -> bool {
+    info.split(|c: char| c == ',' || c.is_whitespace())
+        .next()
+        .map(|lang| lang == "rust")
+        .unwrap_or_default()
+}
+
+/// Find all fenced rust code blocks of a markdown `content`.
+///
+/// Fences that do not start at the beginning of a line, i.e. within a list
+/// item or a quote, are skipped, since their lines carry a prefix that is not
+/// part of the code.
+pub(crate) fn rust_fences(content: &str) -> Vec<RustFence<'_>> {
+    let parser = Parser::new_ext(content, Options::all());
+    let mut acc = Vec::new();
+    for (event, byte_range) in parser.into_offset_iter() {
+        let info = match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => info,
+            _ => continue,
+        };
+        if !is_rust_info(&info) {
+            continue;
+        }
+        let start = byte_range.start;
+        if start > byte_range.end || (start > 0 && !content[..start].ends_with('\n')) {
+            continue;
+        }
+        let block = &content[start..byte_range.end];
+        let fence = block.trim_start();
+        let delimiter = if fence.starts_with("~~~") {
+            "~~~"
+        } else {
+            "```"
+        };
+        // the opening fence occupies a line on its own
+        let code_start = match block.find('\n') {
+            Some(idx) => idx + 1,
+            None => continue,
+        };
+        let code = &block[code_start..];
+        // the closing fence is optional at the end of the document
+        let code = match code.trim_end().rfind('\n') {
+            Some(idx) if code[idx + 1..].trim().starts_with(delimiter) => &code[..idx + 1],
+            None if code.trim().starts_with(delimiter) => "",
+            _ => code,
+        };
+        let line = content[..start].matches('\n').count();
+        acc.push((line, code));
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fences() {
+        const CONTENT: &str = r#"# Example
+
+```rust,ignore
+// A comment
+fn main() {}
+```
+
+```toml
+# not rust
+```
+
+- item
+
+  ```rust
+  // indented
+  ```
+
+~~~rust
+/// Unterminated
+"#;
+        assert_eq!(
+            rust_fences(CONTENT),
+            vec![
+                (2, "// A comment\nfn main() {}\n"),
+                (17, "/// Unterminated\n")
+            ]
+        );
+    }
+}
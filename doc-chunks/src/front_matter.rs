@@ -0,0 +1,191 @@
+//! YAML front matter of markdown files, as used by static site generators.
+//!
+//! Only the plain or quoted string values of selected top level keys are
+//! extracted, there is no full YAML parser involved.
+
+/// Front matter fields that are checked by default.
+pub const DEFAULT_FRONT_MATTER_FIELDS: &[&str] = &["title", "description"];
+
+/// A line of a front matter value, represented by its 1-based line number, the
+/// column the value starts at and the value itself.
+pub(crate) type ValueLine<'a> = (usize, usize, &'a str);
+
+/// Check if `line` is a top level YAML key, i.e. `title:` or `"title": A`.
+fn is_key_line(line: &str) -> bool {
+    let (key, rest) = match line.split_once(':') {
+        Some(key_rest) => key_rest,
+        None => return false,
+    };
+    let plain = |key: &str| {
+        key.chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+    };
+    let quoted = |quote: char| key.len() >= 2 && key.starts_with(quote) && key.ends_with(quote);
+    let is_key = !key.is_empty() && (plain(key) || quoted('"') || quoted('\''));
+    // the value is separated by whitespace, unlike the `:` of prose like `a:b`
+    is_key && (rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+/// Split off a front matter delimited by `---` lines at the very start of
+/// `content`.
+///
+/// A leading `---` is a thematic break in markdown as well, so the line after
+/// the opening delimiter must be a key of the front matter.
+///
+/// Returns the lines in between the delimiters and the byte offset of the
+/// remaining content.
+pub(crate) fn split_front_matter(content: &str) -> Option<(Vec<&str>, usize)> {
+    let mut lines = content.split_inclusive('\n');
+    let first = lines.next()?;
+    if first.trim_end() != "---" {
+        return None;
+    }
+    let mut lines = lines.peekable();
+    if !is_key_line(lines.peek()?.trim_end()) {
+        return None;
+    }
+    let mut offset = first.len();
+    let mut acc = Vec::new();
+    for line in lines {
+        offset += line.len();
+        let line = line.trim_end_matches(|c| c == '\n' || c == '\r');
+        if line.trim_end() == "---" || line.trim_end() == "..." {
+            return Some((acc, offset));
+        }
+        acc.push(line);
+    }
+    None
+}
+
+/// Extract the string values of the top level `fields` of the front matter
+/// `lines`, one entry per field.
+///
+/// Supports plain and quoted scalars on a single line as well as literal and
+/// folded block scalars. Everything else, i.e. lists or nested mappings, is
+/// skipped.
+pub(crate) fn extract_front_matter_fields<'a, S: AsRef<str>>(
+    lines: &[&'a str],
+    fields: &[S],
+) -> Vec<Vec<ValueLine<'a>>> {
+    // the opening delimiter occupies the first line
+    let lineno = |idx: usize| idx + 2;
+    let column = |line: &str, start: usize| line[..start].chars().count();
+
+    let mut acc = Vec::new();
+    let mut iter = lines.iter().enumerate().peekable();
+    while let Some((idx, line)) = iter.next() {
+        // nested entries and continuations are indented
+        if line.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let (key, rest) = match line.split_once(':') {
+            Some(key_rest) => key_rest,
+            None => continue,
+        };
+        let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+        if !fields.iter().any(|field| field.as_ref() == key) {
+            continue;
+        }
+        let value = rest.trim_start();
+        let mut start = line.len() - value.len();
+        let value = value.trim_end();
+        if value.starts_with('|') || value.starts_with('>') {
+            let mut block = Vec::new();
+            while let Some((idx, line)) = iter.next_if(|(_, line)| {
+                line.trim().is_empty() || line.starts_with(char::is_whitespace)
+            }) {
+                let value = line.trim();
+                if !value.is_empty() {
+                    let start = line.len() - line.trim_start().len();
+                    block.push((lineno(idx), column(line, start), value));
+                }
+            }
+            if !block.is_empty() {
+                acc.push(block);
+            }
+            continue;
+        }
+        let value = if let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') {
+            start += quote.len_utf8();
+            match value
+                .strip_prefix(quote)
+                .and_then(|value| value.find(quote).map(|end| &value[..end]))
+            {
+                Some(value) => value,
+                // spans multiple lines
+                None => continue,
+            }
+        } else {
+            // strip trailing comments
+            value.split(" #").next().unwrap_or_default().trim_end()
+        };
+        if value.is_empty() {
+            continue;
+        }
+        acc.push(vec![(lineno(idx), column(line, start), value)]);
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = r#"---
+title: "A Tittle"
+date: 2023-01-01
+description: >
+  Some descriptoin
+  on two lines
+
+tags:
+  - title: nope
+author: 'Alice' # comment
+---
+# Body
+"#;
+
+    #[test]
+    fn split() {
+        let (lines, offset) = split_front_matter(CONTENT).unwrap();
+        assert_eq!(lines.len(), 9);
+        assert_eq!(&CONTENT[offset..], "# Body\n");
+        assert_eq!(split_front_matter("# Body\n---\n"), None);
+        assert_eq!(split_front_matter("---\nunterminated: true\n"), None);
+        assert_eq!(split_front_matter("---\n---\n"), None);
+    }
+
+    #[test]
+    fn thematic_break() {
+        // a markdown file starting with a thematic break
+        const CONTENT: &str = "---\n\nSome text.\n\n---\n# Body\n";
+        assert_eq!(split_front_matter(CONTENT), None);
+        assert_eq!(split_front_matter("---\nSome text: prose\n---\n"), None);
+        assert_eq!(split_front_matter("---\n# Heading\n---\n"), None);
+        assert_eq!(split_front_matter("---\nNote:no space\n---\n"), None);
+        assert_eq!(
+            split_front_matter("---\n\"quoted key\": x\n---\n"),
+            Some((vec!["\"quoted key\": x"], 24))
+        );
+        assert_eq!(
+            split_front_matter("---\ntags:\n  - a\n---\n"),
+            Some((vec!["tags:", "  - a"], 20))
+        );
+    }
+
+    #[test]
+    fn fields() {
+        let (lines, _offset) = split_front_matter(CONTENT).unwrap();
+        assert_eq!(
+            extract_front_matter_fields(&lines, DEFAULT_FRONT_MATTER_FIELDS),
+            vec![
+                vec![(2, 8, "A Tittle")],
+                vec![(5, 2, "Some descriptoin"), (6, 2, "on two lines")],
+            ]
+        );
+        assert_eq!(
+            extract_front_matter_fields(&lines, &["author"]),
+            vec![vec![(10, 9, "Alice")]]
+        );
+    }
+}
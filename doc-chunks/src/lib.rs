@@ -22,38 +22,49 @@ pub use self::span::Span;
 pub use proc_macro2::LineColumn;
 
 pub mod util;
-use self::util::{load_span_from, sub_char_range};
+use self::util::load_span_from;
 
 use indexmap::IndexMap;
 use proc_macro2::TokenTree;
 use rayon::prelude::*;
-use serde::Deserialize;
 use std::path::PathBuf;
-use toml::Spanned;
 
 /// Range based on `usize`, simplification.
 pub type Range = core::ops::Range<usize>;
 
+/// A line of text, represented by its 1-based line number, the column the text
+/// starts at and the text itself.
+pub(crate) type TextLine<'a> = (usize, usize, &'a str);
+
 /// Apply an offset to `start` and `end` members, equaling a shift of the range.
 pub fn apply_offset(range: &mut Range, offset: usize) {
     range.start = range.start.saturating_add(offset);
     range.end = range.end.saturating_add(offset);
 }
 
+mod asciidoc;
 pub mod chunk;
 pub mod cluster;
+mod comments;
 mod developer;
+mod doctest;
 pub mod errors;
+mod front_matter;
 pub mod literal;
 pub mod literalset;
+mod manifest;
 pub mod markdown;
+mod undocumented;
 
 pub use chunk::*;
 pub use cluster::*;
+pub use comments::CommentSyntax;
 pub use errors::*;
+pub use front_matter::DEFAULT_FRONT_MATTER_FIELDS;
 pub use literal::*;
 pub use literalset::*;
 pub use markdown::*;
+pub use undocumented::undocumented_public_items;
 
 /// Collection of all the documentation entries across the project
 #[derive(Debug, Clone)]
@@ -144,128 +155,263 @@ impl Documentation {
         Ok(())
     }
 
+    /// Adds a rust content str to the documentation, only considering the doc
+    /// comments of public items.
+    pub fn add_rust_public(
+        &mut self,
+        origin: ContentOrigin,
+        content: &str,
+        dev_comments: bool,
+    ) -> Result<()> {
+        let cluster = Clusters::load_public_from_str(content, dev_comments)?;
+
+        let chunks = Vec::<CheckableChunk>::from(cluster);
+        self.add_inner(origin, chunks);
+        Ok(())
+    }
+
+    /// Adds the message string literals of `panic!`, `assert!` and `log`
+    /// macros in a rust content str to the documentation.
+    pub fn add_rust_string_literals(&mut self, origin: ContentOrigin, content: &str) -> Result<()> {
+        let cluster = Clusters::load_string_literals_from_str(content)?;
+
+        let chunks = Vec::<CheckableChunk>::from(cluster);
+        self.add_inner(origin, chunks);
+        Ok(())
+    }
+
+    /// Adds the help texts of `clap` derived command line interfaces, given as
+    /// `about = ".."` or `help = ".."` of `#[command(..)]`, `#[arg(..)]` and
+    /// alike attributes, in a rust content str to the documentation.
+    pub fn add_rust_help_texts(&mut self, origin: ContentOrigin, content: &str) -> Result<()> {
+        let cluster = Clusters::load_help_texts_from_str(content)?;
+
+        let chunks = Vec::<CheckableChunk>::from(cluster);
+        self.add_inner(origin, chunks);
+        Ok(())
+    }
+
     /// Adds a content string to the documentation sourced from the
     /// `description` field in a `Cargo.toml` manifest.
+    ///
+    /// Multi-line descriptions are added line by line, so each line maps to
+    /// its exact position within the manifest.
     pub fn add_cargo_manifest_description(
         &mut self,
         path: PathBuf,
         manifest_content: &str,
     ) -> Result<()> {
-        fn extract_range_of_description(manifest_content: &str) -> Result<Range> {
-            #[derive(Deserialize, Debug)]
-            struct Manifest {
-                package: Spanned<Package>,
-            }
-
-            #[derive(Deserialize, Debug)]
-            struct Package {
-                description: Spanned<String>,
-            }
-
-            let value: Manifest = toml::from_str(manifest_content)?;
-            let d = value.package.into_inner().description;
-            let range = d.span();
-            Ok(range)
-        }
-
-        let mut range = extract_range_of_description(manifest_content)?;
-        let description = sub_char_range(manifest_content, range.clone());
-
-        // Attention: `description` does include `\"\"\"` as well as `\\\n`, the latter is not a big issue,
-        // but the trailing start and end delimiters are.
-        // TODO: split into multiple on `\\\n` and create multiple range/span mappings.
-        let description = if range.len() > 6 {
-            if description.starts_with("\"\"\"") {
-                range.start += 3;
-                range.end -= 3;
-                assert!(!range.is_empty());
-            }
-            dbg!(&description[3..(description.len()) - 3])
-        } else {
-            description
-        };
-
-        fn convert_range_to_span(content: &str, range: Range) -> Option<Span> {
-            let mut line = 0_usize;
-            let mut column = 0_usize;
-            let mut prev = '\n';
-            let mut start = None;
-            for (offset, c) in content.chars().enumerate() {
-                if prev == '\n' {
-                    column = 0;
-                    line += 1;
-                }
-                prev = c;
+        let range = manifest::find_string_field(
+            manifest_content,
+            &manifest::split_field_path("package.description"),
+        )?
+        .ok_or_else(|| Error::Span("Cargo.toml manifest has no description".to_owned()))?;
+        self.add_inner(
+            ContentOrigin::CargoManifestDescription(path),
+            vec![manifest::chunk_from_string_value(manifest_content, range)],
+        );
+        Ok(())
+    }
 
-                if offset == range.start {
-                    start = Some(LineColumn { line, column });
-                    continue;
-                }
-                // take care of inclusivity
-                if offset + 1 == range.end {
-                    let end = LineColumn { line, column };
-                    return Some(Span {
-                        start: start.unwrap(),
-                        end,
-                    });
+    /// Adds the string values of the `fields` of a `Cargo.toml` manifest to the
+    /// documentation. Fields are given as dotted paths, i.e.
+    /// `package.description` or `package.metadata."docs.rs".notes`, missing
+    /// ones are skipped.
+    pub fn add_cargo_manifest_fields<S: AsRef<str>>(
+        &mut self,
+        path: PathBuf,
+        manifest_content: &str,
+        fields: &[S],
+    ) -> Result<()> {
+        let mut chunks = Vec::with_capacity(fields.len());
+        for field in fields {
+            let field = field.as_ref();
+            let found =
+                manifest::find_string_field(manifest_content, &manifest::split_field_path(field));
+            match found {
+                Ok(Some(range)) => {
+                    chunks.push(manifest::chunk_from_string_value(manifest_content, range))
                 }
-                column += 1;
+                Ok(None) => {}
+                Err(e) => log::debug!("Failed to locate field {field} in {}: {e}", path.display()),
             }
-            None
         }
+        if !chunks.is_empty() {
+            self.add_inner(ContentOrigin::CargoManifestDescription(path), chunks);
+        }
+        Ok(())
+    }
 
-        let span = convert_range_to_span(manifest_content, range.clone()).expect(
-            "Description is part of the manifest since it was parsed from the same source. qed",
-        );
-        let origin = ContentOrigin::CargoManifestDescription(path);
-        let source_mapping = dbg!(indexmap::indexmap! {
-            range => span
-        });
-        self.add_inner(
-            origin,
-            vec![CheckableChunk::from_str(
-                description,
-                source_mapping,
-                CommentVariant::TomlEntry,
-            )],
+    /// Adds the comments directly above each feature of the `[features]` table
+    /// in a `Cargo.toml` manifest to the documentation.
+    ///
+    /// This is a common convention to document features, which is also used by
+    /// `document-features` to render them into the crate documentation.
+    pub fn add_cargo_manifest_features(
+        &mut self,
+        path: PathBuf,
+        manifest_content: &str,
+    ) -> Result<()> {
+        let chunks = Vec::from_iter(
+            extract_feature_comments(manifest_content)
+                .into_iter()
+                .map(|lines| chunk_from_lines(&lines, CommentVariant::TomlComment)),
         );
+        if !chunks.is_empty() {
+            self.add_inner(ContentOrigin::CargoManifestFeatures(path), chunks);
+        }
         Ok(())
     }
 
     /// Adds a common mark content str to the documentation.
+    ///
+    /// The [`DEFAULT_FRONT_MATTER_FIELDS`] of a YAML front matter are checked.
     pub fn add_commonmark(&mut self, origin: ContentOrigin, content: &str) -> Result<()> {
-        // extract the full content span and range
-        let start = LineColumn { line: 1, column: 0 };
-        let end = content
+        self.add_commonmark_with_front_matter(origin, content, DEFAULT_FRONT_MATTER_FIELDS)
+    }
+
+    /// Adds a common mark content str to the documentation, checking the
+    /// string values of the `fields` of a YAML front matter, if any.
+    ///
+    /// The remaining front matter is skipped. The doc and developer comments
+    /// of fenced rust code blocks are added as well.
+    pub fn add_commonmark_with_front_matter<S: AsRef<str>>(
+        &mut self,
+        origin: ContentOrigin,
+        content: &str,
+        fields: &[S],
+    ) -> Result<()> {
+        if content.lines().next().is_none() {
+            return Err(Error::Span(
+                "Common mark / markdown file does not contain a single line".to_string(),
+            ));
+        }
+
+        let mut chunks = Vec::new();
+        let (body, first_line) = match front_matter::split_front_matter(content) {
+            Some((lines, offset)) => {
+                chunks.extend(
+                    front_matter::extract_front_matter_fields(&lines, fields)
+                        .into_iter()
+                        .map(|lines| chunk_from_lines(&lines, CommentVariant::YamlEntry)),
+                );
+                // both delimiters occupy a line each
+                (&content[offset..], lines.len() + 3)
+            }
+            None => (content, 1),
+        };
+
+        // extract the full body span and range
+        let start = LineColumn {
+            line: first_line,
+            column: 0,
+        };
+        let end = body
             .lines()
             .enumerate()
             .last()
-            .map(|(idx, linecontent)| (idx + 1, linecontent))
-            .map(|(linenumber, linecontent)| LineColumn {
-                line: linenumber,
+            .map(|(idx, linecontent)| LineColumn {
+                line: first_line + idx,
                 column: linecontent.chars().count().saturating_sub(1),
-            })
-            .ok_or_else(|| {
-                Error::Span(
-                    "Common mark / markdown file does not contain a single line".to_string(),
-                )
-            })?;
-
-        let span = Span { start, end };
-        let source_mapping = indexmap::indexmap! {
-            0..content.chars().count() => span
-        };
-        self.add_inner(
-            origin,
-            vec![CheckableChunk::from_str(
-                content,
+            });
+        if let Some(end) = end {
+            let span = Span { start, end };
+            let source_mapping = indexmap::indexmap! {
+                0..body.chars().count() => span
+            };
+            chunks.push(CheckableChunk::from_str(
+                body,
                 source_mapping,
                 CommentVariant::CommonMark,
-            )],
+            ));
+        }
+        for (line, code) in doctest::rust_fences(body) {
+            // the code starts the line after the fence
+            let offset = first_line + line;
+            match Clusters::load_from_str(code, true, true) {
+                Ok(clusters) => chunks.extend(
+                    Vec::<CheckableChunk>::from(clusters)
+                        .into_iter()
+                        .map(|chunk| chunk.offset_lines(offset)),
+                ),
+                Err(e) => log::debug!(
+                    "Failed to parse rust code block in {origin} at line {}: {e:?}",
+                    offset + 1
+                ),
+            }
+        }
+        self.add_inner(origin, chunks);
+        Ok(())
+    }
+
+    /// Adds the comments of a source file in a language other than rust,
+    /// found by the markers of the given `syntax`.
+    pub fn add_foreign_comments(
+        &mut self,
+        origin: ContentOrigin,
+        content: &str,
+        syntax: &CommentSyntax,
+    ) -> Result<()> {
+        let chunks = Vec::from_iter(
+            syntax
+                .extract(content)
+                .into_iter()
+                .map(|lines| chunk_from_lines(&lines, CommentVariant::ForeignComment)),
+        );
+        if !chunks.is_empty() {
+            self.add_inner(origin, chunks);
+        }
+        Ok(())
+    }
+
+    /// Adds the content of an AsciiDoc file, every paragraph, section title
+    /// and block title becomes a chunk on its own.
+    pub fn add_asciidoc(&mut self, origin: ContentOrigin, content: &str) -> Result<()> {
+        let chunks = Vec::from_iter(
+            asciidoc::extract_paragraphs(content)
+                .into_iter()
+                .map(|lines| chunk_from_lines(&lines, CommentVariant::AsciiDoc)),
         );
+        if !chunks.is_empty() {
+            self.add_inner(origin, chunks);
+        }
         Ok(())
     }
 
+    /// Adds the content of a plain text file, every paragraph separated by
+    /// blank lines becomes a chunk on its own.
+    pub fn add_plain_text(&mut self, origin: ContentOrigin, content: &str) -> Result<()> {
+        let mut chunks = Vec::new();
+        let mut paragraph = Vec::new();
+        for (idx, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                if !paragraph.is_empty() {
+                    chunks.push(chunk_from_lines(&paragraph, CommentVariant::PlainText));
+                    paragraph.clear();
+                }
+            } else {
+                paragraph.push((idx + 1, 0, line));
+            }
+        }
+        if !paragraph.is_empty() {
+            chunks.push(chunk_from_lines(&paragraph, CommentVariant::PlainText));
+        }
+        if !chunks.is_empty() {
+            self.add_inner(origin, chunks);
+        }
+        Ok(())
+    }
+
+    /// Retain only the chunks for which `f` returns `true`.
+    pub fn retain_chunks<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&ContentOrigin, &CheckableChunk) -> bool,
+    {
+        for (origin, chunks) in self.index.iter_mut() {
+            chunks.retain(|chunk| f(origin, chunk));
+        }
+    }
+
     /// Obtain the set of chunks for a particular origin.
     #[inline(always)]
     pub fn get(&self, origin: &ContentOrigin) -> Option<&[CheckableChunk]> {
@@ -302,7 +448,16 @@ impl Documentation {
             ContentOrigin::CargoManifestDescription(path) => {
                 docs.add_cargo_manifest_description(path, content)
             }
+            ContentOrigin::CargoManifestFeatures(path) => {
+                docs.add_cargo_manifest_features(path, content)
+            }
             origin @ ContentOrigin::CommonMarkFile(_) => docs.add_commonmark(origin, content),
+            origin @ ContentOrigin::PlainTextFile(_) => docs.add_plain_text(origin, content),
+            origin @ ContentOrigin::AsciiDocFile(_) => docs.add_asciidoc(origin, content),
+            // the syntax is not known here, assume the one of C
+            origin @ ContentOrigin::ForeignSourceFile(_) => {
+                docs.add_foreign_comments(origin, content, &CommentSyntax::default())
+            }
             origin @ ContentOrigin::TestEntityRust => {
                 docs.add_rust(origin, content, doc_comments, dev_comments)
             }
@@ -321,6 +476,101 @@ impl Documentation {
     }
 }
 
+/// Create a chunk from consecutive lines, each given by its 1-based line
+/// number, the column its content starts at, and the content.
+fn chunk_from_lines(lines: &[TextLine<'_>], variant: CommentVariant) -> CheckableChunk {
+    let mut content = String::new();
+    let mut source_mapping = IndexMap::with_capacity(lines.len());
+    let mut cursor = 0usize;
+    for (idx, (lineno, column, line)) in lines.iter().enumerate() {
+        if idx > 0 {
+            // the newline is _not_ covered by a span
+            content.push('\n');
+            cursor += 1;
+        }
+        let len = line.chars().count();
+        if len > 0 {
+            let start = LineColumn {
+                line: *lineno,
+                column: *column,
+            };
+            let end = LineColumn {
+                line: *lineno,
+                column: column + len - 1,
+            };
+            source_mapping.insert(cursor..(cursor + len), Span { start, end });
+        }
+        content.push_str(line);
+        cursor += len;
+    }
+    CheckableChunk::from_string(content, source_mapping, variant)
+}
+
+/// Check if the content of a comment is a commented out `key = value` entry,
+/// i.e. `# legacy = ["dep:foo"]`, rather than prose.
+fn is_commented_entry(comment: &str) -> bool {
+    comment
+        .parse::<toml::Table>()
+        .map(|table| !table.is_empty())
+        .unwrap_or_default()
+}
+
+/// Extract the comment blocks directly above the entries of the `[features]`
+/// table, line based, since the toml parser discards comments.
+///
+/// Blocks of `#!` lines, which `document-features` renders on their own rather
+/// than as part of a feature, are extracted as separate blocks wherever they
+/// appear. Commented out entries are skipped.
+///
+/// Each comment line is represented by its 1-based line number, the column
+/// the content starts at, and the content without the leading `#`.
+fn extract_feature_comments(manifest_content: &str) -> Vec<Vec<(usize, usize, &str)>> {
+    let mut acc = Vec::new();
+    let mut in_features = false;
+    let mut block = Vec::new();
+    let mut standalone = Vec::new();
+    for (idx, line) in manifest_content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if !trimmed.starts_with("#!") && !standalone.is_empty() {
+            acc.push(std::mem::take(&mut standalone));
+        }
+        if trimmed.starts_with('[') {
+            in_features = trimmed.starts_with("[features]");
+            block.clear();
+            continue;
+        }
+        if !in_features {
+            continue;
+        }
+        if let Some(comment) = trimmed.strip_prefix("#!") {
+            block.clear();
+            let column = line.chars().count() - comment.chars().count();
+            standalone.push((idx + 1, column, comment));
+        } else if let Some(comment) = trimmed.strip_prefix('#') {
+            // `##` is used by `document-features`
+            let comment = comment.trim_start_matches('#');
+            if is_commented_entry(comment) {
+                continue;
+            }
+            let column = line.chars().count() - comment.chars().count();
+            block.push((idx + 1, column, comment));
+        } else if trimmed.is_empty() {
+            block.clear();
+        } else if !block.is_empty() {
+            // only comments that document a feature are of interest
+            if trimmed.contains('=') {
+                acc.push(std::mem::take(&mut block));
+            } else {
+                block.clear();
+            }
+        }
+    }
+    if !standalone.is_empty() {
+        acc.push(standalone);
+    }
+    acc
+}
+
 impl IntoIterator for Documentation {
     type Item = (ContentOrigin, Vec<CheckableChunk>);
     type IntoIter = indexmap::map::IntoIter<ContentOrigin, Vec<CheckableChunk>>;
@@ -329,3 +579,184 @@ impl IntoIterator for Documentation {
         self.index.into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feature_comments() {
+        const MANIFEST: &str = r#"[package]
+# not a feature
+name = "foo"
+
+[features]
+## The default
+default = ["a"]
+
+# Enables `a`,
+# which is nice
+a = [
+  # nope
+  "dep:b",
+]
+# trailing
+
+[dependencies]
+"#;
+        assert_eq!(
+            extract_feature_comments(MANIFEST),
+            vec![
+                vec![(6, 2, " The default")],
+                vec![(9, 1, " Enables `a`,"), (10, 1, " which is nice")],
+            ]
+        );
+
+        let mut docs = Documentation::new();
+        docs.add_cargo_manifest_features(PathBuf::from("Cargo.toml"), MANIFEST)
+            .unwrap();
+        let chunks = docs
+            .get(&ContentOrigin::CargoManifestFeatures(PathBuf::from(
+                "Cargo.toml",
+            )))
+            .unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].as_str(), " Enables `a`,\n which is nice");
+        assert_eq!(
+            chunks[1].find_spans(15..20).values().next(),
+            Some(&Span {
+                start: LineColumn {
+                    line: 10,
+                    column: 2
+                },
+                end: LineColumn {
+                    line: 10,
+                    column: 6
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn feature_comments_commented_out_entries() {
+        const MANIFEST: &str = r#"[features]
+# Enables the legacy mode
+# legacy = ["dep:foo"]
+## modern = []
+legacy = []
+"#;
+        assert_eq!(
+            extract_feature_comments(MANIFEST),
+            vec![vec![(2, 1, " Enables the legacy mode")]]
+        );
+    }
+
+    #[test]
+    fn feature_comments_standalone() {
+        const MANIFEST: &str = r#"[features]
+#! ### Optional features
+#! Pick any of them.
+## Enables `a`
+a = []
+
+#! Trailing notes.
+"#;
+        assert_eq!(
+            extract_feature_comments(MANIFEST),
+            vec![
+                vec![
+                    (2, 2, " ### Optional features"),
+                    (3, 2, " Pick any of them.")
+                ],
+                vec![(4, 2, " Enables `a`")],
+                vec![(7, 2, " Trailing notes.")],
+            ]
+        );
+    }
+
+    #[test]
+    fn plain_text() {
+        const CONTENT: &str = "Copyright (c) the authors\n\n  Permission is *hereby*\ngranted.\n\n";
+
+        let origin = ContentOrigin::PlainTextFile(PathBuf::from("LICENSE"));
+        let mut docs = Documentation::new();
+        docs.add_plain_text(origin.clone(), CONTENT).unwrap();
+        let chunks = docs.get(&origin).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].as_str(), "  Permission is *hereby*\ngranted.");
+
+        // the markup is kept as is
+        let plain = chunks[1].erase_cmark(&Default::default());
+        assert_eq!(plain.as_str(), chunks[1].as_str());
+        assert_eq!(
+            plain.find_spans(16..24).values().next(),
+            Some(&Span {
+                start: LineColumn {
+                    line: 3,
+                    column: 16
+                },
+                end: LineColumn {
+                    line: 3,
+                    column: 23
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn commonmark_front_matter() {
+        const CONTENT: &str = "---\ntitle: A Tittle\nlayout: post\n---\n# Heading\n\nSome body.\n";
+
+        let mut docs = Documentation::new();
+        docs.add_commonmark(ContentOrigin::TestEntityCommonMark, CONTENT)
+            .unwrap();
+        let chunks = docs.get(&ContentOrigin::TestEntityCommonMark).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].as_str(), "A Tittle");
+        assert_eq!(
+            chunks[0].find_spans(2..8).values().next(),
+            Some(&Span {
+                start: LineColumn { line: 2, column: 9 },
+                end: LineColumn {
+                    line: 2,
+                    column: 14
+                },
+            })
+        );
+        assert_eq!(chunks[1].as_str(), "# Heading\n\nSome body.\n");
+        assert_eq!(
+            chunks[1].find_spans(11..15).values().next(),
+            Some(&Span {
+                start: LineColumn { line: 7, column: 0 },
+                end: LineColumn { line: 7, column: 3 },
+            })
+        );
+    }
+
+    #[test]
+    fn commonmark_rust_fence() {
+        const CONTENT: &str = r#"# Usage
+
+```rust
+fn main() {
+    // Prnit it
+    println!();
+}
+```
+"#;
+
+        let mut docs = Documentation::new();
+        docs.add_commonmark(ContentOrigin::TestEntityCommonMark, CONTENT)
+            .unwrap();
+        let chunks = docs.get(&ContentOrigin::TestEntityCommonMark).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].variant(), CommentVariant::DoubleSlash);
+        let chunk = &chunks[1];
+        let start = chunk.as_str().find("Prnit").unwrap();
+        let span = chunk.find_spans(start..start + 5).values().next().cloned();
+        assert_eq!(
+            span.map(|span| span.start),
+            Some(LineColumn { line: 5, column: 7 })
+        );
+    }
+}
@@ -1,8 +1,10 @@
 use crate::errors::*;
+use crate::markdown::SourceRange;
 use crate::util::{self, sub_chars};
 use crate::{Range, Span};
 
 use fancy_regex::Regex;
+use indexmap::IndexMap;
 use lazy_static::lazy_static;
 use proc_macro2::LineColumn;
 
@@ -51,6 +53,23 @@ pub enum CommentVariant {
     Unknown,
     /// Toml entry
     TomlEntry,
+    /// String literal argument of a macro invocation, i.e. `panic!("..")`
+    StringLiteral,
+    /// Toml comment, i.e. the documentation of a feature
+    TomlComment,
+    /// Yaml entry, i.e. of a markdown front matter
+    YamlEntry,
+    /// Content of a `#[doc = concat!(..)]` attribute evaluated from its
+    /// literal arguments. Only the parts which appear in the source verbatim
+    /// are mapped to it, the content as a whole can not be fixed.
+    Synthetic,
+    /// Paragraph of a plain text file, checked as is without any markup.
+    PlainText,
+    /// Paragraph or title of an AsciiDoc file.
+    AsciiDoc,
+    /// Comment of a source file in a language other than rust, without the
+    /// comment markers.
+    ForeignComment,
 }
 
 impl Default for CommentVariant {
@@ -71,6 +90,12 @@ impl CommentVariant {
             Self::SlashAsteriskAsterisk => CommentVariantCategory::Doc,
             Self::CommonMark => CommentVariantCategory::CommonMark,
             Self::TomlEntry => CommentVariantCategory::Unmergable,
+            Self::StringLiteral => CommentVariantCategory::Unmergable,
+            Self::TomlComment => CommentVariantCategory::Unmergable,
+            Self::YamlEntry => CommentVariantCategory::Unmergable,
+            Self::Synthetic => CommentVariantCategory::Unmergable,
+            Self::PlainText => CommentVariantCategory::Unmergable,
+            Self::AsciiDoc => CommentVariantCategory::Unmergable,
             _ => CommentVariantCategory::Dev,
         }
     }
@@ -98,6 +123,13 @@ impl CommentVariant {
             CommentVariant::SlashAsteriskEM => "/*!".to_string(),
             CommentVariant::SlashAsteriskAsterisk => "/**".to_string(),
             CommentVariant::TomlEntry => "".to_owned(),
+            CommentVariant::StringLiteral => "".to_owned(),
+            CommentVariant::TomlComment => "#".to_owned(),
+            CommentVariant::YamlEntry => "".to_owned(),
+            CommentVariant::Synthetic => "".to_owned(),
+            CommentVariant::PlainText => "".to_owned(),
+            CommentVariant::AsciiDoc => "".to_owned(),
+            CommentVariant::ForeignComment => "".to_owned(),
             unhandled => {
                 unreachable!("String representation for comment variant {unhandled:?} exists. qed")
             }
@@ -442,6 +474,142 @@ impl TrimmedLiteral {
     }
 }
 
+impl TrimmedLiteral {
+    /// Load a plain or raw string literal, i.e. `"foo"` or `r#"foo"#`, at the
+    /// given `span` of `content`.
+    pub(crate) fn load_string_literal_from(content: &str, mut span: Span) -> Result<Self> {
+        // the end of a `proc_macro2::Span` is exclusive
+        span.end.column = span.end.column.saturating_sub(1);
+
+        let rendered = util::load_span_from(content.as_bytes(), span)?;
+
+        // `r`, the `#`s and `"` vs. `"` and the `#`s
+        let (pre, post) = if let Some(raw) = rendered.strip_prefix('r') {
+            let hashes = raw.chars().take_while(|c| *c == '#').count();
+            (hashes + 2, hashes + 1)
+        } else if rendered.starts_with('"') {
+            (1, 1)
+        } else {
+            return Err(Error::Span(format!("Not a string literal >{rendered}<")));
+        };
+
+        let len_in_chars = rendered.chars().count().saturating_sub(pre + post);
+        let len_in_bytes = rendered.len().saturating_sub(pre + post);
+
+        span.start.column += pre;
+        span.end.column = span.end.column.saturating_sub(post);
+
+        Ok(Self {
+            variant: CommentVariant::StringLiteral,
+            span,
+            rendered,
+            pre,
+            post,
+            len_in_chars,
+            len_in_bytes,
+        })
+    }
+}
+
+/// The end of the escape or format placeholder starting at `idx`, if any,
+/// along with whether it stands for whitespace.
+fn escape_or_placeholder(chars: &[char], idx: usize) -> Option<(usize, bool)> {
+    let next = chars.get(idx + 1).copied();
+    match (chars[idx], next) {
+        // `\u{7FFF}`
+        ('\\', Some('u')) => {
+            let end = chars[idx..]
+                .iter()
+                .position(|&c| c == '}')
+                .map_or(chars.len(), |offset| idx + offset + 1);
+            Some((end, false))
+        }
+        // `\x7F`
+        ('\\', Some('x')) => Some(((idx + 4).min(chars.len()), false)),
+        // a line continuation skips the leading whitespace of the next line
+        ('\\', Some(c)) if c.is_whitespace() => {
+            let whitespace = chars[(idx + 1)..]
+                .iter()
+                .take_while(|c| c.is_whitespace())
+                .count();
+            Some((idx + 1 + whitespace, true))
+        }
+        ('\\', Some(c)) => Some((idx + 2, matches!(c, 'n' | 'r' | 't'))),
+        // `{{` and `}}` are escaped braces
+        ('{', Some('{')) | ('}', Some('}')) => Some((idx + 2, false)),
+        ('{', _) => chars[idx..]
+            .iter()
+            .position(|&c| c == '}')
+            .map(|offset| (idx + offset + 1, false)),
+        _ => None,
+    }
+}
+
+/// Erase the escapes, i.e. `\n` or `\"`, and the format placeholders, i.e.
+/// `{}` or `{name:?}`, of the content of a string literal, just like inline
+/// code is erased from markdown. Escaped whitespace becomes a space, so the
+/// words around it are kept apart.
+///
+/// Returns the plain text and the mapping of its char ranges to those of
+/// `content`.
+pub(crate) fn erase_escapes(content: &str) -> (String, IndexMap<Range, SourceRange>) {
+    let chars = Vec::from_iter(content.chars());
+    // the char ranges of `content` which are kept, `None` for an inserted space
+    let mut kept = Vec::<Option<Range>>::new();
+    let keep = |range: Range, kept: &mut Vec<Option<Range>>| {
+        // split at line breaks, which are not covered by any span
+        let mut start = range.start;
+        for idx in range.clone() {
+            if chars[idx] == '\n' {
+                kept.push(Some(start..idx));
+                kept.push(Some(idx..(idx + 1)));
+                start = idx + 1;
+            }
+        }
+        kept.push(Some(start..range.end));
+    };
+    let mut idx = 0;
+    let mut start = 0;
+    while idx < chars.len() {
+        match escape_or_placeholder(&chars, idx) {
+            Some((end, whitespace)) => {
+                keep(start..idx, &mut kept);
+                if whitespace {
+                    kept.push(None);
+                }
+                idx = end;
+                start = end;
+            }
+            None => idx += 1,
+        }
+    }
+    keep(start..chars.len(), &mut kept);
+
+    let mut plain = String::with_capacity(content.len());
+    let mut mapping = IndexMap::with_capacity(kept.len());
+    let mut cursor = 0;
+    for range in kept {
+        match range {
+            Some(range) if !range.is_empty() => {
+                plain.extend(&chars[range.clone()]);
+                if chars[range.start] != '\n' {
+                    mapping.insert(
+                        cursor..(cursor + range.len()),
+                        SourceRange::Direct(range.clone()),
+                    );
+                }
+                cursor += range.len();
+            }
+            Some(_) => {}
+            None => {
+                plain.push(' ');
+                cursor += 1;
+            }
+        }
+    }
+    (plain, mapping)
+}
+
 impl TrimmedLiteral {
     /// Creates a new (single line) literal from the variant, the content, the
     /// size of the pre & post and the line/column on which it starts. Fails if
@@ -715,6 +883,25 @@ mod tests {
         };
     }
 
+    #[test]
+    fn erase_string_literal_escapes() {
+        let content = "Found {} \\u{1F600}items\\tin \\\"{path}\\\" {{x}}";
+        let (plain, mapping) = erase_escapes(content);
+        assert_eq!(plain, "Found  items in  x");
+        let fragments = Vec::from_iter(mapping.into_iter().map(|(plain_range, raw)| {
+            let raw = String::from_iter(content.chars().skip(raw.start).take(raw.len()));
+            assert_eq!(sub_chars(&plain, plain_range), raw);
+            raw
+        }));
+        assert_eq!(fragments, vec!["Found ", " ", "items", "in ", " ", "x"]);
+    }
+
+    #[test]
+    fn erase_string_literal_continuation() {
+        let (plain, _mapping) = erase_escapes("Spans \\\n    lines\nand more");
+        assert_eq!(plain, "Spans  lines\nand more");
+    }
+
     block_comment_test!(trimmed_oneline_doc, "/** dooc */");
     block_comment_test!(trimmed_oneline_mod, "/*! dooc */");
 
@@ -0,0 +1,227 @@
+//! String fields of a `Cargo.toml` manifest, located by their dotted path.
+//!
+//! The toml parser only provides spans for statically known structures, so
+//! the path is followed by a deserialize seed which skips all other entries.
+
+use crate::{chunk_from_lines, CheckableChunk, CommentVariant, Range};
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, Visitor};
+use serde::Deserialize;
+use std::fmt;
+use toml::Spanned;
+
+/// Split a dotted path at the dots outside of double quotes, i.e.
+/// `package.metadata."docs.rs".notes`.
+pub(crate) fn split_field_path(path: &str) -> Vec<String> {
+    let mut acc = vec![String::new()];
+    let mut quoted = false;
+    for c in path.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            '.' if !quoted => acc.push(String::new()),
+            c => acc
+                .last_mut()
+                .expect("Has at least one element. qed")
+                .push(c),
+        }
+    }
+    acc
+}
+
+/// Locate the string value at `path` in `manifest_content`.
+///
+/// Returns the byte range of the value including its delimiters, if there is
+/// a string at `path`.
+pub(crate) fn find_string_field(
+    manifest_content: &str,
+    path: &[String],
+) -> Result<Option<Range>, toml::de::Error> {
+    FieldSeed { path }.deserialize(toml::Deserializer::new(manifest_content))
+}
+
+/// Create a chunk from the string value at `range`, line by line, without the
+/// delimiters.
+///
+/// A line ending backslash of a basic string is not part of the chunk, just
+/// like the whitespace trimmed along with it, so each line maps to the exact
+/// position of its text within the manifest.
+pub(crate) fn chunk_from_string_value(manifest_content: &str, range: Range) -> CheckableChunk {
+    let value = &manifest_content[range.clone()];
+    let delimiter = if value.starts_with("\"\"\"") || value.starts_with("'''") {
+        3
+    } else {
+        1
+    };
+    let literal = value.starts_with('\'');
+    let start = range.start + delimiter;
+    let end = range.end.saturating_sub(delimiter).max(start);
+    let before = &manifest_content[..start];
+    let first_line = before.matches('\n').count() + 1;
+    let first_column = before
+        .rsplit('\n')
+        .next()
+        .map(|line| line.chars().count())
+        .unwrap_or_default();
+    let mut lines = Vec::new();
+    // all whitespace up to the next content is trimmed after a line ending
+    // backslash, including line breaks
+    let mut continued = false;
+    for (idx, line) in manifest_content[start..end].split('\n').enumerate() {
+        let line = line.trim_end_matches('\r');
+        let mut column = if idx == 0 { first_column } else { 0 };
+        let mut text = line;
+        if continued {
+            text = line.trim_start();
+            if text.is_empty() {
+                continue;
+            }
+            column += line[..(line.len() - text.len())].chars().count();
+        }
+        continued = false;
+        if !literal {
+            if let Some(stripped) = strip_line_ending_backslash(text) {
+                text = stripped;
+                continued = true;
+            }
+        }
+        lines.push((first_line + idx, column, text));
+    }
+    chunk_from_lines(&lines, CommentVariant::TomlEntry)
+}
+
+/// Strip a line ending backslash and the whitespace before it from `line`,
+/// if it ends with one, an escaped backslash `\\` does not count.
+fn strip_line_ending_backslash(line: &str) -> Option<&str> {
+    let trimmed = line.trim_end_matches(|c: char| c == ' ' || c == '\t');
+    let backslashes = trimmed.len() - trimmed.trim_end_matches('\\').len();
+    (backslashes % 2 == 1).then(|| trimmed[..(trimmed.len() - 1)].trim_end())
+}
+
+/// Follows the remaining `path` into nested tables.
+struct FieldSeed<'p> {
+    path: &'p [String],
+}
+
+impl<'de, 'p> DeserializeSeed<'de> for FieldSeed<'p> {
+    type Value = Option<Range>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match self.path.split_first() {
+            None => {
+                let value = Spanned::<toml::Value>::deserialize(deserializer)?;
+                Ok(match value.get_ref() {
+                    toml::Value::String(_) => Some(value.span()),
+                    _ => None,
+                })
+            }
+            Some((key, rest)) => deserializer.deserialize_any(TableVisitor { key, rest }),
+        }
+    }
+}
+
+struct TableVisitor<'p> {
+    key: &'p str,
+    rest: &'p [String],
+}
+
+impl<'de, 'p> Visitor<'de> for TableVisitor<'p> {
+    type Value = Option<Range>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a table with the key `{}`", self.key)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut found = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == self.key {
+                found = map.next_value_seed(FieldSeed { path: self.rest })?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LineColumn;
+
+    const MANIFEST: &str = r#"[workspace.package]
+description = "Shared descripton"
+
+[package.metadata."docs.rs"]
+notes = """
+Bulid with all
+features."""
+"#;
+
+    #[test]
+    fn fields() {
+        assert_eq!(
+            split_field_path(r#"package.metadata."docs.rs".notes"#),
+            vec!["package", "metadata", "docs.rs", "notes"]
+        );
+
+        let range = find_string_field(MANIFEST, &split_field_path("workspace.package.description"))
+            .unwrap()
+            .unwrap();
+        let chunk = chunk_from_string_value(MANIFEST, range);
+        assert_eq!(chunk.as_str(), "Shared descripton");
+        let span = chunk.find_spans(7..17).into_values().next().unwrap();
+        assert_eq!(
+            span.start,
+            LineColumn {
+                line: 2,
+                column: 22
+            }
+        );
+
+        let range = find_string_field(
+            MANIFEST,
+            &split_field_path(r#"package.metadata."docs.rs".notes"#),
+        )
+        .unwrap()
+        .unwrap();
+        let chunk = chunk_from_string_value(MANIFEST, range);
+        assert_eq!(chunk.as_str(), "\nBulid with all\nfeatures.");
+        let span = chunk.find_spans(1..6).into_values().next().unwrap();
+        assert_eq!(span.start, LineColumn { line: 6, column: 0 });
+
+        assert_eq!(
+            find_string_field(MANIFEST, &split_field_path("package.description")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn line_ending_backslash() {
+        const MANIFEST: &str = "[package]
+description = \"\"\"
+A descripton \\
+    which continues, \\
+
+  even \\\\
+after a backslash.\"\"\"
+";
+        let range = find_string_field(MANIFEST, &split_field_path("package.description"))
+            .unwrap()
+            .unwrap();
+        let chunk = chunk_from_string_value(MANIFEST, range);
+        assert_eq!(
+            chunk.as_str(),
+            "\nA descripton\nwhich continues,\neven \\\\\nafter a backslash."
+        );
+        let span = chunk.find_spans(14..19).into_values().next().unwrap();
+        assert_eq!(span.start, LineColumn { line: 4, column: 4 });
+        let span = chunk.find_spans(31..35).into_values().next().unwrap();
+        assert_eq!(span.start, LineColumn { line: 6, column: 2 });
+    }
+}
@@ -74,6 +74,70 @@ fn scoped() {
     assert_eq!(true, is_html_tag_on_no_scope_list("<pre>🌡</pre>\n"));
 }
 
+/// Join the lines of inline code cmark does not recognize, since the code
+/// span is interrupted by a paragraph break or another block, i.e. a code span
+/// split across `///` lines with an empty one in between.
+///
+/// Unpaired backticks are yielded as text by cmark, if the next unpaired run of
+/// backticks is of the same length and only separated by the line breaks at
+/// `boundaries`, the byte offsets of the line breaks between the fragments of
+/// a chunk, with at most one empty line in between, those line breaks are
+/// replaced by spaces. The backticks of recognized code spans are never paired.
+/// Both are single bytes, so all offsets remain valid. Returns `None` if there
+/// is nothing to join.
+fn join_split_code_spans(cmark: &str, boundaries: &[usize]) -> Option<String> {
+    if boundaries.is_empty() {
+        return None;
+    }
+    let parser = Parser::new_ext(cmark, Options::all() ^ Options::ENABLE_SMART_PUNCTUATION);
+    let mut unpaired = Vec::new();
+    for (event, byte_range) in parser.into_offset_iter() {
+        let s = match event {
+            Event::Text(s) => s,
+            _ => continue,
+        };
+        // escaped backticks are plain text
+        if !s.is_empty()
+            && s.chars().all(|c| c == '`')
+            && cmark.get(byte_range.clone()) == Some(s.as_ref())
+            && !cmark[..byte_range.start].ends_with('\\')
+        {
+            unpaired.push(byte_range);
+        }
+    }
+
+    let mut joined = None::<String>;
+    let mut unpaired = unpaired.into_iter().peekable();
+    while let Some(opening) = unpaired.next() {
+        let closing = match unpaired.peek() {
+            Some(closing) if closing.len() == opening.len() => closing.start,
+            _ => continue,
+        };
+        let between = &cmark[opening.end..closing];
+        let breaks = Vec::from_iter(
+            between
+                .match_indices('\n')
+                .map(|(idx, _)| opening.end + idx),
+        );
+        let empty_lines = between
+            .lines()
+            .filter(|line| line.trim().is_empty())
+            .count();
+        if breaks.is_empty()
+            || empty_lines > 1
+            || !breaks.iter().all(|idx| boundaries.contains(idx))
+        {
+            continue;
+        }
+        joined
+            .get_or_insert_with(|| cmark.to_owned())
+            .replace_range(opening.end..closing, &between.replace('\n', " "));
+        // the closing run is consumed
+        unpaired.next();
+    }
+    joined
+}
+
 /// A plain representation of cmark riddled chunk.
 #[derive(Clone)]
 pub struct PlainOverlay<'a> {
@@ -406,7 +470,47 @@ impl<'a> PlainOverlay<'a> {
     // TODO consider returning a `Vec<PlainOverlay<'a>>` to account for list items
     // or other non-linear information which might not pass a grammar check as a whole
     pub fn erase_cmark(chunk: &'a CheckableChunk, ignores: &Ignores) -> Self {
-        let (plain, mapping) = Self::extract_plain_with_mapping(chunk.as_str(), ignores);
+        // plain text has no markup to erase
+        if chunk.variant() == CommentVariant::PlainText {
+            let len = chunk.len_in_chars();
+            return Self {
+                raw: chunk,
+                plain: chunk.as_str().to_owned(),
+                mapping: indexmap::indexmap! { 0..len => SourceRange::Direct(0..len) },
+            };
+        }
+        // asciidoc has an inline syntax of its own
+        if chunk.variant() == CommentVariant::AsciiDoc {
+            let (plain, mapping) = crate::asciidoc::erase_inline(chunk.as_str());
+            return Self {
+                raw: chunk,
+                plain,
+                mapping,
+            };
+        }
+        // string literals carry escapes and format placeholders instead
+        if chunk.variant() == CommentVariant::StringLiteral {
+            let (plain, mapping) = crate::literal::erase_escapes(chunk.as_str());
+            return Self {
+                raw: chunk,
+                plain,
+                mapping,
+            };
+        }
+        // the line breaks inserted between the fragments, i.e. the lines of a
+        // doc comment, are not covered by any of them
+        let content = chunk.as_str();
+        let boundaries = Vec::from_iter(
+            content
+                .char_indices()
+                .enumerate()
+                .filter(|(_offset, (_idx, c))| *c == '\n')
+                .filter(|(offset, _)| !chunk.iter().any(|(range, _span)| range.contains(offset)))
+                .map(|(_offset, (idx, _c))| idx),
+        );
+        let joined = join_split_code_spans(content, &boundaries);
+        let (plain, mapping) =
+            Self::extract_plain_with_mapping(joined.as_deref().unwrap_or(content), ignores);
         Self {
             raw: chunk,
             plain,
@@ -20,15 +20,25 @@ use super::CheckableChunk;
 ///
 /// Line values are 1-indexed relative, lines are inclusive. Column values in
 /// UTF-8 characters in a line, 0-indexed and inclusive.
-#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+#[derive(Clone, Debug, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Span {
     /// Start of the span, inclusive, see
     /// [`LineColumn`](proc_macro2::LineColumn).
+    #[serde(with = "LineColumnDef")]
     pub start: LineColumn,
     /// End of the span, inclusive, see [`LineColumn`](proc_macro2::LineColumn).
+    #[serde(with = "LineColumnDef")]
     pub end: LineColumn,
 }
 
+/// Serialization of the foreign [`LineColumn`].
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(remote = "LineColumn")]
+struct LineColumnDef {
+    line: usize,
+    column: usize,
+}
+
 impl Hash for Span {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.start.line.hash(state);
@@ -0,0 +1,207 @@
+//! Public items without any documentation.
+//!
+//! Only items with an explicit and unrestricted `pub` visibility are
+//! considered, including named fields. Enum variants and trait items inherit
+//! their visibility and are not covered. Out of line modules, `pub mod foo;`,
+//! carry their documentation in their own file and are skipped, as are the
+//! bodies of macros.
+
+use crate::{LineColumn, Span};
+use proc_macro2::{Delimiter, Ident, Spacing, TokenStream, TokenTree};
+
+/// Keywords which introduce a named item.
+const ITEM_KEYWORDS: &[&str] = &[
+    "struct", "enum", "union", "trait", "fn", "mod", "type", "const", "static",
+];
+
+/// Keywords which may be placed between the visibility and the name of an
+/// item.
+const MODIFIERS: &[&str] = &["async", "unsafe", "extern", "mut", "auto"];
+
+/// Check if the attribute `stream`, the content of `#[..]`, is a `doc`
+/// attribute. `#[doc(hidden)]` counts as well.
+fn is_doc_attribute(stream: TokenStream) -> bool {
+    matches!(stream.into_iter().next(), Some(TokenTree::Ident(ident)) if ident == "doc")
+}
+
+/// Check if the outer attributes at the end of `trees` contain a `doc`
+/// attribute.
+fn has_doc_attribute(mut trees: &[TokenTree]) -> bool {
+    while let [rest @ .., TokenTree::Punct(hash), TokenTree::Group(group)] = trees {
+        if hash.as_char() != '#' || group.delimiter() != Delimiter::Bracket {
+            break;
+        }
+        if is_doc_attribute(group.stream()) {
+            return true;
+        }
+        trees = rest;
+    }
+    false
+}
+
+/// Check if the inner attributes at the start of a module body contain a `doc`
+/// attribute, i.e. `//!` comments.
+fn has_inner_doc_attribute(stream: TokenStream) -> bool {
+    let trees = Vec::from_iter(stream);
+    for attribute in trees.chunks(3) {
+        match attribute {
+            [TokenTree::Punct(hash), TokenTree::Punct(bang), TokenTree::Group(group)]
+                if hash.as_char() == '#' && bang.as_char() == '!' =>
+            {
+                if is_doc_attribute(group.stream()) {
+                    return true;
+                }
+            }
+            _ => break,
+        }
+    }
+    false
+}
+
+/// Check if the group following `trees` is the body of a macro invocation or
+/// definition, i.e. `foo! { .. }` or `macro_rules! foo { .. }`.
+fn is_macro_body(trees: &[TokenTree]) -> bool {
+    match trees {
+        [.., TokenTree::Punct(bang)] => bang.as_char() == '!',
+        [.., TokenTree::Punct(bang), TokenTree::Ident(_)] => bang.as_char() == '!',
+        _ => false,
+    }
+}
+
+/// Find the name of the item following its visibility in `rest`.
+///
+/// Returns `None` for items without a name, i.e. `use` declarations, and out
+/// of line or documented modules.
+fn item_name(rest: &[TokenTree]) -> Option<&Ident> {
+    let mut keyword = None;
+    for (idx, tree) in rest.iter().enumerate() {
+        let ident = match tree {
+            TokenTree::Ident(ident) => ident,
+            // the ABI of `extern "C" fn`
+            TokenTree::Literal(_) => continue,
+            _ => return None,
+        };
+        let s = ident.to_string();
+        if ITEM_KEYWORDS.contains(&s.as_str()) {
+            keyword = Some(s);
+            continue;
+        }
+        if MODIFIERS.contains(&s.as_str()) {
+            continue;
+        }
+        if s == "_" {
+            return None;
+        }
+        return match (keyword.as_deref(), rest.get(idx + 1)) {
+            (Some("mod"), Some(TokenTree::Group(body))) => {
+                Some(ident).filter(|_| !has_inner_doc_attribute(body.stream()))
+            }
+            (Some("mod"), _) => None,
+            (Some(_), _) => Some(ident),
+            // a named field
+            (None, Some(TokenTree::Punct(colon)))
+                if colon.as_char() == ':' && colon.spacing() == Spacing::Alone =>
+            {
+                Some(ident)
+            }
+            (None, _) => None,
+        };
+    }
+    None
+}
+
+/// Collect the names of all public items in `stream` which lack
+/// documentation, along with the span of the name.
+fn collect(stream: TokenStream, acc: &mut Vec<(String, Span)>) {
+    let trees = Vec::from_iter(stream);
+    for (idx, tree) in trees.iter().enumerate() {
+        match tree {
+            TokenTree::Group(group) if !is_macro_body(&trees[..idx]) => {
+                collect(group.stream(), acc);
+            }
+            TokenTree::Ident(ident) if ident == "pub" => {
+                // restricted visibility, i.e. `pub(crate)`
+                if matches!(trees.get(idx + 1), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis)
+                {
+                    continue;
+                }
+                if has_doc_attribute(&trees[..idx]) {
+                    continue;
+                }
+                if let Some(name) = item_name(&trees[idx + 1..]) {
+                    let start = name.span().start();
+                    let name = name.to_string();
+                    let end = LineColumn {
+                        line: start.line,
+                        column: start.column + name.chars().count() - 1,
+                    };
+                    acc.push((name, Span { start, end }));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The names of all public items of a tokenized rust source which lack
+/// documentation, along with the span of the name.
+pub fn undocumented_public_items(stream: TokenStream) -> Vec<(String, Span)> {
+    let mut acc = Vec::new();
+    collect(stream, &mut acc);
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undocumented() {
+        const SOURCE: &str = r#"
+/// Documented.
+#[derive(Debug)]
+pub struct Documented {
+    pub field: u8,
+    /// Documented field.
+    pub(crate) restricted: u8,
+}
+
+#[doc(hidden)]
+pub fn hidden() {}
+
+pub const fn bare() {}
+
+pub(crate) fn restricted() {}
+
+pub use std::fmt;
+
+pub mod outofline;
+
+pub mod inline {
+    //! Documented module.
+    pub extern "C" fn ffi() {}
+}
+
+macro_rules! chyrp {
+    () => { pub struct Generated; };
+}
+"#;
+        let stream = syn::parse_str::<TokenStream>(SOURCE).unwrap();
+        let acc = undocumented_public_items(stream);
+        let names = Vec::from_iter(acc.iter().map(|(name, _span)| name.as_str()));
+        assert_eq!(names, vec!["field", "bare", "ffi"]);
+        assert_eq!(
+            acc[1].1,
+            Span {
+                start: LineColumn {
+                    line: 13,
+                    column: 13
+                },
+                end: LineColumn {
+                    line: 13,
+                    column: 16
+                },
+            }
+        );
+    }
+}
@@ -70,6 +70,19 @@ pub fn extract_delimiter(s: &str) -> Option<&'static str> {
         .map(|x| x.newline)
 }
 
+/// Byte order mark some editors on Windows prefix UTF-8 files with.
+pub const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// Strip a leading byte order mark, spans never account for it.
+pub fn strip_byte_order_mark(s: &str) -> &str {
+    s.strip_prefix(BYTE_ORDER_MARK).unwrap_or(s)
+}
+
+/// Use `newline` for all line breaks of `s`, be it `\n` or `\r\n`.
+pub fn normalize_newlines(s: &str, newline: &str) -> String {
+    s.replace("\r\n", "\n").replace('\n', newline)
+}
+
 /// Iterate over a str and annotate with line and column.
 ///
 /// Assumes `s` is content starting from point `start_point`.
@@ -141,7 +154,7 @@ where
         .read_to_string(&mut s)
         .expect("Must read successfully");
 
-    let extraction = iter_with_line_column(s.as_str())
+    let extraction = iter_with_line_column(strip_byte_order_mark(s.as_str()))
         .skip_while(|(_c, _byte_offset, _idx, cursor)| {
             cursor.line < span.start.line
                 || (cursor.line == span.start.line && cursor.column < span.start.column)
@@ -284,6 +297,47 @@ where
     acc
 }
 
+/// Map the byte offsets of `content` to character offsets, including the
+/// offset past the end.
+pub fn char_offsets(content: &str) -> Vec<usize> {
+    let mut char_offsets = vec![0; content.len() + 1];
+    let mut n = 0;
+    for (idx, (byte_offset, c)) in content.char_indices().enumerate() {
+        char_offsets[byte_offset..byte_offset + c.len_utf8()].fill(idx);
+        n = idx + 1;
+    }
+    char_offsets[content.len()] = n;
+    char_offsets
+}
+
+/// Append the character ranges of the words within `byte_range` of
+/// `content`.
+pub fn push_words(
+    content: &str,
+    byte_range: std::ops::Range<usize>,
+    char_offsets: &[usize],
+    acc: &mut Vec<Range>,
+) {
+    let text = &content[byte_range.clone()];
+    let mut start = None;
+    for (offset, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        let is_word_char = c.is_alphanumeric() || c == '\'';
+        match (start, is_word_char) {
+            (None, true) => start = Some(offset),
+            (Some(word_start), false) => {
+                let word_start = byte_range.start + word_start;
+                let word_end = byte_range.start + offset;
+                acc.push(char_offsets[word_start]..char_offsets[word_end]);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+}
+
 /// Extract a subset of chars by iterating. Range must be in characters.
 pub fn sub_char_range<R>(s: &str, range: R) -> &str
 where
@@ -421,6 +475,25 @@ Schlupfwespe,
         );
     }
 
+    #[test]
+    fn iter_span_doc_2_bom_crlf() {
+        const SOURCE: &str = "\u{feff}//! Zebra\r\n//! Grünfink\r\n";
+
+        let span = Span {
+            start: LineColumn { line: 1, column: 4 },
+            end: LineColumn {
+                line: 2,
+                column: 11,
+            },
+        };
+        assert_eq!(
+            load_span_from(&mut SOURCE.as_bytes(), span).expect("Must succeed"),
+            "Zebra\r\n//! Grünfink"
+        );
+        assert_eq!(normalize_newlines("a\nb\r\nc", "\r\n"), "a\r\nb\r\nc");
+        assert_eq!(normalize_newlines("a\nb\r\nc", "\n"), "a\nb\nc");
+    }
+
     #[test]
     fn sub_a() {
         const A: &str = "a🐲o🌡i🡴f🕧aodnferntkng";
@@ -0,0 +1,86 @@
+//! Backups of the original content of modified files.
+//!
+//! A safety net for workspaces without a clean version control state, the
+//! original content is saved right before the patches are applied to a file.
+
+use crate::errors::*;
+
+use fs_err as fs;
+use std::path::{Component, Path, PathBuf};
+
+const BACKUP_EXTENSION: &str = "orig";
+
+/// Where to save the original content of a file before writing changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Backup {
+    /// Beside the file, with an additional `.orig` extension.
+    Beside,
+    /// Into the directory, mirroring the path of the file relative to the
+    /// current working directory.
+    Dir(PathBuf),
+}
+
+impl Backup {
+    /// Determine the path of the backup of the file at the absolute `path`,
+    /// with `cwd` as the current working directory.
+    fn target(&self, path: &Path, cwd: &Path) -> PathBuf {
+        let mut name = path.file_name().unwrap_or_default().to_owned();
+        name.push(".");
+        name.push(BACKUP_EXTENSION);
+        match self {
+            Self::Beside => path.with_file_name(name),
+            Self::Dir(dir) => {
+                // files outside of the current working directory are mirrored
+                // by their absolute path
+                let relative = match path.strip_prefix(cwd) {
+                    Ok(relative) => relative.to_path_buf(),
+                    Err(_) => PathBuf::from_iter(
+                        path.components()
+                            .filter(|component| matches!(component, Component::Normal(_))),
+                    ),
+                };
+                cwd.join(dir).join(relative).with_file_name(name)
+            }
+        }
+    }
+
+    /// Save the original `content` of the file at the absolute `path`.
+    pub fn save(&self, path: &Path, content: &str) -> Result<()> {
+        let cwd = fs::canonicalize(crate::traverse::cwd()?)?;
+        let target = self.target(path, &cwd);
+        if let Some(dir) = target.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&target, content)
+            .wrap_err_with(|| eyre!("Failed to back up {}", path.display()))?;
+        log::debug!(
+            "Saved the original of {} to {}",
+            path.display(),
+            target.display()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets() {
+        let cwd = Path::new("/project");
+        assert_eq!(
+            Backup::Beside.target(Path::new("/project/src/lib.rs"), cwd),
+            PathBuf::from("/project/src/lib.rs.orig")
+        );
+        let backup = Backup::Dir(PathBuf::from("backup"));
+        assert_eq!(
+            backup.target(Path::new("/project/src/lib.rs"), cwd),
+            PathBuf::from("/project/backup/src/lib.rs.orig")
+        );
+        assert_eq!(
+            backup.target(Path::new("/elsewhere/README.md"), cwd),
+            PathBuf::from("/project/backup/elsewhere/README.md.orig")
+        );
+    }
+}
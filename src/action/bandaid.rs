@@ -4,6 +4,7 @@
 //! the user.
 
 use crate::documentation::Span;
+use crate::{LineColumn, Range};
 
 /// A chosen suggestion for a certain span
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -30,13 +31,115 @@ impl From<(String, &Span)> for BandAid {
     }
 }
 
+/// Merge the bandaids of each paragraph, so every paragraph is patched once.
+///
+/// Bandaids on the same or directly adjacent lines are joined into a single
+/// one, covering the original content in between them. This keeps the diff
+/// minimal and avoids several single word patches in one paragraph from
+/// conflicting. Of overlapping bandaids, only the first one is retained.
+///
+/// Insertions, where `span.start == span.end`, are never merged.
+pub(crate) fn coalesce(mut bandaids: Vec<BandAid>, source: &str) -> Vec<BandAid> {
+    let key = |lc: LineColumn| (lc.line, lc.column);
+    bandaids.sort_by_key(|bandaid| (key(bandaid.span.start), key(bandaid.span.end)));
+
+    let line_starts = Vec::from_iter(
+        std::iter::once(0).chain(source.match_indices('\n').map(|(offset, _)| offset + 1)),
+    );
+    // byte offset and character at a line column
+    let char_at = |lc: LineColumn| -> Option<(usize, char)> {
+        let line_start = *line_starts.get(lc.line.checked_sub(1)?)?;
+        source[line_start..]
+            .char_indices()
+            .nth(lc.column)
+            .map(|(offset, c)| (line_start + offset, c))
+    };
+    // byte range of the original content replaced by a bandaid
+    let replaced = |bandaid: &BandAid| -> Option<Range> {
+        let (start, _) = char_at(bandaid.span.start)?;
+        let (end, c) = char_at(bandaid.span.end)?;
+        Some(start..(end + c.len_utf8()))
+    };
+
+    let mut acc = Vec::<BandAid>::with_capacity(bandaids.len());
+    let mut last_replaced = None::<Range>;
+    for bandaid in bandaids {
+        let is_insertion = bandaid.span.start == bandaid.span.end;
+        let range = match replaced(&bandaid) {
+            Some(range) if !is_insertion => range,
+            _ => {
+                last_replaced = None;
+                acc.push(bandaid);
+                continue;
+            }
+        };
+        match (acc.last_mut(), last_replaced.as_mut()) {
+            (Some(last), Some(last_range)) if range.start < last_range.end => {
+                log::warn!("Dropping bandaid {bandaid:?}, it overlaps with {last:?}");
+            }
+            // same or adjacent line, so the same paragraph
+            (Some(last), Some(last_range)) if bandaid.span.start.line <= last.span.end.line + 1 => {
+                last.content.push_str(&source[last_range.end..range.start]);
+                last.content.push_str(&bandaid.content);
+                last.span.end = bandaid.span.end;
+                last_range.end = range.end;
+            }
+            _ => {
+                last_replaced = Some(range);
+                acc.push(bandaid);
+            }
+        }
+    }
+    acc
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
-
+    use super::BandAid;
     use crate::util::load_span_from;
 
     use crate::{LineColumn, Span};
 
+    #[test]
+    fn coalesce_paragraph() {
+        const SOURCE: &str = "/// Sme wrods\n/// in a paragrah.\n///\n/// Anothr one.\n";
+
+        let bandaid = |content: &str, line, start, end| BandAid {
+            content: content.to_owned(),
+            span: Span {
+                start: LineColumn {
+                    line,
+                    column: start,
+                },
+                end: LineColumn { line, column: end },
+            },
+        };
+        let bandaids = vec![
+            bandaid("Another", 4, 4, 9),
+            bandaid("paragraph", 2, 9, 16),
+            bandaid("Some", 1, 4, 6),
+            bandaid("words", 1, 8, 12),
+            // overlaps with the previous one
+            bandaid("rods", 1, 9, 12),
+        ];
+        assert_eq!(
+            super::coalesce(bandaids, SOURCE),
+            vec![
+                BandAid {
+                    content: "Some words\n/// in a paragraph".to_owned(),
+                    span: Span {
+                        start: LineColumn { line: 1, column: 4 },
+                        end: LineColumn {
+                            line: 2,
+                            column: 16
+                        },
+                    },
+                },
+                bandaid("Another", 4, 4, 9),
+            ]
+        );
+    }
+
     #[test]
     fn span_helper_integrity() {
         const SOURCE: &str = r#"0
@@ -0,0 +1,80 @@
+//! Collapsible sections in the logs of CI providers.
+//!
+//! Wraps the findings of each file, so long logs stay navigable.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A CI provider with support for grouping log lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiProvider {
+    /// GitHub Actions
+    Github,
+    /// GitLab CI
+    Gitlab,
+}
+
+impl CiProvider {
+    /// Detect the CI provider from the environment variables it sets.
+    pub fn detect() -> Option<Self> {
+        let is_set = |key: &str| std::env::var(key).map_or(false, |value| value == "true");
+        if is_set("GITHUB_ACTIONS") {
+            Some(Self::Github)
+        } else if is_set("GITLAB_CI") {
+            Some(Self::Gitlab)
+        } else {
+            None
+        }
+    }
+
+    /// Wrap `content` into a collapsible section titled `title`.
+    pub fn group(&self, title: &str, content: &str) -> String {
+        let content = content.trim_end_matches('\n');
+        match self {
+            Self::Github => format!("::group::{title}\n{content}\n::endgroup::\n"),
+            Self::Gitlab => {
+                let name = gitlab_section_name(title);
+                let timestamp = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_secs())
+                    .unwrap_or_default();
+                format!(
+                    "\x1b[0Ksection_start:{timestamp}:{name}[collapsed=true]\r\x1b[0K{title}\n\
+                    {content}\n\
+                    \x1b[0Ksection_end:{timestamp}:{name}\r\x1b[0K\n"
+                )
+            }
+        }
+    }
+}
+
+/// GitLab only accepts alphanumerics, `_`, `.` and `-` in section names.
+fn gitlab_section_name(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '.' | '-' => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn github() {
+        assert_eq!(
+            CiProvider::Github.group("src/lib.rs", "a\nb\n"),
+            "::group::src/lib.rs\na\nb\n::endgroup::\n"
+        );
+    }
+
+    #[test]
+    fn gitlab_name() {
+        assert_eq!(gitlab_section_name("src/some file.rs"), "src_some_file.rs");
+        let group = CiProvider::Gitlab.group("src/lib.rs", "a");
+        assert!(group.contains(":src_lib.rs[collapsed=true]\r\x1b[0Ksrc/lib.rs\na\n"));
+        assert!(group.contains("section_end:"));
+    }
+}
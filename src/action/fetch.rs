@@ -0,0 +1,138 @@
+//! Download hunspell dictionaries.
+//!
+//! CI containers and Windows commonly lack system dictionaries, so the `.dic`
+//! and `.aff` pair of a language can be fetched from a mirror into the user
+//! cache directory and registered as search directory in the configuration.
+
+use crate::config::{Config, Lang5};
+use crate::errors::*;
+
+use fs_err as fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Default mirror of hunspell dictionaries.
+///
+/// `{language}` is replaced by the language code, i.e. `en`, `{lang}` by the
+/// language and country code `en_US` and `{ext}` by either `dic` or `aff`.
+pub const DEFAULT_DICTIONARY_MIRROR: &str =
+    "https://raw.githubusercontent.com/LibreOffice/dictionaries/master/{language}/{lang}.{ext}";
+
+/// Expand the placeholders of the `mirror` template.
+fn dictionary_url(mirror: &str, lang: Lang5, ext: &str) -> Result<url::Url> {
+    let language = lang
+        .lang
+        .to_639_1()
+        .ok_or_else(|| eyre!("Language {lang} has no two letter code"))?;
+    let url = mirror
+        .replace("{language}", language)
+        .replace("{lang}", &lang.to_string())
+        .replace("{ext}", ext);
+    url::Url::parse(&url).wrap_err_with(|| eyre!("Dictionary mirror url {url} is invalid"))
+}
+
+/// Open a reader for `url`, `file://` urls allow local mirrors.
+fn open(url: &url::Url) -> Result<Box<dyn Read + Send + Sync>> {
+    if url.scheme() == "file" {
+        let path = url
+            .to_file_path()
+            .map_err(|()| eyre!("Not a valid file path: {url}"))?;
+        return Ok(Box::new(fs::File::open(path)?));
+    }
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(120))
+        .build();
+    let response = agent
+        .get(url.as_str())
+        .call()
+        .wrap_err_with(|| eyre!("Failed to download {url}"))?;
+    Ok(response.into_reader())
+}
+
+/// The directory fetched dictionaries are stored in.
+pub fn dictionary_dir() -> Result<PathBuf> {
+    let dir = crate::checker::project_dir()?
+        .cache_dir()
+        .join("dictionaries");
+    Ok(dir)
+}
+
+/// Download the `.dic` and `.aff` files for `lang` from `mirror` to `dest`.
+///
+/// Returns the paths of the dictionary and affix file.
+pub fn fetch_dictionary(lang: Lang5, mirror: &str, dest: &Path) -> Result<(PathBuf, PathBuf)> {
+    fs::create_dir_all(dest)?;
+    let fetch = |ext: &str| -> Result<PathBuf> {
+        let url = dictionary_url(mirror, lang, ext)?;
+        let path = dest.join(format!("{lang}.{ext}"));
+        // avoid leaving truncated files behind on failure
+        let partial = dest.join(format!("{lang}.{ext}.part"));
+        log::info!("Fetching {url} to {}", path.display());
+        let mut reader = open(&url)?;
+        let mut file = fs::File::create(&partial)?;
+        let n = std::io::copy(&mut reader, &mut file)
+            .wrap_err_with(|| eyre!("Failed to fetch {url}"))?;
+        if n == 0 {
+            let _ = fs::remove_file(&partial);
+            bail!("Fetched an empty file from {url}");
+        }
+        fs::rename(&partial, &path)?;
+        Ok(path)
+    };
+    let dic = fetch("dic")?;
+    let aff = fetch("aff")?;
+    Ok((dic, aff))
+}
+
+/// Use `lang` for the hunspell checker and add `dir` to its search dirs.
+pub fn register_dictionary(config: &mut Config, lang: Lang5, dir: &Path) {
+    let hunspell = config.hunspell.get_or_insert_with(Default::default);
+    hunspell.lang = lang;
+    if !hunspell.search_dirs.0.iter().any(|known| known == dir) {
+        hunspell.search_dirs.0.push(dir.to_owned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::TempDir;
+    use std::str::FromStr;
+
+    #[test]
+    fn url() {
+        let lang = Lang5::from_str("en_GB").unwrap();
+        assert_eq!(
+            dictionary_url(DEFAULT_DICTIONARY_MIRROR, lang, "aff")
+                .unwrap()
+                .as_str(),
+            "https://raw.githubusercontent.com/LibreOffice/dictionaries/master/en/en_GB.aff"
+        );
+        assert!(dictionary_url("{lang}.{ext}", lang, "dic").is_err());
+    }
+
+    #[test]
+    fn fetch_from_local_mirror() {
+        let base = TempDir::new();
+        let mirror = base.join("mirror").join("en");
+        fs::create_dir_all(&mirror).unwrap();
+        fs::write(mirror.join("en_US.dic"), "1\nfluff\n").unwrap();
+        fs::write(mirror.join("en_US.aff"), "SET UTF-8\n").unwrap();
+
+        let template = url::Url::from_directory_path(base.join("mirror"))
+            .unwrap()
+            .to_string()
+            + "{language}/{lang}.{ext}";
+        let dest = base.join("dest");
+        let (dic, aff) = fetch_dictionary(Lang5::en_US, &template, &dest).unwrap();
+        assert_eq!(dic, dest.join("en_US.dic"));
+        assert_eq!(fs::read_to_string(aff).unwrap(), "SET UTF-8\n");
+
+        let mut config = Config::default();
+        register_dictionary(&mut config, Lang5::en_US, &dest);
+        register_dictionary(&mut config, Lang5::en_US, &dest);
+        let hunspell = config.hunspell.unwrap();
+        assert_eq!(hunspell.search_dirs.0, vec![dest]);
+    }
+}
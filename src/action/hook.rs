@@ -0,0 +1,67 @@
+//! Git hook integration.
+//!
+//! The pre-commit hook feeds the staged files via `stdin`, so only those are
+//! checked, and aborts the commit on the first mistake without printing the
+//! findings.
+
+use crate::errors::*;
+
+use fs_err as fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The pre-commit hook script.
+pub const PRE_COMMIT: &str = r#"#!/bin/sh
+# Installed by `cargo spellcheck hook install`.
+#
+# Checks the staged rust and markdown files, run `cargo spellcheck check` on
+# them to see the findings.
+git diff --cached --name-only --diff-filter=ACMR -- '*.rs' '*.md' \
+    | cargo spellcheck check --quiet --max-issues 0 --paths-from-stdin
+"#;
+
+/// The hooks directory of the git repository containing `dir`, which respects
+/// `core.hooksPath` and work trees.
+fn hooks_dir(dir: &Path) -> Result<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", "hooks"])
+        .current_dir(dir)
+        .output()
+        .wrap_err("Failed to run `git`")?;
+    if !output.status.success() {
+        bail!(
+            "Not a git repository {}: {}",
+            dir.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let hooks = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Ok(if hooks.is_absolute() {
+        hooks
+    } else {
+        dir.join(hooks)
+    })
+}
+
+/// Write the pre-commit hook script into the git repository containing `dir`,
+/// returns the path of the script.
+///
+/// An existing hook is only replaced with `force`.
+pub fn install_pre_commit(dir: &Path, force: bool) -> Result<PathBuf> {
+    let hooks = hooks_dir(dir)?;
+    fs::create_dir_all(&hooks)?;
+    let path = hooks.join("pre-commit");
+    if path.exists() && !force {
+        bail!(
+            "Attempting to overwrite the existing hook {} requires `--force`.",
+            path.display()
+        );
+    }
+    fs::write(&path, PRE_COMMIT)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(path)
+}
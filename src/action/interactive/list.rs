@@ -0,0 +1,797 @@
+//! List view of all suggestions of a file.
+//!
+//! Allows to navigate, search and decide upon multiple suggestions at once,
+//! the replacement prompt of the parent module is used to pick a particular or
+//! custom replacement of a single suggestion.
+
+use super::*;
+
+use crossterm::style::Print;
+
+const LIST_HELP: &str = r##"up/down - move the cursor
+space - mark the suggestion under the cursor
+* - mark all visible suggestions, or unmark them
+y - apply the first replacement of the marked suggestions
+a - apply the first replacement to all identical mistakes of this run
+n - do not apply the marked suggestions
+U - leave the marked suggestions undecided
+u - undo the last decision, going back to the previous file if there is none
+e, enter - pick a replacement for the suggestion under the cursor
+/ - search the flagged words, enter to keep and esc to clear the filter
+d, N - apply the decisions and continue with the next file
+q - quit; do not apply the decisions of this file or any of the remaining ones
+? - toggle this help
+
+Without any marked suggestions, the one under the cursor is used.
+"##;
+
+/// Decision of the user for a single suggestion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Decision {
+    /// Not decided upon yet.
+    Undecided,
+    /// The bandaid is going to be applied.
+    Accept(BandAid),
+    /// No replacement is going to be applied.
+    Skip,
+}
+
+/// The decisions of a reviewed file, kept to return to the file for undoing
+/// them.
+#[derive(Debug, Default)]
+pub(crate) struct Review {
+    decisions: Vec<Decision>,
+    /// The decisions and identical mistakes to fix before each change, the
+    /// latest last.
+    history: Vec<(Vec<Decision>, ApplyToAll)>,
+}
+
+impl Review {
+    /// Revert the last change of the decisions, returns `false` if there is
+    /// nothing to undo.
+    pub(crate) fn undo(&mut self, apply_to_all: &mut ApplyToAll) -> bool {
+        let Some((decisions, previous)) = self.history.pop() else {
+            return false;
+        };
+        self.decisions = decisions;
+        *apply_to_all = previous;
+        true
+    }
+}
+
+/// A suggestion of the list.
+#[derive(Debug)]
+struct Entry<'s, 't> {
+    suggestion: &'s Suggestion<'t>,
+    /// The flagged content, used for searching.
+    flagged: String,
+    decision: Decision,
+    /// Marked for the next bulk operation.
+    marked: bool,
+}
+
+/// Check if all characters of `needle` appear in `haystack` in the same
+/// order, ignoring the case.
+fn fuzzy_match(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars().flat_map(char::to_lowercase);
+    needle
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|c| haystack.any(|h| h == c))
+}
+
+/// Accept the first replacement of a suggestion, if there is any.
+fn first_replacement(suggestion: &Suggestion<'_>) -> Option<Decision> {
+    suggestion
+        .replacements
+        .first()
+        .map(|replacement| Decision::Accept(BandAid::from((replacement.clone(), &suggestion.span))))
+}
+
+/// State of the list view.
+#[derive(Debug)]
+struct ListView<'s, 't> {
+    entries: Vec<Entry<'s, 't>>,
+    /// Entries not matching the query are hidden.
+    query: String,
+    /// The query is being edited.
+    searching: bool,
+    /// Show the help instead of the list.
+    help: bool,
+    /// Position of the cursor within the visible entries.
+    cursor: usize,
+    /// Number of visible entries scrolled past.
+    scroll: usize,
+    /// The decisions and identical mistakes to fix before each change, the
+    /// latest last.
+    history: Vec<(Vec<Decision>, ApplyToAll)>,
+}
+
+impl<'s, 't> ListView<'s, 't> {
+    fn new(suggestions: &'s [Suggestion<'t>], apply_to_all: &ApplyToAll) -> Self {
+        let entries = suggestions
+            .iter()
+            .map(|suggestion| Entry {
+                suggestion,
+                flagged: sub_chars(suggestion.chunk.as_str(), suggestion.range.clone()),
+                decision: apply_to_all
+                    .bandaid(suggestion)
+                    .map(Decision::Accept)
+                    .unwrap_or(Decision::Undecided),
+                marked: false,
+            })
+            .collect();
+        Self {
+            entries,
+            query: String::new(),
+            searching: false,
+            help: false,
+            cursor: 0,
+            scroll: 0,
+            history: Vec::new(),
+        }
+    }
+
+    /// Return to a file reviewed before, with the decisions of the `review`.
+    fn resume(suggestions: &'s [Suggestion<'t>], review: Review) -> Self {
+        let mut view = Self::new(suggestions, &ApplyToAll::default());
+        for (entry, decision) in view.entries.iter_mut().zip(review.decisions) {
+            entry.decision = decision;
+        }
+        view.history = review.history;
+        view
+    }
+
+    /// The decisions made, to return to the file later on.
+    fn review(self) -> Review {
+        Review {
+            decisions: Vec::from_iter(self.entries.into_iter().map(|entry| entry.decision)),
+            history: self.history,
+        }
+    }
+
+    /// The current decisions, to be passed to [`Self::record`] after they
+    /// were changed.
+    fn decisions(&self) -> Vec<Decision> {
+        Vec::from_iter(self.entries.iter().map(|entry| entry.decision.clone()))
+    }
+
+    /// Record the decisions `before` a change for undoing it, if they were
+    /// changed at all.
+    fn record(&mut self, before: Vec<Decision>, apply_to_all: ApplyToAll) {
+        if self
+            .entries
+            .iter()
+            .zip(before.iter())
+            .any(|(entry, decision)| entry.decision != *decision)
+        {
+            self.history.push((before, apply_to_all));
+        }
+    }
+
+    /// Revert the last change of the decisions, returns `false` if there is
+    /// nothing to undo.
+    fn undo(&mut self, apply_to_all: &mut ApplyToAll) -> bool {
+        let Some((decisions, previous)) = self.history.pop() else {
+            return false;
+        };
+        for (entry, decision) in self.entries.iter_mut().zip(decisions) {
+            entry.decision = decision;
+        }
+        *apply_to_all = previous;
+        true
+    }
+
+    /// Indices of the entries matching the query.
+    fn visible(&self) -> Vec<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_idx, entry)| fuzzy_match(&self.query, &entry.flagged))
+            .map(|(idx, _entry)| idx)
+            .collect()
+    }
+
+    /// Index of the entry under the cursor.
+    fn current(&self) -> Option<usize> {
+        self.visible().get(self.cursor).copied()
+    }
+
+    fn select_next(&mut self) {
+        let n = self.visible().len();
+        self.cursor = (self.cursor + 1).min(n.saturating_sub(1));
+    }
+
+    fn select_previous(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    /// Keep the cursor within the visible entries after the query changed.
+    fn clamp_cursor(&mut self) {
+        let n = self.visible().len();
+        self.cursor = self.cursor.min(n.saturating_sub(1));
+    }
+
+    fn push_query(&mut self, c: char) {
+        self.query.push(c);
+        self.clamp_cursor();
+    }
+
+    fn pop_query(&mut self) {
+        self.query.pop();
+        self.clamp_cursor();
+    }
+
+    fn clear_query(&mut self) {
+        self.query.clear();
+        self.clamp_cursor();
+    }
+
+    fn toggle_mark(&mut self) {
+        if let Some(idx) = self.current() {
+            self.entries[idx].marked = !self.entries[idx].marked;
+        }
+    }
+
+    /// Mark all visible entries, or unmark them if all of them are marked
+    /// already.
+    fn toggle_mark_visible(&mut self) {
+        let visible = self.visible();
+        let mark = !visible.iter().all(|idx| self.entries[*idx].marked);
+        for idx in visible {
+            self.entries[idx].marked = mark;
+        }
+    }
+
+    /// The entries a bulk operation applies to, the visible marked ones or
+    /// the one under the cursor.
+    fn targets(&self) -> Vec<usize> {
+        let marked = self
+            .visible()
+            .into_iter()
+            .filter(|idx| self.entries[*idx].marked)
+            .collect::<Vec<_>>();
+        if marked.is_empty() {
+            Vec::from_iter(self.current())
+        } else {
+            marked
+        }
+    }
+
+    /// Decide upon the targets and unmark them, `decide` returning `None`
+    /// keeps the previous decision.
+    ///
+    /// Returns the affected entries. The cursor moves on if only the entry
+    /// under the cursor was affected.
+    fn decide<F>(&mut self, decide: F) -> Vec<usize>
+    where
+        F: Fn(&Suggestion<'t>) -> Option<Decision>,
+    {
+        let targets = self.targets();
+        let single = matches!(targets.as_slice(), [idx] if !self.entries[*idx].marked);
+        for idx in targets.iter().copied() {
+            let entry = &mut self.entries[idx];
+            if let Some(decision) = decide(entry.suggestion) {
+                entry.decision = decision;
+            }
+            entry.marked = false;
+        }
+        if single {
+            self.select_next();
+        }
+        targets
+    }
+
+    /// Accept all undecided entries, that have an identical mistake in
+    /// `apply_to_all`.
+    fn accept_identical(&mut self, apply_to_all: &ApplyToAll) {
+        for entry in self.entries.iter_mut() {
+            if entry.decision == Decision::Undecided {
+                if let Some(bandaid) = apply_to_all.bandaid(entry.suggestion) {
+                    entry.decision = Decision::Accept(bandaid);
+                }
+            }
+        }
+    }
+
+    /// Count the accepted and skipped entries.
+    fn count_decided(&self) -> (usize, usize) {
+        self.entries
+            .iter()
+            .fold((0, 0), |(accepted, skipped), entry| match entry.decision {
+                Decision::Accept(_) => (accepted + 1, skipped),
+                Decision::Skip => (accepted, skipped + 1),
+                Decision::Undecided => (accepted, skipped),
+            })
+    }
+
+    /// The bandaids of all accepted entries.
+    fn bandaids(&self) -> Vec<BandAid> {
+        self.entries
+            .iter()
+            .filter_map(|entry| match &entry.decision {
+                Decision::Accept(bandaid) => Some(bandaid.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Enter the alternate screen in raw mode, both are left on drop.
+struct ScopedScreen {
+    _raw: ScopedRaw,
+}
+
+impl ScopedScreen {
+    fn new() -> Result<Self> {
+        let raw = ScopedRaw::new()?;
+        stdout()
+            .queue(terminal::EnterAlternateScreen)?
+            .queue(cursor::Hide)?
+            .flush()?;
+        Ok(Self { _raw: raw })
+    }
+}
+
+impl Drop for ScopedScreen {
+    fn drop(&mut self) {
+        let _ = stdout()
+            .queue(terminal::LeaveAlternateScreen)
+            .and_then(|stdout| stdout.flush());
+    }
+}
+
+/// Limit `s` to `width` characters.
+fn truncate(s: &str, width: usize) -> String {
+    s.chars().take(width).collect()
+}
+
+/// Render the list view, one line per visible entry, with a header and a
+/// footer line.
+fn render(
+    view: &mut ListView<'_, '_>,
+    origin: &ContentOrigin,
+    progress: &RunProgress,
+) -> Result<()> {
+    let (width, height) = terminal::size()?;
+    let width = width as usize;
+    let rows = (height as usize).saturating_sub(3).max(1);
+
+    let mut header = ContentStyle::new();
+    header.foreground_color = Some(Color::Blue);
+    header.attributes = Attribute::Bold.into();
+
+    let mut highlight = ContentStyle::new();
+    highlight.background_color = Some(Color::Black);
+    highlight.foreground_color = Some(Color::Green);
+    highlight.attributes = Attribute::Bold.into();
+
+    let mut others = ContentStyle::new();
+    others.foreground_color = Some(Color::Reset);
+
+    let mut stdout = stdout();
+    stdout
+        .queue(terminal::Clear(terminal::ClearType::All))?
+        .queue(cursor::MoveTo(0, 0))?;
+
+    if view.help {
+        for (row, line) in LIST_HELP.lines().enumerate().take(height as usize) {
+            stdout
+                .queue(cursor::MoveTo(0, row as u16))?
+                .queue(Print(truncate(line, width)))?;
+        }
+        stdout.flush()?;
+        return Ok(());
+    }
+
+    let (accepted, skipped) = view.count_decided();
+    let current = view
+        .current()
+        .map(|idx| (idx, view.entries[idx].suggestion.detector));
+    let title = format!(
+        "{origin} - {position}{detector} - {accepted} accepted, {skipped} skipped",
+        origin = origin.as_path().display(),
+        position = progress.describe(current.map(|(idx, _)| idx).unwrap_or_default()),
+        detector = current
+            .map(|(_, detector)| format!(" ({detector})"))
+            .unwrap_or_default(),
+    );
+    stdout.queue(PrintStyledContent(StyledContent::new(
+        header,
+        truncate(&title, width),
+    )))?;
+
+    // keep the cursor in sight
+    if view.cursor < view.scroll {
+        view.scroll = view.cursor;
+    } else if view.cursor >= view.scroll + rows {
+        view.scroll = view.cursor + 1 - rows;
+    }
+
+    let visible = view.visible();
+    for (pos, idx) in visible.iter().enumerate().skip(view.scroll).take(rows) {
+        let entry = &view.entries[*idx];
+        let suggestion = entry.suggestion;
+        let (status, replacement) = match &entry.decision {
+            Decision::Undecided => (
+                ' ',
+                suggestion
+                    .replacements
+                    .first()
+                    .map(String::as_str)
+                    .unwrap_or("..."),
+            ),
+            Decision::Accept(bandaid) => ('✓', bandaid.content.as_str()),
+            Decision::Skip => ('✗', entry.flagged.as_str()),
+        };
+        let line = format!(
+            "{pointer} [{mark}] {status} {line}:{column} {flagged} → {replacement} ({detector})",
+            pointer = if pos == view.cursor { '»' } else { ' ' },
+            mark = if entry.marked { 'x' } else { ' ' },
+            line = suggestion.span.start.line,
+            column = suggestion.span.start.column,
+            flagged = entry.flagged,
+            detector = suggestion.detector,
+        );
+        let style = if pos == view.cursor {
+            highlight
+        } else {
+            others
+        };
+        stdout
+            .queue(cursor::MoveTo(0, (pos - view.scroll + 1) as u16))?
+            .queue(PrintStyledContent(StyledContent::new(
+                style,
+                truncate(&line, width),
+            )))?;
+    }
+
+    let description = view
+        .current()
+        .and_then(|idx| view.entries[idx].suggestion.description.as_deref())
+        .unwrap_or_default();
+    let footer = if view.searching || !view.query.is_empty() {
+        format!("/{}", view.query)
+    } else {
+        "? - help".to_owned()
+    };
+    stdout
+        .queue(cursor::MoveTo(0, height.saturating_sub(2)))?
+        .queue(Print(truncate(description, width)))?
+        .queue(cursor::MoveTo(0, height.saturating_sub(1)))?
+        .queue(PrintStyledContent(StyledContent::new(
+            header,
+            truncate(&footer, width),
+        )))?
+        .flush()?;
+    Ok(())
+}
+
+/// Pick a replacement for a single suggestion, using the regular prompt.
+fn pick_replacement(
+    picker: &UserPicked,
+    suggestion: &Suggestion<'_>,
+    idx: usize,
+    progress: &RunProgress,
+) -> Result<UserSelection> {
+    println!("{suggestion}");
+    let mut state = State::from(suggestion);
+    loop {
+        match picker.user_input(&mut state, idx, progress)? {
+            UserSelection::Help => println!("{HELP}"),
+            UserSelection::Previous => {
+                log::warn!("Use the list view to go to another suggestion");
+            }
+            other => return Ok(other),
+        }
+    }
+}
+
+/// Let the user decide upon all `suggestions` of a file in a list view, or
+/// return to the decisions of a previous `review` of the file.
+///
+/// Returns [`UserSelection::Undo`] if there is no decision of this file left
+/// to undo, the last decision of the previous file is to be undone then.
+pub(super) fn select_from_list(
+    origin: ContentOrigin,
+    suggestions: &[Suggestion<'_>],
+    apply_to_all: &mut ApplyToAll,
+    progress: &RunProgress,
+    review: Option<Review>,
+) -> Result<(UserPicked, UserSelection, Review)> {
+    let mut picked = UserPicked::default();
+    let mut view = match review {
+        Some(review) => ListView::resume(suggestions, review),
+        None => ListView::new(suggestions, apply_to_all),
+    };
+    if view.entries.is_empty() {
+        return Ok((picked, UserSelection::Nop, view.review()));
+    }
+
+    let mut screen = None;
+    loop {
+        if screen.is_none() {
+            screen = Some(ScopedScreen::new()?);
+        }
+        render(&mut view, &origin, progress)?;
+
+        let KeyEvent {
+            code, modifiers, ..
+        } = match crossterm::event::read()
+            .wrap_err_with(|| eyre!("Something unexpected happened on the CLI"))?
+        {
+            Event::Key(event) => event,
+            // re-render, i.e. on resize
+            _ => continue,
+        };
+        log::trace!("registered key: {code:?}");
+
+        if modifiers == KeyModifiers::CONTROL && code == KeyCode::Char('c') {
+            return Ok((picked, UserSelection::Abort, view.review()));
+        }
+
+        if view.searching {
+            match code {
+                KeyCode::Enter => view.searching = false,
+                KeyCode::Esc => {
+                    view.searching = false;
+                    view.clear_query();
+                }
+                KeyCode::Backspace => view.pop_query(),
+                KeyCode::Up => view.select_previous(),
+                KeyCode::Down => view.select_next(),
+                KeyCode::Char(c) => view.push_query(c),
+                _ => {}
+            }
+            continue;
+        }
+
+        let before = view.decisions();
+        let remembered = apply_to_all.clone();
+        match code {
+            KeyCode::Up => view.select_previous(),
+            KeyCode::Down => view.select_next(),
+            KeyCode::Char(' ') => view.toggle_mark(),
+            KeyCode::Char('*') => view.toggle_mark_visible(),
+            KeyCode::Char('y') => {
+                view.decide(first_replacement);
+            }
+            KeyCode::Char('a') => {
+                for idx in view.decide(first_replacement) {
+                    let entry = &view.entries[idx];
+                    if let Decision::Accept(bandaid) = &entry.decision {
+                        apply_to_all.remember(entry.suggestion, bandaid.content.clone());
+                    }
+                }
+                view.accept_identical(apply_to_all);
+            }
+            KeyCode::Char('n') => {
+                view.decide(|_| Some(Decision::Skip));
+            }
+            KeyCode::Char('U') => {
+                view.decide(|_| Some(Decision::Undecided));
+            }
+            KeyCode::Char('u') => {
+                if !view.undo(apply_to_all) {
+                    log::trace!("Nothing to undo in {origin}");
+                    return Ok((picked, UserSelection::Undo, view.review()));
+                }
+                continue;
+            }
+            KeyCode::Enter | KeyCode::Char('e') => {
+                let idx = match view.current() {
+                    Some(idx) => idx,
+                    None => continue,
+                };
+                // the prompt prints to the regular screen
+                drop(screen.take());
+                let suggestion = view.entries[idx].suggestion;
+                let decision = match pick_replacement(&picked, suggestion, idx, progress)? {
+                    UserSelection::Replacement(bandaid) => Decision::Accept(bandaid),
+                    UserSelection::ReplacementForAll(bandaid) => {
+                        apply_to_all.remember(suggestion, bandaid.content.clone());
+                        Decision::Accept(bandaid)
+                    }
+                    UserSelection::Skip => Decision::Skip,
+                    UserSelection::SkipFile => {
+                        view.entries[idx].decision = Decision::Skip;
+                        view.record(before, remembered);
+                        break;
+                    }
+                    UserSelection::NextFile => break,
+                    usel @ (UserSelection::Abort | UserSelection::Quit) => {
+                        return Ok((picked, usel, view.review()));
+                    }
+                    _ => continue,
+                };
+                view.entries[idx].decision = decision;
+                view.accept_identical(apply_to_all);
+                view.select_next();
+            }
+            KeyCode::Char('/') => {
+                view.searching = true;
+                view.help = false;
+            }
+            KeyCode::Char('?') => view.help = !view.help,
+            KeyCode::Char('d') | KeyCode::Char('N') => break,
+            KeyCode::Char('q') | KeyCode::Esc => {
+                return Ok((picked, UserSelection::Quit, view.review()))
+            }
+            x => {
+                log::trace!("Unexpected input {x:?}");
+            }
+        }
+        view.record(before, remembered);
+    }
+    drop(screen);
+
+    picked.add_bandaids(&origin, view.bandaids());
+    Ok((picked, UserSelection::Nop, view.review()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CommentVariant, LineColumn};
+
+    fn suggestion<'s>(
+        chunk: &'s CheckableChunk,
+        range: Range,
+        replacement: &str,
+    ) -> Suggestion<'s> {
+        let span = Span {
+            start: LineColumn {
+                line: 1,
+                column: range.start,
+            },
+            end: LineColumn {
+                line: 1,
+                column: range.end - 1,
+            },
+        };
+        Suggestion {
+            detector: Detector::Dummy,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
+            origin: ContentOrigin::TestEntityRust,
+            chunk,
+            range,
+            span,
+            replacements: vec![replacement.to_owned()],
+            description: None,
+        }
+    }
+
+    #[test]
+    fn fuzzy() {
+        assert!(fuzzy_match("", "anything"));
+        assert!(fuzzy_match("tst", "Test"));
+        assert!(fuzzy_match("TE", "test"));
+        assert!(!fuzzy_match("tt", "tea"));
+        assert!(!fuzzy_match("et", "te"));
+    }
+
+    #[test]
+    fn run_progress() {
+        let progress = RunProgress {
+            file: 2,
+            files: 17,
+            before: 4,
+            total: 42,
+            pending: false,
+        };
+        assert_eq!(progress.describe(0), "file 3/17 — suggestion 5/42");
+        assert_eq!(progress.describe(3), "file 3/17 — suggestion 8/42");
+
+        let progress = RunProgress {
+            pending: true,
+            ..progress
+        };
+        assert_eq!(progress.describe(0), "file 3/17+ — suggestion 5/42+");
+    }
+
+    #[test]
+    fn bulk_operations() {
+        const CONTENT: &str = "Thsi is teh test, thsi";
+        let chunk = CheckableChunk::from_str(
+            CONTENT,
+            indexmap::indexmap! { 0..22 => Span {
+                start: LineColumn { line: 1, column: 0 },
+                end: LineColumn { line: 1, column: 21 },
+            }},
+            CommentVariant::TripleSlash,
+        );
+        let suggestions = vec![
+            suggestion(&chunk, 0..4, "This"),
+            suggestion(&chunk, 8..11, "the"),
+            suggestion(&chunk, 18..22, "this"),
+        ];
+        let mut apply_to_all = ApplyToAll::default();
+        let mut view = ListView::new(&suggestions, &apply_to_all);
+
+        // a single decision moves the cursor on
+        view.decide(|_| Some(Decision::Skip));
+        assert_eq!(view.current(), Some(1));
+        assert_eq!(view.entries[0].decision, Decision::Skip);
+
+        // the search limits bulk operations to the visible entries
+        view.push_query('h');
+        view.push_query('s');
+        assert_eq!(view.visible(), vec![0, 2]);
+        view.toggle_mark_visible();
+        view.clear_query();
+        view.toggle_mark_visible();
+        assert!(view.entries.iter().all(|entry| entry.marked));
+        view.toggle_mark_visible();
+        assert!(view.entries.iter().all(|entry| !entry.marked));
+
+        view.toggle_mark();
+        view.decide(first_replacement);
+        assert_eq!(view.count_decided(), (1, 1));
+        assert_eq!(view.current(), Some(1));
+
+        // identical mistakes are accepted as well
+        apply_to_all.remember(&suggestions[0], "This".to_owned());
+        view.entries[0].decision = Decision::Undecided;
+        view.accept_identical(&apply_to_all);
+        assert_eq!(view.count_decided(), (2, 0));
+        assert_eq!(
+            view.bandaids()
+                .into_iter()
+                .map(|bandaid| bandaid.content)
+                .collect::<Vec<_>>(),
+            vec!["This", "the"]
+        );
+    }
+
+    #[test]
+    fn undo() {
+        const CONTENT: &str = "Thsi is teh test, thsi";
+        let chunk = CheckableChunk::from_str(
+            CONTENT,
+            indexmap::indexmap! { 0..22 => Span {
+                start: LineColumn { line: 1, column: 0 },
+                end: LineColumn { line: 1, column: 21 },
+            }},
+            CommentVariant::TripleSlash,
+        );
+        let suggestions = vec![
+            suggestion(&chunk, 0..4, "This"),
+            suggestion(&chunk, 8..11, "the"),
+            suggestion(&chunk, 18..22, "this"),
+        ];
+        let mut apply_to_all = ApplyToAll::default();
+        let mut view = ListView::new(&suggestions, &apply_to_all);
+        assert!(!view.undo(&mut apply_to_all));
+
+        // skip the first one
+        let before = view.decisions();
+        view.decide(|_| Some(Decision::Skip));
+        view.record(before, apply_to_all.clone());
+
+        // apply the second one to all identical mistakes
+        let before = view.decisions();
+        let remembered = apply_to_all.clone();
+        view.decide(first_replacement);
+        apply_to_all.remember(&suggestions[1], "the".to_owned());
+        view.record(before, remembered);
+
+        // moving the cursor does not change any decision
+        let before = view.decisions();
+        view.select_next();
+        view.record(before, apply_to_all.clone());
+        assert_eq!(view.history.len(), 2);
+        assert_eq!(view.count_decided(), (1, 1));
+
+        // undone after moving on to the next file
+        let mut review = view.review();
+        assert!(review.undo(&mut apply_to_all));
+        let mut view = ListView::resume(&suggestions, review);
+        assert_eq!(view.count_decided(), (0, 1));
+        assert_eq!(apply_to_all.bandaid(&suggestions[1]), None);
+        assert!(view.undo(&mut apply_to_all));
+        assert_eq!(view.count_decided(), (0, 0));
+        assert!(!view.undo(&mut apply_to_all));
+    }
+}
@@ -13,12 +13,18 @@ use crossterm::{
     terminal, QueueableCommand,
 };
 
+use std::collections::HashMap;
 use std::io::stdout;
 
+mod list;
+pub(crate) use self::list::Review;
+
 const HELP: &str = r##"y - apply this suggestion
 n - do not apply the suggested correction
+a - apply this suggestion to all identical mistakes of this run
 q - quit; do not stage this hunk or any of the remaining ones
 d - do not apply this suggestion and skip the rest of the file
+N - leave this hunk undecided and jump to the next file
 g - select a suggestion to go to
 j - leave this hunk undecided, see next undecided hunk
 J - leave this hunk undecided, see next hunk
@@ -61,35 +67,93 @@ impl Drop for ScopedRaw {
     }
 }
 
-/// In which direction we should progress.
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    /// In order.
-    Forward,
-    /// Reverse order from the current position.
-    #[allow(unused)]
-    Backward,
-}
-
 /// The user picked something. This is the pick representation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) enum UserSelection {
     /// This `BandAid` is going to be applied.
     Replacement(BandAid),
+    /// This `BandAid` is going to be applied, and so is the same replacement
+    /// to all further identical mistakes.
+    ReplacementForAll(BandAid),
     /// Skip this suggestion and move on to the next suggestion.
     Skip,
     /// Jump to the previous suggestion.
     Previous,
     /// Print the help message and exit.
     Help,
-    /// Skip the remaining fixes for the current file.
+    /// Skip this suggestion and the remaining fixes for the current file.
     SkipFile,
+    /// Leave this suggestion undecided and continue with the next file.
+    NextFile,
     /// continue as if whatever returned this was never called.
     Nop,
     /// Stop execution, forget all previous choices.
     Abort,
     /// Stop fixing chunks, move on to applying the ones chosen so far.
     Quit,
+    /// Undo the last decision, which was made for a previous file.
+    Undo,
+}
+
+/// Replacements the user picked to be applied to all identical mistakes of
+/// the current run.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ApplyToAll {
+    /// Replacement by detector and flagged word.
+    replacements: HashMap<(Detector, String), String>,
+}
+
+impl ApplyToAll {
+    fn key(suggestion: &Suggestion<'_>) -> (Detector, String) {
+        (
+            suggestion.detector,
+            sub_chars(suggestion.chunk.as_str(), suggestion.range.clone()),
+        )
+    }
+
+    /// Remember the replacement for all further occurrences of the mistake.
+    fn remember(&mut self, suggestion: &Suggestion<'_>, replacement: String) {
+        self.replacements.insert(Self::key(suggestion), replacement);
+    }
+
+    /// Create the bandaid for a mistake, if an identical one was fixed with
+    /// apply-to-all before.
+    fn bandaid(&self, suggestion: &Suggestion<'_>) -> Option<BandAid> {
+        self.replacements
+            .get(&Self::key(suggestion))
+            .map(|replacement| BandAid::from((replacement.to_owned(), &suggestion.span)))
+    }
+}
+
+/// Position of the file being fixed within the whole run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct RunProgress {
+    /// 0-based index of the file among the files with suggestions.
+    pub file: usize,
+    /// Number of files with suggestions.
+    pub files: usize,
+    /// Number of suggestions of the files before this one.
+    pub before: usize,
+    /// Number of suggestions of all files.
+    pub total: usize,
+    /// Further files are still being checked, so `files` and `total` only
+    /// cover the ones checked so far.
+    pub pending: bool,
+}
+
+impl RunProgress {
+    /// Describe the position of the suggestion at `idx` within the current
+    /// file, i.e. `file 3/17 — suggestion 5/42`.
+    fn describe(&self, idx: usize) -> String {
+        let more = if self.pending { "+" } else { "" };
+        format!(
+            "file {}/{}{more} — suggestion {}/{}{more}",
+            self.file + 1,
+            self.files,
+            self.before + idx + 1,
+            self.total
+        )
+    }
 }
 
 /// Statefulness for the selection process
@@ -223,9 +287,11 @@ impl UserPicked {
             .extend(iter);
     }
 
-    /// Join two `UserPick`s.
+    /// Join two `UserPick`s, keeping the bandaids of both for a common origin.
     pub fn extend(&mut self, other: Self) {
-        self.bandaids.extend(other.bandaids);
+        for (origin, bandaids) in other.bandaids {
+            self.add_bandaids(&origin, bandaids);
+        }
     }
 
     /// Provide a replacement that was not provided by the backend
@@ -365,7 +431,7 @@ impl UserPicked {
         &self,
         state: &mut State,
         running_idx: usize,
-        total: usize,
+        progress: &RunProgress,
     ) -> Result<UserSelection> {
         let skip = {
             let _guard = ScopedRaw::new();
@@ -375,9 +441,9 @@ impl UserPicked {
             boring.attributes = Attribute::Bold.into();
 
             let question = format!(
-                "({nth}/{of_n}) Apply this suggestion [y,n,q,a,d,j,e,?]?",
-                nth = running_idx + 1,
-                of_n = total
+                "({position}, {detector}) Apply this suggestion [y,n,q,a,d,N,j,e,?]?",
+                position = progress.describe(running_idx),
+                detector = state.suggestion.detector,
             );
 
             // a new suggestion, so prepare for the number of items that are visible
@@ -465,6 +531,10 @@ impl UserPicked {
                     // TODO handle interactive intput for those where there are no suggestions
                     return Ok(UserSelection::Replacement(bandaid));
                 }
+                KeyCode::Char('a') => {
+                    let bandaid = state.to_bandaid();
+                    return Ok(UserSelection::ReplacementForAll(bandaid));
+                }
                 KeyCode::Char('n') => return Ok(UserSelection::Skip),
                 KeyCode::Char('j') => return Ok(UserSelection::Previous),
                 KeyCode::Char('q') | KeyCode::Esc => return Ok(UserSelection::Quit),
@@ -472,6 +542,7 @@ impl UserPicked {
                     return Ok(UserSelection::Abort)
                 }
                 KeyCode::Char('d') => return Ok(UserSelection::SkipFile),
+                KeyCode::Char('N') => return Ok(UserSelection::NextFile),
                 KeyCode::Char('e') => {
                     // jump to the user input entry
                     state.select_custom();
@@ -485,72 +556,15 @@ impl UserPicked {
         unreachable!("Unexpected return when dealing with user input")
     }
 
+    /// Let the user decide on the `suggestions` of a file in a list view,
+    /// resuming a previous `review` of the file if given.
     pub(super) fn select_interactive(
         origin: ContentOrigin,
-        suggestions: Vec<Suggestion<'_>>,
-    ) -> Result<(Self, UserSelection)> {
-        let count = suggestions.len();
-        let mut picked = UserPicked::default();
-
-        let mut suggestions_it = suggestions.iter().enumerate();
-        let start = suggestions_it.clone();
-
-        // TODO make use of it
-        let direction = Direction::Forward;
-        'outer: loop {
-            let opt_next = match direction {
-                Direction::Forward => suggestions_it.next(),
-                // FIXME TODO this is just plain wrong
-                Direction::Backward => suggestions_it.next_back(),
-            };
-
-            log::trace!("next() ---> {opt_next:?}");
-
-            let (idx, suggestion) = match opt_next {
-                Some(x) => x,
-                None => match direction {
-                    Direction::Forward => {
-                        log::trace!("completed file, continue to next");
-                        break; // we completed this file, move on to the next
-                    }
-                    Direction::Backward => {
-                        log::trace!("went back, now back at the beginning");
-                        suggestions_it = start.clone();
-                        continue;
-                    } // go to the start
-                },
-            };
-            if suggestion.replacements.is_empty() {
-                log::trace!("BUG: Suggestion did not contain a replacement, skip");
-                continue;
-            }
-            println!("{suggestion}");
-
-            let mut state = State::from(suggestion);
-
-            'inner: loop {
-                match picked.user_input(&mut state, idx, count)? {
-                    usel @ (UserSelection::Abort | UserSelection::Quit) => {
-                        let _ = ScopedRaw::restore_terminal();
-                        return Ok((picked, usel));
-                    }
-                    UserSelection::SkipFile => break 'outer,
-                    UserSelection::Previous => {
-                        log::warn!("Requires a iterator which works bidrectionally");
-                        continue 'inner;
-                    }
-                    UserSelection::Help => {
-                        println!("{HELP}");
-                        continue 'inner;
-                    }
-                    UserSelection::Replacement(bandaid) => {
-                        picked.add_bandaid(&origin, bandaid);
-                    }
-                    UserSelection::Nop | UserSelection::Skip => {}
-                };
-                break 'inner;
-            }
-        }
-        Ok((picked, UserSelection::Nop))
+        suggestions: &[Suggestion<'_>],
+        apply_to_all: &mut ApplyToAll,
+        progress: RunProgress,
+        review: Option<Review>,
+    ) -> Result<(Self, UserSelection, Review)> {
+        list::select_from_list(origin, suggestions, apply_to_all, &progress, review)
     }
 }
@@ -0,0 +1,163 @@
+//! Advisory workspace lock.
+//!
+//! Prevents two concurrent invocations, i.e. an editor hook and a manual run,
+//! from interleaving patches to the same files. The lock file resides in the
+//! workspace root and contains the PID of the holder, so a leftover lock of a
+//! process that died without cleaning up is detected as stale and replaced.
+//! A lock without a valid PID may be one which was just created, and is never
+//! considered stale.
+
+use crate::errors::*;
+
+use fs_err as fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const LOCK_FILE_NAME: &str = ".spellcheck.lock";
+
+/// Number of attempts to acquire the lock.
+const ATTEMPTS: usize = 3;
+
+/// Time for the holder of a lock without a PID to write it.
+const RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Held for as long as files are being modified, removes the lock file on drop.
+#[derive(Debug)]
+pub struct WorkspaceLock {
+    path: PathBuf,
+}
+
+impl WorkspaceLock {
+    /// Acquire the lock for the workspace rooted at `dir`.
+    ///
+    /// Fails if another live process holds the lock already, or the lock does
+    /// not contain a valid PID after a few attempts.
+    pub fn acquire(dir: impl AsRef<Path>) -> Result<Self> {
+        let path = dir.as_ref().join(LOCK_FILE_NAME);
+        // retry after removing a stale lock, or giving the holder of a lock
+        // without PID the chance to write it
+        for _ in 0..ATTEMPTS {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut f) => {
+                    write!(f, "{}", std::process::id())?;
+                    log::debug!("Acquired workspace lock {}", path.display());
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let content = fs::read_to_string(&path).unwrap_or_default();
+                    match content.trim().parse::<u32>() {
+                        Ok(pid) if is_process_alive(pid) => bail!(
+                            "Another cargo-spellcheck instance (PID {pid}) holds {}, \
+                            wait for it to finish or pass `--no-lock`",
+                            path.display()
+                        ),
+                        Ok(pid) => {
+                            log::warn!(
+                                "Removing stale lock {} of dead process {pid}",
+                                path.display()
+                            );
+                            fs::remove_file(&path)?;
+                        }
+                        // the holder might not have written its PID yet
+                        Err(_) => {
+                            log::debug!(
+                                "Workspace lock {} without PID {content:?}, retrying",
+                                path.display()
+                            );
+                            std::thread::sleep(RETRY_DELAY);
+                        }
+                    }
+                }
+                Err(e) => return Err(e).wrap_err("Failed to create workspace lock file"),
+            }
+        }
+        bail!(
+            "Failed to acquire workspace lock {}, remove it if no other cargo-spellcheck \
+            instance is running or pass `--no-lock`",
+            path.display()
+        )
+    }
+}
+
+impl Drop for WorkspaceLock {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            log::warn!("Failed to remove workspace lock: {e}");
+        }
+    }
+}
+
+/// Check if a process with the given `pid` is still running.
+///
+/// Errs on the side of caution and assumes the process is alive if it can not
+/// be determined.
+fn is_process_alive(pid: u32) -> bool {
+    pid == std::process::id() || is_foreign_process_alive(pid)
+}
+
+#[cfg(target_os = "linux")]
+fn is_foreign_process_alive(pid: u32) -> bool {
+    Path::new("/proc").join(pid.to_string()).exists()
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn is_foreign_process_alive(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .arg("-0")
+        .arg(pid.to_string())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(true)
+}
+
+#[cfg(target_os = "windows")]
+fn is_foreign_process_alive(pid: u32) -> bool {
+    std::process::Command::new("tasklist")
+        .args(["/NH", "/FI", &format!("PID eq {pid}")])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+        .unwrap_or(true)
+}
+
+#[cfg(not(any(unix, target_os = "windows")))]
+fn is_foreign_process_alive(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::TempDir;
+
+    #[test]
+    fn exclusive() {
+        let dir = TempDir::new();
+
+        let lock = WorkspaceLock::acquire(&dir).expect("Nobody holds the lock. qed");
+        assert!(WorkspaceLock::acquire(&dir).is_err());
+        drop(lock);
+        assert!(!dir.join(LOCK_FILE_NAME).exists());
+
+        // a lock of a process that does not exist anymore is replaced
+        fs::write(dir.join(LOCK_FILE_NAME), format!("{}", u32::MAX)).unwrap();
+        let lock = WorkspaceLock::acquire(&dir).expect("Stale lock is replaced. qed");
+        drop(lock);
+
+        // a lock without a PID yet is held, not replaced
+        for content in ["", "not a pid"] {
+            fs::write(dir.join(LOCK_FILE_NAME), content).unwrap();
+            assert!(WorkspaceLock::acquire(&dir).is_err());
+            assert_eq!(
+                fs::read_to_string(dir.join(LOCK_FILE_NAME)).unwrap(),
+                content
+            );
+        }
+    }
+}
@@ -1,26 +1,43 @@
 //! Covers all user triggered actions (except for signals).
 
 use super::*;
-use crate::checker::Checkers;
+use crate::checker::{Baseline, Checkers};
 use crate::errors::*;
 use crate::reflow::Reflow;
+use crate::traverse::Extraction;
 
 use fs_err as fs;
-use futures::stream::{self, StreamExt};
-use rayon::iter::ParallelIterator;
+use rayon::iter::{ParallelBridge, ParallelIterator};
 
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+pub mod backup;
 pub mod bandaid;
+pub mod ci;
+pub mod fetch;
+pub mod hook;
 pub mod interactive;
+pub mod lock;
+mod progress;
+mod report;
+pub mod simulate;
+pub mod summary;
+pub mod validate;
+mod verify;
+mod watch;
+pub mod words;
 
 pub(crate) use bandaid::*;
 
-use interactive::{UserPicked, UserSelection};
+use backup::Backup;
+use ci::CiProvider;
+use interactive::{ApplyToAll, Review, RunProgress, UserPicked, UserSelection};
+use progress::progress_bar;
+use summary::Summary;
 
 /// State of conclusion.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum Finish {
     /// Operation ran to the end, successfully.
     Success,
@@ -29,6 +46,9 @@ pub enum Finish {
     /// Completion of the check run, with the resulting number of mistakes
     /// accumulated.
     MistakeCount(usize),
+    /// Completion of the check run, with statistics of what was checked and
+    /// found.
+    Checked(Summary),
 }
 
 impl Finish {
@@ -36,6 +56,7 @@ impl Finish {
     pub fn found_any(&self) -> bool {
         match *self {
             Self::MistakeCount(n) if n > 0 => true,
+            Self::Checked(ref summary) if summary.mistakes() > 0 => true,
             _ => false,
         }
     }
@@ -82,6 +103,24 @@ impl From<BandAid> for Patch {
     }
 }
 
+/// Transfer the permissions and, if permitted, the ownership of the original
+/// file to the file at `path` replacing it.
+fn preserve_metadata(metadata: &std::fs::Metadata, path: &Path) -> Result<()> {
+    fs::set_permissions(path, metadata.permissions())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        // changing the owner requires privileges
+        if let Err(e) = std::os::unix::fs::chown(path, Some(metadata.uid()), Some(metadata.gid())) {
+            log::debug!(
+                "Failed to preserve the ownership of {}: {e}",
+                path.display()
+            );
+        }
+    }
+    Ok(())
+}
+
 /// Correct lines by applying patches.
 ///
 /// Assumes all `BandAids` do not overlap when replacing. Inserting multiple
@@ -89,7 +128,10 @@ impl From<BandAid> for Patch {
 /// of the original source is not.
 ///
 /// This function is not concerned with _any_ semantics or comments or
-/// whatsoever at all, it blindly replaces what is given to it.
+/// whatsoever at all, it blindly replaces what is given to it. A byte order
+/// mark of `source_buffer` is preserved and not accounted for by the spans, and
+/// line breaks of the patches are written with the line endings of
+/// `source_buffer`.
 pub(crate) fn apply_patches<'s, II, I>(
     patches: II,
     source_buffer: &str,
@@ -99,7 +141,26 @@ where
     II: IntoIterator<IntoIter = I, Item = Patch>,
     I: Iterator<Item = Patch>,
 {
-    let mut patches = patches.into_iter().peekable();
+    // spans never account for the byte order mark
+    let bom = source_buffer.starts_with(BYTE_ORDER_MARK);
+    let source_buffer = strip_byte_order_mark(source_buffer);
+    let newline = extract_delimiter(source_buffer).unwrap_or("\n");
+    let mut patches = patches
+        .into_iter()
+        .map(|patch| match patch {
+            Patch::Replace {
+                replace_span,
+                replacement,
+            } => Patch::Replace {
+                replace_span,
+                replacement: normalize_newlines(&replacement, newline),
+            },
+            Patch::Insert { insert_at, content } => Patch::Insert {
+                insert_at,
+                content: normalize_newlines(&content, newline),
+            },
+        })
+        .peekable();
 
     let mut source_iter =
         iter_with_line_column_from(source_buffer, LineColumn { line: 1, column: 0 }).peekable();
@@ -111,6 +172,10 @@ where
         Ok(())
     };
 
+    if bom {
+        write_to_sink("bom", BYTE_ORDER_MARK.encode_utf8(&mut [0; 4]))?;
+    }
+
     let mut cc_end_byte_offset = 0;
 
     let mut current = None;
@@ -217,6 +282,55 @@ where
     Ok(())
 }
 
+/// Capitalization of a word, which a safe replacement must preserve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CasePattern {
+    Lower,
+    Upper,
+    Capitalized,
+    Mixed,
+}
+
+impl CasePattern {
+    fn of(word: &str) -> Self {
+        let mut chars = word.chars().filter(|c| c.is_alphabetic());
+        let first_upper = chars.next().map_or(false, char::is_uppercase);
+        let (mut upper, mut lower) = (false, false);
+        for c in chars {
+            upper |= c.is_uppercase();
+            lower |= c.is_lowercase();
+        }
+        match (first_upper, upper, lower) {
+            (false, false, _) => Self::Lower,
+            (true, false, true) => Self::Capitalized,
+            (true, true, false) | (true, false, false) => Self::Upper,
+            _ => Self::Mixed,
+        }
+    }
+}
+
+/// The replacement of a suggestion, if it can be applied without asking.
+///
+/// That is the case if there is exactly one, it is one edit away from the
+/// flagged word and has the same case pattern.
+fn safe_replacement<'a>(suggestion: &'a Suggestion<'_>) -> Option<&'a str> {
+    let replacement = match suggestion.replacements.as_slice() {
+        [replacement] => replacement.as_str(),
+        _ => return None,
+    };
+    if suggestion.span.start.line != suggestion.span.end.line {
+        return None;
+    }
+    let word = crate::util::sub_chars(suggestion.chunk.as_str(), suggestion.range.clone());
+    if crate::checker::edit_distance(&word, replacement) != 1 {
+        return None;
+    }
+    if CasePattern::of(&word) != CasePattern::of(replacement) {
+        return None;
+    }
+    Some(replacement)
+}
+
 /// Mode in which `cargo-spellcheck` operates.
 ///
 /// Eventually to be used directly in parsing arguments.
@@ -228,26 +342,62 @@ pub enum Action {
     /// Interactively choose from checker provided suggestions.
     Fix,
 
+    /// Apply unambiguous suggestions without asking, interactively choose
+    /// from the remaining ones.
+    FixSafe,
+
     /// Reflow doc comments, so they adhere to a given maximum column width.
     Reflow,
 
+    /// Print the changes a reflow would write, without writing them.
+    ReflowCheck,
+
     /// List all files in depth first sorted order in which they would be
     /// checked.
     ListFiles,
+
+    /// Record the current mistakes as baseline.
+    Baseline,
+
+    /// Check again whenever a checked file or an extra dictionary changes.
+    Watch,
 }
 
 impl Action {
+    /// Determine if the action writes changes to the checked files.
+    pub fn modifies_files(&self) -> bool {
+        matches!(self, Self::Fix | Self::FixSafe | Self::Reflow)
+    }
+
     /// Apply bandaids to the file represented by content origin.
     pub fn write_changes_to_disk(
         &self,
         origin: ContentOrigin,
         bandaids: impl IntoIterator<Item = BandAid>,
+        backup: Option<&Backup>,
+        verify: bool,
     ) -> Result<()> {
         match origin {
-            ContentOrigin::CargoManifestDescription(path) => self.correct_file(path, bandaids),
-            ContentOrigin::CommonMarkFile(path) => self.correct_file(path, bandaids),
-            ContentOrigin::RustSourceFile(path) => self.correct_file(path, bandaids),
-            ContentOrigin::RustDocTest(path, _span) => self.correct_file(path, bandaids),
+            ContentOrigin::CargoManifestDescription(path) => {
+                self.correct_file(path, bandaids, backup, verify)
+            }
+            ContentOrigin::CargoManifestFeatures(path) => {
+                self.correct_file(path, bandaids, backup, verify)
+            }
+            ContentOrigin::CommonMarkFile(path) => {
+                self.correct_file(path, bandaids, backup, verify)
+            }
+            ContentOrigin::PlainTextFile(path) => self.correct_file(path, bandaids, backup, verify),
+            ContentOrigin::AsciiDocFile(path) => self.correct_file(path, bandaids, backup, verify),
+            ContentOrigin::ForeignSourceFile(path) => {
+                self.correct_file(path, bandaids, backup, verify)
+            }
+            ContentOrigin::RustSourceFile(path) => {
+                self.correct_file(path, bandaids, backup, verify)
+            }
+            ContentOrigin::RustDocTest(path, _span) => {
+                self.correct_file(path, bandaids, backup, verify)
+            }
             ContentOrigin::TestEntityRust => unreachable!("Use a proper file"),
             ContentOrigin::TestEntityCommonMark => unreachable!("Use a proper file"),
         }
@@ -255,52 +405,75 @@ impl Action {
 
     /// assumes suggestions are sorted by line number and column number and must
     /// be non overlapping
+    ///
+    /// With `verify`, the file is left untouched if the patched content differs
+    /// in more than its prose, see [`verify::verify`].
     fn correct_file(
         &self,
         path: PathBuf,
         bandaids: impl IntoIterator<Item = BandAid>,
+        backup: Option<&Backup>,
+        verify: bool,
     ) -> Result<()> {
+        // the target of a symlink is rewritten, the link itself is kept
         let path = fs::canonicalize(path.as_path())?;
         let path = path.as_path();
         log::trace!("Attempting to open {} as read", path.display());
         let ro = fs::OpenOptions::new().read(true).open(path)?;
+        let metadata = ro.metadata()?;
 
         let mut reader = std::io::BufReader::new(ro);
 
+        let mut content = String::with_capacity(2e6 as usize);
+        reader.get_mut().read_to_string(&mut content)?;
+        // Required for windows support, which does not allow
+        // to move a file while it is opened, see
+        // <https://github.com/drahnr/cargo-spellcheck/issues/251>
+        drop(reader);
+
+        // FIXME for efficiency, correct_lines should integrate with `BufRead` instead of a `String` buffer
+        let mut patched = Vec::with_capacity(content.len());
+        apply_patches(
+            coalesce(Vec::from_iter(bandaids), strip_byte_order_mark(&content))
+                .into_iter()
+                .map(Patch::from),
+            content.as_str(),
+            &mut patched,
+        )?;
+
+        if verify {
+            let patched = std::str::from_utf8(&patched)?;
+            verify::verify(path, &content, patched)
+                .wrap_err_with(|| eyre!("Refusing to write {}", path.display()))?;
+        }
+
+        if let Some(backup) = backup {
+            backup.save(path, &content)?;
+        }
+
         const TEMPORARY: &str = ".spellcheck.tmp";
 
         // Avoid issues when processing multiple files in parallel
         let tmp_name = TEMPORARY.to_owned() + uuid::Uuid::new_v4().to_string().as_str();
 
-        let tmp = std::env::current_dir()
-            .expect("Must have cwd")
+        // renaming is only possible within the same file system
+        let tmp = path
+            .parent()
+            .ok_or_else(|| eyre!("File {} has no parent dir", path.display()))?
             .join(tmp_name);
-        let wr = fs::OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .create(true)
-            .open(&tmp)?;
-
-        let mut writer = std::io::BufWriter::with_capacity(1024, wr);
-
-        let mut content = String::with_capacity(2e6 as usize);
-        reader.get_mut().read_to_string(&mut content)?;
 
         {
             let th = crate::TinHat::on();
 
-            apply_patches(
-                bandaids.into_iter().map(Patch::from),
-                content.as_str(), // FIXME for efficiency, correct_lines should integrate with `BufRead` instead of a `String` buffer
-                &mut writer,
-            )?;
-
-            writer.flush()?;
-            // Required for windows support, which does not allow
-            // to move a file while it is opened, see
-            // <https://github.com/drahnr/cargo-spellcheck/issues/251>
-            drop(writer);
-            drop(reader);
+            let mut wr = fs::OpenOptions::new()
+                .write(true)
+                .truncate(true)
+                .create(true)
+                .open(&tmp)?;
+            wr.write_all(&patched)?;
+            wr.flush()?;
+            drop(wr);
+            preserve_metadata(&metadata, &tmp)?;
             fs::rename(tmp, path)?;
 
             // Writing for this file is done, unblock the signal handler.
@@ -317,11 +490,24 @@ impl Action {
     pub fn write_user_pick_changes_to_disk(
         &self,
         userpicked: interactive::UserPicked,
+        backup: Option<&Backup>,
+        verify: bool,
     ) -> Result<()> {
         if userpicked.total_count() > 0 {
             log::debug!("Writing changes back to disk");
+            // a source file and its doc tests must be written at once, the
+            // spans of a second write would be stale
+            let mut queue = indexmap::IndexMap::<PathBuf, (ContentOrigin, Vec<BandAid>)>::new();
             for (origin, bandaids) in userpicked.bandaids.into_iter() {
-                self.write_changes_to_disk(origin, bandaids.into_iter())?;
+                let path = origin.as_path().to_path_buf();
+                queue
+                    .entry(path)
+                    .or_insert_with(|| (origin, Vec::new()))
+                    .1
+                    .extend(bandaids);
+            }
+            for (_path, (origin, bandaids)) in queue {
+                self.write_changes_to_disk(origin, bandaids, backup, verify)?;
             }
         } else {
             log::debug!("No band aids to apply");
@@ -329,12 +515,60 @@ impl Action {
         Ok(())
     }
     /// Run the requested action.
-    pub async fn run(self, documents: Documentation, config: Config) -> Result<Finish> {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn run(
+        self,
+        extraction: Extraction,
+        config: Config,
+        output_format: OutputFormat,
+        render: RenderOptions,
+        ci: Option<CiProvider>,
+        reports: Vec<Report>,
+        max_issues: Option<usize>,
+        progress: bool,
+        backup: Option<Backup>,
+        verify: bool,
+    ) -> Result<Finish> {
         let fin = match self {
-            Self::ListFiles { .. } => self.run_list_files(documents, &config)?,
-            Self::Reflow { .. } => self.run_reflow(documents, config).await?,
-            Self::Check { .. } => self.run_check(documents, config).await?,
-            Self::Fix { .. } => self.run_fix_interactive(documents, config).await?,
+            Self::ListFiles { .. } => self.run_list_files(extraction.extract()?, &config)?,
+            Self::Reflow { .. } => {
+                self.run_reflow(extraction.extract()?, config, backup, verify)
+                    .await?
+            }
+            Self::ReflowCheck { .. } => {
+                self.run_reflow_check(extraction.extract()?, config).await?
+            }
+            Self::Check { .. } => {
+                self.run_check(
+                    extraction,
+                    config,
+                    output_format,
+                    render,
+                    ci,
+                    reports,
+                    max_issues,
+                    progress,
+                )
+                .await?
+            }
+            Self::Watch { .. } => {
+                self.run_watch(
+                    extraction,
+                    config,
+                    output_format,
+                    render,
+                    ci,
+                    reports,
+                    max_issues,
+                    progress,
+                )
+                .await?
+            }
+            Self::Fix { .. } | Self::FixSafe { .. } => {
+                self.run_fix_interactive(extraction.extract()?, config, progress, backup, verify)
+                    .await?
+            }
+            Self::Baseline { .. } => self.run_baseline(extraction.extract()?, config).await?,
         };
         Ok(fin)
     }
@@ -348,95 +582,328 @@ impl Action {
     }
 
     /// Run the requested action _interactively_, waiting for user input.
-    async fn run_fix_interactive(self, documents: Documentation, config: Config) -> Result<Finish> {
-        let n_cpus = num_cpus::get();
-
-        let checkers = Checkers::new(config)?;
+    ///
+    /// The files are checked in the background while the user reviews the
+    /// suggestions of the files checked already, in the order of the
+    /// documents.
+    async fn run_fix_interactive(
+        self,
+        documents: Documentation,
+        config: Config,
+        progress: bool,
+        backup: Option<Backup>,
+        verify: bool,
+    ) -> Result<Finish> {
+        let mut checkers = Checkers::new(config)?;
+        checkers.collect_identifiers(&documents);
 
         let n = documents.entry_count();
+        let bar = progress_bar(n, progress);
+        checkers.set_progress(bar.clone());
         log::debug!("Running checkers on all documents {n}");
-        let mut pick_stream = stream::iter(documents.iter().enumerate())
-            .map(|(mut idx, (origin, chunks))| {
-                // align the debug output with the user output
-                idx += 1;
-                log::trace!("Running checkers on {idx}/{n},{origin:?}");
-                let suggestions = checkers.check(origin, &chunks[..]);
-                async move { Ok::<_, color_eyre::eyre::Report>((idx, origin, suggestions?)) }
-            })
-            .buffered(n_cpus)
-            .fuse();
-
-        let mut collected_picks = UserPicked::default();
-        while let Some(result) = pick_stream.next().await {
-            match result {
-                Ok((idx, origin, suggestions)) => {
-                    let (picked, user_sel) =
-                        interactive::UserPicked::select_interactive(origin.clone(), suggestions)?;
-
-                    match user_sel {
-                        UserSelection::Quit => break,
-                        UserSelection::Abort => return Ok(Finish::Abort),
-                        UserSelection::Nop if !picked.is_empty() => {
+
+        let (sink, source) = std::sync::mpsc::channel();
+        let checkers = &checkers;
+        let documents = &documents;
+        let collected_picks = std::thread::scope(|scope| {
+            let bar = &bar;
+            scope.spawn(move || {
+                documents.iter().enumerate().par_bridge().try_for_each_with(
+                    sink,
+                    |sink, (mut idx, (origin, chunks))| {
+                        // align the debug output with the user output
+                        idx += 1;
+                        log::trace!("Running checkers on {idx}/{n},{origin:?}");
+                        let suggestions = checkers.check(origin, &chunks[..]);
+                        bar.inc(1);
+                        // the receiver is gone once the user quit
+                        sink.send((idx, origin, suggestions)).ok()
+                    },
+                )
+            });
+
+            let mut collected_picks = UserPicked::default();
+            // checked files, until all files before them are checked too
+            let mut reordered = std::collections::BTreeMap::new();
+            let mut next = 1;
+            // checked files with suggestions, in the order of the documents
+            let mut queue = std::collections::VecDeque::new();
+            let mut position = RunProgress {
+                file: 0,
+                files: 0,
+                before: 0,
+                total: 0,
+                pending: true,
+            };
+            let mut apply_to_all = ApplyToAll::default();
+            // the reviewed files with their picks, which are only collected
+            // once the review is complete, so their decisions can be undone
+            let mut reviewed = Vec::<((_, _, Vec<Suggestion>, UserPicked), Review)>::new();
+            // the previous review of the next file, when returning to it
+            let mut review = None;
+            loop {
+                // take what is checked already, only wait if there is nothing
+                // to review
+                let received = if queue.is_empty() {
+                    source
+                        .recv()
+                        .map_err(|_| std::sync::mpsc::TryRecvError::Disconnected)
+                } else {
+                    source.try_recv()
+                };
+                match received {
+                    Ok((idx, origin, suggestions)) => {
+                        reordered.insert(idx, (origin, suggestions));
+                        while let Some((origin, suggestions)) = reordered.remove(&next) {
+                            let suggestions =
+                                self.triage(next, n, origin, suggestions?, &mut collected_picks);
+                            if !suggestions.is_empty() {
+                                position.files += 1;
+                                position.total += suggestions.len();
+                                queue.push_back((next, origin, suggestions));
+                            }
+                            next += 1;
+                        }
+                        continue;
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => {}
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        position.pending = false;
+                        if queue.is_empty() {
+                            break;
+                        }
+                    }
+                }
+
+                let (idx, origin, suggestions) = queue
+                    .pop_front()
+                    .expect("Only reviewing if there are queued files. qed");
+                if !bar.is_hidden() {
+                    // the bar must not interfere with the user interaction
+                    bar.finish_and_clear();
+                    bar.set_draw_target(indicatif::ProgressDrawTarget::hidden());
+                }
+                let (picked, user_sel, done) = interactive::UserPicked::select_interactive(
+                    origin.clone(),
+                    &suggestions,
+                    &mut apply_to_all,
+                    position,
+                    review.take(),
+                )?;
+
+                match user_sel {
+                    UserSelection::Undo => {
+                        queue.push_front((idx, origin, suggestions));
+                        // return to the last file with a decision to undo
+                        while let Some((previous, mut done)) = reviewed.pop() {
+                            let (idx, origin, suggestions, _picked) = previous;
+                            position.file -= 1;
+                            position.before -= suggestions.len();
+                            queue.push_front((idx, origin, suggestions));
+                            if done.undo(&mut apply_to_all) {
+                                review = Some(done);
+                                break;
+                            }
+                        }
+                    }
+                    UserSelection::Quit => {
+                        // the unambiguous ones of the remaining files are
+                        // applied nonetheless
+                        if self == Self::FixSafe {
+                            for (idx, origin, suggestions) in reordered
+                                .into_iter()
+                                .map(|(idx, (origin, suggestions))| (idx, origin, suggestions))
+                                .chain(source.iter())
+                            {
+                                self.triage(idx, n, origin, suggestions?, &mut collected_picks);
+                            }
+                        }
+                        break;
+                    }
+                    UserSelection::Abort => return Ok(None),
+                    UserSelection::Nop => {
+                        if picked.is_empty() {
+                            log::debug!("Nothing to do for {idx}/{n},{origin:?}");
+                        } else {
                             log::debug!(
                                 "User picked patches to be applied for {idx}/{n},{origin:?}"
                             );
-                            collected_picks.extend(picked);
                         }
-                        UserSelection::Nop => {
-                            log::debug!("Nothing to do for {idx}/{n},{origin:?}");
-                        }
-                        _ => unreachable!(
-                            "All other variants are only internal to `select_interactive`. qed"
-                        ),
+                        position.file += 1;
+                        position.before += suggestions.len();
+                        reviewed.push(((idx, origin, suggestions, picked), done));
                     }
+                    _ => unreachable!(
+                        "All other variants are only internal to `select_interactive`. qed"
+                    ),
                 }
-                Err(e) => Err(e)?,
             }
-        }
+            for ((_idx, _origin, _suggestions, picked), _done) in reviewed {
+                collected_picks.extend(picked);
+            }
+            Ok::<_, color_eyre::eyre::Report>(Some(collected_picks))
+        })?;
+        bar.finish_and_clear();
+
+        let Some(collected_picks) = collected_picks else {
+            return Ok(Finish::Abort);
+        };
         let total = collected_picks.total_count();
         // clustering per file is not reasonable
         // since user abort (`<CTRL>-C` or `q`) should not
         // leave any residue on disk.
-        self.write_user_pick_changes_to_disk(collected_picks)?;
+        self.write_user_pick_changes_to_disk(collected_picks, backup.as_ref(), verify)?;
 
         Ok(Finish::MistakeCount(total))
     }
 
+    /// Drop the suggestions of a checked file which are not to be reviewed,
+    /// the unambiguous ones are picked right away for `fix --safe`.
+    fn triage<'s>(
+        &self,
+        idx: usize,
+        n: usize,
+        origin: &ContentOrigin,
+        mut suggestions: Vec<Suggestion<'s>>,
+        collected_picks: &mut UserPicked,
+    ) -> Vec<Suggestion<'s>> {
+        // the content of macro built doc attributes is not in the
+        // source as is
+        suggestions.retain(|suggestion| suggestion.chunk.variant() != CommentVariant::Synthetic);
+        if *self == Self::FixSafe {
+            suggestions.retain(|suggestion| match safe_replacement(suggestion) {
+                Some(replacement) => {
+                    log::debug!("Applying safe replacement {replacement} in {idx}/{n},{origin:?}");
+                    collected_picks.add_bandaid(
+                        origin,
+                        BandAid::from((replacement.to_owned(), &suggestion.span)),
+                    );
+                    false
+                }
+                None => true,
+            });
+        }
+        if suggestions.is_empty() {
+            log::debug!("Nothing to do for {idx}/{n},{origin:?}");
+        }
+        suggestions
+    }
+
     /// Run the requested action.
-    async fn run_check(self, documents: Documentation, config: Config) -> Result<Finish> {
-        let checkers = Checkers::new(config)?;
-        let num_mistakes = documents
-            .into_par_iter()
-            .map(|(origin, chunks)| {
-                checkers.check(&origin, &chunks).map(|suggestions| {
-                    let path = origin.as_path();
-                    let n = suggestions.len();
-                    match suggestions.is_empty() {
-                        true => log::info!("✅ {}", path.display()),
-                        false => log::info!("❌ {} : {}", path.display(), n),
-                    };
-                    for suggestion in suggestions {
-                        println!("{suggestion}");
-                    }
-                    n
-                })
-            })
-            .try_fold_with(0, |count, res| res.map(|it| it + count))
-            .try_reduce(|| 0, |l, r| Ok(l + r))?;
+    #[allow(clippy::too_many_arguments)]
+    async fn run_check(
+        self,
+        extraction: Extraction,
+        config: Config,
+        output_format: OutputFormat,
+        render: RenderOptions,
+        ci: Option<CiProvider>,
+        reports: Vec<Report>,
+        max_issues: Option<usize>,
+        progress: bool,
+    ) -> Result<Finish> {
+        let mut checkers = Checkers::new(config)?;
+        let summary = check_all(
+            &mut checkers,
+            extraction,
+            output_format,
+            &render,
+            ci,
+            &reports,
+            max_issues,
+            progress,
+        )?;
+        Ok(Finish::Checked(summary))
+    }
 
-        if num_mistakes > 0 {
-            Ok(Finish::MistakeCount(num_mistakes))
-        } else {
-            Ok(Finish::Success)
+    /// Check all files again whenever one of them or an extra dictionary
+    /// changes, until interrupted.
+    #[allow(clippy::too_many_arguments)]
+    async fn run_watch(
+        self,
+        extraction: Extraction,
+        config: Config,
+        output_format: OutputFormat,
+        render: RenderOptions,
+        ci: Option<CiProvider>,
+        reports: Vec<Report>,
+        max_issues: Option<usize>,
+        progress: bool,
+    ) -> Result<Finish> {
+        let sources = extraction.paths();
+        let dictionaries = watch::dictionaries(&config);
+        let mut checkers = Checkers::new(config.clone())?;
+        loop {
+            let before = (
+                watch::Snapshot::of(watch::files(&sources)),
+                watch::Snapshot::of(dictionaries.iter().cloned()),
+            );
+            // a failed run, i.e. a file which is not readable while being
+            // saved, must not end the session
+            if let Err(err) = check_all(
+                &mut checkers,
+                extraction.clone(),
+                output_format,
+                &render,
+                ci,
+                &reports,
+                max_issues,
+                progress,
+            ) {
+                log::error!("Check failed: {err:?}");
+            }
+            eprintln!("Watching for changes, press Ctrl+C to stop");
+            let dictionaries_changed = loop {
+                tokio::time::sleep(watch::INTERVAL).await;
+                let sources_changed = watch::Snapshot::of(watch::files(&sources)) != before.0;
+                let dictionaries_changed =
+                    watch::Snapshot::of(dictionaries.iter().cloned()) != before.1;
+                if sources_changed || dictionaries_changed {
+                    break dictionaries_changed;
+                }
+            };
+            checkers = checkers.reload(config.clone(), dictionaries_changed)?;
         }
     }
 
+    /// Record the fingerprints of all current mistakes to the baseline file.
+    async fn run_baseline(self, documents: Documentation, mut config: Config) -> Result<Finish> {
+        // the existing baseline is to be replaced, not applied
+        let path = config
+            .baseline
+            .take()
+            .ok_or_else(|| eyre!("Missing path to write the baseline to"))?;
+        let mut checkers = Checkers::new(config)?;
+        checkers.collect_identifiers(&documents);
+        let baseline = documents
+            .into_par_iter()
+            .map(|(origin, chunks)| -> Result<Baseline> {
+                let suggestions = checkers.check(&origin, &chunks)?;
+                Ok(Baseline::from_iter(suggestions.iter()))
+            })
+            .try_reduce(Baseline::default, |l, r| Ok(l.merge(r)))?;
+        baseline.write_to(&path)?;
+        println!(
+            "Recorded {} mistakes in baseline {}",
+            baseline.len(),
+            path.display()
+        );
+        Ok(Finish::Success)
+    }
+
     /// Run the requested action.
-    async fn run_reflow(self, documents: Documentation, config: Config) -> Result<Finish> {
-        let reflow_config = config.reflow.clone().unwrap_or_default();
+    async fn run_reflow(
+        self,
+        documents: Documentation,
+        config: Config,
+        backup: Option<Backup>,
+        verify: bool,
+    ) -> Result<Finish> {
+        let reflow_config = config.reflow_or_default()?;
         let reflow = Reflow::new(reflow_config)?;
 
-        documents
+        // the files are written one after another, once all are reflown
+        let picked = documents
             .into_par_iter()
             .map(|(origin, chunks)| {
                 let mut picked = UserPicked::default();
@@ -450,20 +917,478 @@ impl Action {
                 }
                 Ok::<_, color_eyre::eyre::Report>(picked)
             })
-            .try_for_each(move |picked| {
-                self.write_user_pick_changes_to_disk(picked?)?;
-                Ok::<_, color_eyre::eyre::Report>(())
+            .try_reduce(UserPicked::default, |mut picked, other| {
+                picked.extend(other);
+                Ok(picked)
             })?;
+        self.write_user_pick_changes_to_disk(picked, backup.as_ref(), verify)?;
 
         Ok(Finish::Success)
     }
+
+    /// Print the diff of what a reflow would change, without writing it.
+    async fn run_reflow_check(self, documents: Documentation, config: Config) -> Result<Finish> {
+        let reflow_config = config.reflow_or_default()?;
+        let reflow = Reflow::new(reflow_config)?;
+
+        let mut diffs = documents
+            .into_par_iter()
+            .map(|(origin, chunks)| {
+                let suggestions = reflow.check(&origin, &chunks[..])?;
+                if suggestions.is_empty() {
+                    return Ok((origin, 0, String::new()));
+                }
+                let path = origin.as_path();
+                let content = fs::read_to_string(path)?;
+                let diff = unified_diff(path, &content, &suggestions);
+                Ok::<_, color_eyre::eyre::Report>((origin, suggestions.len(), diff))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        diffs.sort_by(|(a, _, _), (b, _, _)| a.as_path().cmp(b.as_path()));
+
+        let mut total = 0;
+        for (_origin, n, diff) in diffs {
+            total += n;
+            print!("{diff}");
+        }
+        Ok(Finish::MistakeCount(total))
+    }
+}
+
+/// Render the reflow `suggestions` of the file at `path` with the given
+/// `content` as unified diff, one hunk per reflown paragraph.
+fn unified_diff(path: &Path, content: &str, suggestions: &[Suggestion<'_>]) -> String {
+    use console::Style;
+
+    let removed = Style::new().red();
+    let added = Style::new().green();
+    let hunk = Style::new().cyan();
+    let lines = Vec::from_iter(content.lines());
+    let mut diff = format!("--- {}\n+++ {}\n", path.display(), path.display());
+    // the shift of the line numbers by the preceding hunks
+    let mut shift = 0_isize;
+    let mut suggestions = Vec::from_iter(suggestions.iter());
+    suggestions.sort();
+    for suggestion in suggestions {
+        let Some(replacement) = suggestion.replacements.first() else {
+            continue;
+        };
+        let Span { start, end } = suggestion.span;
+        if start.line == 0 || end.line > lines.len() || start.line > end.line {
+            continue;
+        }
+        let before = &lines[(start.line - 1)..end.line];
+        let prefix = String::from_iter(before[0].chars().take(start.column));
+        let suffix = String::from_iter(before[before.len() - 1].chars().skip(end.column + 1));
+        let reflown = format!("{prefix}{replacement}{suffix}");
+        let after = Vec::from_iter(reflown.lines());
+        diff.push_str(&format!(
+            "{}\n",
+            hunk.apply_to(format!(
+                "@@ -{},{} +{},{} @@",
+                start.line,
+                before.len(),
+                start.line as isize + shift,
+                after.len()
+            ))
+        ));
+        for line in before {
+            diff.push_str(&format!("{}\n", removed.apply_to(format!("-{line}"))));
+        }
+        for line in after.iter() {
+            diff.push_str(&format!("{}\n", added.apply_to(format!("+{line}"))));
+        }
+        shift += after.len() as isize - before.len() as isize;
+    }
+    diff
+}
+
+/// Render the suggestions of a single file for humans, below a header naming
+/// the file and followed by a footer with their count.
+///
+/// Empty if there are no suggestions.
+fn human_report(path: &Path, suggestions: &[Suggestion<'_>], render: &RenderOptions) -> String {
+    use console::Style;
+
+    if suggestions.is_empty() {
+        return String::new();
+    }
+    let header = Style::new().bold().underlined();
+    let footer = Style::new().dim();
+    let n = suggestions.len();
+    let mut report = format!("{}\n\n", header.apply_to(path.display()));
+    for suggestion in suggestions {
+        report.push_str(&format!("{}\n", suggestion.render(render)));
+    }
+    let issues = if n == 1 { "issue" } else { "issues" };
+    report.push_str(&format!(
+        "{}\n\n",
+        footer.apply_to(format!("{n} {issues} in {}", path.display()))
+    ));
+    report
+}
+
+/// Check all files of `extraction` and print the findings, returns the
+/// summary.
+///
+/// Extraction and checking are pipelined, files are checked as soon as they
+/// are extracted, unless a checker requires all documents upfront, i.e. to
+/// collect the project symbols.
+#[allow(clippy::too_many_arguments)]
+fn check_all(
+    checkers: &mut Checkers,
+    extraction: Extraction,
+    output_format: OutputFormat,
+    render: &RenderOptions,
+    ci: Option<CiProvider>,
+    reports: &[Report],
+    max_issues: Option<usize>,
+    progress: bool,
+) -> Result<Summary> {
+    let quiet = render.verbosity == ReportVerbosity::Quiet;
+    let start = std::time::Instant::now();
+    let bar = progress_bar(0, progress);
+    checkers.set_progress(bar.clone());
+
+    // a checker cross referencing all documents rules out pipelining
+    enum Pending {
+        Extracted(Documentation),
+        Streamed(Box<Extraction>),
+    }
+    let pending = if checkers.needs_all_documents() {
+        let documents = extraction.extract()?;
+        checkers.collect_identifiers(&documents);
+        bar.set_length(documents.entry_count() as u64);
+        Pending::Extracted(documents)
+    } else {
+        Pending::Streamed(Box::new(extraction))
+    };
+
+    // sections of the HTML report per file, assembled once all are checked
+    let html_sections = reports
+        .iter()
+        .any(|report| report.kind == ReportKind::Html)
+        .then(|| std::sync::Mutex::new(Vec::new()));
+    // the findings per file, printed ordered by path once all are checked,
+    // so the output does not depend on the scheduling of the threads
+    let printed = std::sync::Mutex::new(Vec::<(PathBuf, String)>::new());
+
+    // once the given number of mistakes is found, the outcome is settled,
+    // `0` stops at the first one just like `1`
+    let found = std::sync::atomic::AtomicUsize::new(0);
+    let exceeded = || {
+        max_issues.map_or(false, |max| {
+            found.load(std::sync::atomic::Ordering::Relaxed) >= max.max(1)
+        })
+    };
+
+    let check = |origin: ContentOrigin, chunks: Vec<CheckableChunk>| {
+        if exceeded() {
+            bar.inc(1);
+            return Ok(Summary::default());
+        }
+        checkers.check(&origin, &chunks).map(|mut suggestions| {
+            let path = origin.as_path();
+            let n = suggestions.len();
+            if n == 0 {
+                log::info!("✅ {}", path.display());
+            }
+            bar.inc(1);
+            let mut summary = Summary::default();
+            summary.record(chunks.len(), &suggestions, &render.severities);
+            // warnings do not count towards the maximum number of mistakes
+            found.fetch_add(summary.mistakes(), std::sync::atomic::Ordering::Relaxed);
+            let needs_content =
+                html_sections.is_some() || (!quiet && output_format == OutputFormat::Rustfix);
+            if n == 0 || (quiet && html_sections.is_none()) {
+                return summary;
+            }
+            suggestions.sort_by_key(|suggestion| {
+                (
+                    suggestion.span.start.line,
+                    suggestion.span.start.column,
+                    suggestion.span.end.line,
+                    suggestion.span.end.column,
+                )
+            });
+            // byte offsets of the diagnostics and the excerpts of the
+            // reports are relative to the file
+            let content = if needs_content {
+                fs::read_to_string(path)
+                    .map_err(|e| {
+                        log::warn!("Failed to read {} for byte offsets: {e}", path.display())
+                    })
+                    .unwrap_or_default()
+            } else {
+                String::new()
+            };
+            if let Some(ref html_sections) = html_sections {
+                let section =
+                    report::html_section(path, &content, &suggestions, render.context_lines);
+                html_sections
+                    .lock()
+                    .expect("Lock is not poisoned. qed")
+                    .push((path.to_path_buf(), section));
+            }
+            if quiet {
+                return summary;
+            }
+            let report = match output_format {
+                OutputFormat::Human => human_report(path, &suggestions, render),
+                OutputFormat::Github => String::from_iter(suggestions.iter().map(|suggestion| {
+                    let severity = render.severities.of(&suggestion.id());
+                    format!("{}\n", suggestion.github_annotation(severity))
+                })),
+                OutputFormat::Rustfix => String::from_iter(
+                    suggestions
+                        .iter()
+                        .map(|suggestion| format!("{}\n", suggestion.rustfix_diagnostic(&content))),
+                ),
+            };
+            let report = match ci {
+                // keep the output parsable line by line
+                Some(ci) if output_format != OutputFormat::Rustfix => {
+                    ci.group(&path.display().to_string(), &report)
+                }
+                _ => report,
+            };
+            printed
+                .lock()
+                .expect("Lock is not poisoned. qed")
+                .push((path.to_path_buf(), report));
+            summary
+        })
+    };
+    let mut summary = match pending {
+        Pending::Extracted(documents) => documents
+            .into_par_iter()
+            .map(|(origin, chunks)| check(origin, chunks))
+            .try_reduce(Summary::default, |l, r| Ok(l.merge(r)))?,
+        Pending::Streamed(extraction) => {
+            // at most one extracted file per job is waiting to be checked
+            let jobs = rayon::current_num_threads();
+            let (sink, source) = std::sync::mpsc::sync_channel(jobs);
+            std::thread::scope(|scope| {
+                let exceeded = &exceeded;
+                let extraction = scope.spawn(move || extraction.extract_into(sink, jobs, exceeded));
+                let summary = source
+                    .into_iter()
+                    .inspect(|_| bar.inc_length(1))
+                    .par_bridge()
+                    .map(|(origin, chunks)| check(origin, chunks))
+                    .try_reduce(Summary::default, |l, r| Ok(l.merge(r)));
+                let extracted = extraction
+                    .join()
+                    .map_err(|_| eyre!("Extracting the documentation panicked"))?;
+                // a failed check stops the extraction, report its cause
+                let summary = summary?;
+                extracted?;
+                Ok::<_, color_eyre::eyre::Report>(summary)
+            })?
+        }
+    };
+    bar.finish_and_clear();
+    summary.elapsed = start.elapsed();
+
+    let mut printed = printed.into_inner().expect("Lock is not poisoned. qed");
+    printed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (_path, report) in printed {
+        print!("{report}");
+    }
+
+    if let Some(html_sections) = html_sections {
+        let html_sections = html_sections
+            .into_inner()
+            .expect("Lock is not poisoned. qed");
+        let page = report::html_page(html_sections, &summary);
+        for report in reports
+            .iter()
+            .filter(|report| report.kind == ReportKind::Html)
+        {
+            if let Some(dir) = report
+                .path
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+            {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&report.path, &page)?;
+            log::info!("Wrote HTML report to {}", report.path.display());
+        }
+    }
+
+    // keep stdout reserved for the findings
+    if quiet {
+        eprintln!("{}", summary.headline());
+    } else {
+        eprintln!("{summary}");
+    }
+
+    Ok(summary)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tests::TempDir;
     use std::convert::TryInto;
 
+    /// The chunk of the doc comment `/// Speling is hard.`.
+    fn speling_chunk() -> CheckableChunk {
+        CheckableChunk::from_str(
+            "Speling is hard.",
+            indexmap::indexmap! { 0..16 => Span {
+                start: LineColumn { line: 1, column: 4 },
+                end: LineColumn { line: 1, column: 19 },
+            }},
+            CommentVariant::TripleSlash,
+        )
+    }
+
+    /// The misspelled `Speling` of the [`speling_chunk`], with the given
+    /// `replacements`.
+    fn speling<'s>(chunk: &'s CheckableChunk, replacements: &[&str]) -> Suggestion<'s> {
+        Suggestion {
+            detector: Detector::Hunspell,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
+            origin: ContentOrigin::TestEntityRust,
+            chunk,
+            span: Span {
+                start: LineColumn { line: 1, column: 4 },
+                end: LineColumn {
+                    line: 1,
+                    column: 10,
+                },
+            },
+            range: 0..7,
+            replacements: Vec::from_iter(replacements.iter().map(|s| s.to_string())),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn human_report_per_file() {
+        assert_eq!(
+            human_report(Path::new("src/lib.rs"), &[], &RenderOptions::default()),
+            ""
+        );
+
+        let chunk = speling_chunk();
+        let report = human_report(
+            Path::new("src/lib.rs"),
+            &[speling(&chunk, &["Spelling"])],
+            &RenderOptions::default(),
+        );
+        let report = console::strip_ansi_codes(&report);
+        assert!(report.starts_with("src/lib.rs\n\n"));
+        assert!(report.ends_with("\n1 issue in src/lib.rs\n\n"));
+    }
+
+    #[test]
+    fn reflow_diff() {
+        const CONTENT: &str = "/// A line which is too long.\nstruct A;\n/// Short\n/// one.\n";
+        let chunk = CheckableChunk::from_str(
+            "A line which is too long.",
+            indexmap::indexmap! { 0..25 => Span {
+                start: LineColumn { line: 1, column: 4 },
+                end: LineColumn { line: 1, column: 28 },
+            }},
+            CommentVariant::TripleSlash,
+        );
+        let joined = CheckableChunk::from_str(
+            "Short\none.",
+            indexmap::indexmap! {
+                0..5 => Span {
+                    start: LineColumn { line: 3, column: 4 },
+                    end: LineColumn { line: 3, column: 8 },
+                },
+                6..10 => Span {
+                    start: LineColumn { line: 4, column: 4 },
+                    end: LineColumn { line: 4, column: 7 },
+                },
+            },
+            CommentVariant::TripleSlash,
+        );
+        fn suggestion<'s>(
+            chunk: &'s CheckableChunk,
+            span: Span,
+            replacement: &str,
+        ) -> Suggestion<'s> {
+            Suggestion {
+                detector: Detector::Reflow,
+                agreeing: Vec::new(),
+                rule: None,
+                category: None,
+                origin: ContentOrigin::TestEntityRust,
+                chunk,
+                span,
+                range: 0..chunk.len_in_chars(),
+                replacements: vec![replacement.to_owned()],
+                description: None,
+            }
+        }
+        let suggestions = [
+            suggestion(
+                &joined,
+                Span {
+                    start: LineColumn { line: 3, column: 4 },
+                    end: LineColumn { line: 4, column: 7 },
+                },
+                "Short one.",
+            ),
+            suggestion(
+                &chunk,
+                Span {
+                    start: LineColumn { line: 1, column: 4 },
+                    end: LineColumn {
+                        line: 1,
+                        column: 28,
+                    },
+                },
+                "A line which\n/// is too long.",
+            ),
+        ];
+        let diff = unified_diff(Path::new("src/lib.rs"), CONTENT, &suggestions);
+        assert_eq!(
+            console::strip_ansi_codes(&diff),
+            r#"--- src/lib.rs
++++ src/lib.rs
+@@ -1,1 +1,2 @@
+-/// A line which is too long.
++/// A line which
++/// is too long.
+@@ -3,2 +4,1 @@
+-/// Short
+-/// one.
++/// Short one.
+"#
+        );
+    }
+
+    #[test]
+    fn safe_replacements() {
+        assert_eq!(CasePattern::of("word"), CasePattern::Lower);
+        assert_eq!(CasePattern::of("Word"), CasePattern::Capitalized);
+        assert_eq!(CasePattern::of("WORD"), CasePattern::Upper);
+        assert_eq!(CasePattern::of("iPhone"), CasePattern::Mixed);
+
+        let chunk = speling_chunk();
+        let suggestion = |replacements: &[&str]| speling(&chunk, replacements);
+        assert_eq!(
+            safe_replacement(&suggestion(&["Spelling"])),
+            Some("Spelling")
+        );
+        assert_eq!(safe_replacement(&suggestion(&["spelling"])), None);
+        assert_eq!(safe_replacement(&suggestion(&["Spellings"])), None);
+        assert_eq!(
+            safe_replacement(&suggestion(&["Spelling", "Spieling"])),
+            None
+        );
+        assert_eq!(safe_replacement(&suggestion(&["Spilling"])), None);
+    }
+
     macro_rules! verify_correction {
         ($text:literal, $bandaids:expr, $expected:literal) => {
             let mut sink: Vec<u8> = Vec::with_capacity(1024);
@@ -512,6 +1437,58 @@ Icecream truck"#
         );
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn correct_file_keeps_permissions_and_symlinks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let base = TempDir::new();
+        let file = base.join("script.rs");
+        fs::write(&file, "//! Thsi is a script.\n").unwrap();
+        fs::set_permissions(&file, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let link = base.join("link.rs");
+        std::os::unix::fs::symlink(&file, &link).unwrap();
+
+        Action::Fix
+            .correct_file(
+                link.clone(),
+                vec![BandAid {
+                    content: "This".to_owned(),
+                    span: (1_usize, 4..8).try_into().unwrap(),
+                }],
+                None,
+                false,
+            )
+            .unwrap();
+
+        assert!(fs::symlink_metadata(&link).unwrap().is_symlink());
+        assert_eq!(
+            fs::read_to_string(&link).unwrap(),
+            "//! This is a script.\n"
+        );
+        let mode = fs::metadata(&file).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[test]
+    fn patch_crlf_bom() {
+        let patches = vec![
+            Patch::Replace {
+                replace_span: (1_usize, 4..8).try_into().unwrap(),
+                replacement: "Alpha\nbeta".to_owned(),
+            },
+            Patch::Insert {
+                insert_at: LineColumn { line: 2, column: 0 },
+                content: "//! Gamma\n".to_owned(),
+            },
+        ];
+        verify_correction!(
+            "\u{feff}//! Alfa\r\n//! Delta\r\n",
+            patches,
+            "\u{feff}//! Alpha\r\nbeta\r\n//! Gamma\r\n//! Delta\r\n"
+        );
+    }
+
     #[test]
     fn patch_replace_1() {
         let _ = env_logger::Builder::new()
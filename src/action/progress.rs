@@ -0,0 +1,28 @@
+//! Progress of the checked files, displayed on `stderr`.
+//!
+//! Checking with `nlprules` in particular may take a while for larger
+//! projects, the bar shows the number of completed files and the files being
+//! checked, with the checker currently running on each.
+
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::io::IsTerminal;
+use std::time::Duration;
+
+/// Create a progress bar for `total` files, which is hidden unless `enabled`
+/// and `stderr` is a terminal.
+pub(crate) fn progress_bar(total: usize, enabled: bool) -> ProgressBar {
+    if !enabled || !std::io::stderr().is_terminal() {
+        return ProgressBar::hidden();
+    }
+    let bar = ProgressBar::with_draw_target(Some(total as u64), ProgressDrawTarget::stderr());
+    bar.set_style(
+        ProgressStyle::with_template(
+            "{spinner} [{elapsed_precise}] {bar:40} {pos}/{len} files {wide_msg}",
+        )
+        .expect("Progress bar template is valid. qed")
+        .progress_chars("=> "),
+    );
+    // keep the spinner alive while a large file is checked
+    bar.enable_steady_tick(Duration::from_millis(120));
+    bar
+}
@@ -0,0 +1,258 @@
+//! Standalone reports of a check run, written in addition to the output on
+//! `stdout`.
+
+use super::summary::Summary;
+use crate::{Detector, Suggestion};
+
+use std::path::{Path, PathBuf};
+
+/// Inline style sheet of the HTML report, so the page is self contained.
+const STYLE: &str = r#"
+body { font-family: sans-serif; margin: 2em auto; max-width: 70em; color: #24292f; }
+h1 small, h2 small { color: #57606a; font-weight: normal; }
+section { margin-bottom: 3em; }
+article { border: 1px solid #d0d7de; border-radius: 6px; margin: 1em 0; padding: 0.5em 1em; }
+article header { font-weight: bold; }
+article header .id { color: #cf222e; }
+article header .location { color: #57606a; font-weight: normal; }
+table.excerpt { border-collapse: collapse; font-family: monospace; width: 100%; }
+table.excerpt td { padding: 0 0.5em; white-space: pre; }
+table.excerpt td.lineno { color: #57606a; text-align: right; user-select: none; width: 1%; }
+.comment { color: #0a3069; }
+.plain { color: #24292f; }
+mark { background: #ffebe9; color: #cf222e; text-decoration: underline wavy #cf222e; }
+del { background: #ffebe9; }
+ins { background: #dafbe1; text-decoration: none; }
+.preview { font-family: monospace; white-space: pre-wrap; }
+"#;
+
+/// Escape the characters with a meaning in HTML.
+fn escape(s: &str) -> String {
+    let mut acc = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => acc.push_str("&amp;"),
+            '<' => acc.push_str("&lt;"),
+            '>' => acc.push_str("&gt;"),
+            '"' => acc.push_str("&quot;"),
+            '\'' => acc.push_str("&#39;"),
+            c => acc.push(c),
+        }
+    }
+    acc
+}
+
+/// Class to highlight a source line with, comments apart from everything
+/// else.
+fn line_class(line: &str) -> &'static str {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("//") || trimmed.starts_with("/*") || trimmed.starts_with('*') {
+        "comment"
+    } else {
+        "plain"
+    }
+}
+
+/// Split `line` at the characters `columns`, clamped to the length of the
+/// line.
+fn split_line(line: &str, columns: std::ops::Range<usize>) -> (&str, &str, &str) {
+    let offset = |column: usize| {
+        line.char_indices()
+            .nth(column)
+            .map(|(offset, _c)| offset)
+            .unwrap_or(line.len())
+    };
+    let start = offset(columns.start);
+    let end = offset(columns.end).max(start);
+    (&line[..start], &line[start..end], &line[end..])
+}
+
+/// Lines of `content` around the suggestion, with the mistake marked.
+fn excerpt(content: &str, suggestion: &Suggestion<'_>, context_lines: usize) -> String {
+    let (_path, first) = suggestion.location();
+    let last = first + suggestion.span.end.line - suggestion.span.start.line;
+    let lines = Vec::from_iter(content.lines());
+    if first == 0 || first > lines.len() {
+        return String::new();
+    }
+    let mut acc = String::from("<table class=\"excerpt\">\n");
+    let start = first.saturating_sub(context_lines).max(1);
+    let end = (last + context_lines).min(lines.len());
+    for lineno in start..=end {
+        let line = lines[lineno - 1];
+        let text = if (first..=last).contains(&lineno) {
+            let from = if lineno == first {
+                suggestion.span.start.column
+            } else {
+                0
+            };
+            let to = if lineno == last {
+                suggestion.span.end.column + 1
+            } else {
+                usize::MAX
+            };
+            let (before, mistake, after) = split_line(line, from..to);
+            format!(
+                "{}<mark>{}</mark>{}",
+                escape(before),
+                escape(mistake),
+                escape(after)
+            )
+        } else {
+            escape(line)
+        };
+        acc.push_str(&format!(
+            "<tr><td class=\"lineno\">{lineno}</td><td class=\"{}\">{text}</td></tr>\n",
+            line_class(line)
+        ));
+    }
+    acc.push_str("</table>\n");
+    acc
+}
+
+/// The offending line with the first replacement applied, if the mistake is
+/// on a single line.
+fn preview(content: &str, suggestion: &Suggestion<'_>) -> Option<String> {
+    let replacement = suggestion.replacements.first()?;
+    if suggestion.span.start.line != suggestion.span.end.line {
+        return None;
+    }
+    let (_path, lineno) = suggestion.location();
+    let line = content.lines().nth(lineno.checked_sub(1)?)?;
+    let (before, mistake, after) = split_line(
+        line,
+        suggestion.span.start.column..(suggestion.span.end.column + 1),
+    );
+    Some(format!(
+        "{}<del>{}</del><ins>{}</ins>{}",
+        escape(before),
+        escape(mistake),
+        escape(replacement),
+        escape(after)
+    ))
+}
+
+/// Render the suggestions of a single file as HTML section, with excerpts of
+/// the file `content` and a preview of the first replacement.
+pub(crate) fn html_section(
+    path: &Path,
+    content: &str,
+    suggestions: &[Suggestion<'_>],
+    context_lines: usize,
+) -> String {
+    let n = suggestions.len();
+    let mut acc = format!(
+        "<section>\n<h2>{} <small>{n} {}</small></h2>\n",
+        escape(&path.display().to_string()),
+        if n == 1 { "issue" } else { "issues" },
+    );
+    for suggestion in suggestions {
+        let (location, line) = suggestion.location();
+        acc.push_str(&format!(
+            "<article>\n<header><span class=\"id\">error[{}]</span>: spellcheck({}) ",
+            escape(&suggestion.id()),
+            suggestion.detectors(),
+        ));
+        acc.push_str(&format!(
+            "<span class=\"location\">{}:{line}:{}</span></header>\n",
+            escape(&location),
+            suggestion.span.start.column + 1,
+        ));
+        if let Some(ref description) = suggestion.description {
+            acc.push_str(&format!("<p>{}</p>\n", escape(description)));
+        }
+        acc.push_str(&excerpt(content, suggestion, context_lines));
+        if !suggestion.replacements.is_empty() {
+            let replacements = suggestion
+                .replacements
+                .iter()
+                .map(|replacement| format!("<code>{}</code>", escape(replacement)))
+                .collect::<Vec<_>>();
+            acc.push_str(&format!(
+                "<p>Replacements: {}</p>\n",
+                replacements.join(", ")
+            ));
+        }
+        // reflown paragraphs span multiple lines, shown as replacement only
+        if !matches!(suggestion.detector, Detector::Reflow | Detector::LineLength) {
+            if let Some(preview) = preview(content, suggestion) {
+                acc.push_str(&format!("<p class=\"preview\">{preview}</p>\n"));
+            }
+        }
+        acc.push_str("</article>\n");
+    }
+    acc.push_str("</section>\n");
+    acc
+}
+
+/// Assemble a standalone HTML page from the `sections` per file, ordered by
+/// their path, headed by the `summary` of the run.
+pub(crate) fn html_page(mut sections: Vec<(PathBuf, String)>, summary: &Summary) -> String {
+    sections.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut acc = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    acc.push_str("<meta charset=\"utf-8\">\n<title>cargo spellcheck report</title>\n");
+    acc.push_str(&format!("<style>{STYLE}</style>\n</head>\n<body>\n"));
+    acc.push_str(&format!(
+        "<h1>cargo spellcheck <small>{}</small></h1>\n",
+        escape(&summary.headline())
+    ));
+    for (_path, section) in sections {
+        acc.push_str(&section);
+    }
+    acc.push_str("</body>\n</html>\n");
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documentation::{CheckableChunk, CommentVariant, ContentOrigin};
+    use crate::{LineColumn, Span};
+
+    #[test]
+    fn html() {
+        const CONTENT: &str = "/// A <b>speling</b> mistake.\nstruct X;\n";
+        let chunk = CheckableChunk::from_str(
+            " A <b>speling</b> mistake.",
+            indexmap::indexmap! { 0..26 => Span {
+                start: LineColumn { line: 1, column: 3 },
+                end: LineColumn { line: 1, column: 28 },
+            }},
+            CommentVariant::TripleSlash,
+        );
+        let suggestion = Suggestion {
+            detector: Detector::Hunspell,
+            agreeing: Vec::new(),
+            origin: ContentOrigin::TestEntityRust,
+            chunk: &chunk,
+            span: Span {
+                start: LineColumn { line: 1, column: 9 },
+                end: LineColumn {
+                    line: 1,
+                    column: 15,
+                },
+            },
+            range: 6..13,
+            replacements: vec!["spelling".to_owned()],
+            rule: None,
+            category: None,
+            description: None,
+        };
+        let section = html_section(Path::new("src/lib.rs"), CONTENT, &[suggestion], 1);
+        assert!(section.starts_with("<section>\n<h2>src/lib.rs <small>1 issue</small></h2>\n"));
+        assert!(section.contains(
+            "<td class=\"comment\">/// A &lt;b&gt;<mark>speling</mark>&lt;/b&gt; mistake.</td>"
+        ));
+        assert!(section.contains("<td class=\"plain\">struct X;</td>"));
+        assert!(section
+            .contains("/// A &lt;b&gt;<del>speling</del><ins>spelling</ins>&lt;/b&gt; mistake."));
+
+        let page = html_page(
+            vec![(PathBuf::from("src/lib.rs"), section)],
+            &Summary::default(),
+        );
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains("Found 0 mistakes in 0 files"));
+        assert!(page.ends_with("</body>\n</html>\n"));
+    }
+}
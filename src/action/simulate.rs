@@ -0,0 +1,155 @@
+//! Estimate the impact of adding words to the dictionary.
+//!
+//! Runs the configured checkers and reports how many of the findings would
+//! disappear, if the given words were part of the dictionary. The findings of
+//! previous runs are not persisted, the baseline only holds their
+//! fingerprints, so every simulation is a full check run.
+
+use crate::checker::{Checker, Checkers};
+use crate::errors::*;
+use crate::util::sub_chars;
+use crate::{Config, Detector, Documentation};
+
+use indexmap::IndexMap;
+use rayon::iter::ParallelIterator;
+
+/// Detectors which are based on a dictionary, and as such are affected by
+/// adding words to it.
+pub(crate) const fn is_dictionary_based(detector: Detector) -> bool {
+    matches!(
+        detector,
+        Detector::Hunspell | Detector::ZSpell | Detector::Spellbook
+    )
+}
+
+/// Determine if `flagged` would be accepted, if `word` was part of the
+/// dictionary.
+///
+/// Like hunspell, a lowercase dictionary entry also covers the capitalized
+/// and the all uppercase variants.
+fn is_covered_by(word: &str, flagged: &str) -> bool {
+    if word == flagged {
+        return true;
+    }
+    word.chars().all(|c| !c.is_uppercase()) && flagged.to_lowercase() == word
+}
+
+/// Impact of the hypothetical dictionary additions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Simulation {
+    /// Total number of findings of the current run.
+    pub total: usize,
+    /// Number of findings that would disappear per word, in order of the
+    /// provided words.
+    pub per_word: IndexMap<String, usize>,
+}
+
+impl Simulation {
+    /// Number of findings that would disappear in total.
+    pub fn resolved(&self) -> usize {
+        self.per_word.values().sum()
+    }
+
+    /// Account for the flagged words of one run.
+    fn record<'a>(&mut self, words: &[String], flagged: impl IntoIterator<Item = &'a str>) {
+        for flagged in flagged {
+            if let Some(word) = words.iter().find(|word| is_covered_by(word, flagged)) {
+                if let Some(count) = self.per_word.get_mut(word) {
+                    *count += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Check all `documents`, returns the total number of findings and the words
+/// flagged by the dictionary based checkers.
+pub(crate) fn check_flagged_words(
+    documents: Documentation,
+    config: Config,
+) -> Result<(usize, Vec<String>)> {
+    let mut checkers = Checkers::new(config)?;
+    if checkers.needs_all_documents() {
+        checkers.collect_identifiers(&documents);
+    }
+
+    documents
+        .into_par_iter()
+        .map(|(origin, chunks)| {
+            checkers.check(&origin, &chunks).map(|suggestions| {
+                let flagged = Vec::from_iter(
+                    suggestions
+                        .iter()
+                        .filter(|suggestion| is_dictionary_based(suggestion.detector))
+                        .map(|suggestion| {
+                            sub_chars(suggestion.chunk.as_str(), suggestion.range.clone())
+                        }),
+                );
+                (suggestions.len(), flagged)
+            })
+        })
+        .try_reduce(
+            || (0, Vec::new()),
+            |(l_total, mut l_flagged), (r_total, r_flagged)| {
+                l_flagged.extend(r_flagged);
+                Ok((l_total + r_total, l_flagged))
+            },
+        )
+}
+
+/// Check all `documents` and evaluate which findings would be gone with
+/// `words` being part of the dictionary.
+pub fn simulate_dictionary_additions(
+    documents: Documentation,
+    config: Config,
+    words: &[String],
+) -> Result<Simulation> {
+    let (total, flagged) = check_flagged_words(documents, config)?;
+    let mut simulation = Simulation {
+        total,
+        per_word: IndexMap::from_iter(words.iter().map(|word| (word.to_owned(), 0))),
+    };
+    simulation.record(words, flagged.iter().map(String::as_str));
+    Ok(simulation)
+}
+
+impl std::fmt::Display for Simulation {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (word, count) in self.per_word.iter() {
+            writeln!(formatter, "{word}: {count}")?;
+        }
+        write!(
+            formatter,
+            "{} of {} findings would disappear",
+            self.resolved(),
+            self.total
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn covered() {
+        assert!(is_covered_by("foo", "foo"));
+        assert!(is_covered_by("foo", "Foo"));
+        assert!(is_covered_by("foo", "FOO"));
+        assert!(!is_covered_by("Foo", "foo"));
+        assert!(!is_covered_by("foo", "foobar"));
+    }
+
+    #[test]
+    fn record() {
+        let words = vec!["foo".to_owned(), "Bar".to_owned()];
+        let mut simulation = Simulation {
+            total: 5,
+            per_word: IndexMap::from_iter(words.iter().map(|word| (word.to_owned(), 0))),
+        };
+        simulation.record(&words, ["Foo", "foo", "bar", "Bar", "baz"]);
+        assert_eq!(simulation.per_word["foo"], 2);
+        assert_eq!(simulation.per_word["Bar"], 1);
+        assert_eq!(simulation.resolved(), 3);
+    }
+}
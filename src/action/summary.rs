@@ -0,0 +1,170 @@
+//! Statistics of a check run.
+
+use super::simulate::is_dictionary_based;
+use crate::config::{Severity, SeverityConfig};
+use crate::util::sub_chars;
+use crate::{Detector, Suggestion};
+
+use indexmap::IndexMap;
+use std::time::Duration;
+
+/// Number of misspellings listed in the summary.
+const TOP_MISSPELLINGS: usize = 10;
+
+/// Statistics of a check run, for trend data and quick triage.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Summary {
+    /// Number of files that were checked.
+    pub files: usize,
+    /// Number of chunks that were checked.
+    pub chunks: usize,
+    /// Number of suggestions per detector, warnings excluded.
+    pub per_detector: IndexMap<Detector, usize>,
+    /// Number of suggestions with a severity of warning, which do not fail
+    /// the run.
+    pub warnings: usize,
+    /// Number of occurrences per misspelled word, as flagged by the dictionary
+    /// based detectors.
+    pub misspellings: IndexMap<String, usize>,
+    /// Wall clock time the run took.
+    pub elapsed: Duration,
+}
+
+impl Summary {
+    /// Account for one checked file.
+    pub(crate) fn record(
+        &mut self,
+        chunks: usize,
+        suggestions: &[Suggestion<'_>],
+        severities: &SeverityConfig,
+    ) {
+        self.files += 1;
+        self.chunks += chunks;
+        for suggestion in suggestions {
+            if severities.of(&suggestion.id()) == Severity::Warning {
+                self.warnings += 1;
+                continue;
+            }
+            *self.per_detector.entry(suggestion.detector).or_default() += 1;
+            if is_dictionary_based(suggestion.detector) {
+                let word = sub_chars(suggestion.chunk.as_str(), suggestion.range.clone());
+                *self.misspellings.entry(word).or_default() += 1;
+            }
+        }
+    }
+
+    /// Join the statistics of two disjoint sets of files.
+    pub(crate) fn merge(mut self, other: Self) -> Self {
+        self.files += other.files;
+        self.chunks += other.chunks;
+        self.warnings += other.warnings;
+        for (detector, count) in other.per_detector {
+            *self.per_detector.entry(detector).or_default() += count;
+        }
+        for (word, count) in other.misspellings {
+            *self.misspellings.entry(word).or_default() += count;
+        }
+        self
+    }
+
+    /// Total number of suggestions across all detectors, warnings excluded.
+    pub fn mistakes(&self) -> usize {
+        self.per_detector.values().sum()
+    }
+
+    /// A single line summary of the number of mistakes and checked files.
+    pub fn headline(&self) -> String {
+        let mut headline = format!("Found {} mistakes in {} files", self.mistakes(), self.files);
+        if self.warnings > 0 {
+            headline.push_str(&format!(" and {} warnings", self.warnings));
+        }
+        headline
+    }
+
+    /// The `n` most frequent misspellings, most frequent first.
+    pub fn top_misspellings(&self, n: usize) -> Vec<(&str, usize)> {
+        let mut top = Vec::from_iter(
+            self.misspellings
+                .iter()
+                .map(|(word, count)| (word.as_str(), *count)),
+        );
+        top.sort_by(|(a_word, a_count), (b_word, b_count)| {
+            b_count.cmp(a_count).then_with(|| a_word.cmp(b_word))
+        });
+        top.truncate(n);
+        top
+    }
+}
+
+impl std::fmt::Display for Summary {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            formatter,
+            "Checked {} files with {} chunks in {:.2?}",
+            self.files, self.chunks, self.elapsed
+        )?;
+        let mut per_detector = Vec::from_iter(self.per_detector.iter());
+        per_detector.sort_by_key(|(detector, _count)| detector.as_str());
+        write!(formatter, "Found {} mistakes", self.mistakes())?;
+        for (idx, (detector, count)) in per_detector.into_iter().enumerate() {
+            let separator = if idx == 0 { ": " } else { ", " };
+            write!(formatter, "{separator}{detector}: {count}")?;
+        }
+        if self.warnings > 0 {
+            write!(formatter, "\nFound {} warnings", self.warnings)?;
+        }
+        let top = self.top_misspellings(TOP_MISSPELLINGS);
+        if !top.is_empty() {
+            write!(formatter, "\nMost frequent misspellings:")?;
+            for (word, count) in top {
+                write!(formatter, "\n  {word}: {count}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_and_top() {
+        let lhs = Summary {
+            files: 1,
+            chunks: 3,
+            per_detector: indexmap::indexmap! { Detector::Hunspell => 3 },
+            warnings: 0,
+            misspellings: indexmap::indexmap! { "teh".to_owned() => 2, "foo".to_owned() => 1 },
+            elapsed: Duration::ZERO,
+        };
+        let rhs = Summary {
+            files: 2,
+            chunks: 1,
+            per_detector: indexmap::indexmap! { Detector::Hunspell => 2, Detector::NlpRules => 1 },
+            warnings: 2,
+            misspellings: indexmap::indexmap! { "foo".to_owned() => 2, "bar".to_owned() => 1 },
+            elapsed: Duration::ZERO,
+        };
+        let mut summary = lhs.merge(rhs);
+        summary.elapsed = Duration::from_millis(20);
+        assert_eq!(summary.files, 3);
+        assert_eq!(summary.chunks, 4);
+        assert_eq!(summary.mistakes(), 6);
+        assert_eq!(
+            summary.headline(),
+            "Found 6 mistakes in 3 files and 2 warnings"
+        );
+        assert_eq!(summary.top_misspellings(2), vec![("foo", 3), ("teh", 2)]);
+        assert_eq!(
+            summary.to_string(),
+            "Checked 3 files with 4 chunks in 20.00ms
+Found 6 mistakes: Hunspell: 5, NlpRules: 1
+Found 2 warnings
+Most frequent misspellings:
+  foo: 3
+  teh: 2
+  bar: 1"
+        );
+    }
+}
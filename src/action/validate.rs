@@ -0,0 +1,356 @@
+//! Validation of a configuration file.
+//!
+//! Reports all unknown keys, unresolvable dictionary paths and checkers
+//! failing to initialize at once, rather than the first one as a runtime
+//! failure of a check run.
+
+use crate::checker::{edit_distance, Checkers};
+use crate::config::HunspellConfig;
+use crate::errors::*;
+use crate::Config;
+
+use fs_err as fs;
+use std::fmt;
+use std::path::Path;
+
+/// How severe a finding of the validation is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The configuration is not usable.
+    Error,
+    /// The configuration is usable, but likely not as intended.
+    Warning,
+}
+
+/// A single finding of the validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// How severe the finding is.
+    pub severity: Severity,
+    /// The dotted path of the affected entry, i.e. `Hunspell.lang`, empty
+    /// for the file as a whole.
+    pub entry: String,
+    /// What is wrong and how to fix it.
+    pub message: String,
+}
+
+impl Finding {
+    fn error(entry: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            entry: entry.into(),
+            message: message.into(),
+        }
+    }
+
+    fn warning(entry: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            entry: entry.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        if self.entry.is_empty() {
+            write!(formatter, "{severity}: {}", self.message)
+        } else {
+            write!(formatter, "{severity}[{}]: {}", self.entry, self.message)
+        }
+    }
+}
+
+/// Extract the unknown key and the expected ones from a deserialization error
+/// message, i.e. ``unknown field `lnag`, expected one of `lang`, `quirks` ``.
+fn unknown_field(message: &str) -> Option<(&str, Vec<&str>)> {
+    let rest = &message[(message.find("unknown field `")? + 15)..];
+    let (field, rest) = rest.split_once('`')?;
+    let expected = rest
+        .split_once("expected")
+        .map(|(_, expected)| {
+            expected
+                .split('`')
+                .skip(1)
+                .step_by(2)
+                .take_while(|key| !key.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    Some((field, expected))
+}
+
+/// Remove the first occurrence of `key` within `table` or any nested table,
+/// returns the dotted path of the removed entry.
+fn remove_key(table: &mut toml::Table, key: &str) -> Option<String> {
+    if table.remove(key).is_some() {
+        return Some(key.to_owned());
+    }
+    for (name, value) in table.iter_mut() {
+        let nested = match value {
+            toml::Value::Table(nested) => remove_key(nested, key),
+            toml::Value::Array(items) => items.iter_mut().find_map(|item| match item {
+                toml::Value::Table(nested) => remove_key(nested, key),
+                _ => None,
+            }),
+            _ => None,
+        };
+        if let Some(path) = nested {
+            return Some(format!("{name}.{path}"));
+        }
+    }
+    None
+}
+
+/// Parse the configuration `content`, reporting all unknown keys with the
+/// closest known one as suggestion.
+///
+/// Returns the configuration without the unknown keys, if it is usable at all.
+pub(crate) fn parse(content: &str) -> (Option<Config>, Vec<Finding>) {
+    let mut findings = Vec::new();
+    let mut table = match toml::from_str::<toml::Table>(content) {
+        Ok(table) => table,
+        Err(e) => {
+            findings.push(Finding::error("", e.to_string().trim().to_owned()));
+            return (None, findings);
+        }
+    };
+    loop {
+        let e = match toml::Value::Table(table.clone()).try_into::<Config>() {
+            Ok(config) => return (Some(config), findings),
+            Err(e) => e,
+        };
+        let message = e.message().trim().to_owned();
+        let Some((field, expected)) = unknown_field(&message) else {
+            findings.push(Finding::error("", message));
+            return (None, findings);
+        };
+        let Some(entry) = remove_key(&mut table, field) else {
+            findings.push(Finding::error("", message));
+            return (None, findings);
+        };
+        let closest = expected
+            .iter()
+            .map(|key| (edit_distance(field, key), *key))
+            .filter(|(distance, _key)| *distance <= 3)
+            .min();
+        let message = match closest {
+            Some((_distance, key)) => format!("Unknown key `{field}`, did you mean `{key}`?"),
+            None if expected.is_empty() => format!("Unknown key `{field}`"),
+            None => format!(
+                "Unknown key `{field}`, expected one of `{}`",
+                expected.join("`, `")
+            ),
+        };
+        findings.push(Finding::error(entry, message));
+    }
+}
+
+/// Resolve the dictionaries of a dictionary based checker `section` relative
+/// to the directory `base` of the configuration file.
+fn dictionaries(section: &str, config: &HunspellConfig, base: &Path) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    // only the explicitly configured ones, the OS provided ones are optional
+    for search_dir in config.search_dirs.iter(false) {
+        let search_dir = base.join(search_dir);
+        if !search_dir.is_dir() {
+            findings.push(Finding::warning(
+                format!("{section}.search_dirs"),
+                format!("Search dir {} does not exist", search_dir.display()),
+            ));
+            continue;
+        }
+        let lang = config.lang().to_string();
+        let dic = search_dir.join(&lang).with_extension("dic");
+        let aff = search_dir.join(&lang).with_extension("aff");
+        if dic.is_file() && !aff.is_file() {
+            findings.push(Finding::error(
+                format!("{section}.search_dirs"),
+                format!(
+                    "Found {} but the affix file {} is missing, the dictionary is skipped",
+                    dic.display(),
+                    aff.display()
+                ),
+            ));
+        }
+    }
+    for dictionary in config.extra_dictionaries() {
+        let mut single = config.clone();
+        single.extra_dictionaries = vec![dictionary.clone()];
+        if let Err(e) = single.sanitize_paths(base) {
+            findings.push(Finding::error(
+                format!("{section}.extra_dictionaries"),
+                format!("{e}, the paths are relative to the search dirs"),
+            ));
+        }
+    }
+    findings
+}
+
+/// Resolve all paths of the `config` relative to the directory `base` of the
+/// configuration file.
+pub(crate) fn paths(config: &Config, base: &Path) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for (section, checker) in [
+        ("Hunspell", &config.hunspell),
+        ("ZSpell", &config.zet),
+        ("Spellbook", &config.spellbook),
+    ] {
+        if let Some(checker) = checker {
+            findings.extend(dictionaries(section, checker, base));
+        }
+    }
+    if let Some(ref baseline) = config.baseline {
+        let baseline = base.join(baseline);
+        if !baseline.is_file() {
+            findings.push(Finding::warning(
+                "baseline",
+                format!(
+                    "Baseline {} does not exist, record it with `cargo spellcheck baseline`",
+                    baseline.display()
+                ),
+            ));
+        }
+    }
+    findings
+}
+
+/// Initialize each enabled checker on its own, to attribute failures to the
+/// section of the checker.
+fn probe(config: &Config) -> Vec<Finding> {
+    let only = |section: &str| {
+        let mut single = config.clone();
+        if section != "Hunspell" {
+            single.hunspell = None;
+        }
+        if section != "ZSpell" {
+            single.zet = None;
+        }
+        if section != "Spellbook" {
+            single.spellbook = None;
+        }
+        if section != "NlpRules" {
+            single.nlprules = None;
+        }
+        // the paths were resolved already
+        single.baseline = None;
+        single
+    };
+    let enabled = [
+        ("Hunspell", config.hunspell.is_some()),
+        ("ZSpell", config.zet.is_some()),
+        ("Spellbook", config.spellbook.is_some()),
+        ("NlpRules", config.nlprules.is_some()),
+        // all the remaining ones
+        ("", true),
+    ];
+    let mut findings = Vec::new();
+    for (section, _enabled) in enabled.into_iter().filter(|(_section, enabled)| *enabled) {
+        if let Err(e) = Checkers::new(only(section)) {
+            let entry = if section.is_empty() {
+                "checker"
+            } else {
+                section
+            };
+            findings.push(Finding::error(
+                entry,
+                format!("Failed to initialize: {e:#}"),
+            ));
+        }
+    }
+    findings
+}
+
+/// Validate the configuration file at `path`, the findings are ordered by
+/// severity.
+pub fn validate(path: &Path) -> Result<Vec<Finding>> {
+    let content = fs::read_to_string(path)?;
+    let (config, mut findings) = parse(&content);
+    if let Some(config) = config {
+        let base = path
+            .parent()
+            .ok_or_else(|| eyre!("Config file {} has no parent dir", path.display()))?;
+        let resolved = paths(&config, base);
+        // initialization fails for the same reason, again
+        let unresolved = resolved
+            .iter()
+            .any(|finding| finding.severity == Severity::Error);
+        findings.extend(resolved);
+        if !unresolved {
+            findings.extend(probe(&config));
+        }
+    }
+    findings.sort_by_key(|finding| finding.severity == Severity::Warning);
+    Ok(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_keys() {
+        let (config, mut findings) = parse(
+            r#"
+dev_coments = false
+[Hunspell]
+lnag = "en_US"
+totally_unrelated = true
+"#,
+        );
+        assert!(config.is_some());
+        assert_eq!(findings.len(), 3);
+        findings.sort_by(|a, b| a.entry.cmp(&b.entry));
+        assert_eq!(
+            findings[0],
+            Finding::error("Hunspell.lnag", "Unknown key `lnag`, did you mean `lang`?")
+        );
+        assert_eq!(findings[1].entry, "Hunspell.totally_unrelated");
+        assert!(findings[1].message.contains("expected one of `lang`"));
+        assert_eq!(
+            findings[2],
+            Finding::error(
+                "dev_coments",
+                "Unknown key `dev_coments`, did you mean `dev_comments`?"
+            )
+        );
+
+        // nested sections are checked as well
+        let (config, findings) = parse("[Reflow]\nmax_line_lenght = 80");
+        assert!(config.is_some());
+        assert_eq!(
+            findings,
+            vec![Finding::error(
+                "Reflow.max_line_lenght",
+                "Unknown key `max_line_lenght`, did you mean `max_line_length`?"
+            )]
+        );
+
+        let (config, findings) = parse("[Hunspell\nlang = 1");
+        assert!(config.is_none());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn missing_dictionaries() {
+        let (config, findings) = parse(
+            r#"
+[Hunspell]
+search_dirs = ["."]
+extra_dictionaries = ["missing.dic"]
+"#,
+        );
+        assert!(findings.is_empty());
+        let base = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let findings = paths(&config.unwrap(), base);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].entry, "Hunspell.extra_dictionaries");
+        assert_eq!(findings[0].severity, Severity::Error);
+    }
+}
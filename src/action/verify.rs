@@ -0,0 +1,249 @@
+//! Verification of patched content, before it replaces the original file.
+//!
+//! Fixes and reflows must only ever touch prose, so everything else, the code
+//! of a rust source file, the code spans, code blocks and links of a markdown
+//! file or the structure of a manifest, must be identical before and after.
+
+use crate::errors::*;
+use crate::util::strip_byte_order_mark;
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use std::path::Path;
+
+/// Verify that `patched` only differs from the `original` content of the file
+/// at `path` in its prose. Files of unknown kind are not verified.
+pub(crate) fn verify(path: &Path, original: &str, patched: &str) -> Result<()> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default();
+    match extension {
+        "rs" => verify_rust(original, patched),
+        "md" => verify_markdown(original, patched),
+        "toml" => verify_toml(original, patched),
+        _ => {
+            log::debug!("Not verifying {}, unknown file kind", path.display());
+            Ok(())
+        }
+    }
+}
+
+/// Strip the byte order mark and a shebang line, the latter is not a token.
+fn rust_source(content: &str) -> &str {
+    let content = strip_byte_order_mark(content);
+    match content.strip_prefix("#!") {
+        Some(rest) if !rest.trim_start().starts_with('[') => {
+            &content[content.find('\n').unwrap_or(content.len())..]
+        }
+        _ => content,
+    }
+}
+
+fn verify_rust(original: &str, patched: &str) -> Result<()> {
+    // nothing to compare against, if the original is not valid already
+    if syn::parse_file(original).is_err() {
+        log::debug!("Not verifying, the original content does not parse");
+        return Ok(());
+    }
+    syn::parse_file(patched).map_err(|e| eyre!("does not parse anymore: {e}"))?;
+    let tokens = |content: &str| -> Result<Vec<String>> {
+        let stream = syn::parse_str::<TokenStream>(rust_source(content))?;
+        let mut acc = Vec::new();
+        flatten(stream, &mut acc);
+        Ok(acc)
+    };
+    if tokens(original)? != tokens(patched)? {
+        bail!("differs in its code, not only in its doc comments");
+    }
+    Ok(())
+}
+
+/// The length of the doc attribute at the start of `trees`, either `#[doc ..]`
+/// or `#![doc ..]`, which is what doc comments are turned into.
+fn doc_attribute(trees: &[TokenTree]) -> Option<usize> {
+    let [TokenTree::Punct(hash), rest @ ..] = trees else {
+        return None;
+    };
+    if hash.as_char() != '#' {
+        return None;
+    }
+    let (bang, rest) = match rest {
+        [TokenTree::Punct(bang), rest @ ..] if bang.as_char() == '!' => (1, rest),
+        _ => (0, rest),
+    };
+    let Some(TokenTree::Group(group)) = rest.first() else {
+        return None;
+    };
+    let name = group.stream().into_iter().next();
+    (group.delimiter() == Delimiter::Bracket
+        && matches!(name, Some(TokenTree::Ident(ident)) if ident == "doc"))
+    .then_some(2 + bang)
+}
+
+/// Flatten the token `stream` into comparable strings, without doc attributes
+/// and without the content of string literals, which are checked with
+/// `--check-strings`.
+fn flatten(stream: TokenStream, acc: &mut Vec<String>) {
+    let trees = Vec::from_iter(stream);
+    let mut idx = 0;
+    while idx < trees.len() {
+        if let Some(len) = doc_attribute(&trees[idx..]) {
+            idx += len;
+            continue;
+        }
+        match &trees[idx] {
+            TokenTree::Group(group) => {
+                acc.push(format!("{:?}", group.delimiter()));
+                flatten(group.stream(), acc);
+                acc.push(format!("/{:?}", group.delimiter()));
+            }
+            TokenTree::Literal(literal) => {
+                let literal = literal.to_string();
+                let unprefixed = literal.trim_start_matches(['b', 'c', 'r']);
+                if unprefixed.starts_with(['"', '#']) {
+                    acc.push("\"\"".to_owned());
+                } else {
+                    acc.push(literal);
+                }
+            }
+            tree => acc.push(tree.to_string()),
+        }
+        idx += 1;
+    }
+}
+
+/// Collect everything of a markdown document which is not prose, code spans,
+/// code blocks, html and the destinations of links and images.
+fn markdown_verbatim(content: &str) -> Vec<String> {
+    let mut acc = Vec::new();
+    let mut code_block = false;
+    for event in Parser::new_ext(content, Options::all()) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => code_block = true,
+            Event::End(TagEnd::CodeBlock) => code_block = false,
+            Event::Text(text) if code_block => acc.push(text.into_string()),
+            Event::Code(code) | Event::Html(code) | Event::InlineHtml(code) => {
+                acc.push(code.into_string())
+            }
+            Event::Start(Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. }) => {
+                acc.push(dest_url.into_string())
+            }
+            _ => {}
+        }
+    }
+    acc
+}
+
+fn verify_markdown(original: &str, patched: &str) -> Result<()> {
+    if markdown_verbatim(original) != markdown_verbatim(patched) {
+        bail!("differs in its code or links, not only in its prose");
+    }
+    Ok(())
+}
+
+/// Erase all strings of a manifest, the description is the only prose within.
+fn erase_strings(value: toml::Value) -> toml::Value {
+    match value {
+        toml::Value::String(_) => toml::Value::String(String::new()),
+        toml::Value::Array(array) => {
+            toml::Value::Array(Vec::from_iter(array.into_iter().map(erase_strings)))
+        }
+        toml::Value::Table(table) => toml::Value::Table(toml::Table::from_iter(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, erase_strings(value))),
+        )),
+        value => value,
+    }
+}
+
+fn verify_toml(original: &str, patched: &str) -> Result<()> {
+    let Ok(original) = original.parse::<toml::Value>() else {
+        log::debug!("Not verifying, the original content does not parse");
+        return Ok(());
+    };
+    let patched = patched
+        .parse::<toml::Value>()
+        .map_err(|e| eyre!("does not parse anymore: {e}"))?;
+    if erase_strings(original) != erase_strings(patched) {
+        bail!("differs in its structure, not only in its strings");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rust() {
+        const ORIGINAL: &str = r#"#!/usr/bin/env run-cargo-script
+//! A scirpt.
+
+/// Thsi is a
+/// function.
+#[doc = "more"]
+fn f() -> &'static str {
+    "Helo"
+}
+"#;
+        const PATCHED: &str = r#"#!/usr/bin/env run-cargo-script
+//! A script.
+
+/// This is a function.
+#[doc = "more"]
+fn f() -> &'static str {
+    "Hello"
+}
+"#;
+        assert!(verify(Path::new("lib.rs"), ORIGINAL, PATCHED).is_ok());
+        assert!(verify(
+            Path::new("lib.rs"),
+            ORIGINAL,
+            &PATCHED.replace("fn f", "fn g")
+        )
+        .is_err());
+        // a doc comment broken by a replacement containing a newline
+        assert!(verify(
+            Path::new("lib.rs"),
+            ORIGINAL,
+            &PATCHED.replace("script.", "script.\nfn")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn markdown() {
+        const ORIGINAL: &str = "# Titel\n\nUse `foo` as [link](https://exmaple.com).\n";
+        assert!(verify(
+            Path::new("README.md"),
+            ORIGINAL,
+            "# Title\n\nUse `foo` as\n[link](https://exmaple.com).\n"
+        )
+        .is_ok());
+        assert!(verify(
+            Path::new("README.md"),
+            ORIGINAL,
+            "# Title\n\nUse `foo` as [link](https://example.com).\n"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn manifest() {
+        const ORIGINAL: &str = "[package]\nname = \"x\"\ndescription = \"Helo\"\n";
+        assert!(verify(
+            Path::new("Cargo.toml"),
+            ORIGINAL,
+            "[package]\nname = \"x\"\ndescription = \"Hello\"\n"
+        )
+        .is_ok());
+        assert!(verify(
+            Path::new("Cargo.toml"),
+            ORIGINAL,
+            "[package]\nname = \"x\"\ndescription = \"Hello\n"
+        )
+        .is_err());
+    }
+}
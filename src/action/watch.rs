@@ -0,0 +1,109 @@
+//! Change detection of the watch mode.
+//!
+//! The checked files and the dictionaries are polled for changes of their
+//! modification time, which requires no platform specific notification
+//! mechanism and is cheap enough at the interval used.
+
+use crate::config::Config;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// The time between two polls.
+pub(crate) const INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification times of a set of files, files which do not exist are
+/// absent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Snapshot(HashMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    pub(crate) fn of(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self(HashMap::from_iter(paths.into_iter().filter_map(|path| {
+            let modified = std::fs::metadata(&path).and_then(|meta| meta.modified());
+            modified.ok().map(|modified| (path, modified))
+        })))
+    }
+}
+
+/// All files below `paths`, ignoring those ignored by git, or below the
+/// current working directory if there are none.
+pub(crate) fn files(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut paths = paths.iter();
+    let mut walk = match paths.next() {
+        Some(first) => ignore::WalkBuilder::new(first),
+        None => ignore::WalkBuilder::new("."),
+    };
+    for path in paths {
+        walk.add(path);
+    }
+    Vec::from_iter(
+        walk.build()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().map_or(false, |kind| kind.is_file()))
+            .map(|entry| entry.into_path()),
+    )
+}
+
+/// The extra dictionaries of all spelling checkers, and the personal word
+/// lists, the `.dic` and `.aff` files of the language in any of the search
+/// directories, i.e. `~/.local/share/hunspell/en_US.dic`. The latter do not
+/// have to exist yet.
+pub(crate) fn dictionaries(config: &Config) -> Vec<PathBuf> {
+    let configs = config
+        .hunspell
+        .iter()
+        .chain(config.zet.iter())
+        .chain(config.spellbook.iter());
+    let mut acc = Vec::from_iter(
+        configs
+            .clone()
+            .flat_map(|config| config.extra_dictionaries())
+            .cloned(),
+    );
+    for config in configs {
+        let lang = config.lang().to_string();
+        for search_dir in config.search_dirs() {
+            acc.push(search_dir.join(&lang).with_extension("dic"));
+            acc.push(search_dir.join(&lang).with_extension("aff"));
+        }
+    }
+    acc.sort();
+    acc.dedup();
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::TempDir;
+
+    #[test]
+    fn snapshot_changes() {
+        let dir = TempDir::new();
+        let path = dir.join("watched.rs");
+        let missing = Snapshot::of([path.clone()]);
+        assert!(missing.0.is_empty());
+        std::fs::write(&path, "Speling").unwrap();
+        let written = Snapshot::of([path.clone()]);
+        assert_ne!(written, missing);
+        assert_eq!(Snapshot::of([path.clone()]), written);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(Snapshot::of([path]), missing);
+    }
+
+    #[test]
+    fn dictionaries_in_search_dirs() {
+        let dir = TempDir::new();
+        let mut config = Config::default();
+        let hunspell = config.hunspell.get_or_insert_with(Default::default);
+        hunspell.skip_os_lookups = true;
+        hunspell.search_dirs = vec![dir.to_path_buf()].into();
+        hunspell.extra_dictionaries = vec![dir.join("lingo.dic")];
+        let dictionaries = dictionaries(&config);
+        assert!(dictionaries.contains(&dir.join("lingo.dic")));
+        assert!(dictionaries.contains(&dir.join("en_US.dic")));
+        assert!(dictionaries.contains(&dir.join("en_US.aff")));
+    }
+}
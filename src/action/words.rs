@@ -0,0 +1,122 @@
+//! Collect the words unknown to the dictionaries, to bootstrap the allowlist
+//! of a project.
+//!
+//! The words are either printed, the most frequent ones first, or added to a
+//! hunspell dictionary file, which can then be used as extra dictionary.
+
+use super::simulate::check_flagged_words;
+use crate::errors::*;
+use crate::{Config, Documentation};
+
+use fs_err as fs;
+use indexmap::IndexMap;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// The words flagged by the dictionary based checkers, with the number of
+/// their occurrences.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnknownWords(IndexMap<String, usize>);
+
+impl UnknownWords {
+    /// Count the occurrences of each of the `flagged` words, the most frequent
+    /// ones first, ties are ordered alphabetically.
+    fn from_flagged<'a>(flagged: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut words = IndexMap::<String, usize>::new();
+        for word in flagged {
+            *words.entry(word.to_owned()).or_default() += 1;
+        }
+        words.sort_by(|l_word, l_count, r_word, r_count| {
+            r_count.cmp(l_count).then_with(|| l_word.cmp(r_word))
+        });
+        Self(words)
+    }
+
+    /// Are there any unknown words?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Add all words to the hunspell dictionary at `path`, which is created if
+    /// it does not exist yet. Returns the number of words added, words which
+    /// are part of the dictionary already are skipped.
+    pub fn write_to_dictionary(&self, path: &Path) -> Result<usize> {
+        let existing = if path.exists() {
+            fs::read_to_string(path)?
+        } else {
+            String::new()
+        };
+        let (dic, added) = merge_into_dic(&existing, self.0.keys().map(String::as_str));
+        fs::write(path, dic).wrap_err_with(|| eyre!("Failed to write {}", path.display()))?;
+        Ok(added)
+    }
+}
+
+impl std::fmt::Display for UnknownWords {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (word, count) in self.0.iter() {
+            writeln!(formatter, "{word}: {count}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Add the `words` to the content of the `.dic` file `existing`, keeping its
+/// entries and their flags, and update the leading entry count.
+fn merge_into_dic<'a>(existing: &str, words: impl IntoIterator<Item = &'a str>) -> (String, usize) {
+    let mut lines = existing.lines();
+    let mut entries = Vec::from_iter(lines.by_ref().take(1).filter(|line| {
+        // the first line is the entry count, which is recalculated
+        line.trim().parse::<usize>().is_err()
+    }));
+    entries.extend(lines);
+    entries.retain(|entry| !entry.trim().is_empty());
+
+    let known = HashSet::<&str>::from_iter(entries.iter().map(|entry| {
+        // the flags are separated by a slash, i.e. `word/S`
+        entry.split('/').next().unwrap_or_default().trim()
+    }));
+    let added = Vec::from_iter(words.into_iter().filter(|word| !known.contains(word)));
+    let count = added.len();
+    entries.extend(added);
+
+    let mut dic = format!("{}\n", entries.len());
+    for entry in entries {
+        dic.push_str(entry);
+        dic.push('\n');
+    }
+    (dic, count)
+}
+
+/// Check all `documents` and collect the words the dictionary based checkers
+/// do not know.
+pub fn collect_unknown_words(documents: Documentation, config: Config) -> Result<UnknownWords> {
+    let (_total, flagged) = check_flagged_words(documents, config)?;
+    Ok(UnknownWords::from_flagged(
+        flagged.iter().map(String::as_str),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frequency() {
+        let words =
+            UnknownWords::from_flagged(["serde", "tokio", "rustc", "serde", "tokio", "serde"]);
+        assert_eq!(words.to_string(), "serde: 3\ntokio: 2\nrustc: 1\n");
+    }
+
+    #[test]
+    fn merge() {
+        assert_eq!(
+            merge_into_dic("", ["serde", "tokio"]),
+            ("2\nserde\ntokio\n".to_owned(), 2)
+        );
+        assert_eq!(
+            merge_into_dic("2\nserde/S\nrustc\n\n", ["serde", "tokio"]),
+            ("3\nserde/S\nrustc\ntokio\n".to_owned(), 1)
+        );
+    }
+}
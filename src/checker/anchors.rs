@@ -0,0 +1,249 @@
+//! Duplicate headings and broken intra-document anchors of markdown files.
+//!
+//! Renderers derive an anchor from the text of each heading, like GitHub
+//! does, lowercase, without punctuation and with dashes instead of spaces.
+//! Duplicate headings get ambiguous anchors, which are silently disambiguated
+//! by a numeric suffix, and links to an anchor which does not exist lead
+//! nowhere.
+
+use super::{edit_distance, Checker};
+use crate::documentation::CommentVariant;
+use crate::errors::*;
+use crate::util::byte_range_to_char_range;
+use crate::{CheckableChunk, ContentOrigin, Detector, Suggestion};
+
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use std::collections::{HashMap, HashSet};
+
+/// Derive the anchor of a heading from its `text`, the way GitHub does.
+fn slugify(text: &str) -> String {
+    String::from_iter(text.trim().to_lowercase().chars().filter_map(|c| match c {
+        ' ' => Some('-'),
+        c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+        _ => None,
+    }))
+}
+
+/// Collect the `id` and `name` attributes of `html`, i.e. of
+/// `<a name="usage"></a>`, which are anchors as well.
+fn html_anchors(html: &str, acc: &mut HashSet<String>) {
+    for attribute in [" id=\"", " name=\""] {
+        let mut rest = html;
+        while let Some(idx) = rest.find(attribute) {
+            rest = &rest[(idx + attribute.len())..];
+            if let Some(end) = rest.find('"') {
+                acc.insert(rest[..end].to_owned());
+            }
+        }
+    }
+}
+
+/// A heading, with the byte range of its text.
+struct Heading {
+    id: Option<String>,
+    text: String,
+    bytes: std::ops::Range<usize>,
+}
+
+pub(crate) struct AnchorsChecker;
+
+impl AnchorsChecker {
+    fn check_chunk<'s>(
+        &self,
+        origin: &ContentOrigin,
+        chunk: &'s CheckableChunk,
+        acc: &mut Vec<Suggestion<'s>>,
+    ) {
+        let content = chunk.as_str();
+        let mut headings = Vec::new();
+        let mut links = Vec::new();
+        let mut anchors = HashSet::new();
+        let mut heading = None::<Heading>;
+        for (event, byte_range) in Parser::new_ext(content, Options::all()).into_offset_iter() {
+            match event {
+                Event::Start(Tag::Heading { id, .. }) => {
+                    heading = Some(Heading {
+                        id: id.map(|id| id.to_string()),
+                        text: String::new(),
+                        bytes: byte_range.end..byte_range.start,
+                    })
+                }
+                Event::End(TagEnd::Heading(_)) => headings.extend(heading.take()),
+                Event::Text(s) | Event::Code(s) => {
+                    if let Some(ref mut heading) = heading {
+                        heading.text.push_str(&s);
+                        heading.bytes.start = heading.bytes.start.min(byte_range.start);
+                        heading.bytes.end = heading.bytes.end.max(byte_range.end);
+                    }
+                }
+                Event::Start(Tag::Link { dest_url, .. }) => {
+                    if let Some(target) = dest_url.strip_prefix('#') {
+                        links.push((target.to_owned(), byte_range));
+                    }
+                }
+                Event::Html(s) | Event::InlineHtml(s) => html_anchors(&s, &mut anchors),
+                _ => {}
+            }
+        }
+
+        let mut push = |bytes, description: String, rule: &str, replacements: Vec<String>| {
+            let Some(range) = byte_range_to_char_range(content, bytes) else {
+                return;
+            };
+            for (range, span) in chunk.find_spans(range) {
+                acc.push(Suggestion {
+                    detector: Detector::Anchors,
+                    agreeing: Vec::new(),
+                    rule: Some(rule.to_owned()),
+                    category: None,
+                    origin: origin.clone(),
+                    chunk,
+                    span,
+                    range,
+                    replacements: replacements.clone(),
+                    description: Some(description.clone()),
+                });
+            }
+        };
+
+        // the number of headings with the same anchor so far
+        let mut seen = HashMap::<String, usize>::new();
+        for heading in headings {
+            if let Some(id) = heading.id {
+                anchors.insert(id);
+                continue;
+            }
+            let slug = slugify(&heading.text);
+            let count = seen.entry(slug.clone()).or_default();
+            if *count == 0 {
+                anchors.insert(slug);
+            } else {
+                anchors.insert(format!("{slug}-{count}"));
+                push(
+                    heading.bytes,
+                    format!("Duplicate heading, the anchor `#{slug}` is ambiguous."),
+                    "duplicate-heading",
+                    Vec::new(),
+                );
+            }
+            *count += 1;
+        }
+
+        for (target, bytes) in links {
+            // the top of the document
+            if target.is_empty()
+                || anchors.contains(&target)
+                || anchors.contains(&target.to_lowercase())
+            {
+                continue;
+            }
+            let mut candidates = Vec::from_iter(anchors.iter().filter_map(|anchor| {
+                let distance = edit_distance(anchor, &target.to_lowercase());
+                (distance <= (anchor.chars().count() / 3).max(2)).then_some((distance, anchor))
+            }));
+            candidates.sort();
+            let replacements = Vec::from_iter(
+                candidates
+                    .into_iter()
+                    .map(|(_distance, anchor)| format!("#{anchor}")),
+            );
+            // only the destination, if it is given inline
+            let destination = format!("#{target}");
+            let bytes = match content[bytes.clone()].rfind(&destination) {
+                Some(idx) => (bytes.start + idx)..(bytes.start + idx + destination.len()),
+                None => bytes,
+            };
+            push(
+                bytes,
+                format!("Link to the anchor `{destination}`, which does not exist."),
+                "broken-anchor",
+                replacements,
+            );
+        }
+    }
+}
+
+impl Checker for AnchorsChecker {
+    type Config = ();
+
+    fn detector() -> Detector {
+        Detector::Anchors
+    }
+
+    fn check<'a, 's>(
+        &self,
+        origin: &ContentOrigin,
+        chunks: &'a [CheckableChunk],
+    ) -> Result<Vec<Suggestion<'s>>>
+    where
+        'a: 's,
+    {
+        let mut acc = Vec::new();
+        for chunk in chunks {
+            // each doc comment is rendered on its own, as part of the item docs
+            if chunk.variant() == CommentVariant::CommonMark {
+                self.check_chunk(origin, chunk, &mut acc);
+            }
+        }
+        Ok(acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::sub_chars;
+    use crate::Documentation;
+
+    fn findings(content: &str) -> Vec<(String, String, Vec<String>)> {
+        let mut docs = Documentation::new();
+        docs.add_commonmark(ContentOrigin::TestEntityCommonMark, content)
+            .unwrap();
+        let chunks = docs.get(&ContentOrigin::TestEntityCommonMark).unwrap();
+        let suggestions = AnchorsChecker
+            .check(&ContentOrigin::TestEntityCommonMark, chunks)
+            .unwrap();
+        Vec::from_iter(suggestions.into_iter().map(|suggestion| {
+            (
+                suggestion.rule.clone().unwrap(),
+                sub_chars(suggestion.chunk.as_str(), suggestion.range.clone()),
+                suggestion.replacements.clone(),
+            )
+        }))
+    }
+
+    #[test]
+    fn slugs() {
+        assert_eq!(slugify("Getting Started"), "getting-started");
+        assert_eq!(slugify("`cargo spellcheck` & CI!"), "cargo-spellcheck--ci");
+        assert_eq!(slugify("Über_alles"), "über_alles");
+    }
+
+    #[test]
+    fn anchors() {
+        const CONTENT: &str = r#"# Usage
+
+See [install](#instal), [usage](#usage), [custom](#custom-id),
+[html](#legacy) and [the top](#).
+
+## Install
+
+## Install
+
+## Other {#custom-id}
+
+<a name="legacy"></a>
+"#;
+        assert_eq!(
+            findings(CONTENT),
+            vec![
+                ("duplicate-heading".to_owned(), "Install".to_owned(), vec![]),
+                (
+                    "broken-anchor".to_owned(),
+                    "#instal".to_owned(),
+                    vec!["#install".to_owned(), "#install-1".to_owned()]
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Accept the mistakes which existed when the baseline was recorded.
+//!
+//! Adopting the tool on a large code base is otherwise blocked on fixing all
+//! findings at once. The baseline stores the [`Suggestion::fingerprint`] of
+//! each finding, which does not include the line number, so unrelated edits
+//! of a file do not invalidate it, keyed by the [`Suggestion::id`], so the
+//! accepted findings of a rule can be reviewed or removed as a whole.
+
+use crate::errors::*;
+use crate::Suggestion;
+
+use fs_err as fs;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Format version of the baseline file.
+const BASELINE_VERSION: u32 = 1;
+
+/// The set of accepted findings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Baseline {
+    version: u32,
+    /// Fingerprints of the findings per finding identifier.
+    fingerprints: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Self {
+            version: BASELINE_VERSION,
+            fingerprints: BTreeMap::new(),
+        }
+    }
+}
+
+impl Baseline {
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .wrap_err_with(|| eyre!("Failed to read baseline {}", path.display()))?;
+        let baseline: Self = serde_json::from_str(&content)
+            .wrap_err_with(|| eyre!("Failed to parse baseline {}", path.display()))?;
+        if baseline.version != BASELINE_VERSION {
+            bail!(
+                "Baseline {} has version {}, expected {}, record it again",
+                path.display(),
+                baseline.version,
+                BASELINE_VERSION
+            );
+        }
+        Ok(baseline)
+    }
+
+    pub(crate) fn write_to(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content + "\n")
+            .wrap_err_with(|| eyre!("Failed to write baseline {}", path.display()))?;
+        Ok(())
+    }
+
+    pub(crate) fn merge(mut self, other: Self) -> Self {
+        for (id, fingerprints) in other.fingerprints {
+            self.fingerprints
+                .entry(id)
+                .or_default()
+                .extend(fingerprints);
+        }
+        self
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.fingerprints.values().map(BTreeSet::len).sum()
+    }
+
+    /// Check if the finding was present when the baseline was recorded.
+    pub(crate) fn contains(&self, suggestion: &Suggestion<'_>) -> bool {
+        self.fingerprints
+            .get(&suggestion.id())
+            .map_or(false, |fingerprints| {
+                fingerprints.contains(&suggestion.fingerprint())
+            })
+    }
+}
+
+impl<'a, 's: 'a> FromIterator<&'a Suggestion<'s>> for Baseline {
+    fn from_iter<I: IntoIterator<Item = &'a Suggestion<'s>>>(iter: I) -> Self {
+        let mut baseline = Self::default();
+        for suggestion in iter {
+            baseline
+                .fingerprints
+                .entry(suggestion.id())
+                .or_default()
+                .insert(suggestion.fingerprint());
+        }
+        baseline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documentation::{CheckableChunk, CommentVariant};
+    use crate::tests::TempDir;
+    use crate::{ContentOrigin, Detector, LineColumn, Span};
+
+    #[test]
+    fn roundtrip() {
+        let chunk = CheckableChunk::from_str(
+            "An existng mistake.",
+            indexmap::indexmap! { 0..19 => Span {
+                start: LineColumn { line: 3, column: 4 },
+                end: LineColumn { line: 3, column: 22 },
+            }},
+            CommentVariant::TripleSlash,
+        );
+        let suggestion = |line| Suggestion {
+            detector: Detector::Hunspell,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
+            origin: ContentOrigin::TestEntityRust,
+            chunk: &chunk,
+            span: Span {
+                start: LineColumn { line, column: 7 },
+                end: LineColumn { line, column: 13 },
+            },
+            range: 3..10,
+            replacements: vec!["existing".to_owned()],
+            description: None,
+        };
+        let baseline = Baseline::from_iter([&suggestion(3)]);
+        assert_eq!(baseline.len(), 1);
+        // moved lines are still covered
+        assert!(baseline.contains(&suggestion(7)));
+        // the same word flagged by another rule is not
+        assert!(!baseline.contains(&Suggestion {
+            detector: Detector::NlpRules,
+            rule: Some("MORFOLOGIK_RULE_EN_US".to_owned()),
+            ..suggestion(3)
+        }));
+
+        let dir = TempDir::new();
+        let path = dir.join("baseline.json");
+        baseline.write_to(&path).unwrap();
+        let loaded = Baseline::load(&path).unwrap();
+        assert!(loaded.contains(&suggestion(3)));
+    }
+}
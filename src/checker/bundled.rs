@@ -0,0 +1,98 @@
+//! The `en_US` dictionary and affix file bundled with the binary.
+//!
+//! Only available with the `bundled-dictionary` feature, which is the last
+//! resort for environments without system dictionaries, i.e. musl based
+//! containers.
+
+use crate::errors::*;
+
+#[cfg(feature = "bundled-dictionary")]
+use fs_err as fs;
+#[cfg(feature = "bundled-dictionary")]
+use std::io::Write;
+use std::path::PathBuf;
+
+#[cfg(feature = "bundled-dictionary")]
+static BUILTIN_HUNSPELL_AFF: &[u8] = include_bytes!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/hunspell-data/en_US.aff"
+));
+
+#[cfg(feature = "bundled-dictionary")]
+static BUILTIN_HUNSPELL_DIC: &[u8] = include_bytes!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/hunspell-data/en_US.dic"
+));
+
+// XXX hunspell does not provide an API for using in-memory dictionary or
+// XXX affix files
+// XXX https://github.com/hunspell/hunspell/issues/721
+#[cfg(feature = "bundled-dictionary")]
+fn cache_builtin_inner(
+    cache_dir: impl AsRef<std::path::Path>,
+    extension: &'static str,
+    data: &[u8],
+) -> Result<PathBuf> {
+    let path = cache_dir.as_ref().join(format!(
+        "cargo-spellcheck/{}/{}.{}",
+        env!("CARGO_PKG_VERSION"),
+        "en_US",
+        extension
+    ));
+    fs::create_dir_all(path.parent().unwrap())?;
+    // check if file exists
+    if let Ok(f) = fs::File::open(&path) {
+        // in case somebody else is currently writing to it
+        // wait for that to complete
+        let flock = fd_lock::RwLock::new(f);
+        let _ = flock.read()?;
+        return Ok(path);
+    }
+    let f = fs::OpenOptions::new()
+        .truncate(true)
+        .create(true)
+        .write(true)
+        .open(&path)?;
+    let mut flock = fd_lock::RwLock::new(f);
+    // if there are multiple instances, allow the first to write it all
+    if let Ok(mut f) = flock.try_write() {
+        f.write_all(data)?;
+        return Ok(path);
+    }
+
+    // .. but block execution until the first completed so
+    // there are no cases of partial data
+    let _ = flock.read()?;
+
+    Ok(path)
+}
+
+/// Write the bundled dictionary and affix file to the cache dir, since
+/// `libhunspell` only accepts paths.
+///
+/// Returns the paths of the dictionary and affix file.
+#[cfg(feature = "bundled-dictionary")]
+pub(super) fn cache_builtin() -> Result<(PathBuf, PathBuf)> {
+    log::info!("Using builtin en_US hunspell dictionary and affix files");
+    let base = directories::BaseDirs::new();
+    let cache_dir = super::cached::writable_cache_dir(base.as_ref().map(|base| base.cache_dir()));
+    let path_aff = cache_builtin_inner(&cache_dir, "aff", BUILTIN_HUNSPELL_AFF)?;
+    let path_dic = cache_builtin_inner(&cache_dir, "dic", BUILTIN_HUNSPELL_DIC)?;
+    Ok((path_dic, path_aff))
+}
+
+#[cfg(not(feature = "bundled-dictionary"))]
+pub(super) fn cache_builtin() -> Result<(PathBuf, PathBuf)> {
+    bail!("Built without the `bundled-dictionary` feature, run `cargo spellcheck fetch-dictionaries` to obtain a dictionary")
+}
+
+/// The bundled affix file.
+#[cfg(feature = "bundled-dictionary")]
+pub(super) fn builtin_aff() -> Result<&'static [u8]> {
+    Ok(BUILTIN_HUNSPELL_AFF)
+}
+
+#[cfg(not(feature = "bundled-dictionary"))]
+pub(super) fn builtin_aff() -> Result<&'static [u8]> {
+    bail!("Built without the `bundled-dictionary` feature, an affix file must be given")
+}
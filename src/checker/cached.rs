@@ -5,9 +5,34 @@ use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
 use sha2::Digest;
 use std::io::Seek;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
 use std::time::{Duration, Instant};
 
+/// Resolve a writable cache directory, preferring `preferred`.
+///
+/// Read-only containers commonly lack a `HOME` or a writable cache directory,
+/// in which case a directory in the temporary dir is used instead. Warns only
+/// once about it.
+pub(crate) fn writable_cache_dir(preferred: Option<&Path>) -> PathBuf {
+    static WARN_ONCE: Once = Once::new();
+
+    if let Some(preferred) = preferred {
+        match fs_err::create_dir_all(preferred) {
+            Ok(()) => return preferred.to_owned(),
+            Err(e) => log::debug!("Cache directory is not writable: {e}"),
+        }
+    }
+    let fallback = std::env::temp_dir().join("cargo-spellcheck");
+    WARN_ONCE.call_once(|| {
+        log::warn!(
+            "No writable cache directory available, using {} instead",
+            fallback.display()
+        );
+    });
+    fallback
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 struct CacheEntry<T> {
     what: String,
@@ -1,4 +1,5 @@
-use super::hunspell::cache_builtin;
+use super::bundled::cache_builtin;
+use super::dictlint::{validate_dic, AffixInfo};
 use super::Result;
 use crate::config::{Lang5, SearchDirs};
 use color_eyre::eyre::{bail, eyre, WrapErr};
@@ -90,23 +91,26 @@ impl DicAff {
         // be more strict about the extra dictionaries, they have to exist
         log::info!(target: "dicaff", "Adding {} extra dictionaries", extra_dictionaries.len());
 
+        let affix = AffixInfo::parse(aff.as_bytes());
         for extra_dic_path in extra_dictionaries {
             log::debug!(target: "affdic", "Adding extra dictionary {}", extra_dic_path.display());
             // after calling `sanitize_paths`
             // the ought to be all absolutes
             assert!(extra_dic_path.is_absolute());
-            let extra_dic = fs::read_to_string(extra_dic_path)?;
-            is_valid_hunspell_dic(&mut extra_dic.as_bytes())?;
+            let extra_dic = fs::read(extra_dic_path)?;
+            validate_dic(extra_dic_path, &extra_dic, &affix)?;
+            let extra_dic = String::from_utf8(extra_dic).wrap_err_with(|| {
+                eyre!(
+                    "Extra dictionary {} must be UTF-8 encoded",
+                    extra_dic_path.display()
+                )
+            })?;
             log::trace!(target: "affdic", "Adding extra dict to main dict: {}", extra_dic.trim().lines().count() - 1);
-            dic_acc.push('\n');
-            // trim the initil number
-            dic_acc.push_str(
-                extra_dic
-                    .trim()
-                    .split_once("\n")
-                    .expect("It's a valid dictionary. qed")
-                    .1,
-            );
+            // trim the initial number, a dictionary might only consist of the header
+            if let Some((_count, entries)) = extra_dic.trim().split_once('\n') {
+                dic_acc.push('\n');
+                dic_acc.push_str(entries);
+            }
         }
 
         // sort them, just in case
@@ -0,0 +1,505 @@
+//! Validate hunspell dictionaries against the affix file they are used with.
+//!
+//! Hunspell itself silently drops entries it can not make sense of, or fails
+//! deep inside the FFI layer, so catch the common mistakes early with a
+//! precise line number.
+
+use super::Result;
+use color_eyre::eyre::{bail, WrapErr};
+use fs_err as fs;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+/// Flag encoding as declared by `FLAG` in the affix file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FlagType {
+    /// A single ASCII character per flag, the default.
+    Short,
+    /// Two ASCII characters per flag, `FLAG long`.
+    Long,
+    /// Comma separated decimal numbers, `FLAG num`.
+    Numeric,
+    /// A single unicode character per flag, `FLAG UTF-8`.
+    Utf8,
+}
+
+/// The subset of an affix file that is relevant for validating dictionaries.
+#[derive(Debug, Clone)]
+pub(crate) struct AffixInfo {
+    flag_type: FlagType,
+    encoding: Option<String>,
+    /// All flags declared by affix rules or flag directives.
+    flags: HashSet<String>,
+    /// Number of flag vector aliases declared with `AF`.
+    aliases: usize,
+    /// Compounding of arbitrary words is declared by `COMPOUNDFLAG` or
+    /// `COMPOUNDBEGIN`. `COMPOUNDRULE`s are commonly limited to numerals.
+    compounding: bool,
+    /// Minimal length of the words of a compound, as declared by `COMPOUNDMIN`.
+    compound_min: Option<usize>,
+}
+
+/// Directives which take a single flag as argument.
+const FLAG_DIRECTIVES: &[&str] = &[
+    "NOSUGGEST",
+    "KEEPCASE",
+    "FORBIDDENWORD",
+    "NEEDAFFIX",
+    "PSEUDOROOT",
+    "CIRCUMFIX",
+    "ONLYINCOMPOUND",
+    "SUBSTANDARD",
+    "LEMMA_PRESENT",
+    "WARN",
+    "FORCEUCASE",
+    "COMPOUNDFLAG",
+    "COMPOUNDBEGIN",
+    "COMPOUNDMIDDLE",
+    "COMPOUNDLAST",
+    "COMPOUNDEND",
+    "COMPOUNDPERMITFLAG",
+    "COMPOUNDFORBIDFLAG",
+    "COMPOUNDROOT",
+    "NOSPLITSUGS",
+];
+
+impl AffixInfo {
+    /// Extract flag declarations and settings from the content of an affix
+    /// file.
+    pub(crate) fn parse(aff: &[u8]) -> Self {
+        let aff = String::from_utf8_lossy(aff);
+        let mut flag_type = FlagType::Short;
+        let mut encoding = None;
+        let mut flags = HashSet::new();
+        let mut aliases = 0;
+        let mut compound_rules = Vec::new();
+        let mut compounding = false;
+        let mut compound_min = None;
+        for line in aff.lines() {
+            let mut fields = line.split_whitespace();
+            let (Some(directive), Some(arg)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            match directive {
+                "FLAG" => {
+                    flag_type = match arg.to_uppercase().as_str() {
+                        "LONG" => FlagType::Long,
+                        "NUM" => FlagType::Numeric,
+                        "UTF-8" | "UTF8" => FlagType::Utf8,
+                        _ => FlagType::Short,
+                    }
+                }
+                "SET" => encoding = Some(arg.to_owned()),
+                // the first `AF` line carries the count, each following one a flag vector
+                "AF" if arg.parse::<usize>().is_err() => aliases += 1,
+                "PFX" | "SFX" => {
+                    flags.insert(arg.to_owned());
+                }
+                "COMPOUNDRULE" if arg.parse::<usize>().is_err() => {
+                    compound_rules.push(arg.to_owned())
+                }
+                "COMPOUNDMIN" => compound_min = arg.parse::<usize>().ok(),
+                directive if FLAG_DIRECTIVES.contains(&directive) => {
+                    compounding |= matches!(directive, "COMPOUNDFLAG" | "COMPOUNDBEGIN");
+                    flags.insert(arg.to_owned());
+                }
+                _ => {}
+            }
+        }
+        // compound rules reference flags interleaved with the `*?()` operators
+        for rule in compound_rules {
+            if flag_type == FlagType::Short || flag_type == FlagType::Utf8 {
+                flags.extend(
+                    rule.chars()
+                        .filter(|c| !"*?()".contains(*c))
+                        .map(String::from),
+                );
+            } else {
+                flags.extend(
+                    rule.split(['(', ')', '*', '?'])
+                        .filter(|flag| !flag.is_empty())
+                        .map(ToOwned::to_owned),
+                );
+            }
+        }
+        Self {
+            flag_type,
+            encoding,
+            flags,
+            aliases,
+            compounding,
+            compound_min,
+        }
+    }
+
+    /// The minimal length of the words of a compound, if the affix file
+    /// declares compounding of words. Shorter words than hunspell's default of
+    /// three characters would accept too many typos.
+    pub(crate) fn compound_min(&self) -> Option<usize> {
+        self.compounding
+            .then(|| self.compound_min.unwrap_or(3).max(3))
+    }
+
+    /// The dictionary content must be UTF-8, unless the affix file declares a
+    /// different encoding.
+    fn expects_utf8(&self) -> bool {
+        self.encoding
+            .as_ref()
+            .map(|encoding| {
+                let encoding = encoding.to_uppercase();
+                encoding == "UTF-8" || encoding == "UTF8"
+            })
+            .unwrap_or(true)
+    }
+
+    /// Split a flag string according to the declared flag type.
+    fn split_flags(&self, flags: &str) -> std::result::Result<Vec<String>, String> {
+        match self.flag_type {
+            _ if self.aliases > 0 => Ok(vec![flags.to_owned()]),
+            FlagType::Short => flags
+                .chars()
+                .map(|c| {
+                    if c.is_ascii() {
+                        Ok(c.to_string())
+                    } else {
+                        Err(format!(
+                            "invalid flag >{c}<, the affix file declares single ASCII character flags"
+                        ))
+                    }
+                })
+                .collect(),
+            FlagType::Long => {
+                let chars = Vec::from_iter(flags.chars());
+                if chars.len() % 2 != 0 || !flags.is_ascii() {
+                    return Err(format!(
+                        "invalid flags >{flags}<, the affix file declares two character flags (`FLAG long`)"
+                    ));
+                }
+                Ok(Vec::from_iter(
+                    chars.chunks(2).map(String::from_iter),
+                ))
+            }
+            FlagType::Numeric => flags
+                .split(',')
+                .map(|flag| match flag.parse::<u16>() {
+                    Ok(n) if n > 0 && n <= 65000 => Ok(n.to_string()),
+                    _ => Err(format!(
+                        "invalid flag >{flag}<, the affix file declares numeric flags (`FLAG num`)"
+                    )),
+                })
+                .collect(),
+            FlagType::Utf8 => Ok(Vec::from_iter(flags.chars().map(String::from))),
+        }
+    }
+}
+
+/// How severe a dictionary issue is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Severity {
+    /// The entry is ignored by hunspell, but the dictionary is usable.
+    Warning,
+    /// The dictionary is broken.
+    Error,
+}
+
+/// A problem with a particular line of a dictionary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DicIssue {
+    /// One based line number.
+    pub(crate) lineno: usize,
+    pub(crate) severity: Severity,
+    pub(crate) message: String,
+}
+
+impl fmt::Display for DicIssue {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(
+            formatter,
+            "line {}: {severity}: {}",
+            self.lineno, self.message
+        )
+    }
+}
+
+/// Validate the content of a `.dic` file.
+pub(crate) fn lint_dic(dic: &[u8], affix: &AffixInfo) -> Vec<DicIssue> {
+    let mut issues = Vec::new();
+    let mut push = |lineno: usize, severity: Severity, message: String| {
+        issues.push(DicIssue {
+            lineno,
+            severity,
+            message,
+        })
+    };
+
+    let mut lines = dic.split(|b| *b == b'\n').enumerate().map(|(idx, line)| {
+        // strip windows line endings
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        (idx + 1, line)
+    });
+
+    if let Some((lineno, header)) = lines.next() {
+        let header = String::from_utf8_lossy(header);
+        if header.trim().parse::<u64>().is_err() {
+            push(
+                lineno,
+                Severity::Error,
+                format!("first line must be the number of entries, but is >{header}<"),
+            );
+        }
+    }
+
+    for (lineno, line) in lines {
+        let line = if affix.expects_utf8() {
+            match std::str::from_utf8(line) {
+                Ok(line) => line.to_owned(),
+                Err(e) => {
+                    push(
+                        lineno,
+                        Severity::Error,
+                        format!(
+                            "invalid UTF-8 at byte {}, but the affix file expects UTF-8",
+                            e.valid_up_to()
+                        ),
+                    );
+                    continue;
+                }
+            }
+        } else {
+            String::from_utf8_lossy(line).into_owned()
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with("SET ") {
+            push(
+                lineno,
+                Severity::Error,
+                "encoding declarations belong into the affix file".to_owned(),
+            );
+            continue;
+        }
+        if line.parse::<i64>().is_ok() {
+            push(
+                lineno,
+                Severity::Error,
+                format!("entry must not be a number, but is >{line}<"),
+            );
+            continue;
+        }
+
+        // morphological fields follow after whitespace
+        let entry = line.split(['\t', ' ']).next().unwrap_or_default();
+        let Some((word, flags)) = split_entry(entry) else {
+            continue;
+        };
+        if word.is_empty() {
+            push(
+                lineno,
+                Severity::Error,
+                format!("missing word in front of the flags >{flags}<"),
+            );
+            continue;
+        }
+        match affix.split_flags(flags) {
+            Err(message) => push(lineno, Severity::Error, message),
+            Ok(flags) if affix.aliases > 0 => {
+                for flag in flags {
+                    match flag.parse::<usize>() {
+                        Ok(n) if n > 0 && n <= affix.aliases => {}
+                        _ => push(
+                            lineno,
+                            Severity::Error,
+                            format!(
+                                "invalid flag alias >{flag}<, the affix file declares {} aliases with `AF`",
+                                affix.aliases
+                            ),
+                        ),
+                    }
+                }
+            }
+            Ok(flags) => {
+                for flag in flags {
+                    if !affix.flags.contains(&flag) {
+                        push(
+                            lineno,
+                            Severity::Warning,
+                            format!("unknown flag >{flag}<, it is not declared in the affix file"),
+                        );
+                    }
+                }
+            }
+        }
+    }
+    issues
+}
+
+/// Split a dictionary entry into the word and the flags, honoring escaped
+/// slashes `\/` within the word.
+fn split_entry(entry: &str) -> Option<(&str, &str)> {
+    let mut escaped = false;
+    for (idx, c) in entry.char_indices() {
+        match c {
+            '\\' => escaped = !escaped,
+            '/' if !escaped => return Some((&entry[..idx], &entry[idx + 1..])),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+/// Validate the content of an extra dictionary, errors on the first sign of a
+/// broken dictionary, and logs entries that will be ignored.
+pub(crate) fn validate_dic(path: &Path, dic: &[u8], affix: &AffixInfo) -> Result<()> {
+    let mut errors = Vec::new();
+    for issue in lint_dic(dic, affix) {
+        match issue.severity {
+            Severity::Warning => log::warn!(target: "affdic", "{}: {issue}", path.display()),
+            Severity::Error => errors.push(issue.to_string()),
+        }
+    }
+    if !errors.is_empty() {
+        bail!(
+            "Extra dictionary {} is invalid:\n{}",
+            path.display(),
+            errors.join("\n")
+        );
+    }
+    Ok(())
+}
+
+/// Read and validate an extra dictionary file, see [`validate_dic`].
+pub(crate) fn validate_dic_path(path: impl AsRef<Path>, affix: &AffixInfo) -> Result<()> {
+    let path = path.as_ref();
+    let dic = fs::read(path)?;
+    validate_dic(path, &dic, affix)
+}
+
+/// Lint a set of dictionaries and print all findings, used by the `dict lint`
+/// sub command.
+///
+/// Returns the number of dictionaries with at least one error.
+pub(crate) fn lint_dictionaries(
+    aff: Option<&Path>,
+    dictionaries: &[std::path::PathBuf],
+) -> Result<usize> {
+    let aff = match aff {
+        Some(aff) => fs::read(aff).wrap_err("Failed to read affix file")?,
+        None => super::bundled::builtin_aff()?.to_vec(),
+    };
+    let affix = AffixInfo::parse(&aff);
+
+    let mut broken = 0;
+    for path in dictionaries {
+        let dic = fs::read(path)?;
+        let issues = lint_dic(&dic, &affix);
+        if issues.is_empty() {
+            log::info!("✅ {}", path.display());
+        }
+        if issues.iter().any(|issue| issue.severity == Severity::Error) {
+            broken += 1;
+        }
+        for issue in issues {
+            println!("{}: {issue}", path.display());
+        }
+    }
+    Ok(broken)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "SET UTF-8
+NOSUGGEST !
+COMPOUNDRULE 1
+COMPOUNDRULE n*1t
+
+PFX A Y 1
+PFX A   0     re         .
+
+SFX S Y 1
+SFX S   0     s          .
+";
+
+    fn lint(dic: &str) -> Vec<DicIssue> {
+        lint_dic(dic.as_bytes(), &AffixInfo::parse(AFF.as_bytes()))
+    }
+
+    #[test]
+    fn affix_info() {
+        let affix = AffixInfo::parse(AFF.as_bytes());
+        assert_eq!(affix.flag_type, FlagType::Short);
+        assert!(affix.expects_utf8());
+        for flag in ["!", "n", "1", "t", "A", "S"] {
+            assert!(affix.flags.contains(flag), "Missing {flag}");
+        }
+    }
+
+    #[test]
+    fn compounding() {
+        // compound rules only
+        assert_eq!(AffixInfo::parse(AFF.as_bytes()).compound_min(), None);
+        assert_eq!(
+            AffixInfo::parse(b"COMPOUNDFLAG X\n").compound_min(),
+            Some(3)
+        );
+        assert_eq!(
+            AffixInfo::parse(b"COMPOUNDBEGIN X\nCOMPOUNDMIN 4\n").compound_min(),
+            Some(4)
+        );
+        assert_eq!(AffixInfo::parse(b"SET UTF-8\n").compound_min(), None);
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(lint("3\nfoo/AS\nbar\nand\\/or/S\tpo:noun\n"), vec![]);
+    }
+
+    #[test]
+    fn header() {
+        assert_matches::assert_matches!(
+            &lint("foo\nbar\n")[..],
+            [DicIssue {
+                lineno: 1,
+                severity: Severity::Error,
+                ..
+            }]
+        );
+    }
+
+    #[test]
+    fn flags() {
+        let issues = lint("2\nfoo/AX\nbar/Aä\n");
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].lineno, 2);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert_eq!(issues[1].lineno, 3);
+        assert_eq!(issues[1].severity, Severity::Error);
+    }
+
+    #[test]
+    fn long_flags() {
+        let affix = AffixInfo::parse(b"FLAG long\nSFX Aa Y 1\nSFX Aa 0 s .\n");
+        assert_eq!(lint_dic(b"1\nfoo/Aa\n", &affix), vec![]);
+        assert_eq!(lint_dic(b"1\nfoo/Aab\n", &affix).len(), 1);
+    }
+
+    #[test]
+    fn encoding() {
+        let issues = lint_dic(
+            b"2\nSET UTF-8\nfo\xffo\n",
+            &AffixInfo::parse(AFF.as_bytes()),
+        );
+        assert_eq!(
+            Vec::from_iter(issues.iter().map(|issue| issue.lineno)),
+            vec![2, 3]
+        );
+    }
+}
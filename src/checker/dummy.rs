@@ -54,6 +54,9 @@ impl Checker for DummyChecker {
                 let replacements = vec![format!("replacement_{index}")];
                 let suggestion = Suggestion {
                     detector,
+                    agreeing: Vec::new(),
+                    rule: None,
+                    category: None,
                     span,
                     range,
                     origin: origin.clone(),
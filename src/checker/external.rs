@@ -0,0 +1,214 @@
+//! External checkers, speaking JSON over `stdin` and `stdout`.
+//!
+//! For every checked file the configured command is spawned and receives a
+//! request with the text of all chunks:
+//!
+//! ```json
+//! { "origin": "src/lib.rs", "chunks": ["Some doc comment.", "Another one."] }
+//! ```
+//!
+//! and must respond with the found suggestions before exiting successfully:
+//!
+//! ```json
+//! { "suggestions": [
+//!     { "chunk": 0, "start": 5, "end": 8, "replacements": ["dock"], "description": "...", "rule": "..." }
+//! ] }
+//! ```
+//!
+//! `start` and `end` are character offsets into the chunk, `end` being
+//! exclusive. The optional `rule` becomes part of the finding identifier
+//! `external/<name>/<rule>`.
+
+use super::Checker;
+use crate::config::ExternalCheckerConfig;
+use crate::errors::*;
+use crate::{CheckableChunk, ContentOrigin, Detector, Suggestion};
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Serialize)]
+struct Request<'a> {
+    origin: String,
+    chunks: Vec<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Response {
+    #[serde(default)]
+    suggestions: Vec<ExternalSuggestion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExternalSuggestion {
+    chunk: usize,
+    start: usize,
+    end: usize,
+    #[serde(default)]
+    replacements: Vec<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    rule: Option<String>,
+}
+
+pub(crate) struct ExternalChecker {
+    config: ExternalCheckerConfig,
+}
+
+impl ExternalChecker {
+    pub fn new(config: &ExternalCheckerConfig) -> Result<Self> {
+        Ok(Self {
+            config: config.clone(),
+        })
+    }
+
+    /// Spawn the external command and exchange request and response.
+    fn exchange(&self, request: &Request<'_>) -> Result<Response> {
+        let name = self.config.name.as_str();
+        let request = serde_json::to_vec(request)?;
+        let mut child = Command::new(&self.config.command)
+            .args(&self.config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .wrap_err_with(|| eyre!("Failed to spawn external checker {name}"))?;
+
+        // write from a separate thread, the child might start responding
+        // before consuming all of its input
+        let mut stdin = child
+            .stdin
+            .take()
+            .expect("Stdin is piped, so it is present. qed");
+        let writer = std::thread::spawn(move || stdin.write_all(&request));
+
+        let output = child
+            .wait_with_output()
+            .wrap_err_with(|| eyre!("Failed to wait for external checker {name}"))?;
+        if let Ok(Err(e)) = writer.join() {
+            log::debug!("External checker {name} did not consume all input: {e}");
+        }
+        if !output.status.success() {
+            bail!(
+                "External checker {name} failed with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        serde_json::from_slice(&output.stdout)
+            .wrap_err_with(|| eyre!("External checker {name} responded with invalid JSON"))
+    }
+}
+
+impl Checker for ExternalChecker {
+    type Config = ExternalCheckerConfig;
+
+    fn detector() -> Detector {
+        Detector::External
+    }
+
+    fn check<'a, 's>(
+        &self,
+        origin: &ContentOrigin,
+        chunks: &'a [CheckableChunk],
+    ) -> Result<Vec<Suggestion<'s>>>
+    where
+        'a: 's,
+    {
+        let request = Request {
+            origin: origin.as_path().display().to_string(),
+            chunks: Vec::from_iter(chunks.iter().map(CheckableChunk::as_str)),
+        };
+        let response = self.exchange(&request)?;
+
+        let mut acc = Vec::with_capacity(response.suggestions.len());
+        for external in response.suggestions {
+            let chunk = match chunks.get(external.chunk) {
+                Some(chunk)
+                    if external.start < external.end && external.end <= chunk.len_in_chars() =>
+                {
+                    chunk
+                }
+                _ => {
+                    log::warn!(
+                        "External checker {} returned an out of bounds suggestion {external:?}",
+                        self.config.name
+                    );
+                    continue;
+                }
+            };
+            let description = external
+                .description
+                .map(|description| format!("{}: {description}", self.config.name));
+            // namespaced by the name of the external checker
+            let rule = match external.rule {
+                Some(rule) => format!("{}/{rule}", self.config.name),
+                None => self.config.name.clone(),
+            };
+            for (range, span) in chunk.find_spans(external.start..external.end) {
+                acc.push(Suggestion {
+                    detector: Detector::External,
+                    agreeing: Vec::new(),
+                    rule: Some(rule.clone()),
+                    category: None,
+                    origin: origin.clone(),
+                    chunk,
+                    span,
+                    range,
+                    replacements: external.replacements.clone(),
+                    description: description.clone(),
+                });
+            }
+        }
+        Ok(acc)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::Documentation;
+
+    #[test]
+    fn roundtrip() {
+        let mut docs = Documentation::new();
+        docs.add_rust(
+            ContentOrigin::TestEntityRust,
+            "/// Some dok comment.\nstruct X;",
+            true,
+            false,
+        )
+        .unwrap();
+        let (origin, chunks) = docs.iter().next().unwrap();
+
+        let checker = ExternalChecker::new(&ExternalCheckerConfig {
+            name: "fake".to_owned(),
+            command: "sh".to_owned(),
+            args: vec![
+                "-c".to_owned(),
+                r#"grep -q '"chunks":\[" Some dok comment."\]' && echo '{"suggestions": [{"chunk": 0, "start": 6, "end": 9, "replacements": ["doc"], "description": "Typo"}, {"chunk": 1, "start": 0, "end": 1}]}'"#.to_owned(),
+            ],
+        })
+        .unwrap();
+        let suggestions = checker.check(origin, chunks).unwrap();
+        assert_eq!(suggestions.len(), 1);
+        let suggestion = &suggestions[0];
+        assert_eq!(suggestion.detector, Detector::External);
+        assert_eq!(suggestion.replacements, vec!["doc".to_owned()]);
+        assert_eq!(suggestion.description.as_deref(), Some("fake: Typo"));
+        assert_eq!(suggestion.id(), "external/fake");
+        assert_eq!(
+            suggestion.span.start,
+            crate::LineColumn { line: 1, column: 9 }
+        );
+        assert_eq!(
+            suggestion.span.end,
+            crate::LineColumn {
+                line: 1,
+                column: 11
+            }
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Capitalization of headings.
+//!
+//! Style guides commonly mandate either title case or sentence case for
+//! headings. Only words which are entirely lowercase or capitalized are
+//! adjusted, acronyms, identifiers and words containing digits are kept as
+//! they are, and so is inline code.
+
+use super::Checker;
+use crate::config::{HeadingStyle, HeadingsConfig};
+use crate::errors::*;
+use crate::util::{char_offsets, push_words, sub_chars};
+use crate::{CheckableChunk, ContentOrigin, Detector, Range, Suggestion};
+
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+/// Words which stay lowercase in title case, unless they are the first or
+/// last word.
+const MINOR_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "but", "by", "for", "from", "if", "in", "into", "nor", "of",
+    "on", "or", "per", "so", "than", "the", "to", "up", "via", "vs", "with", "yet",
+];
+
+pub(crate) struct HeadingsChecker {
+    config: HeadingsConfig,
+}
+
+impl HeadingsChecker {
+    pub fn new(config: &HeadingsConfig) -> Result<Self> {
+        Ok(Self {
+            config: config.clone(),
+        })
+    }
+
+    /// The expected form of the word at `idx` of a heading with `n` words.
+    fn recase(&self, word: &str, idx: usize, n: usize) -> Option<String> {
+        if let Some(allowed) = self
+            .config
+            .allow
+            .iter()
+            .find(|allowed| allowed.eq_ignore_ascii_case(word))
+        {
+            return Some(allowed.to_owned());
+        }
+        let mut chars = word.chars();
+        let first = chars.next()?;
+        let rest = chars.as_str();
+        let is_plain = word.chars().all(char::is_alphabetic)
+            && rest.chars().all(char::is_lowercase)
+            && (first.is_lowercase() || first.is_uppercase());
+        if !is_plain {
+            return None;
+        }
+        let capitalized = || String::from_iter(first.to_uppercase().chain(rest.chars()));
+        let lowercase = || word.to_lowercase();
+        Some(match self.config.style {
+            _ if idx == 0 => capitalized(),
+            HeadingStyle::Title if idx + 1 < n && MINOR_WORDS.contains(&lowercase().as_str()) => {
+                lowercase()
+            }
+            HeadingStyle::Title => capitalized(),
+            HeadingStyle::Sentence => lowercase(),
+        })
+    }
+
+    fn check_heading<'s>(
+        &self,
+        origin: &ContentOrigin,
+        chunk: &'s CheckableChunk,
+        words: &[Range],
+        acc: &mut Vec<Suggestion<'s>>,
+    ) {
+        let content = chunk.as_str();
+        let changes = Vec::from_iter(words.iter().enumerate().filter_map(|(idx, range)| {
+            let word = sub_chars(content, range.clone());
+            self.recase(&word, idx, words.len())
+                .filter(|expected| *expected != word)
+                .map(|expected| (range.clone(), expected))
+        }));
+        let (first, last) = match (changes.first(), changes.last()) {
+            (Some(first), Some(last)) => (first.0.start, last.0.end),
+            _ => return,
+        };
+        // keep everything in between, i.e. inline code
+        let mut replacement = String::new();
+        let mut cursor = first;
+        for (range, expected) in changes.iter() {
+            replacement.push_str(&sub_chars(content, cursor..range.start));
+            replacement.push_str(expected);
+            cursor = range.end;
+        }
+        let style = match self.config.style {
+            HeadingStyle::Title => "title case",
+            HeadingStyle::Sentence => "sentence case",
+        };
+        for (range, span) in chunk.find_spans(first..last) {
+            acc.push(Suggestion {
+                detector: Detector::Headings,
+                agreeing: Vec::new(),
+                rule: None,
+                category: None,
+                origin: origin.clone(),
+                chunk,
+                span,
+                range,
+                replacements: vec![replacement.clone()],
+                description: Some(format!("Headings are written in {style}.")),
+            });
+        }
+    }
+}
+
+impl Checker for HeadingsChecker {
+    type Config = HeadingsConfig;
+
+    fn detector() -> Detector {
+        Detector::Headings
+    }
+
+    fn check<'a, 's>(
+        &self,
+        origin: &ContentOrigin,
+        chunks: &'a [CheckableChunk],
+    ) -> Result<Vec<Suggestion<'s>>>
+    where
+        'a: 's,
+    {
+        let mut acc = Vec::new();
+        for chunk in chunks {
+            let content = chunk.as_str();
+            let char_offsets = char_offsets(content);
+            let mut heading: Option<Vec<Range>> = None;
+            for (event, byte_range) in Parser::new(content).into_offset_iter() {
+                match event {
+                    Event::Start(Tag::Heading { .. }) => heading = Some(Vec::new()),
+                    Event::End(TagEnd::Heading(_)) => {
+                        if let Some(words) = heading.take() {
+                            self.check_heading(origin, chunk, &words, &mut acc);
+                        }
+                    }
+                    Event::Text(_) => {
+                        if let Some(ref mut words) = heading {
+                            push_words(content, byte_range, &char_offsets, words);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Documentation;
+
+    fn replacements(style: HeadingStyle, content: &str) -> Vec<(String, String)> {
+        let mut docs = Documentation::new();
+        docs.add_commonmark(ContentOrigin::TestEntityCommonMark, content)
+            .unwrap();
+        let chunks = docs.get(&ContentOrigin::TestEntityCommonMark).unwrap();
+        let checker = HeadingsChecker::new(&HeadingsConfig {
+            style,
+            allow: vec!["Rust".to_owned()],
+        })
+        .unwrap();
+        let suggestions = checker
+            .check(&ContentOrigin::TestEntityCommonMark, chunks)
+            .unwrap();
+        Vec::from_iter(suggestions.into_iter().map(|suggestion| {
+            (
+                sub_chars(suggestion.chunk.as_str(), suggestion.range.clone()),
+                suggestion.replacements[0].clone(),
+            )
+        }))
+    }
+
+    #[test]
+    fn title_case() {
+        assert_eq!(
+            replacements(
+                HeadingStyle::Title,
+                "# check for spelling Mistakes in `code_like` rust\n\nthe prose is kept as is"
+            ),
+            vec![(
+                "check for spelling Mistakes in `code_like` rust".to_owned(),
+                "Check for Spelling Mistakes in `code_like` Rust".to_owned()
+            )]
+        );
+        assert!(replacements(HeadingStyle::Title, "## Use the API of GitHub for It").is_empty());
+    }
+
+    #[test]
+    fn sentence_case() {
+        assert_eq!(
+            replacements(HeadingStyle::Sentence, "## Use The API With rust"),
+            vec![(
+                "The API With rust".to_owned(),
+                "the API with Rust".to_owned()
+            )]
+        );
+        assert!(replacements(HeadingStyle::Sentence, "Not A Heading").is_empty());
+    }
+}
@@ -4,22 +4,22 @@
 //! the individual tokens against the dictionary using the defined affixes. Can
 //! handle multiple dictionaries.
 
-use super::{apply_tokenizer, Checker, Detector, Suggestion};
+use super::bundled::cache_builtin;
+use super::{apply_tokenizer_with, sentence_starts, Checker, Detector, NonProseFilter, Suggestion};
 
 use crate::checker::dictaffix::is_valid_hunspell_dic_path;
-use crate::config::{Lang5, WrappedRegex};
+use crate::checker::dictlint::{validate_dic_path, AffixInfo};
+use crate::config::{CodeLike, Lang5, TokenizerConfig, WrappedRegex};
 use crate::documentation::{CheckableChunk, ContentOrigin, PlainOverlay};
 use crate::util::sub_chars;
 use crate::Range;
 
 use fs_err as fs;
-use io::Write;
 use lazy_static::lazy_static;
 
 use nlprule::Tokenizer;
-use std::io::{self};
 
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use hunspell_rs::{CheckResult, Hunspell};
@@ -29,71 +29,11 @@ use doc_chunks::Ignores;
 use crate::errors::*;
 
 use super::quirks::{
-    replacements_contain_dashed, replacements_contain_dashless, transform, Transformed,
+    is_capitalized_mid_sentence, is_compound_of_words, is_concatenation_of_words,
+    rank_replacements, replacements_contain_dashed, replacements_contain_dashless, transform,
+    CodeLikeFilter, Transformed,
 };
 
-pub(super) static BUILTIN_HUNSPELL_AFF: &[u8] = include_bytes!(concat!(
-    env!("CARGO_MANIFEST_DIR"),
-    "/hunspell-data/en_US.aff"
-));
-
-pub(super) static BUILTIN_HUNSPELL_DIC: &[u8] = include_bytes!(concat!(
-    env!("CARGO_MANIFEST_DIR"),
-    "/hunspell-data/en_US.dic"
-));
-
-// XXX hunspell does not provide an API for using in-memory dictionary or
-// XXX affix files
-// XXX https://github.com/hunspell/hunspell/issues/721
-pub(super) fn cache_builtin_inner(
-    cache_dir: impl AsRef<Path>,
-    extension: &'static str,
-    data: &[u8],
-) -> Result<PathBuf> {
-    let path = cache_dir.as_ref().join(format!(
-        "cargo-spellcheck/{}/{}.{}",
-        env!("CARGO_PKG_VERSION"),
-        "en_US",
-        extension
-    ));
-    fs::create_dir_all(path.parent().unwrap())?;
-    // check if file exists
-    if let Ok(f) = fs::File::open(&path) {
-        // in case somebody else is currently writing to it
-        // wait for that to complete
-        let flock = fd_lock::RwLock::new(f);
-        let _ = flock.read()?;
-        return Ok(path);
-    }
-    let f = fs::OpenOptions::new()
-        .truncate(true)
-        .create(true)
-        .write(true)
-        .open(&path)?;
-    let mut flock = fd_lock::RwLock::new(f);
-    // if there are multiple instances, allow the first to write it all
-    if let Ok(mut f) = flock.try_write() {
-        f.write_all(data)?;
-        return Ok(path);
-    }
-
-    // .. but block execution until the first completed so
-    // there are no cases of partial data
-    let _ = flock.read()?;
-
-    Ok(path)
-}
-
-pub(super) fn cache_builtin() -> Result<(PathBuf, PathBuf)> {
-    log::info!("Using builtin en_US hunspell dictionary and affix files");
-    let base = directories::BaseDirs::new().expect("env HOME must be set");
-
-    let cache_dir = base.cache_dir();
-    let path_aff = cache_builtin_inner(cache_dir, "aff", BUILTIN_HUNSPELL_AFF)?;
-    let path_dic = cache_builtin_inner(cache_dir, "dic", BUILTIN_HUNSPELL_DIC)?;
-    Ok((path_dic, path_aff))
-}
-
 /// The value is `true` if string is made of emoji's or Unicode
 /// `VULGAR FRACTION`.
 pub fn consists_of_vulgar_fractions_or_emojis(word: &str) -> bool {
@@ -137,6 +77,14 @@ pub struct HunspellCheckerInner {
     allow_dashed: bool,
     allow_emojis: bool,
     check_footnote_references: bool,
+    code_like: CodeLike,
+    split_compounds: bool,
+    ignore_capitalized: bool,
+    /// Minimal word length of compounds, if the dictionary declares
+    /// compounding.
+    compound_min: Option<usize>,
+    tokenizer: TokenizerConfig,
+    max_replacements: Option<usize>,
     ignorelist: String,
 }
 
@@ -149,6 +97,9 @@ impl HunspellCheckerInner {
             allow_dashed,
             allow_emojis,
             check_footnote_references,
+            code_like,
+            split_compounds,
+            ignore_capitalized,
         ) = {
             let quirks = &config.quirks;
             (
@@ -157,6 +108,9 @@ impl HunspellCheckerInner {
                 quirks.allow_dashed(),
                 quirks.allow_emojis(),
                 quirks.check_footnote_references(),
+                quirks.code_like().clone(),
+                quirks.split_compounds(),
+                quirks.ignore_capitalized(),
             )
         };
         // FIXME rename the config option
@@ -242,12 +196,13 @@ impl HunspellCheckerInner {
 
         // suggestion must contain the word itself if it is valid extra dictionary
         // be more strict about the extra dictionaries, they have to exist
+        let affix = AffixInfo::parse(&fs::read(aff)?);
         for extra_dic in config.extra_dictionaries() {
             log::debug!("Adding extra dictionary {}", extra_dic.display());
             if !extra_dic.is_file() {
                 bail!("Extra dictionary {} is not a file", extra_dic.display())
             }
-            is_valid_hunspell_dic_path(extra_dic)?;
+            validate_dic_path(extra_dic, &affix)?;
             if let Some(extra_dic) = extra_dic.to_str() {
                 if !hunspell.add_dictionary(extra_dic) {
                     bail!("Failed to add extra dictionary path to context {extra_dic}")
@@ -267,6 +222,12 @@ impl HunspellCheckerInner {
             allow_dashed,
             allow_emojis,
             check_footnote_references,
+            code_like,
+            split_compounds,
+            ignore_capitalized,
+            compound_min: affix.compound_min(),
+            tokenizer: config.tokenizer.clone(),
+            max_replacements: config.max_replacements,
             ignorelist,
         })
     }
@@ -284,7 +245,7 @@ impl std::ops::Deref for HunspellChecker {
 
 impl HunspellChecker {
     pub fn new(config: &<HunspellChecker as Checker>::Config) -> Result<Self> {
-        let tokenizer = super::tokenizer::<&PathBuf>(None)?;
+        let tokenizer = super::tokenizer(config.tokenizer.override_tokenizer.as_ref())?;
         let inner = HunspellCheckerInner::new(config)?;
         let hunspell = Arc::new(inner);
         Ok(HunspellChecker(hunspell, tokenizer))
@@ -307,6 +268,7 @@ impl Checker for HunspellChecker {
         'a: 's,
     {
         let mut acc = Vec::with_capacity(chunks.len());
+        let code_like = CodeLikeFilter::new(&self.0.code_like, origin);
 
         for chunk in chunks {
             let plain = chunk.erase_cmark(&Ignores {
@@ -314,9 +276,16 @@ impl Checker for HunspellChecker {
             });
             log::trace!("{plain:?}");
             let txt = plain.as_str();
+            let chars = Vec::from_iter(txt.chars());
+            let non_prose = NonProseFilter::new(txt, &self.tokenizer);
+            let sentence_starts = if self.ignore_capitalized {
+                sentence_starts(&self.1, txt)
+            } else {
+                Vec::new()
+            };
             let hunspell = &*self.hunspell.0;
 
-            'tokenization: for range in apply_tokenizer(&self.1, txt) {
+            'tokenization: for range in apply_tokenizer_with(&self.1, txt, &self.tokenizer) {
                 let word = sub_chars(txt, range.clone());
                 if range.len() == 1
                     && word
@@ -327,6 +296,33 @@ impl Checker for HunspellChecker {
                 {
                     continue 'tokenization;
                 }
+                if non_prose.classify(&range).is_some() {
+                    continue 'tokenization;
+                }
+                if code_like.is_code_like(&chars, range.clone()) {
+                    continue 'tokenization;
+                }
+                if self.ignore_capitalized
+                    && is_capitalized_mid_sentence(&word, range.start, &sentence_starts)
+                {
+                    continue 'tokenization;
+                }
+                if self.split_compounds
+                    && is_compound_of_words(&word, |part| {
+                        matches!(hunspell.check(part), CheckResult::FoundInDictionary)
+                    })
+                {
+                    continue 'tokenization;
+                }
+                if let Some(min) = self.compound_min {
+                    if !matches!(hunspell.check(&word), CheckResult::FoundInDictionary)
+                        && is_concatenation_of_words(&word, min, |part| {
+                            matches!(hunspell.check(part), CheckResult::FoundInDictionary)
+                        })
+                    {
+                        continue 'tokenization;
+                    }
+                }
                 if self.transform_regex.is_empty() {
                     obtain_suggestions(
                         &plain,
@@ -338,6 +334,7 @@ impl Checker for HunspellChecker {
                         self.allow_concatenated,
                         self.allow_dashed,
                         self.allow_emojis,
+                        self.max_replacements,
                         &mut acc,
                     )
                 } else {
@@ -354,6 +351,7 @@ impl Checker for HunspellChecker {
                                     self.allow_concatenated,
                                     self.allow_dashed,
                                     self.allow_emojis,
+                                    self.max_replacements,
                                     &mut acc,
                                 );
                             }
@@ -369,6 +367,7 @@ impl Checker for HunspellChecker {
                                 self.allow_concatenated,
                                 self.allow_dashed,
                                 self.allow_emojis,
+                                self.max_replacements,
                                 &mut acc,
                             );
                         }
@@ -391,6 +390,7 @@ fn obtain_suggestions<'s>(
     allow_concatenated: bool,
     allow_dashed: bool,
     allow_emojis: bool,
+    max_replacements: Option<usize>,
     acc: &mut Vec<Suggestion<'s>>,
 ) {
     log::trace!("Checking {word} in {range:?}..");
@@ -419,9 +419,13 @@ fn obtain_suggestions<'s>(
                 log::trace!(target: "quirks", "Found dashed word in replacement suggestions, treating {word} as ok");
                 return;
             }
+            let replacements = rank_replacements(&word, replacements, max_replacements);
             for (range, span) in plain.find_spans(range.clone()) {
                 acc.push(Suggestion {
                     detector: Detector::Hunspell,
+                    agreeing: Vec::new(),
+                    rule: None,
+                    category: None,
                     range,
                     span,
                     origin: origin.clone(),
@@ -440,7 +444,7 @@ fn obtain_suggestions<'s>(
 #[cfg(test)]
 mod tests {
     use crate::checker::dictaffix::is_valid_hunspell_dic;
-    use std::io::BufRead;
+    use std::io::{self, BufRead};
 
     use super::*;
 
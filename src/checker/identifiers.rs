@@ -0,0 +1,242 @@
+//! Cross check of code references in documentation.
+//!
+//! Backticked tokens are skipped by the spelling checkers, so a typo in a
+//! reference to a function or type, or a reference to something that was
+//! renamed, goes unnoticed. This checker compares such references against the
+//! identifiers of all checked rust sources and flags those that do not exist,
+//! but are within a small edit distance of one that does.
+
+use super::{edit_distance, Checker};
+use crate::config::IdentifiersConfig;
+use crate::errors::*;
+use crate::{CheckableChunk, ContentOrigin, Detector, Range, Suggestion};
+
+use fs_err as fs;
+use proc_macro2::{TokenStream, TokenTree};
+use pulldown_cmark::{Event, Parser};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Minimal length of a code reference to be considered, shorter ones are too
+/// likely to be in the edit distance of some unrelated identifier.
+const MIN_LEN: usize = 4;
+
+pub(crate) struct IdentifiersChecker {
+    config: IdentifiersConfig,
+    known: Arc<HashSet<String>>,
+}
+
+impl IdentifiersChecker {
+    pub fn new(config: &IdentifiersConfig) -> Result<Self> {
+        Ok(Self {
+            config: config.clone(),
+            known: Arc::default(),
+        })
+    }
+
+    /// Provide the identifiers of all checked rust source files.
+    pub fn set_identifiers(&mut self, known: Arc<HashSet<String>>) {
+        self.known = known;
+    }
+
+    /// Find the closest identifier, if it is unambiguous and within the
+    /// allowed distance.
+    fn closest(&self, reference: &str) -> Option<&str> {
+        closest(&self.known, reference, self.config.max_distance)
+    }
+}
+
+/// Find the identifier of `known` closest to `reference`, if it is unambiguous
+/// and within `max_distance`.
+pub(super) fn closest<'k>(
+    known: &'k HashSet<String>,
+    reference: &str,
+    max_distance: usize,
+) -> Option<&'k str> {
+    let reference_len = reference.chars().count();
+    if reference_len < MIN_LEN || known.contains(reference) {
+        return None;
+    }
+    // a single edit in a short reference is already a lot
+    let max_distance = if reference_len < 8 {
+        max_distance.min(1)
+    } else {
+        max_distance
+    };
+    let mut best: Option<(usize, &str)> = None;
+    let mut ambiguous = false;
+    for ident in known.iter() {
+        if ident.chars().count().abs_diff(reference_len) > max_distance {
+            continue;
+        }
+        let distance = edit_distance(reference, ident);
+        if distance == 0 || distance > max_distance {
+            continue;
+        }
+        match best {
+            Some((best_distance, _)) if distance > best_distance => {}
+            Some((best_distance, _)) if distance == best_distance => ambiguous = true,
+            _ => {
+                best = Some((distance, ident.as_str()));
+                ambiguous = false;
+            }
+        }
+    }
+    best.filter(|_| !ambiguous).map(|(_, ident)| ident)
+}
+
+impl Checker for IdentifiersChecker {
+    type Config = IdentifiersConfig;
+
+    fn detector() -> Detector {
+        Detector::Identifiers
+    }
+
+    fn check<'a, 's>(
+        &self,
+        origin: &ContentOrigin,
+        chunks: &'a [CheckableChunk],
+    ) -> Result<Vec<Suggestion<'s>>>
+    where
+        'a: 's,
+    {
+        let mut acc = Vec::new();
+        if self.known.is_empty() {
+            return Ok(acc);
+        }
+        for chunk in chunks {
+            for range in code_references(chunk.as_str()) {
+                let reference = crate::util::sub_chars(chunk.as_str(), range.clone());
+                let ident = match self.closest(&reference) {
+                    Some(ident) => ident,
+                    None => continue,
+                };
+                for (range, span) in chunk.find_spans(range) {
+                    acc.push(Suggestion {
+                        detector: Detector::Identifiers,
+                        agreeing: Vec::new(),
+                        rule: None,
+                        category: None,
+                        origin: origin.clone(),
+                        chunk,
+                        span,
+                        range,
+                        replacements: vec![ident.to_owned()],
+                        description: Some(format!(
+                            "No identifier `{reference}` found, did you mean `{ident}`?"
+                        )),
+                    });
+                }
+            }
+        }
+        Ok(acc)
+    }
+}
+
+/// Read and tokenize the rust source file at `path`.
+pub(super) fn read_token_stream(path: &Path) -> Option<TokenStream> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            log::debug!("Failed to read {} for identifiers: {e}", path.display());
+            return None;
+        }
+    };
+    match syn::parse_str::<TokenStream>(&content) {
+        Ok(stream) => Some(stream),
+        Err(e) => {
+            log::debug!("Failed to tokenize {} for identifiers: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Collect the identifiers of the rust source file at `path`.
+pub(super) fn file_identifiers(path: &Path, acc: &mut HashSet<String>) {
+    if let Some(stream) = read_token_stream(path) {
+        collect_idents(stream, acc);
+    }
+}
+
+/// Collect the identifiers of a tokenized rust source file.
+pub(super) fn collect_idents(stream: TokenStream, acc: &mut HashSet<String>) {
+    for tree in stream {
+        match tree {
+            TokenTree::Ident(ident) => {
+                let ident = ident.to_string();
+                let ident = ident.strip_prefix("r#").unwrap_or(&ident);
+                acc.insert(ident.to_owned());
+            }
+            TokenTree::Group(group) => collect_idents(group.stream(), acc),
+            _ => {}
+        }
+    }
+}
+
+/// Character ranges of inline code in `content`, that look like a plain
+/// identifier, optionally followed by `()`.
+fn code_references(content: &str) -> Vec<Range> {
+    let mut acc = Vec::new();
+    for (event, byte_range) in Parser::new(content).into_offset_iter() {
+        let code = match event {
+            Event::Code(code) => code,
+            _ => continue,
+        };
+        let ident = code.strip_suffix("()").unwrap_or(&code);
+        let mut chars = ident.chars();
+        let is_ident = chars
+            .next()
+            .map_or(false, |c| c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || c == '_');
+        if !is_ident {
+            continue;
+        }
+        // the code span without the backticks and padding
+        let raw = &content[byte_range.clone()];
+        let offset = match raw.find(ident) {
+            Some(offset) => byte_range.start + offset,
+            None => continue,
+        };
+        let start = content[..offset].chars().count();
+        acc.push(start..(start + ident.chars().count()));
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn references() {
+        let content = "Calls `recieve_msg()` with ` Foo ` but not `a + b` or `x`.";
+        let refs = Vec::from_iter(
+            code_references(content)
+                .into_iter()
+                .map(|range| crate::util::sub_chars(content, range)),
+        );
+        assert_eq!(refs, vec!["recieve_msg", "Foo", "x"]);
+    }
+
+    #[test]
+    fn closest() {
+        let mut checker = IdentifiersChecker::new(&IdentifiersConfig::default()).unwrap();
+        let mut known = HashSet::new();
+        collect_idents(
+            syn::parse_str(
+                "fn receive_msg() {} struct Checker; struct Checkers; fn send(r#type: u8) {}",
+            )
+            .unwrap(),
+            &mut known,
+        );
+        checker.set_identifiers(Arc::new(known));
+        assert_eq!(checker.closest("recieve_msg"), Some("receive_msg"));
+        assert_eq!(checker.closest("receive_msg"), None);
+        // a transposition in a short reference is too much
+        assert_eq!(checker.closest("sned"), None);
+        // both `Checker` and `Checkers` exist
+        assert_eq!(checker.closest("Checkerz"), None);
+        assert_eq!(checker.closest("Chekcers"), Some("Checkers"));
+    }
+}
@@ -0,0 +1,324 @@
+//! Cross check of intra-doc link targets.
+//!
+//! Rustdoc resolves links like [`crate::Documentation`] or
+//! `[the docs](crate::Documentation)` to items, but a typo in the target only
+//! surfaces as a warning of a docs build, if at all. The path segments of such
+//! targets are compared against the identifiers of all checked rust sources,
+//! without any network access, and those which do not exist, but are close to
+//! one that does, are flagged.
+//!
+//! Paths of multiple segments are only cross checked if they start at the
+//! checked crate, with `crate::`, `self::`, `super::` or `Self::`, others may
+//! well be items of `std` or a dependency, i.e. [`std::fmt::Display`], which
+//! are not part of the checked sources. Single segments are, unless they name
+//! an item of the prelude or a primitive type, i.e. [`Option`].
+
+use super::identifiers::closest;
+use super::Checker;
+use crate::config::IdentifiersConfig;
+use crate::errors::*;
+use crate::util::byte_range_to_char_range;
+use crate::{CheckableChunk, CommentVariantCategory, ContentOrigin, Detector, Suggestion};
+
+use pulldown_cmark::{BrokenLink, CowStr, Event, Options, Parser, Tag};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Path segments which refer to a relative position, not to an item.
+const RELATIVE: &[&str] = &["crate", "self", "super", "Self"];
+
+/// Items in scope of every crate without being part of the checked sources,
+/// the prelude, primitive types and the common macros of `std`.
+const PRELUDE: &[&str] = &[
+    "AsMut",
+    "AsRef",
+    "Box",
+    "Clone",
+    "Copy",
+    "Default",
+    "DoubleEndedIterator",
+    "Drop",
+    "Eq",
+    "Err",
+    "ExactSizeIterator",
+    "Extend",
+    "Fn",
+    "FnMut",
+    "FnOnce",
+    "From",
+    "FromIterator",
+    "Into",
+    "IntoIterator",
+    "Iterator",
+    "None",
+    "Ok",
+    "Option",
+    "Ord",
+    "PartialEq",
+    "PartialOrd",
+    "Result",
+    "Send",
+    "Sized",
+    "Some",
+    "String",
+    "Sync",
+    "ToOwned",
+    "ToString",
+    "TryFrom",
+    "TryInto",
+    "Unpin",
+    "Vec",
+    "bool",
+    "char",
+    "f32",
+    "f64",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "isize",
+    "str",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "usize",
+    "assert",
+    "assert_eq",
+    "format",
+    "panic",
+    "print",
+    "println",
+    "eprintln",
+    "todo",
+    "unreachable",
+    "vec",
+    "write",
+    "writeln",
+];
+
+/// Extract the item path of an intra-doc link target, i.e. `Vec::new` of
+/// ``fn@`Vec::new()` ``, if `dest` is one.
+fn item_path(dest: &str) -> Option<&str> {
+    let dest = dest.trim().trim_matches('`');
+    // disambiguators, i.e. `struct@Foo`
+    let dest = match dest.split_once('@') {
+        Some((disambiguator, path)) if disambiguator.chars().all(char::is_alphabetic) => path,
+        Some(_) => return None,
+        None => dest,
+    };
+    let dest = dest
+        .strip_suffix("()")
+        .or_else(|| dest.strip_suffix('!'))
+        .unwrap_or(dest);
+    // generics, i.e. `Vec<T>`
+    let path = dest.split('<').next().unwrap_or(dest);
+    let is_path = path.split("::").all(|segment| {
+        let mut chars = segment.chars();
+        chars
+            .next()
+            .map_or(false, |c| c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || c == '_')
+    });
+    is_path.then_some(path)
+}
+
+/// Byte ranges of the item paths of all intra-doc links in `content`.
+fn link_targets(content: &str) -> Vec<std::ops::Range<usize>> {
+    // references without a definition, i.e. [`Foo`], are intra-doc links
+    let broken_link_handler = &mut |broken: BrokenLink<'_>| -> Option<(CowStr, CowStr)> {
+        Some((
+            CowStr::from(broken.reference.to_string()),
+            CowStr::Borrowed(""),
+        ))
+    };
+    let parser =
+        Parser::new_with_broken_link_callback(content, Options::all(), Some(broken_link_handler));
+    let mut acc = Vec::new();
+    for (event, byte_range) in parser.into_offset_iter() {
+        let Event::Start(Tag::Link { dest_url, .. }) = event else {
+            continue;
+        };
+        let Some(path) = item_path(&dest_url) else {
+            continue;
+        };
+        // the target is the last part of the link, unless it is given by a
+        // reference definition, i.e. `[link]: crate::Foo`
+        let offset = content[byte_range.clone()]
+            .rfind(path)
+            .map(|offset| byte_range.start + offset)
+            .or_else(|| {
+                content
+                    .match_indices(path)
+                    .map(|(offset, _)| offset)
+                    .find(|offset| content[..*offset].trim_end().ends_with("]:"))
+            });
+        if let Some(start) = offset {
+            let target = start..(start + path.len());
+            // references may be used by multiple links
+            if !acc.contains(&target) {
+                acc.push(target);
+            }
+        }
+    }
+    acc
+}
+
+pub(crate) struct IntraDocLinksChecker {
+    config: IdentifiersConfig,
+    known: Arc<HashSet<String>>,
+}
+
+impl IntraDocLinksChecker {
+    pub fn new(config: &IdentifiersConfig) -> Self {
+        Self {
+            config: config.clone(),
+            known: Arc::default(),
+        }
+    }
+
+    /// Provide the identifiers of all checked rust source files.
+    pub fn set_identifiers(&mut self, known: Arc<HashSet<String>>) {
+        self.known = known;
+    }
+}
+
+impl Checker for IntraDocLinksChecker {
+    type Config = IdentifiersConfig;
+
+    fn detector() -> Detector {
+        Detector::IntraDocLinks
+    }
+
+    fn check<'a, 's>(
+        &self,
+        origin: &ContentOrigin,
+        chunks: &'a [CheckableChunk],
+    ) -> Result<Vec<Suggestion<'s>>>
+    where
+        'a: 's,
+    {
+        let mut acc = Vec::new();
+        if self.known.is_empty() {
+            return Ok(acc);
+        }
+        for chunk in chunks {
+            // only doc comments are rendered by rustdoc
+            if chunk.variant().category() != CommentVariantCategory::Doc {
+                continue;
+            }
+            let content = chunk.as_str();
+            for target in link_targets(content) {
+                // of longer paths, only those within the checked crate are
+                // known, single segments are unless part of the prelude
+                let path = &content[target.clone()];
+                match path.split_once("::") {
+                    Some((root, _)) if !RELATIVE.contains(&root) => continue,
+                    None if PRELUDE.contains(&path) => continue,
+                    _ => {}
+                }
+                let mut start = target.start;
+                for segment in content[target].split("::") {
+                    let bytes = start..(start + segment.len());
+                    start = bytes.end + 2;
+                    if RELATIVE.contains(&segment) {
+                        continue;
+                    }
+                    let Some(ident) = closest(&self.known, segment, self.config.max_distance)
+                    else {
+                        continue;
+                    };
+                    let Some(range) = byte_range_to_char_range(content, bytes) else {
+                        continue;
+                    };
+                    for (range, span) in chunk.find_spans(range) {
+                        acc.push(Suggestion {
+                            detector: Detector::IntraDocLinks,
+                            agreeing: Vec::new(),
+                            rule: None,
+                            category: None,
+                            origin: origin.clone(),
+                            chunk,
+                            span,
+                            range,
+                            replacements: vec![ident.to_owned()],
+                            description: Some(format!(
+                                "Link target `{segment}` does not resolve, did you mean `{ident}`?"
+                            )),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::sub_chars;
+    use crate::Documentation;
+
+    #[test]
+    fn paths() {
+        assert_eq!(item_path("`Documentation`"), Some("Documentation"));
+        assert_eq!(
+            item_path("fn@crate::util::sub_chars()"),
+            Some("crate::util::sub_chars")
+        );
+        assert_eq!(item_path("Vec<T>"), Some("Vec"));
+        assert_eq!(item_path("`format!`"), Some("format"));
+        assert_eq!(item_path("https://docs.rs"), None);
+        assert_eq!(item_path("../README.md"), None);
+        assert_eq!(item_path("#usage"), None);
+        assert_eq!(item_path("some prose"), None);
+    }
+
+    #[test]
+    fn typos() {
+        const CONTENT: &str = r#"
+/// Holds all [`Documenation`], see [the chunks](crate::CheckableChunk) and
+/// [`self::Checkers::chek`][link], but [not code](https://docs.rs),
+/// [`Vec`], [`Option`] or [`std::fmt::Dispay`].
+///
+/// [link]: crate::checker::Chekers::check
+struct Documentation;
+"#;
+        let mut docs = Documentation::new();
+        docs.add_rust(ContentOrigin::TestEntityRust, CONTENT, true, false)
+            .unwrap();
+        let chunks = docs.get(&ContentOrigin::TestEntityRust).unwrap();
+        let mut checker = IntraDocLinksChecker::new(&IdentifiersConfig::default());
+        checker.set_identifiers(Arc::new(HashSet::from_iter(
+            [
+                "Documentation",
+                "CheckableChunk",
+                "checker",
+                "Checkers",
+                "check",
+                "Options",
+                "Display",
+            ]
+            .map(ToOwned::to_owned),
+        )));
+        let suggestions = checker
+            .check(&ContentOrigin::TestEntityRust, chunks)
+            .unwrap();
+        let flagged = Vec::from_iter(suggestions.iter().map(|suggestion| {
+            (
+                sub_chars(suggestion.chunk.as_str(), suggestion.range.clone()),
+                suggestion.replacements.clone(),
+            )
+        }));
+        assert_eq!(
+            flagged,
+            vec![
+                ("Documenation".to_owned(), vec!["Documentation".to_owned()]),
+                ("Chekers".to_owned(), vec!["Checkers".to_owned()]),
+            ]
+        );
+    }
+}
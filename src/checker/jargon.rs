@@ -0,0 +1,197 @@
+//! Terminology of the checked project, learned from word frequencies.
+//!
+//! Projects tend to use invented names consistently, which the spelling
+//! checkers flag at every single site. Words used at least as often as the
+//! configured threshold across all checked files are hence reported only once,
+//! at their first occurrence, as possible project terminology.
+
+use crate::config::JargonConfig;
+use crate::documentation::Ignores;
+use crate::util::sub_chars;
+use crate::{ContentOrigin, Detector, Documentation, Suggestion};
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Occurrences of a word across all checked files.
+struct Term {
+    count: usize,
+    /// The first file the word occurs in.
+    first: ContentOrigin,
+}
+
+pub(crate) struct ProjectJargon {
+    config: JargonConfig,
+    terms: HashMap<String, Term>,
+}
+
+impl ProjectJargon {
+    pub fn new(config: &JargonConfig) -> Self {
+        Self {
+            config: config.clone(),
+            terms: HashMap::new(),
+        }
+    }
+
+    /// Count the words of all chunks in `documents`.
+    pub fn collect(&mut self, documents: &Documentation) {
+        for (origin, chunks) in documents.iter() {
+            for chunk in chunks {
+                let plain = chunk.erase_cmark(&Ignores::default());
+                for word in words(plain.as_str()) {
+                    self.terms
+                        .entry(word.to_owned())
+                        .and_modify(|term| term.count += 1)
+                        .or_insert_with(|| Term {
+                            count: 1,
+                            first: origin.clone(),
+                        });
+                }
+            }
+        }
+        log::debug!(
+            "Found {} terms used at least {} times",
+            self.terms
+                .values()
+                .filter(|term| term.count >= self.config.threshold)
+                .count(),
+            self.config.threshold
+        );
+    }
+
+    /// The term, if `word` is used often enough to be project terminology.
+    fn recurring(&self, word: &str) -> Option<&Term> {
+        self.terms
+            .get(word)
+            .filter(|term| term.count >= self.config.threshold)
+    }
+
+    /// Reduce the spelling suggestions of project terminology in `origin` to a
+    /// single one, if `origin` is the first file the term occurs in, or none.
+    pub fn collapse<'s>(
+        &self,
+        origin: &ContentOrigin,
+        suggestions: HashSet<Suggestion<'s>>,
+    ) -> HashSet<Suggestion<'s>> {
+        let mut first = BTreeMap::<String, Suggestion<'s>>::new();
+        let mut acc = HashSet::with_capacity(suggestions.len());
+        for suggestion in suggestions {
+            if !matches!(
+                suggestion.detector,
+                Detector::Hunspell | Detector::ZSpell | Detector::Spellbook
+            ) {
+                acc.insert(suggestion);
+                continue;
+            }
+            let word = sub_chars(suggestion.chunk.as_str(), suggestion.range.clone());
+            let term = match self.recurring(&word) {
+                Some(term) => term,
+                None => {
+                    acc.insert(suggestion);
+                    continue;
+                }
+            };
+            if term.first != *origin {
+                log::trace!("Dropped >{word}< as project terminology reported before");
+                continue;
+            }
+            match first.get(&word) {
+                Some(earlier) if *earlier <= suggestion => {}
+                _ => {
+                    first.insert(word, suggestion);
+                }
+            }
+        }
+        acc.extend(first.into_iter().map(|(word, mut suggestion)| {
+            let count = self.terms.get(&word).map(|term| term.count).unwrap_or(1);
+            suggestion.description = Some(format!(
+                "Possible project terminology, used {count} times across the checked files."
+            ));
+            suggestion
+        }));
+        acc
+    }
+}
+
+/// Split `text` into words, consisting of alphanumeric characters and inner
+/// apostrophes.
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .map(|word| word.trim_matches('\''))
+        .filter(|word| !word.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CheckableChunk, CommentVariant, LineColumn, Span};
+    use std::path::PathBuf;
+
+    #[test]
+    fn collapse() {
+        let first = ContentOrigin::CommonMarkFile(PathBuf::from("/tmp/test/first.md"));
+        let second = ContentOrigin::CommonMarkFile(PathBuf::from("/tmp/test/second.md"));
+        let mut documents = Documentation::new();
+        documents
+            .add_commonmark(first.clone(), "The frobnicator and the frobnicator.\n")
+            .unwrap();
+        documents
+            .add_commonmark(second.clone(), "A frobnicator, a single wibble.\n")
+            .unwrap();
+        let mut jargon = ProjectJargon::new(&JargonConfig { threshold: 3 });
+        jargon.collect(&documents);
+        assert!(jargon.recurring("frobnicator").is_some());
+        assert!(jargon.recurring("wibble").is_none());
+
+        let chunk = CheckableChunk::from_str(
+            "frobnicator frobnicator wibble",
+            indexmap::indexmap! { 0..30 => Span {
+                start: LineColumn { line: 1, column: 0 },
+                end: LineColumn { line: 1, column: 29 },
+            }},
+            CommentVariant::CommonMark,
+        );
+        let suggestion = |origin: &ContentOrigin, range: crate::Range| Suggestion {
+            detector: Detector::Hunspell,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
+            origin: origin.clone(),
+            chunk: &chunk,
+            span: Span {
+                start: LineColumn {
+                    line: 1,
+                    column: range.start,
+                },
+                end: LineColumn {
+                    line: 1,
+                    column: range.end - 1,
+                },
+            },
+            range,
+            replacements: vec![],
+            description: None,
+        };
+
+        let collapsed = jargon.collapse(
+            &first,
+            HashSet::from_iter([
+                suggestion(&first, 0..11),
+                suggestion(&first, 12..23),
+                suggestion(&first, 24..30),
+            ]),
+        );
+        let mut collapsed = Vec::from_iter(collapsed);
+        collapsed.sort();
+        assert_eq!(collapsed.len(), 2);
+        assert_eq!(collapsed[0].range, 0..11);
+        assert!(collapsed[0]
+            .description
+            .as_deref()
+            .unwrap()
+            .contains("used 3 times"));
+        assert_eq!(collapsed[1].range, 24..30);
+
+        let collapsed = jargon.collapse(&second, HashSet::from_iter([suggestion(&second, 0..11)]));
+        assert!(collapsed.is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+//! Over-long lines of doc comments.
+//!
+//! Reports the paragraphs the `reflow` action would rewrite because one of
+//! their lines exceeds the maximum line length, with the reflown paragraph as
+//! the single replacement. Paragraphs which are only rejoined, since their
+//! lines are shorter than they could be, are left alone. This allows to
+//! enforce the line length in CI without writing any files.
+
+use super::Checker;
+use crate::config::ReflowConfig;
+use crate::errors::*;
+use crate::reflow::Reflow;
+use crate::{CheckableChunk, ContentOrigin, Detector, Suggestion};
+
+pub(crate) struct LineLengthChecker {
+    reflow: Reflow,
+    config: ReflowConfig,
+}
+
+impl LineLengthChecker {
+    pub fn new(config: &ReflowConfig) -> Result<Self> {
+        Ok(Self {
+            reflow: Reflow::new(config.clone())?,
+            config: config.clone(),
+        })
+    }
+}
+
+impl Checker for LineLengthChecker {
+    type Config = ReflowConfig;
+
+    fn detector() -> Detector {
+        Detector::LineLength
+    }
+
+    fn check<'a, 's>(
+        &'s self,
+        origin: &ContentOrigin,
+        chunks: &'a [CheckableChunk],
+    ) -> Result<Vec<Suggestion<'s>>>
+    where
+        'a: 's,
+    {
+        let suggestions = self.reflow.check(origin, chunks)?;
+        Ok(Vec::from_iter(suggestions.into_iter().filter_map(
+            |suggestion| {
+                // variants which are not reflown are skipped by the reflow already
+                let max = self
+                    .config
+                    .max_line_length_of(&suggestion.chunk.variant())?;
+                // the spans are absolute, so the columns include the comment
+                // prefix and indentation
+                let longest = suggestion
+                    .chunk
+                    .find_spans(suggestion.range.clone())
+                    .values()
+                    .map(|span| span.end.column + 1)
+                    .max()?;
+                if longest <= max {
+                    return None;
+                }
+                Some(Suggestion {
+                    detector: Detector::LineLength,
+                    description: Some(format!(
+                        "Line of {longest} characters exceeds the maximum of {max}"
+                    )),
+                    ..suggestion
+                })
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documentation::Documentation;
+
+    #[test]
+    fn long_lines_only() {
+        const CONTENT: &str = r#"/// A short line
+/// joined with the next one.
+struct A;
+
+/// A line which is way too long to fit into the configured maximum line length.
+struct B;
+"#;
+        let docs =
+            Documentation::load_from_str(ContentOrigin::TestEntityRust, CONTENT, true, false);
+        let chunks = docs.get(&ContentOrigin::TestEntityRust).unwrap();
+        let checker = LineLengthChecker::new(&ReflowConfig {
+            max_line_length: Some(60),
+            ..Default::default()
+        })
+        .unwrap();
+        let suggestions = checker
+            .check(&ContentOrigin::TestEntityRust, chunks)
+            .unwrap();
+        assert_eq!(suggestions.len(), 1);
+        let suggestion = &suggestions[0];
+        assert_eq!(suggestion.detector, Detector::LineLength);
+        assert_eq!(suggestion.span.start.line, 5);
+        assert_eq!(
+            suggestion.description.as_deref(),
+            Some("Line of 80 characters exceeds the maximum of 60")
+        );
+        assert_eq!(
+            suggestion.replacements,
+            vec![
+                "A line which is way too long to fit into the configured\n/// maximum line length."
+            ]
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//! Doc coverage of public items.
+//!
+//! Flags public items without any doc comment, like the `missing_docs` lint
+//! of rustc does, but alongside all other findings and without the need to
+//! build the crate. There is no doc comment to point at, so the checker keeps
+//! a chunk of its own for each undocumented item, which only contains the name
+//! of the item.
+
+use crate::documentation::{undocumented_public_items, CommentVariant};
+use crate::{CheckableChunk, ContentOrigin, Detector, Suggestion};
+
+use indexmap::IndexMap;
+use proc_macro2::TokenStream;
+use std::collections::HashMap;
+
+pub(crate) struct MissingDocsChecker {
+    /// The names of the undocumented items of each rust source file.
+    items: HashMap<ContentOrigin, Vec<CheckableChunk>>,
+}
+
+impl MissingDocsChecker {
+    pub fn new() -> Self {
+        Self {
+            items: HashMap::new(),
+        }
+    }
+
+    /// Collect the undocumented public items of a tokenized rust source file.
+    pub fn collect(&mut self, origin: ContentOrigin, stream: TokenStream) {
+        let chunks = Vec::from_iter(undocumented_public_items(stream).into_iter().map(
+            |(name, span)| {
+                let mut source_mapping = IndexMap::with_capacity(1);
+                source_mapping.insert(0..name.chars().count(), span);
+                CheckableChunk::from_string(name, source_mapping, CommentVariant::Unknown)
+            },
+        ));
+        if !chunks.is_empty() {
+            self.items.insert(origin, chunks);
+        }
+    }
+
+    pub fn check<'s>(&'s self, origin: &ContentOrigin) -> Vec<Suggestion<'s>> {
+        let mut acc = Vec::new();
+        let Some(chunks) = self.items.get(origin) else {
+            return acc;
+        };
+        for chunk in chunks {
+            let name = chunk.as_str();
+            for (range, span) in chunk.find_spans(0..chunk.len_in_chars()) {
+                acc.push(Suggestion {
+                    detector: Detector::MissingDocs,
+                    agreeing: Vec::new(),
+                    rule: None,
+                    category: None,
+                    range,
+                    span,
+                    origin: origin.clone(),
+                    replacements: Vec::new(),
+                    chunk,
+                    description: Some(format!("Public item `{name}` lacks documentation.")),
+                });
+            }
+        }
+        acc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_docs() {
+        const SOURCE: &str = "/// Documented.
+pub struct Documented;
+
+pub fn undocumented() {}
+";
+        let mut checker = MissingDocsChecker::new();
+        checker.collect(
+            ContentOrigin::TestEntityRust,
+            syn::parse_str(SOURCE).unwrap(),
+        );
+        let suggestions = checker.check(&ContentOrigin::TestEntityRust);
+        assert_eq!(suggestions.len(), 1);
+        let suggestion = &suggestions[0];
+        assert!(suggestion.replacements.is_empty());
+        assert_eq!(suggestion.span.start.line, 4);
+        assert_eq!(suggestion.span.start.column, 7);
+        assert_eq!(suggestion.span.end.column, 18);
+    }
+}
@@ -3,14 +3,21 @@
 //! Trait to handle additional trackers. Contains also helpers to avoid
 //! re-implementing generic algorithms again and again, i.e. tokenization.
 
-use crate::{CheckableChunk, Config, ContentOrigin, Detector, Suggestion};
+use crate::config::{OverridesConfig, SuppressConfig};
+use crate::util::sub_chars;
+use crate::{CheckableChunk, Config, ContentOrigin, Detector, Documentation, Suggestion};
 
 use crate::errors::*;
 
 mod cached;
 use self::cached::Cached;
 
+use indexmap::IndexMap;
+use indicatif::ProgressBar;
+use itertools::Itertools;
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 mod tokenize;
 
@@ -36,8 +43,46 @@ mod spellbook;
 #[cfg(feature = "nlprules")]
 mod nlprules;
 
+mod bundled;
+
 mod dictaffix;
 
+mod identifiers;
+use self::identifiers::{collect_idents, read_token_stream, IdentifiersChecker};
+
+mod redundancy;
+use self::redundancy::RedundancyChecker;
+
+mod headings;
+use self::headings::HeadingsChecker;
+
+mod symbols;
+use self::symbols::ProjectSymbols;
+
+mod jargon;
+use self::jargon::ProjectJargon;
+
+mod external;
+use self::external::ExternalChecker;
+
+mod missing_docs;
+use self::missing_docs::MissingDocsChecker;
+
+mod line_length;
+use self::line_length::LineLengthChecker;
+
+mod anchors;
+use self::anchors::AnchorsChecker;
+
+mod intra_doc_links;
+use self::intra_doc_links::IntraDocLinksChecker;
+
+mod dictlint;
+pub(crate) use self::dictlint::lint_dictionaries;
+
+mod baseline;
+pub(crate) use self::baseline::Baseline;
+
 #[cfg(any(feature = "spellbook", feature = "zet", feature = "hunspell"))]
 mod quirks;
 
@@ -48,7 +93,7 @@ pub trait Checker {
     fn detector() -> Detector;
 
     fn check<'a, 's>(
-        &self,
+        &'s self,
         origin: &ContentOrigin,
         chunks: &'a [CheckableChunk],
     ) -> Result<Vec<Suggestion<'s>>>
@@ -66,50 +111,231 @@ pub struct Checkers {
     #[cfg(feature = "spellbook")]
     spellbook: Option<SpellbookChecker>,
     nlprules: Option<NlpRulesChecker>,
+    identifiers: Option<IdentifiersChecker>,
+    intra_doc_links: Option<IntraDocLinksChecker>,
+    redundancy: Option<RedundancyChecker>,
+    headings: Option<HeadingsChecker>,
+    symbols: Option<ProjectSymbols>,
+    jargon: Option<ProjectJargon>,
+    external: Vec<ExternalChecker>,
+    missing_docs: Option<MissingDocsChecker>,
+    line_length: Option<LineLengthChecker>,
+    anchors: Option<AnchorsChecker>,
+    suppress: SuppressConfig,
+    overrides: OverridesConfig,
+    baseline: Option<Baseline>,
+    progress: Progress,
 }
 
-impl Checkers {
-    pub fn new(config: Config) -> Result<Self> {
-        macro_rules! create_checker {
-            ($feature:literal, $checker:ty, $config:expr, $checker_config:expr) => {
-                if !cfg!(feature = $feature) {
-                    log::debug!("Feature {} is disabled by compilation.", $feature);
-                    None
-                } else {
-                    let config = $config;
-                    let detector = <$checker>::detector();
-                    if config.is_enabled(detector) {
-                        log::debug!("Enabling {} checks.", detector);
-                        Some(<$checker>::new($checker_config.unwrap())?)
-                    } else {
-                        log::debug!("Checker {detector} is disabled by configuration.");
-                        None
-                    }
-                }
-            };
+/// The progress bar, with the files checked in parallel and the checker
+/// currently running on each as message.
+#[derive(Default)]
+struct Progress {
+    bar: Option<ProgressBar>,
+    running: Mutex<IndexMap<PathBuf, Detector>>,
+}
+
+impl Progress {
+    /// Track the checkers running on the file at `path` until the returned
+    /// guard is dropped.
+    fn checking(&self, path: PathBuf) -> Checking<'_> {
+        Checking {
+            progress: self,
+            path,
         }
+    }
 
-        let hunspell = create_checker!(
-            "hunspell",
-            HunspellChecker,
-            &config,
-            config.hunspell.as_ref()
-        );
-        #[cfg(feature = "zet")]
-        let zet = create_checker!("zet", ZetChecker, &config, config.zet.as_ref());
-        #[cfg(feature = "spellbook")]
-        let spellbook = create_checker!(
-            "spellbook",
-            SpellbookChecker,
-            &config,
-            config.spellbook.as_ref()
-        );
-        let nlprules = create_checker!(
-            "nlprules",
-            NlpRulesChecker,
-            &config,
-            config.nlprules.as_ref()
+    fn update(&self, path: &Path, detector: Option<Detector>) {
+        let Some(ref bar) = self.bar else {
+            return;
+        };
+        let mut running = self.running.lock().expect("Lock is not poisoned. qed");
+        match detector {
+            Some(detector) => {
+                running.insert(path.to_path_buf(), detector);
+            }
+            None => {
+                running.shift_remove(path);
+            }
+        }
+        bar.set_message(
+            running
+                .iter()
+                .map(|(path, detector)| format!("{} ({detector})", path.display()))
+                .join(", "),
         );
+    }
+}
+
+/// Guard of a file being checked, see [`Progress::checking`].
+struct Checking<'p> {
+    progress: &'p Progress,
+    path: PathBuf,
+}
+
+impl Checking<'_> {
+    fn running(&self, detector: Detector) {
+        self.progress.update(&self.path, Some(detector));
+    }
+}
+
+impl Drop for Checking<'_> {
+    fn drop(&mut self) {
+        self.progress.update(&self.path, None);
+    }
+}
+
+macro_rules! create_checker {
+    ($feature:literal, $checker:ty, $config:expr, $checker_config:expr) => {
+        if !cfg!(feature = $feature) {
+            log::debug!("Feature {} is disabled by compilation.", $feature);
+            None
+        } else {
+            let config = $config;
+            let detector = <$checker>::detector();
+            if config.is_enabled(detector) {
+                log::debug!("Enabling {} checks.", detector);
+                Some(<$checker>::new($checker_config.unwrap())?)
+            } else {
+                log::debug!("Checker {detector} is disabled by configuration.");
+                None
+            }
+        }
+    };
+}
+
+/// The checkers which load dictionaries or rules, creating them takes by far
+/// the longest.
+struct Loaded {
+    hunspell: Option<HunspellChecker>,
+    #[cfg(feature = "zet")]
+    zet: Option<ZetChecker>,
+    #[cfg(feature = "spellbook")]
+    spellbook: Option<SpellbookChecker>,
+    nlprules: Option<NlpRulesChecker>,
+}
+
+impl Loaded {
+    fn new(config: &Config) -> Result<Self> {
+        Ok(Self {
+            nlprules: create_checker!(
+                "nlprules",
+                NlpRulesChecker,
+                config,
+                config.nlprules.as_ref()
+            ),
+            ..Self::spelling(config)?
+        })
+    }
+
+    /// Only load the dictionaries of the spelling checkers.
+    fn spelling(config: &Config) -> Result<Self> {
+        Ok(Self {
+            hunspell: create_checker!(
+                "hunspell",
+                HunspellChecker,
+                config,
+                config.hunspell.as_ref()
+            ),
+            #[cfg(feature = "zet")]
+            zet: create_checker!("zet", ZetChecker, config, config.zet.as_ref()),
+            #[cfg(feature = "spellbook")]
+            spellbook: create_checker!(
+                "spellbook",
+                SpellbookChecker,
+                config,
+                config.spellbook.as_ref()
+            ),
+            nlprules: None,
+        })
+    }
+}
+
+impl Checkers {
+    pub fn new(mut config: Config) -> Result<Self> {
+        config.inherit_max_replacements();
+        let loaded = Loaded::new(&config)?;
+        Self::with_loaded(config, loaded)
+    }
+
+    /// Recreate all checkers from `config` for another run of a long running
+    /// mode, which is cheap besides loading the dictionaries. So the spelling
+    /// checkers are only recreated if `dictionaries_changed`.
+    pub fn reload(self, mut config: Config, dictionaries_changed: bool) -> Result<Self> {
+        config.inherit_max_replacements();
+        let previous = Loaded {
+            hunspell: self.hunspell,
+            #[cfg(feature = "zet")]
+            zet: self.zet,
+            #[cfg(feature = "spellbook")]
+            spellbook: self.spellbook,
+            nlprules: self.nlprules,
+        };
+        let loaded = if dictionaries_changed {
+            log::info!("Reloading the dictionaries");
+            Loaded {
+                nlprules: previous.nlprules,
+                ..Loaded::spelling(&config)?
+            }
+        } else {
+            previous
+        };
+        let mut checkers = Self::with_loaded(config, loaded)?;
+        checkers.progress = self.progress;
+        Ok(checkers)
+    }
+
+    fn with_loaded(config: Config, loaded: Loaded) -> Result<Self> {
+        let Loaded {
+            hunspell,
+            #[cfg(feature = "zet")]
+            zet,
+            #[cfg(feature = "spellbook")]
+            spellbook,
+            nlprules,
+        } = loaded;
+        let identifiers = config
+            .identifiers
+            .as_ref()
+            .filter(|_| config.is_enabled(Detector::Identifiers))
+            .map(IdentifiersChecker::new)
+            .transpose()?;
+        // shares the edit distance with the code references
+        let intra_doc_links = Some(config.identifiers.clone().unwrap_or_default())
+            .filter(|_| config.is_enabled(Detector::IntraDocLinks))
+            .map(|identifiers| IntraDocLinksChecker::new(&identifiers));
+        let redundancy = config
+            .redundancy
+            .as_ref()
+            .filter(|_| config.is_enabled(Detector::Redundancy))
+            .map(RedundancyChecker::new)
+            .transpose()?;
+        let headings = config
+            .headings
+            .as_ref()
+            .filter(|_| config.is_enabled(Detector::Headings))
+            .map(HeadingsChecker::new)
+            .transpose()?;
+        let symbols = Some(&config.symbols)
+            .filter(|symbols| symbols.accept)
+            .map(ProjectSymbols::new);
+        let jargon = config.jargon.as_ref().map(ProjectJargon::new);
+        let external = config
+            .checker
+            .external
+            .iter()
+            .filter(|_| config.is_enabled(Detector::External))
+            .map(ExternalChecker::new)
+            .collect::<Result<Vec<_>>>()?;
+        let missing_docs =
+            Some(MissingDocsChecker::new()).filter(|_| config.is_enabled(Detector::MissingDocs));
+        let line_length = if config.is_enabled(Detector::LineLength) {
+            Some(LineLengthChecker::new(&config.reflow_or_default()?)?)
+        } else {
+            None
+        };
+        let anchors = Some(AnchorsChecker).filter(|_| config.is_enabled(Detector::Anchors));
+        let baseline = config.baseline.as_deref().map(Baseline::load).transpose()?;
         Ok(Self {
             hunspell,
             #[cfg(feature = "zet")]
@@ -117,8 +343,79 @@ impl Checkers {
             #[cfg(feature = "spellbook")]
             spellbook,
             nlprules,
+            identifiers,
+            intra_doc_links,
+            redundancy,
+            headings,
+            symbols,
+            jargon,
+            external,
+            missing_docs,
+            line_length,
+            anchors,
+            suppress: config.suppress,
+            overrides: config.overrides,
+            baseline,
+            progress: Progress::default(),
         })
     }
+
+    /// Display the files being checked, with the checker currently running
+    /// on each, as message of `progress`.
+    pub fn set_progress(&mut self, progress: ProgressBar) {
+        self.progress.bar = Some(progress);
+    }
+
+    /// Check if a checker cross references all documents, which then must be
+    /// passed to [`Self::collect_identifiers`] before checking any of them.
+    pub fn needs_all_documents(&self) -> bool {
+        self.identifiers.is_some()
+            || self.intra_doc_links.is_some()
+            || self.symbols.is_some()
+            || self.jargon.is_some()
+            || self.missing_docs.is_some()
+    }
+
+    /// Provide the identifiers of the checked sources to the checkers which
+    /// cross reference them, and collect the project symbols, undocumented
+    /// items and terminology.
+    ///
+    /// Each rust source file is read and tokenized once for all of them.
+    pub fn collect_identifiers(&mut self, documents: &Documentation) {
+        let cross_references = self.identifiers.is_some() || self.intra_doc_links.is_some();
+        if cross_references || self.symbols.is_some() || self.missing_docs.is_some() {
+            let mut known = HashSet::new();
+            for (origin, _chunks) in documents.iter() {
+                let stream = match origin {
+                    ContentOrigin::RustSourceFile(path) => read_token_stream(path),
+                    _ => None,
+                };
+                let Some(stream) = stream else {
+                    continue;
+                };
+                if let Some(ref mut symbols) = self.symbols {
+                    symbols.collect(stream.clone());
+                }
+                if let Some(ref mut missing_docs) = self.missing_docs {
+                    missing_docs.collect(origin.clone(), stream.clone());
+                }
+                if cross_references {
+                    collect_idents(stream, &mut known);
+                }
+            }
+            log::debug!("Collected {} distinct identifiers", known.len());
+            let known = Arc::new(known);
+            if let Some(ref mut identifiers) = self.identifiers {
+                identifiers.set_identifiers(Arc::clone(&known));
+            }
+            if let Some(ref mut intra_doc_links) = self.intra_doc_links {
+                intra_doc_links.set_identifiers(known);
+            }
+        }
+        if let Some(ref mut jargon) = self.jargon {
+            jargon.collect(documents);
+        }
+    }
 }
 
 impl Checker for Checkers {
@@ -129,28 +426,124 @@ impl Checker for Checkers {
     }
 
     fn check<'a, 's>(
-        &self,
+        &'s self,
         origin: &ContentOrigin,
         chunks: &'a [CheckableChunk],
     ) -> Result<Vec<Suggestion<'s>>>
     where
         'a: 's,
     {
+        let checking = self.progress.checking(origin.as_path().to_path_buf());
+        let running = |detector: Detector| checking.running(detector);
         let mut collective = HashSet::<Suggestion<'s>>::new();
         if let Some(ref hunspell) = self.hunspell {
+            running(Detector::Hunspell);
             collective.extend(hunspell.check(origin, chunks)?);
         }
         #[cfg(feature = "zet")]
         if let Some(ref zet) = self.zet {
+            running(Detector::ZSpell);
             collective.extend(zet.check(origin, chunks)?);
         }
         #[cfg(feature = "spellbook")]
         if let Some(ref spellbook) = self.spellbook {
+            running(Detector::Spellbook);
             collective.extend(spellbook.check(origin, chunks)?);
         }
         if let Some(ref nlprule) = self.nlprules {
+            running(Detector::NlpRules);
             collective.extend(nlprule.check(origin, chunks)?);
         }
+        if let Some(ref identifiers) = self.identifiers {
+            running(Detector::Identifiers);
+            collective.extend(identifiers.check(origin, chunks)?);
+        }
+        if let Some(ref intra_doc_links) = self.intra_doc_links {
+            running(Detector::IntraDocLinks);
+            collective.extend(intra_doc_links.check(origin, chunks)?);
+        }
+        if let Some(ref redundancy) = self.redundancy {
+            running(Detector::Redundancy);
+            collective.extend(redundancy.check(origin, chunks)?);
+        }
+        if let Some(ref headings) = self.headings {
+            running(Detector::Headings);
+            collective.extend(headings.check(origin, chunks)?);
+        }
+        for external in self.external.iter() {
+            running(Detector::External);
+            collective.extend(external.check(origin, chunks)?);
+        }
+        if let Some(ref missing_docs) = self.missing_docs {
+            running(Detector::MissingDocs);
+            collective.extend(missing_docs.check(origin));
+        }
+        if let Some(ref line_length) = self.line_length {
+            running(Detector::LineLength);
+            collective.extend(line_length.check(origin, chunks)?);
+        }
+        if let Some(ref anchors) = self.anchors {
+            running(Detector::Anchors);
+            collective.extend(anchors.check(origin, chunks)?);
+        }
+
+        if let Some(ref symbols) = self.symbols {
+            collective.retain(|suggestion| {
+                if !matches!(
+                    suggestion.detector,
+                    Detector::Hunspell | Detector::ZSpell | Detector::Spellbook
+                ) {
+                    return true;
+                }
+                let word = sub_chars(suggestion.chunk.as_str(), suggestion.range.clone());
+                let accepted = symbols.accepts(&word);
+                if accepted {
+                    log::trace!("Accepted >{word}< as project symbol");
+                }
+                !accepted
+            });
+        }
+
+        if !self.suppress.is_empty() {
+            collective.retain(|suggestion| {
+                let word = sub_chars(suggestion.chunk.as_str(), suggestion.range.clone());
+                let suppressed = self
+                    .suppress
+                    .suppresses(&word, suggestion.description.as_deref())
+                    || self.suppress.suppresses_id(&suggestion.id());
+                if suppressed {
+                    log::trace!("Suppressed suggestion for >{word}< by configuration");
+                }
+                !suppressed
+            });
+        }
+
+        if !self.overrides.is_empty() {
+            let path = origin.as_path();
+            collective.retain(|suggestion| {
+                let exempt = self.overrides.exempts(
+                    path,
+                    suggestion.detector.namespace(),
+                    suggestion.span.start.line,
+                );
+                if exempt {
+                    log::trace!(
+                        "Dropped suggestion in {}:{} by override",
+                        path.display(),
+                        suggestion.span.start.line
+                    );
+                }
+                !exempt
+            });
+        }
+
+        if let Some(ref baseline) = self.baseline {
+            collective.retain(|suggestion| !baseline.contains(suggestion));
+        }
+
+        if let Some(ref jargon) = self.jargon {
+            collective = jargon.collapse(origin, collective);
+        }
 
         let mut suggestions: Vec<Suggestion<'s>> = Vec::from_iter(collective);
         suggestions.sort();
@@ -158,21 +551,105 @@ impl Checker for Checkers {
             return Ok(suggestions);
         }
 
-        // Iterate through suggestions and identify overlapping ones.
-        let suggestions = Vec::from_iter(suggestions.clone().into_iter().enumerate().filter_map(
-            |(idx, cur)| {
-                if idx == 0 || !cur.is_overlapped(&suggestions[idx - 1]) {
-                    Some(cur)
-                } else {
-                    None
+        // Iterate through suggestions and identify overlapping ones, the same
+        // word flagged by multiple detectors is merged into one suggestion.
+        let mut merged: Vec<Suggestion<'s>> = Vec::with_capacity(suggestions.len());
+        for (idx, cur) in suggestions.iter().enumerate() {
+            if let Some(last) = merged.last_mut() {
+                if last.merge(cur) {
+                    continue;
                 }
-            },
-        ));
+            }
+            if idx == 0 || !cur.is_overlapped(&suggestions[idx - 1]) {
+                merged.push(cur.clone());
+            }
+        }
+        let suggestions = merged;
 
         Ok(suggestions)
     }
 }
 
+/// Levenshtein distance in characters.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b = Vec::from_iter(b.chars());
+    let mut previous = Vec::from_iter(0..=b.len());
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Neighbouring keys of a QWERTY keyboard, row by row.
+const KEYBOARD_ROWS: &[&str] = &["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// Check if `a` and `b` are next to each other on a QWERTY keyboard,
+/// including the keys diagonally below and above.
+fn is_adjacent_key(a: char, b: char) -> bool {
+    let position = |c: char| {
+        let c = c.to_ascii_lowercase();
+        KEYBOARD_ROWS
+            .iter()
+            .enumerate()
+            .find_map(|(row, keys)| keys.find(c).map(|column| (row, column)))
+    };
+    match (position(a), position(b)) {
+        (Some((row_a, col_a)), Some((row_b, col_b))) => {
+            match row_a as isize - row_b as isize {
+                0 => col_a.abs_diff(col_b) == 1,
+                // the rows are shifted to the right by about half a key
+                1 => col_a == col_b || col_a + 1 == col_b,
+                -1 => col_b == col_a || col_b + 1 == col_a,
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Edit distance weighted by the likelihood of the typo, in half edits.
+///
+/// Insertions, deletions and substitutions cost two, while substituting a
+/// neighbouring key, changing the case and swapping two adjacent characters
+/// only cost one.
+pub(crate) fn typo_distance(a: &str, b: &str) -> usize {
+    let a = Vec::from_iter(a.chars());
+    let b = Vec::from_iter(b.chars());
+    let mut d = vec![vec![0_usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = 2 * i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = 2 * j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let (ca, cb) = (a[i - 1], b[j - 1]);
+            let substitution = if ca == cb {
+                0
+            } else if ca.to_lowercase().eq(cb.to_lowercase()) || is_adjacent_key(ca, cb) {
+                1
+            } else {
+                2
+            };
+            let mut cost = (d[i - 1][j - 1] + substitution)
+                .min(d[i - 1][j] + 2)
+                .min(d[i][j - 1] + 2);
+            if i > 1 && j > 1 && ca == b[j - 2] && a[i - 2] == cb {
+                cost = cost.min(d[i - 2][j - 2] + 1);
+            }
+            d[i][j] = cost;
+        }
+    }
+    d[a.len()][b.len()]
+}
+
 #[cfg(test)]
 pub mod dummy;
 
@@ -202,6 +679,25 @@ pub mod tests {
         ];
     }
 
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("receive_msg", "receive_msg"), 0);
+        assert_eq!(edit_distance("recieve_msg", "receive_msg"), 2);
+        assert_eq!(edit_distance("send_mgs", "send_msg"), 2);
+        assert_eq!(edit_distance("Checker", "Checkers"), 1);
+    }
+
+    #[test]
+    fn typos() {
+        assert_eq!(typo_distance("word", "word"), 0);
+        // neighbouring keys are more likely than distant ones
+        assert!(typo_distance("wprd", "word") < typo_distance("wqrd", "word"));
+        // swapped characters
+        assert_eq!(typo_distance("recieve", "receive"), 1);
+        assert_eq!(typo_distance("Word", "word"), 1);
+        assert_eq!(typo_distance("wrd", "word"), 2);
+    }
+
     #[test]
     fn tokens() {
         let tokenizer = tokenizer::<&PathBuf>(None).unwrap();
@@ -211,6 +707,17 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn snake_case_compounds() {
+        const TEXT: &str = "Use load_from_str here.";
+        // `_` is not a token boundary, so the whole identifier is checked
+        let tokenizer = tokenizer::<&PathBuf>(None).unwrap();
+        let words = Vec::from_iter(apply_tokenizer(&tokenizer, TEXT).map(|range| &TEXT[range]));
+        assert_eq!(words, vec!["Use", "load_from_str", "here", "."]);
+        let is_word = |word: &str| ["load", "from", "str"].contains(&word);
+        assert!(quirks::is_compound_of_words(words[1], is_word));
+    }
+
     pub fn extraction_test_body(content: &str, expected_spans: &[Span]) {
         let _ = env_logger::builder()
             .filter(None, log::LevelFilter::Trace)
@@ -363,9 +870,9 @@ struct X;
             }
         };
 
-        let hun = dbg!(cs.hunspell.unwrap().check(origin, ccs)).unwrap();
-        let book = dbg!(cs.spellbook.unwrap().check(origin, ccs)).unwrap();
-        let z = dbg!(cs.zet.unwrap().check(origin, ccs)).unwrap();
+        let hun = dbg!(cs.hunspell.as_ref().unwrap().check(origin, ccs)).unwrap();
+        let book = dbg!(cs.spellbook.as_ref().unwrap().check(origin, ccs)).unwrap();
+        let z = dbg!(cs.zet.as_ref().unwrap().check(origin, ccs)).unwrap();
         assert_cmp(&hun, &z);
         assert_cmp(&z, &book);
     }
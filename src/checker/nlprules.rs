@@ -4,81 +4,120 @@
 //! splitting is done in hand-waving way. To be improved.
 
 use super::{Checker, Detector, Suggestion};
+use crate::config::NlpRulesConfig;
 use crate::{CheckableChunk, ContentOrigin};
 
 use crate::errors::*;
 
 use std::collections::{hash_map::Entry, HashMap};
 use std::{
-    path::{Path, PathBuf},
-    sync::{Arc, Mutex},
+    path::PathBuf,
+    sync::{Arc, Mutex, OnceLock},
 };
 
 use nlprule::{Rules, Tokenizer};
 
 use lazy_static::lazy_static;
 
+/// Identifies a loaded model by the override paths and the category
+/// configuration.
+type ModelKey = (Option<PathBuf>, Option<PathBuf>, Vec<String>, Vec<String>);
+
 lazy_static! {
-    static ref RULES: Mutex<HashMap<Option<PathBuf>, Arc<Rules>>> = Mutex::new(HashMap::new());
+    static ref MODELS: Mutex<HashMap<ModelKey, Arc<Model>>> = Mutex::new(HashMap::new());
 }
 
-pub(crate) fn filtered_rules<P: AsRef<Path> + Clone>(
-    override_path: Option<P>,
-) -> Result<Arc<Rules>> {
-    match RULES
-        .lock()
-        .unwrap()
-        .entry(override_path.clone().map(|x| x.as_ref().to_path_buf()))
-    {
-        Entry::Occupied(occupied) => Ok(occupied.get().clone()),
-        Entry::Vacant(empty) => {
-            let rules = super::rules(override_path)?;
-            let rules = rules
-                .rules()
-                .iter()
-                .filter(|rule| {
-                    match rule
-                        .category_type()
-                        .map(str::to_lowercase)
-                        .as_ref()
-                        .map(|x| x as &str)
-                    {
-                        // The hunspell backend is aware of
-                        // custom lingo, which this one is not,
-                        // so there would be a lot of false
-                        // positives.
-                        Some("misspelling") => false,
-                        // Anything quotes related is not relevant
-                        // for code documentation.
-                        Some("typographical") => false,
-                        _other => true,
-                    }
-                })
-                .cloned()
-                .collect::<Rules>();
-
-            let rules = Arc::new(rules);
-            empty.insert(rules.clone());
-            Ok(rules)
+/// The tokenizer and the rules filtered by the enabled categories, shared by
+/// all checker instances and threads with the same configuration.
+struct Model {
+    tokenizer: Arc<Tokenizer>,
+    rules: Rules,
+    /// The lowercase category of each rule, by the rule id.
+    categories: HashMap<String, String>,
+}
+
+impl Model {
+    /// Load the model of `config` once, subsequent calls return the same one.
+    fn load(config: &NlpRulesConfig) -> Result<Arc<Self>> {
+        let key = (
+            config.override_tokenizer.clone(),
+            config.override_rules.clone(),
+            config.categories.clone(),
+            config.disabled_categories.clone(),
+        );
+        // held while loading, so concurrent first uses load only once
+        let mut models = MODELS.lock().unwrap();
+        match models.entry(key) {
+            Entry::Occupied(occupied) => Ok(occupied.get().clone()),
+            Entry::Vacant(empty) => {
+                let lang = config.lang();
+                let tokenizer = super::tokenizer(config.override_tokenizer.as_ref())
+                    .wrap_err_with(|| eyre!("Failed to load the nlprule tokenizer for `{lang}`"))?;
+                let rules = super::rules(config.override_rules.as_ref())
+                    .wrap_err_with(|| eyre!("Failed to load the nlprule rules for `{lang}`"))?;
+                let rules = rules
+                    .rules()
+                    .iter()
+                    .filter(|rule| config.is_category_enabled(rule.category_type()))
+                    .cloned()
+                    .collect::<Rules>();
+                let categories = HashMap::from_iter(rules.rules().iter().filter_map(|rule| {
+                    let category = rule.category_type()?.to_lowercase();
+                    Some((rule.id().to_string(), category))
+                }));
+                let model = Arc::new(Self {
+                    tokenizer,
+                    rules,
+                    categories,
+                });
+                empty.insert(model.clone());
+                Ok(model)
+            }
         }
     }
 }
 
+/// Checks grammar with `nlprule`, the model is loaded on first use, so
+/// enabling the checker does not cost anything unless there is something to
+/// check.
 pub(crate) struct NlpRulesChecker {
-    tokenizer: Arc<Tokenizer>,
-    rules: Arc<Rules>,
+    config: NlpRulesConfig,
+    model: OnceLock<Arc<Model>>,
 }
 
 impl NlpRulesChecker {
     pub fn new(config: &<Self as Checker>::Config) -> Result<Self> {
-        let tokenizer = super::tokenizer(config.override_tokenizer.as_ref())?;
-        let rules = filtered_rules(config.override_tokenizer.as_ref())?;
-        Ok(Self { tokenizer, rules })
+        // fail early on a misconfiguration, the loading itself is deferred
+        for path in [&config.override_tokenizer, &config.override_rules]
+            .into_iter()
+            .flatten()
+        {
+            if !path.is_file() {
+                bail!(
+                    "The nlprule binary {} for `{}` does not exist",
+                    path.display(),
+                    config.lang()
+                );
+            }
+        }
+        Ok(Self {
+            config: config.clone(),
+            model: OnceLock::new(),
+        })
+    }
+
+    /// The shared model, loaded on first use.
+    fn model(&self) -> Result<&Model> {
+        if let Some(model) = self.model.get() {
+            return Ok(model);
+        }
+        let model = Model::load(&self.config)?;
+        Ok(self.model.get_or_init(|| model))
     }
 }
 
 impl Checker for NlpRulesChecker {
-    type Config = crate::config::NlpRulesConfig;
+    type Config = NlpRulesConfig;
 
     fn detector() -> Detector {
         Detector::NlpRules
@@ -92,14 +131,19 @@ impl Checker for NlpRulesChecker {
     where
         'a: 's,
     {
+        if chunks.is_empty() {
+            return Ok(Vec::new());
+        }
+        let model = self.model()?;
         let mut acc = Vec::with_capacity(chunks.len());
 
         for chunk in chunks {
             acc.extend(check_chunk(
                 origin.clone(),
                 chunk,
-                &self.tokenizer,
-                &self.rules,
+                &model.tokenizer,
+                &model.rules,
+                &model.categories,
             ));
         }
 
@@ -113,6 +157,7 @@ fn check_chunk<'a>(
     chunk: &'a CheckableChunk,
     tokenizer: &Tokenizer,
     rules: &Rules,
+    categories: &HashMap<String, String>,
 ) -> Vec<Suggestion<'a>> {
     // TODO We should control which parts need to be ignored of the markdown
     // entities, however the `NlpRulesConfig`, which is the only configuration
@@ -132,6 +177,9 @@ fn check_chunk<'a>(
 
     'nlp: for fix in nlpfixes {
         let message = fix.message();
+        let category = categories.get(fix.source()).cloned();
+        // strip the index within a rule group, i.e. `EN_A_VS_AN/1`
+        let rule = fix.source().split('/').next().unwrap_or_default();
         let replacements = fix.replacements();
         let start = fix.span().char().start;
         let end = fix.span().char().end;
@@ -146,6 +194,9 @@ fn check_chunk<'a>(
                 .into_iter()
                 .map(|(range, span)| Suggestion {
                     detector: Detector::NlpRules,
+                    agreeing: Vec::new(),
+                    rule: Some(rule.to_owned()),
+                    category: category.clone(),
                     range,
                     span,
                     origin: origin.clone(),
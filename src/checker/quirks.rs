@@ -1,7 +1,11 @@
 //! A set of quirks, not necessarily specific to a checker
 
-use crate::Range;
+use super::symbols::split_case;
+use super::typo_distance;
+use crate::config::CodeLike;
+use crate::{ContentOrigin, Range};
 use fancy_regex::Regex;
+use std::collections::HashSet;
 
 /// Returns `true` iff the replacements contains a variant of `word` without
 /// dashes.
@@ -34,6 +38,154 @@ pub(crate) fn replacements_contain_dashed<T: AsRef<str>>(word: &str, replacement
         .any(|s| itertools::equal(s.chars().filter(|c| *c != '-'), word.chars()))
 }
 
+/// Order `replacements` by their edit distance to `word`, weighted by the
+/// likelihood of the typo, and keep at most `max_replacements` of them. The
+/// first one is the most probable fix.
+///
+/// Equally distant replacements retain the order of the dictionary.
+pub(crate) fn rank_replacements(
+    word: &str,
+    mut replacements: Vec<String>,
+    max_replacements: Option<usize>,
+) -> Vec<String> {
+    replacements.sort_by_cached_key(|replacement| typo_distance(word, replacement));
+    if let Some(max_replacements) = max_replacements {
+        replacements.truncate(max_replacements);
+    }
+    replacements
+}
+
+/// Returns `true` iff `word` is a compound identifier, i.e. `LineColumn` or
+/// `load_from_str`, and all of its sub-words satisfy `is_word`.
+pub(crate) fn is_compound_of_words(word: &str, is_word: impl Fn(&str) -> bool) -> bool {
+    let parts = split_case(word);
+    if parts.len() < 2 || !parts.iter().all(|part| is_word(part)) {
+        return false;
+    }
+    log::trace!(target: "quirks", "Found compound of known words {word}");
+    true
+}
+
+/// Returns `true` iff `word` is the concatenation of two words which satisfy
+/// `is_word`, both at least `min` characters long. Used for dictionaries
+/// which declare compounding, i.e. german `Haustür`.
+///
+/// The second word is also accepted capitalized, as it would be standalone.
+pub(crate) fn is_concatenation_of_words(
+    word: &str,
+    min: usize,
+    is_word: impl Fn(&str) -> bool,
+) -> bool {
+    let found = word
+        .char_indices()
+        .skip(min)
+        .map(|(offset, _)| word.split_at(offset))
+        .filter(|(_, tail)| tail.chars().count() >= min)
+        .any(|(head, tail)| {
+            if !is_word(head) {
+                return false;
+            }
+            let mut chars = tail.chars();
+            is_word(tail)
+                || chars
+                    .next()
+                    .map(|first| is_word(&String::from_iter(first.to_uppercase().chain(chars))))
+                    .unwrap_or_default()
+        });
+    if found {
+        log::trace!(target: "quirks", "Found compound of dictionary words {word}");
+    }
+    found
+}
+
+/// Returns `true` iff `word` starts with an uppercase letter, but is not the
+/// first word of a sentence according to `sentence_starts`, so it is likely a
+/// proper noun.
+pub(crate) fn is_capitalized_mid_sentence(
+    word: &str,
+    start: usize,
+    sentence_starts: &[usize],
+) -> bool {
+    if !word
+        .chars()
+        .next()
+        .map(char::is_uppercase)
+        .unwrap_or_default()
+    {
+        return false;
+    }
+    if sentence_starts.binary_search(&start).is_ok() {
+        return false;
+    }
+    log::trace!(target: "quirks", "Found capitalized word within a sentence {word}");
+    true
+}
+
+/// Detects tokens which are code rather than prose, based on the enabled
+/// [`CodeLike`] heuristics.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CodeLikeFilter {
+    config: CodeLike,
+    /// Identifiers of the checked file.
+    identifiers: HashSet<String>,
+}
+
+impl CodeLikeFilter {
+    /// Create the filter for the chunks of `origin`, the identifiers of the
+    /// file are only collected if required.
+    pub(crate) fn new(config: &CodeLike, origin: &ContentOrigin) -> Self {
+        let mut identifiers = HashSet::new();
+        if config.file_identifiers {
+            if let ContentOrigin::RustSourceFile(path) = origin {
+                super::identifiers::file_identifiers(path, &mut identifiers);
+            }
+        }
+        Self {
+            config: config.clone(),
+            identifiers,
+        }
+    }
+
+    /// Check if the token at `range` within `chars` looks like code.
+    ///
+    /// Tokens are commonly split at `_` and `:`, so the surrounding characters
+    /// are taken into account.
+    pub(crate) fn is_code_like(&self, chars: &[char], range: Range) -> bool {
+        if !self.config.is_enabled() || range.end > chars.len() {
+            return false;
+        }
+        let expand = |f: fn(char) -> bool| -> String {
+            let start = chars[..range.start]
+                .iter()
+                .rposition(|c| !f(*c))
+                .map(|idx| idx + 1)
+                .unwrap_or_default();
+            let end = chars[range.end..]
+                .iter()
+                .position(|c| !f(*c))
+                .map(|idx| idx + range.end)
+                .unwrap_or(chars.len());
+            chars[start..end].iter().collect()
+        };
+        let ident = expand(|c| c.is_alphanumeric() || c == '_');
+        let ident = ident.trim_matches('_');
+        if ident.contains('_') {
+            let screaming = !ident.chars().any(char::is_lowercase);
+            if (screaming && self.config.screaming_case) || (!screaming && self.config.snake_case) {
+                log::trace!(target: "quirks", "Found code like token {ident}");
+                return true;
+            }
+        }
+        if self.config.paths
+            && expand(|c| c.is_alphanumeric() || c == '_' || c == ':').contains("::")
+        {
+            log::trace!(target: "quirks", "Found path segment {ident}");
+            return true;
+        }
+        self.config.file_identifiers && self.identifiers.contains(ident)
+    }
+}
+
 /// Transformed word with information on the transformation outcome.
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) enum Transformed<'i> {
@@ -146,6 +298,79 @@ mod tests {
     use crate::config::WrappedRegex;
     use env_logger;
 
+    #[test]
+    fn code_like() {
+        let config = CodeLike {
+            snake_case: true,
+            screaming_case: false,
+            paths: true,
+            file_identifiers: false,
+        };
+        let filter = CodeLikeFilter::new(&config, &ContentOrigin::TestEntityRust);
+        let chars = Vec::from_iter("Use foo_bar, not MAX_LEN or std::mem::take here.".chars());
+        let is_code_like = |word: &str| {
+            let s = String::from_iter(chars.iter());
+            let start = s[..s.find(word).unwrap()].chars().count();
+            filter.is_code_like(&chars, start..(start + word.chars().count()))
+        };
+        assert!(!is_code_like("Use"));
+        assert!(is_code_like("foo"));
+        assert!(is_code_like("bar"));
+        assert!(!is_code_like("MAX"));
+        assert!(is_code_like("std"));
+        assert!(is_code_like("take"));
+        assert!(!is_code_like("here"));
+
+        let disabled = CodeLikeFilter::new(&CodeLike::default(), &ContentOrigin::TestEntityRust);
+        assert!(!disabled.is_code_like(&chars, 4..11));
+    }
+
+    #[test]
+    fn compounds() {
+        let is_word = |word: &str| {
+            ["line", "column", "load", "from", "str"].contains(&word.to_lowercase().as_str())
+        };
+        assert!(is_compound_of_words("LineColumn", is_word));
+        assert!(is_compound_of_words("load_from_str", is_word));
+        assert!(!is_compound_of_words("LineColumnz", is_word));
+        // not a compound, left to the dictionary
+        assert!(!is_compound_of_words("line", is_word));
+    }
+
+    #[test]
+    fn concatenation() {
+        let is_word = |word: &str| ["Haus", "Tür"].contains(&word);
+        assert!(is_concatenation_of_words("Haustür", 3, is_word));
+        assert!(!is_concatenation_of_words("Haustür", 5, is_word));
+        assert!(!is_concatenation_of_words("Hausboot", 3, is_word));
+    }
+
+    #[test]
+    fn capitalized() {
+        let sentence_starts = [0, 10];
+        assert!(is_capitalized_mid_sentence("Tokio", 4, &sentence_starts));
+        assert!(!is_capitalized_mid_sentence("Tokio", 10, &sentence_starts));
+        assert!(!is_capitalized_mid_sentence("tokio", 4, &sentence_starts));
+    }
+
+    #[test]
+    fn ranked() {
+        let replacements = vec![
+            "deck".to_owned(),
+            "dock".to_owned(),
+            "doc".to_owned(),
+            "duck".to_owned(),
+        ];
+        assert_eq!(
+            rank_replacements("dok", replacements.clone(), None),
+            vec!["dock", "doc", "deck", "duck"]
+        );
+        assert_eq!(
+            rank_replacements("dok", replacements, Some(2)),
+            vec!["dock", "doc"]
+        );
+    }
+
     #[test]
     fn dashed() {
         let _ = env_logger::builder()
@@ -0,0 +1,191 @@
+//! Repeated words.
+//!
+//! Flags immediately repeated words, i.e. "the the", which are easy to
+//! overlook when a sentence is edited, and consecutive sentences starting with
+//! the same word. Unlike the grammar rules of `nlprules`, this needs neither
+//! models nor additional binary size.
+
+use super::Checker;
+use crate::config::RedundancyConfig;
+use crate::errors::*;
+use crate::util::{char_offsets, push_words, sub_chars};
+use crate::{CheckableChunk, ContentOrigin, Detector, Range, Suggestion};
+
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+pub(crate) struct RedundancyChecker {
+    config: RedundancyConfig,
+}
+
+impl RedundancyChecker {
+    pub fn new(config: &RedundancyConfig) -> Result<Self> {
+        Ok(Self {
+            config: config.clone(),
+        })
+    }
+
+    fn is_allowed(&self, word: &str) -> bool {
+        self.config
+            .allow
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(word))
+    }
+}
+
+impl Checker for RedundancyChecker {
+    type Config = RedundancyConfig;
+
+    fn detector() -> Detector {
+        Detector::Redundancy
+    }
+
+    fn check<'a, 's>(
+        &self,
+        origin: &ContentOrigin,
+        chunks: &'a [CheckableChunk],
+    ) -> Result<Vec<Suggestion<'s>>>
+    where
+        'a: 's,
+    {
+        let mut acc = Vec::new();
+        for chunk in chunks {
+            let content = chunk.as_str();
+            let words = words(content);
+            let mut previous_sentence_start: Option<String> = None;
+            for (idx, current) in words.iter().enumerate() {
+                let word = sub_chars(content, current.clone());
+                let previous = match idx.checked_sub(1).map(|idx| &words[idx]) {
+                    Some(previous) => previous,
+                    None => {
+                        previous_sentence_start = Some(word.to_lowercase());
+                        continue;
+                    }
+                };
+                let gap = sub_chars(content, previous.end..current.start);
+                let previous_word = sub_chars(content, previous.clone());
+
+                if gap.chars().all(char::is_whitespace)
+                    && word.eq_ignore_ascii_case(&previous_word)
+                    && !word.chars().all(|c| c.is_numeric())
+                    && !self.is_allowed(&word)
+                {
+                    // remove the repetition along with the whitespace, which is
+                    // only sensible within one line
+                    let (range, replacements) = if gap.contains('\n') {
+                        (current.clone(), Vec::new())
+                    } else {
+                        (previous.start..current.end, vec![previous_word.clone()])
+                    };
+                    for (range, span) in chunk.find_spans(range) {
+                        acc.push(Suggestion {
+                            detector: Detector::Redundancy,
+                            agreeing: Vec::new(),
+                            rule: None,
+                            category: None,
+                            origin: origin.clone(),
+                            chunk,
+                            span,
+                            range,
+                            replacements: replacements.clone(),
+                            description: Some(format!("The word `{word}` is repeated.")),
+                        });
+                    }
+                }
+
+                let starts_sentence = gap.contains(['.', '!', '?']);
+                if !starts_sentence {
+                    continue;
+                }
+                let lowercase = word.to_lowercase();
+                if self.config.sentence_starts
+                    && previous_sentence_start.as_deref() == Some(lowercase.as_str())
+                    && !self.is_allowed(&word)
+                {
+                    for (range, span) in chunk.find_spans(current.clone()) {
+                        acc.push(Suggestion {
+                            detector: Detector::Redundancy,
+                            agreeing: Vec::new(),
+                            rule: Some("repeated-sentence-start".to_owned()),
+                            category: None,
+                            origin: origin.clone(),
+                            chunk,
+                            span,
+                            range,
+                            replacements: Vec::new(),
+                            description: Some(format!(
+                                "Consecutive sentences start with `{word}`."
+                            )),
+                        });
+                    }
+                }
+                previous_sentence_start = Some(lowercase);
+            }
+        }
+        Ok(acc)
+    }
+}
+
+/// Character ranges of the words of the prose in `content`, skipping inline
+/// code and code blocks.
+fn words(content: &str) -> Vec<Range> {
+    let char_offsets = char_offsets(content);
+    let mut acc = Vec::new();
+    let mut in_code_block = false;
+    for (event, byte_range) in Parser::new(content).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Text(_) if !in_code_block => {
+                push_words(content, byte_range, &char_offsets, &mut acc)
+            }
+            _ => {}
+        }
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Documentation;
+
+    fn flagged(content: &str) -> Vec<(String, Vec<String>)> {
+        let mut docs = Documentation::new();
+        docs.add_commonmark(ContentOrigin::TestEntityCommonMark, content)
+            .unwrap();
+        let chunks = docs.get(&ContentOrigin::TestEntityCommonMark).unwrap();
+        let checker = RedundancyChecker::new(&RedundancyConfig::default()).unwrap();
+        let suggestions = checker
+            .check(&ContentOrigin::TestEntityCommonMark, chunks)
+            .unwrap();
+        Vec::from_iter(suggestions.into_iter().map(|suggestion| {
+            (
+                sub_chars(suggestion.chunk.as_str(), suggestion.range.clone()),
+                suggestion.replacements,
+            )
+        }))
+    }
+
+    #[test]
+    fn repeated_words() {
+        assert_eq!(
+            flagged("Check the the spelling."),
+            vec![("the the".to_owned(), vec!["the".to_owned()])]
+        );
+        assert_eq!(
+            flagged("Use The\nthe spelling."),
+            vec![("the".to_owned(), vec![])]
+        );
+        assert!(flagged("It had had an effect, 2 2 times.").is_empty());
+        assert!(flagged("Call `x` `x` or this, this.").is_empty());
+        assert!(flagged("```\nlet x = x x;\n```").is_empty());
+    }
+
+    #[test]
+    fn repeated_sentence_starts() {
+        assert_eq!(
+            flagged("It checks. It fixes. Then it stops."),
+            vec![("It".to_owned(), vec![])]
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Dictionary of the symbols defined by the checked project.
+//!
+//! Prose commonly refers to types, functions or modules without backticks,
+//! i.e. "Documentation holds CheckableChunks". The spelling checkers flag
+//! those, even in the crate which defines them. The names of all items of the
+//! checked rust sources are hence accepted as words.
+
+use crate::config::SymbolsConfig;
+
+use proc_macro2::{TokenStream, TokenTree};
+use std::collections::HashSet;
+
+/// Keywords which are followed by the name of the item they define.
+const ITEM_KEYWORDS: &[&str] = &[
+    "struct",
+    "enum",
+    "union",
+    "trait",
+    "fn",
+    "mod",
+    "type",
+    "const",
+    "static",
+    "macro_rules",
+];
+
+/// Keywords which may be placed between an item keyword and the name.
+const MODIFIERS: &[&str] = &["mut", "unsafe", "async", "extern"];
+
+pub(crate) struct ProjectSymbols {
+    config: SymbolsConfig,
+    words: HashSet<String>,
+}
+
+impl ProjectSymbols {
+    pub fn new(config: &SymbolsConfig) -> Self {
+        Self {
+            config: config.clone(),
+            words: HashSet::new(),
+        }
+    }
+
+    /// Collect the item names of a tokenized rust source file.
+    pub fn collect(&mut self, stream: TokenStream) {
+        let mut names = Vec::new();
+        collect_item_names(stream, &mut names);
+        for name in names {
+            if self.config.split_case {
+                self.words
+                    .extend(split_case(&name).into_iter().map(ToOwned::to_owned));
+            }
+            self.words.insert(name);
+        }
+    }
+
+    /// Check if `word` is a project symbol, or the plural of one.
+    pub fn accepts(&self, word: &str) -> bool {
+        self.words.contains(word)
+            || word
+                .strip_suffix('s')
+                .filter(|singular| self.words.contains(*singular))
+                .is_some()
+    }
+}
+
+/// Collect the names of all items defined in `stream`.
+fn collect_item_names(stream: TokenStream, acc: &mut Vec<String>) {
+    // the previous token introduces an item name
+    let mut expect_name = false;
+    // the previous token is the `'` of a lifetime
+    let mut lifetime = false;
+    for tree in stream {
+        match tree {
+            TokenTree::Ident(ident) => {
+                let ident = ident.to_string();
+                let ident = ident.strip_prefix("r#").unwrap_or(&ident);
+                if lifetime {
+                    expect_name = false;
+                } else if ITEM_KEYWORDS.contains(&ident) {
+                    expect_name = true;
+                } else if expect_name && !MODIFIERS.contains(&ident) {
+                    if ident != "_" {
+                        acc.push(ident.to_owned());
+                    }
+                    expect_name = false;
+                }
+                lifetime = false;
+            }
+            TokenTree::Punct(punct) => {
+                lifetime = punct.as_char() == '\'';
+                // `macro_rules!` is followed by the name
+                expect_name &= punct.as_char() == '!';
+            }
+            TokenTree::Group(group) => {
+                expect_name = false;
+                lifetime = false;
+                collect_item_names(group.stream(), acc);
+            }
+            TokenTree::Literal(_) => {
+                expect_name = false;
+                lifetime = false;
+            }
+        }
+    }
+}
+
+/// Split an identifier at `_` and case boundaries, i.e. `CheckableChunk` into
+/// `Checkable` and `Chunk` or `HTTPServer` into `HTTP` and `Server`.
+pub(crate) fn split_case(ident: &str) -> Vec<&str> {
+    let chars = Vec::from_iter(ident.char_indices());
+    let mut acc = Vec::new();
+    let mut start = 0;
+    for (idx, (offset, c)) in chars.iter().copied().enumerate() {
+        if c == '_' {
+            if start < offset {
+                acc.push(&ident[start..offset]);
+            }
+            start = offset + c.len_utf8();
+            continue;
+        }
+        let previous = idx.checked_sub(1).map(|idx| chars[idx].1);
+        let next = chars.get(idx + 1).map(|(_, c)| *c);
+        let boundary = match previous {
+            Some(previous) if c.is_uppercase() => {
+                previous.is_lowercase()
+                    || (previous.is_uppercase() && next.map(char::is_lowercase).unwrap_or_default())
+            }
+            _ => false,
+        };
+        if boundary && start < offset {
+            acc.push(&ident[start..offset]);
+            start = offset;
+        }
+    }
+    if start < ident.len() {
+        acc.push(&ident[start..]);
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split() {
+        assert_eq!(split_case("CheckableChunk"), vec!["Checkable", "Chunk"]);
+        assert_eq!(split_case("HTTPServer"), vec!["HTTP", "Server"]);
+        assert_eq!(split_case("load_from_str"), vec!["load", "from", "str"]);
+        assert_eq!(split_case("_private"), vec!["private"]);
+        assert_eq!(split_case("word"), vec!["word"]);
+    }
+
+    #[test]
+    fn item_names() {
+        let stream = syn::parse_str::<TokenStream>(
+            r#"
+pub struct CheckableChunk;
+mod inner {
+    pub(crate) const fn load_from_str(x: &'static str) -> usize { let y = x; y }
+    static mut COUNT: usize = 0;
+    macro_rules! chyrp { () => {} }
+}
+"#,
+        )
+        .unwrap();
+        let mut names = Vec::new();
+        collect_item_names(stream, &mut names);
+        assert_eq!(
+            names,
+            vec!["CheckableChunk", "inner", "load_from_str", "COUNT", "chyrp"]
+        );
+    }
+
+    #[test]
+    fn accepts() {
+        let mut symbols = ProjectSymbols::new(&SymbolsConfig {
+            accept: true,
+            split_case: true,
+        });
+        symbols.words.insert("CheckableChunk".to_owned());
+        symbols.words.extend(
+            split_case("CheckableChunk")
+                .into_iter()
+                .map(ToOwned::to_owned),
+        );
+        assert!(symbols.accepts("CheckableChunks"));
+        assert!(symbols.accepts("Chunk"));
+        assert!(!symbols.accepts("Chunky"));
+    }
+}
@@ -1,9 +1,10 @@
 use super::Cached;
-use crate::checker::cached::CachedValue;
+use crate::checker::cached::{writable_cache_dir, CachedValue};
 use crate::errors::*;
 use fs_err as fs;
 use lazy_static::lazy_static;
 
+use fancy_regex::Regex;
 use nlprule::{Rules, Tokenizer};
 use std::collections::{hash_map::Entry, HashMap};
 use std::{
@@ -34,6 +35,12 @@ pub fn project_dir() -> Result<directories::ProjectDirs> {
         .ok_or_else(|| color_eyre::eyre::eyre!("Missing project dir"))
 }
 
+/// The cache directory, or a temporary one if there is no writable one.
+fn cache_dir() -> PathBuf {
+    let project_dir = project_dir().ok();
+    writable_cache_dir(project_dir.as_ref().map(|dirs| dirs.cache_dir()))
+}
+
 fn tokenizer_inner<P: AsRef<Path>>(
     override_path: Option<P>,
     cache_dir: &Path,
@@ -41,17 +48,24 @@ fn tokenizer_inner<P: AsRef<Path>>(
     log::info!("🧮 Loading tokenizer...");
     let tokenizer = if let Some(override_path) = override_path.as_ref() {
         let override_path = override_path.as_ref();
-        let mut cached = Cached::new(override_path.display().to_string(), cache_dir)?;
+        let load = |override_path: &str| -> Result<Tokenizer> {
+            let f = fs::File::open(override_path)?;
+            Ok(Tokenizer::from_reader(f)?)
+        };
+        let mut cached = match Cached::new(override_path.display().to_string(), cache_dir) {
+            Ok(cached) => cached,
+            Err(e) => {
+                log::warn!("🧮 Failed to access the cache, loading tokenizer uncached: {e:?}");
+                return load(&override_path.display().to_string());
+            }
+        };
         let CachedValue {
             fetch,
             update,
             creation,
             total,
             value: tokenizer,
-        } = cached.fetch_or_update(|override_path| {
-            let f = fs::File::open(override_path)?;
-            Ok(Tokenizer::from_reader(f)?)
-        })?;
+        } = cached.fetch_or_update(load)?;
         log::info!("🧮 Loaded tokenizer in {total} us (fetch: {fetch} us, update: {update} us, creation: {creation} us)",
             total = maybe_display_micros(total),
             fetch = maybe_display_micros(fetch),
@@ -81,7 +95,7 @@ pub(crate) fn tokenizer<P: AsRef<Path> + Clone>(
     {
         Entry::Occupied(occupied) => Ok(occupied.get().clone()),
         Entry::Vacant(empty) => {
-            let tokenizer = tokenizer_inner(override_path, project_dir()?.cache_dir())?;
+            let tokenizer = tokenizer_inner(override_path, &cache_dir())?;
             let tokenizer = Arc::new(tokenizer);
             empty.insert(tokenizer.clone());
             Ok(tokenizer)
@@ -89,25 +103,28 @@ pub(crate) fn tokenizer<P: AsRef<Path> + Clone>(
     }
 }
 
-lazy_static! {
-    static ref RULES: Mutex<HashMap<Option<PathBuf>, Arc<Rules>>> = Mutex::new(HashMap::new());
-}
-
 fn rules_inner<P: AsRef<Path>>(override_path: Option<P>, cache_dir: &Path) -> Result<Rules> {
     log::info!("🧮 Loading rules...");
     let rules = if let Some(override_path) = override_path.as_ref() {
         let override_path = override_path.as_ref();
-        let mut cached = Cached::new(override_path.display().to_string(), cache_dir)?;
+        let load = |override_path: &str| -> Result<Rules> {
+            let f = fs::File::open(override_path)?;
+            Ok(Rules::from_reader(f)?)
+        };
+        let mut cached = match Cached::new(override_path.display().to_string(), cache_dir) {
+            Ok(cached) => cached,
+            Err(e) => {
+                log::warn!("🧮 Failed to access the cache, loading rules uncached: {e:?}");
+                return load(&override_path.display().to_string());
+            }
+        };
         let CachedValue {
             fetch,
             update,
             creation,
             total,
             value: rules,
-        } = cached.fetch_or_update(|override_path| {
-            let f = fs::File::open(override_path)?;
-            Ok(Rules::from_reader(f)?)
-        })?;
+        } = cached.fetch_or_update(load)?;
         log::info!("🧮 Loaded rules in {total} us (fetch: {fetch} us, update: {update} us, creation: {creation} us)",
             total = maybe_display_micros(total),
             fetch = maybe_display_micros(fetch),
@@ -129,24 +146,19 @@ fn rules_inner<P: AsRef<Path>>(override_path: Option<P>, cache_dir: &Path) -> Re
     Ok(rules)
 }
 
-pub(crate) fn rules<P: AsRef<Path> + Clone>(override_path: Option<P>) -> Result<Arc<Rules>> {
-    match RULES
-        .lock()
-        .unwrap()
-        .entry(override_path.clone().map(|p| p.as_ref().to_path_buf()))
-    {
-        Entry::Occupied(occupied) => Ok(occupied.get().clone()),
-        Entry::Vacant(empty) => {
-            let rules = rules_inner(override_path, project_dir()?.cache_dir())?;
-            let rules = Arc::new(rules);
-            empty.insert(rules.clone());
-            Ok(rules)
-        }
-    }
+/// Load the rules, not kept around, since only the filtered ones of the
+/// `nlprules` checker are used.
+pub(crate) fn rules<P: AsRef<Path>>(override_path: Option<P>) -> Result<Rules> {
+    rules_inner(override_path, &cache_dir())
 }
 
+use crate::config::TokenizerConfig;
 use crate::Range;
 
+/// Elided words are short, longer prefixes are kept, i.e. `aujourd'hui`.
+const ELISION_MAX_PREFIX: usize = 6;
+
+#[cfg(test)]
 pub(crate) fn apply_tokenizer<'t, 'z>(
     tokenizer: &'t Arc<Tokenizer>,
     text: &'z str,
@@ -154,86 +166,221 @@ pub(crate) fn apply_tokenizer<'t, 'z>(
 where
     't: 'z,
 {
-    tokenizer.pipe(text).flat_map(|sentence| {
-        let mut backlog: Vec<Range> = Vec::with_capacity(4);
-        let mut acc = Vec::with_capacity(32);
-        let mut iter = sentence
-            .into_iter()
-            .filter(|token| !token.span().char().is_empty())
-            .peekable();
-
-        #[derive(Clone, Copy, Debug)]
-        enum Stage {
-            Empty,
-            Pre,
-            Tick,
-        }
+    lazy_static! {
+        static ref DEFAULT: TokenizerConfig = TokenizerConfig::default();
+    }
+    apply_tokenizer_with(tokenizer, text, &DEFAULT)
+}
 
-        let mut stage = Stage::Empty;
-
-        // special cases all abbreviated variants, i.e. `isn't` such
-        // that the tokenizer treats them as a single word.
-        //
-        // Also allows i.e. `ink!'s` to be detected as a single
-        // token.
-        while let Some(token) = iter.next() {
-            let char_range = token.span().char().clone();
-
-            let space = iter
-                .peek()
-                .map(|upcoming| upcoming.has_space_before())
-                .unwrap_or(false);
-            let s = token.word().as_str();
-            // TODO workaround for a bug in srx
-            // TODO that does not split `[7f` after `[` as expected
-            if s.starts_with('[') && char_range.len() > 1 {
-                acc.push((char_range.start)..(char_range.start + 1));
-                acc.push((char_range.start + 1)..(char_range.end));
-                continue;
+/// Split `text` into words, treating the characters of `config` as
+/// apostrophes.
+pub(crate) fn apply_tokenizer_with<'t, 'z>(
+    tokenizer: &'t Arc<Tokenizer>,
+    text: &'z str,
+    config: &'z TokenizerConfig,
+) -> impl std::iter::Iterator<Item = Range> + 'z
+where
+    't: 'z,
+{
+    let elide = move |range: Range| -> Vec<Range> {
+        if config.elision {
+            let prefix = text
+                .chars()
+                .skip(range.start)
+                .take(range.len().saturating_sub(1))
+                .position(|c| config.apostrophes.contains(c));
+            if let Some(prefix) = prefix.filter(|prefix| (1..=ELISION_MAX_PREFIX).contains(prefix))
+            {
+                let split = range.start + prefix + 1;
+                return vec![range.start..split, split..range.end];
             }
-            let belongs_to_genitive_s = match s {
-                "(" | ")" | r#"""# => false,
-                _ => true,
-            };
-            stage = if belongs_to_genitive_s {
-                match stage {
-                    Stage::Empty if s != "'" && !space => {
-                        backlog.push(char_range);
-                        Stage::Pre
-                    }
-                    Stage::Pre if s != "'" && !space => {
-                        backlog.push(char_range);
-                        Stage::Pre
-                    }
-                    Stage::Pre if s == "'" && !space => {
-                        backlog.push(char_range);
-                        Stage::Tick
-                    }
-                    Stage::Tick if s != "'" => {
-                        // combine all in backlog to one
-                        acc.push(backlog.first().unwrap().start..char_range.end);
-                        backlog.clear();
-                        Stage::Empty
-                    }
-                    _stage => {
-                        acc.append(&mut backlog);
-                        acc.push(char_range);
-                        Stage::Empty
+        }
+        vec![range]
+    };
+    tokenizer
+        .pipe(text)
+        .flat_map(move |sentence| {
+            let mut backlog: Vec<Range> = Vec::with_capacity(4);
+            let mut acc = Vec::with_capacity(32);
+            let mut iter = sentence
+                .into_iter()
+                .filter(|token| !token.span().char().is_empty())
+                .peekable();
+
+            #[derive(Clone, Copy, Debug)]
+            enum Stage {
+                Empty,
+                Pre,
+                Tick,
+            }
+
+            let mut stage = Stage::Empty;
+
+            // special cases all abbreviated variants, i.e. `isn't` such
+            // that the tokenizer treats them as a single word.
+            //
+            // Also allows i.e. `ink!'s` to be detected as a single
+            // token.
+            while let Some(token) = iter.next() {
+                let char_range = token.span().char().clone();
+
+                let space = iter
+                    .peek()
+                    .map(|upcoming| upcoming.has_space_before())
+                    .unwrap_or(false);
+                let s = token.word().as_str();
+                // TODO workaround for a bug in srx
+                // TODO that does not split `[7f` after `[` as expected
+                if s.starts_with('[') && char_range.len() > 1 {
+                    acc.push((char_range.start)..(char_range.start + 1));
+                    acc.push((char_range.start + 1)..(char_range.end));
+                    continue;
+                }
+                let tick = config.is_apostrophe(s);
+                let belongs_to_genitive_s = match s {
+                    "(" | ")" | r#"""# => false,
+                    _ => true,
+                };
+                stage = if belongs_to_genitive_s {
+                    match stage {
+                        Stage::Empty if !tick && !space => {
+                            backlog.push(char_range);
+                            Stage::Pre
+                        }
+                        Stage::Pre if !tick && !space => {
+                            backlog.push(char_range);
+                            Stage::Pre
+                        }
+                        Stage::Pre if tick && !space => {
+                            backlog.push(char_range);
+                            Stage::Tick
+                        }
+                        Stage::Tick if !tick => {
+                            // combine all in backlog to one
+                            acc.push(backlog.first().unwrap().start..char_range.end);
+                            backlog.clear();
+                            Stage::Empty
+                        }
+                        _stage => {
+                            acc.append(&mut backlog);
+                            acc.push(char_range);
+                            Stage::Empty
+                        }
                     }
+                } else {
+                    acc.append(&mut backlog);
+                    acc.push(char_range);
+                    Stage::Empty
+                };
+            }
+            acc.append(&mut backlog);
+            acc.into_iter()
+        })
+        .flat_map(elide)
+}
+
+/// Kinds of tokens which are not prose, and hence never looked up in a
+/// dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NonProse {
+    Url,
+    Email,
+    Path,
+    Version,
+    Hash,
+    /// Matched by one of the configured `skip_patterns`.
+    Custom,
+}
+
+lazy_static! {
+    static ref NON_PROSE: Vec<(NonProse, Regex)> = [
+        (
+            NonProse::Url,
+            r"\b[A-Za-z][A-Za-z0-9+.-]*://[^\s<>()\[\]]+|\bwww\.[^\s<>()\[\]]+"
+        ),
+        (NonProse::Email, r"[\w.+-]+@[\w-]+(?:\.[\w-]+)+"),
+        (
+            NonProse::Path,
+            r"(?:~|\.{1,2})?(?:/[\w.-]+){2,}/?|\b(?:[\w.-]+/)+[\w-]+\.[A-Za-z0-9]+\b|\b[A-Za-z]:\\\S+"
+        ),
+        (
+            NonProse::Version,
+            r"\bv?\d+(?:\.\d+){1,3}(?:-[0-9A-Za-z.]+)?(?:\+[0-9A-Za-z.]+)?\b"
+        ),
+        (
+            NonProse::Hash,
+            r"\b0x[0-9A-Fa-f]+\b|\b(?=[0-9a-f]*[0-9])(?=[0-9a-f]*[a-f])[0-9a-f]{7,64}\b"
+        ),
+    ]
+    .into_iter()
+    .map(|(kind, pattern)| (kind, Regex::new(pattern).expect("Builtin regex is valid. qed")))
+    .collect();
+}
+
+/// The character ranges of a text which are not prose, i.e. URLs or file
+/// paths, shared by all dictionary based checkers.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NonProseFilter {
+    ranges: Vec<(NonProse, Range)>,
+}
+
+impl NonProseFilter {
+    /// Match the builtin patterns, unless disabled, and the configured
+    /// `skip_patterns` against `text`.
+    pub(crate) fn new(text: &str, config: &TokenizerConfig) -> Self {
+        let builtin = NON_PROSE
+            .iter()
+            .filter(|_| config.skip_non_prose)
+            .map(|(kind, regex)| (*kind, regex));
+        let custom = config
+            .skip_patterns
+            .iter()
+            .map(|regex| (NonProse::Custom, regex.as_ref()));
+        let mut ranges = Vec::new();
+        for (kind, regex) in builtin.chain(custom) {
+            for m in regex.find_iter(text).filter_map(|m| m.ok()) {
+                if m.start() == m.end() {
+                    continue;
                 }
-            } else {
-                acc.append(&mut backlog);
-                acc.push(char_range);
-                Stage::Empty
-            };
+                let start = text[..m.start()].chars().count();
+                let len = m.as_str().chars().count();
+                ranges.push((kind, start..(start + len)));
+            }
         }
-        acc.append(&mut backlog);
-        acc.into_iter()
-    })
+        Self { ranges }
+    }
+
+    /// The kind of non prose the token at character `range` is part of, if
+    /// any.
+    pub(crate) fn classify(&self, range: &Range) -> Option<NonProse> {
+        let kind = self
+            .ranges
+            .iter()
+            .find(|(_kind, r)| r.start <= range.start && range.end <= r.end)
+            .map(|(kind, _r)| *kind)?;
+        log::trace!(target: "quirks", "Skipping token at {range:?}, part of {kind:?}");
+        Some(kind)
+    }
+}
+
+/// The character offsets of the first word of each sentence of `text`, in
+/// ascending order.
+pub(crate) fn sentence_starts(tokenizer: &Arc<Tokenizer>, text: &str) -> Vec<usize> {
+    tokenizer
+        .pipe(text)
+        .filter_map(|sentence| {
+            sentence
+                .into_iter()
+                .filter(|token| !token.span().char().is_empty())
+                .find(|token| token.word().as_str().chars().any(char::is_alphanumeric))
+                .map(|token| token.span().char().start)
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::config::WrappedRegex;
     use crate::sub_chars;
 
     use super::*;
@@ -250,6 +397,51 @@ mod tests {
             });
     }
 
+    #[test]
+    fn non_prose() {
+        const TEXT: &str = "See https://ahoi.io/x or mail to bernhard@ahoi.io about \
+src/lib.rs and /usr/share/dict at v1.2.3-rc.1 since 1a2b3c4d, ./look/here.";
+        let config = TokenizerConfig::default();
+        let filter = NonProseFilter::new(TEXT, &config);
+        let classify = |word: &str| {
+            let start = TEXT[..TEXT.find(word).unwrap()].chars().count();
+            filter.classify(&(start..(start + word.chars().count())))
+        };
+        assert_eq!(classify("See"), None);
+        assert_eq!(classify("https"), Some(NonProse::Url));
+        assert_eq!(classify("ahoi"), Some(NonProse::Url));
+        assert_eq!(classify("bernhard"), Some(NonProse::Email));
+        assert_eq!(classify("src"), Some(NonProse::Path));
+        assert_eq!(classify("share"), Some(NonProse::Path));
+        assert_eq!(classify("v1"), Some(NonProse::Version));
+        assert_eq!(classify("1a2b3c4d"), Some(NonProse::Hash));
+        assert_eq!(classify("look"), Some(NonProse::Path));
+        assert_eq!(classify("about"), None);
+        assert_eq!(classify("since"), None);
+
+        let config = TokenizerConfig {
+            skip_non_prose: false,
+            skip_patterns: vec![WrappedRegex(Regex::new("about").unwrap())],
+            ..Default::default()
+        };
+        let filter = NonProseFilter::new(TEXT, &config);
+        let start = TEXT.find("about").unwrap();
+        assert_eq!(
+            filter.classify(&(start..(start + 5))),
+            Some(NonProse::Custom)
+        );
+        assert_eq!(filter.classify(&(4..9)), None);
+    }
+
+    #[test]
+    fn sentence_starts_of_text() {
+        let tok = tokenizer::<PathBuf>(None).unwrap();
+        assert_eq!(
+            sentence_starts(&tok, "It works. Does Tokio work? (Yes) it does."),
+            vec![0, 10, 28]
+        );
+    }
+
     #[test]
     fn tokenize_for_abbrev_short() {
         let tok = tokenizer::<PathBuf>(None).unwrap();
@@ -361,6 +553,21 @@ mod tests {
             });
     }
 
+    #[test]
+    fn tokenize_elision() {
+        let tok = tokenizer::<PathBuf>(None).unwrap();
+        let config = TokenizerConfig {
+            apostrophes: "'’".to_owned(),
+            elision: true,
+            ..Default::default()
+        };
+        let text = "l'homme d’État aujourd'hui";
+        let words = Vec::from_iter(
+            apply_tokenizer_with(&tok, text, &config).map(|range| sub_chars(text, range)),
+        );
+        assert_eq!(words, vec!["l'", "homme", "d’", "État", "aujourd'hui"]);
+    }
+
     #[test]
     fn tokenize_square_bracket_foo_square_bracket() {
         let text = r#"[1337]"#;
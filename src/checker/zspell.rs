@@ -4,17 +4,16 @@
 //! the individual tokens against the dictionary using the defined affixes. Can
 //! handle multiple dictionaries.
 
-use super::{apply_tokenizer, Checker, Detector, Suggestion};
+use super::{apply_tokenizer_with, sentence_starts, Checker, Detector, NonProseFilter, Suggestion};
 
 use crate::checker::dictaffix::DicAff;
-use crate::config::WrappedRegex;
+use crate::config::{CodeLike, TokenizerConfig, WrappedRegex};
 use crate::documentation::{CheckableChunk, ContentOrigin, PlainOverlay};
 use crate::util::sub_chars;
 use crate::Range;
 
 use nlprule::Tokenizer;
 
-use std::path::PathBuf;
 use std::sync::Arc;
 
 use doc_chunks::Ignores;
@@ -22,7 +21,9 @@ use doc_chunks::Ignores;
 use crate::errors::*;
 
 use super::quirks::{
-    replacements_contain_dashed, replacements_contain_dashless, transform, Transformed,
+    is_capitalized_mid_sentence, is_compound_of_words, rank_replacements,
+    replacements_contain_dashed, replacements_contain_dashless, transform, CodeLikeFilter,
+    Transformed,
 };
 
 use super::hunspell::consists_of_vulgar_fractions_or_emojis;
@@ -35,6 +36,11 @@ pub struct ZetCheckerInner {
     allow_dashed: bool,
     allow_emojis: bool,
     check_footnote_references: bool,
+    code_like: CodeLike,
+    split_compounds: bool,
+    ignore_capitalized: bool,
+    tokenizer: TokenizerConfig,
+    max_replacements: Option<usize>,
     ignorelist: String,
 }
 
@@ -47,6 +53,9 @@ impl ZetCheckerInner {
             allow_dashed,
             allow_emojis,
             check_footnote_references,
+            code_like,
+            split_compounds,
+            ignore_capitalized,
         ) = {
             let quirks = &config.quirks;
             (
@@ -55,6 +64,9 @@ impl ZetCheckerInner {
                 quirks.allow_dashed(),
                 quirks.allow_emojis(),
                 quirks.check_footnote_references(),
+                quirks.code_like().clone(),
+                quirks.split_compounds(),
+                quirks.ignore_capitalized(),
             )
         };
         // FIXME rename the config option
@@ -85,6 +97,11 @@ impl ZetCheckerInner {
             allow_dashed,
             allow_emojis,
             check_footnote_references,
+            code_like,
+            split_compounds,
+            ignore_capitalized,
+            tokenizer: config.tokenizer.clone(),
+            max_replacements: config.max_replacements,
             ignorelist,
         })
     }
@@ -102,7 +119,7 @@ impl std::ops::Deref for ZetChecker {
 
 impl ZetChecker {
     pub fn new(config: &<ZetChecker as Checker>::Config) -> Result<Self> {
-        let tokenizer = super::tokenizer::<&PathBuf>(None)?;
+        let tokenizer = super::tokenizer(config.tokenizer.override_tokenizer.as_ref())?;
         let inner = ZetCheckerInner::new(config)?;
         let hunspell = Arc::new(inner);
         Ok(ZetChecker(hunspell, tokenizer))
@@ -125,6 +142,7 @@ impl Checker for ZetChecker {
         'a: 's,
     {
         let mut acc = Vec::with_capacity(chunks.len());
+        let code_like = CodeLikeFilter::new(&self.0.code_like, origin);
 
         for chunk in chunks {
             let plain = chunk.erase_cmark(&Ignores {
@@ -132,8 +150,15 @@ impl Checker for ZetChecker {
             });
             log::trace!("{plain:?}");
             let txt = plain.as_str();
+            let chars = Vec::from_iter(txt.chars());
+            let non_prose = NonProseFilter::new(txt, &self.tokenizer);
+            let sentence_starts = if self.ignore_capitalized {
+                sentence_starts(&self.1, txt)
+            } else {
+                Vec::new()
+            };
 
-            'tokenization: for range in apply_tokenizer(&self.1, txt) {
+            'tokenization: for range in apply_tokenizer_with(&self.1, txt, &self.tokenizer) {
                 let word = sub_chars(txt, range.clone());
                 if range.len() == 1
                     && word
@@ -144,6 +169,22 @@ impl Checker for ZetChecker {
                 {
                     continue 'tokenization;
                 }
+                if non_prose.classify(&range).is_some() {
+                    continue 'tokenization;
+                }
+                if code_like.is_code_like(&chars, range.clone()) {
+                    continue 'tokenization;
+                }
+                if self.ignore_capitalized
+                    && is_capitalized_mid_sentence(&word, range.start, &sentence_starts)
+                {
+                    continue 'tokenization;
+                }
+                if self.split_compounds
+                    && is_compound_of_words(&word, |part| self.zspell.check_word(part))
+                {
+                    continue 'tokenization;
+                }
                 if self.transform_regex.is_empty() {
                     obtain_suggestions(
                         &plain,
@@ -155,6 +196,7 @@ impl Checker for ZetChecker {
                         self.allow_concatenated,
                         self.allow_dashed,
                         self.allow_emojis,
+                        self.max_replacements,
                         &mut acc,
                     )
                 } else {
@@ -171,6 +213,7 @@ impl Checker for ZetChecker {
                                     self.allow_concatenated,
                                     self.allow_dashed,
                                     self.allow_emojis,
+                                    self.max_replacements,
                                     &mut acc,
                                 );
                             }
@@ -186,6 +229,7 @@ impl Checker for ZetChecker {
                                 self.allow_concatenated,
                                 self.allow_dashed,
                                 self.allow_emojis,
+                                self.max_replacements,
                                 &mut acc,
                             );
                         }
@@ -208,6 +252,7 @@ fn obtain_suggestions<'s>(
     allow_concatenated: bool,
     allow_dashed: bool,
     allow_emojis: bool,
+    max_replacements: Option<usize>,
     acc: &mut Vec<Suggestion<'s>>,
 ) {
     log::trace!("Checking {word} in {range:?}..");
@@ -235,9 +280,13 @@ fn obtain_suggestions<'s>(
                 log::trace!(target: "quirks", "Found dashed word in replacement suggestions, treating {word} as ok");
                 return;
             }
+            let replacements = rank_replacements(&word, replacements, max_replacements);
             for (range, span) in plain.find_spans(range.clone()) {
                 acc.push(Suggestion {
                     detector: Detector::ZSpell,
+                    agreeing: Vec::new(),
+                    rule: None,
+                    category: None,
                     range,
                     span,
                     origin: origin.clone(),
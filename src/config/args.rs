@@ -7,9 +7,11 @@ use itertools::Itertools;
 use serde::Deserialize;
 use std::str::FromStr;
 
-use crate::Action;
+use crate::action::backup::Backup;
+use crate::action::ci::CiProvider;
+use crate::{Action, Detector, ReportVerbosity};
 
-use super::Config;
+use super::{Config, Lang5};
 
 use clap_complete::Shell;
 
@@ -31,6 +33,13 @@ pub enum CheckerType {
     Spellbook,
     NlpRules,
     Reflow,
+    LineLength,
+    Identifiers,
+    Redundancy,
+    Headings,
+    Anchors,
+    IntraDocLinks,
+    External,
 }
 
 impl FromStr for CheckerType {
@@ -43,6 +52,13 @@ impl FromStr for CheckerType {
             "spellbook" | "book" => Self::Spellbook,
             "hunspell" => Self::Hunspell,
             "reflow" => Self::Reflow,
+            "linelength" | "line-length" => Self::LineLength,
+            "identifiers" | "idents" => Self::Identifiers,
+            "redundancy" | "repeated" => Self::Redundancy,
+            "headings" => Self::Headings,
+            "anchors" => Self::Anchors,
+            "intradoclinks" | "intra-doc-links" => Self::IntraDocLinks,
+            "external" => Self::External,
             _other => return Err(UnknownCheckerTypeVariant(s)),
         })
     }
@@ -83,10 +99,348 @@ impl FromStr for MultipleCheckerTypes {
     }
 }
 
+/// Checkers enabled if none are selected explicitly.
+pub const DEFAULT_CHECKERS: &[CheckerType] = &[CheckerType::Hunspell];
+
+/// Selection of checkers, i.e. `hunspell,-nlprules,+headings`.
+///
+/// Checkers without a sign replace the default set, `+` adds to and `-`
+/// removes from it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CheckerSelection {
+    only: Vec<CheckerType>,
+    add: Vec<CheckerType>,
+    remove: Vec<CheckerType>,
+}
+
+impl CheckerSelection {
+    /// Resolve the selection against the `default` set of checkers.
+    pub fn resolve(&self, default: &[CheckerType]) -> Vec<CheckerType> {
+        let base = if self.only.is_empty() {
+            default
+        } else {
+            self.only.as_slice()
+        };
+        Vec::from_iter(
+            base.iter()
+                .chain(self.add.iter())
+                .filter(|checker| !self.remove.contains(checker))
+                .unique()
+                .copied(),
+        )
+    }
+}
+
+impl FromStr for CheckerSelection {
+    type Err = UnknownCheckerTypeVariant;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut selection = Self {
+            only: Vec::new(),
+            add: Vec::new(),
+            remove: Vec::new(),
+        };
+        for item in s.split(',').map(str::trim) {
+            if let Some(item) = item.strip_prefix('-') {
+                selection.remove.push(item.parse()?);
+            } else if let Some(item) = item.strip_prefix('+') {
+                selection.add.push(item.parse()?);
+            } else {
+                selection.only.push(item.parse()?);
+            }
+        }
+        Ok(selection)
+    }
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("Unknown checker type variant: {0}")]
 pub struct UnknownCheckerTypeVariant(String);
 
+/// Exit code if the given detectors found mistakes, parsed from either a plain
+/// `<code>` or `<category>=<code>`.
+///
+/// A category is either `spelling`, `grammar` or the namespace of a detector,
+/// i.e. `reflow`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExitCodeRule {
+    /// Detectors the code applies to, all of them if empty.
+    pub detectors: Vec<Detector>,
+    /// The exit code.
+    pub code: u8,
+}
+
+impl FromStr for ExitCodeRule {
+    type Err = InvalidExitCodeRule;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (category, code) = match s.split_once('=') {
+            Some((category, code)) => (Some(category.trim().to_lowercase()), code),
+            None => (None, s),
+        };
+        let code = code
+            .trim()
+            .parse::<u8>()
+            .map_err(|_| InvalidExitCodeRule::Code(code.to_owned()))?;
+        let detectors = match category.as_deref() {
+            None => Vec::new(),
+            Some("spelling") => vec![Detector::Hunspell, Detector::ZSpell, Detector::Spellbook],
+            Some("grammar") => vec![Detector::NlpRules, Detector::Redundancy],
+            Some(namespace) => Detector::ALL
+                .iter()
+                .copied()
+                .filter(|detector| detector.namespace() == namespace)
+                .collect(),
+        };
+        if detectors.is_empty() {
+            if let Some(category) = category {
+                return Err(InvalidExitCodeRule::Category(category));
+            }
+        }
+        Ok(Self { detectors, code })
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum InvalidExitCodeRule {
+    #[error("Unknown detector category: {0}")]
+    Category(String),
+    #[error("Exit code must be within 0..=255: {0}")]
+    Code(String),
+}
+
+/// Exit codes per detector category, as given by repeated `--code` flags.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExitCodes(pub Vec<ExitCodeRule>);
+
+impl From<u8> for ExitCodes {
+    fn from(code: u8) -> Self {
+        Self(vec![ExitCodeRule {
+            detectors: Vec::new(),
+            code,
+        }])
+    }
+}
+
+impl ExitCodes {
+    /// The code if mistakes were found, regardless by which detector. The last
+    /// plain code wins, `0` if there is none.
+    pub fn fallback(&self) -> u8 {
+        self.0
+            .iter()
+            .rev()
+            .find(|rule| rule.detectors.is_empty())
+            .map(|rule| rule.code)
+            .unwrap_or_default()
+    }
+
+    /// The highest code of all categories with mistakes found, so the order of
+    /// the flags does not matter, otherwise the `fallback`.
+    pub fn resolve(&self, per_detector: &indexmap::IndexMap<Detector, usize>) -> u8 {
+        let found =
+            |detector: &Detector| per_detector.get(detector).copied().unwrap_or_default() > 0;
+        self.0
+            .iter()
+            .filter(|rule| rule.detectors.iter().any(found))
+            .map(|rule| rule.code)
+            .max()
+            .unwrap_or_else(|| self.fallback())
+    }
+}
+
+/// Format in which check results are printed to `stdout`.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Colored, `rustc`-like output for humans.
+    #[default]
+    Human,
+    /// GitHub Actions workflow commands, rendered as inline annotations.
+    Github,
+    /// JSON diagnostics like `rustc` emits, one per line, which `rustfix` can
+    /// apply.
+    Rustfix,
+}
+
+impl OutputFormat {
+    /// Converts the output format to its static str representation.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Human => "human",
+            Self::Github => "github",
+            Self::Rustfix => "rustfix",
+        }
+    }
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = UnknownOutputFormatVariant;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        Ok(match s.as_str() {
+            "human" => Self::Human,
+            "github" | "gh" => Self::Github,
+            "rustfix" | "json" => Self::Rustfix,
+            _other => return Err(UnknownOutputFormatVariant(s)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Unknown output format variant: {0}")]
+pub struct UnknownOutputFormatVariant(String);
+
+/// Format of the log messages printed to `stderr`.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Free form lines for humans.
+    #[default]
+    Text,
+    /// A JSON object per line with `timestamp`, `level`, `module` and
+    /// `message`, for log processors.
+    Json,
+}
+
+impl LogFormat {
+    /// Converts the log format to its static str representation.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Json => "json",
+        }
+    }
+}
+
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = UnknownLogFormatVariant;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        Ok(match s.as_str() {
+            "text" => Self::Text,
+            "json" => Self::Json,
+            _other => return Err(UnknownLogFormatVariant(s)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Unknown log format variant: {0}")]
+pub struct UnknownLogFormatVariant(String);
+
+/// Kind of a standalone report of the findings.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ReportKind {
+    /// A self contained HTML page.
+    Html,
+}
+
+/// A report of the findings written to a file, given as `<kind>=<path>`, i.e.
+/// `html=target/spellcheck.html`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// Format of the report.
+    pub kind: ReportKind,
+    /// File the report is written to.
+    pub path: PathBuf,
+}
+
+impl FromStr for Report {
+    type Err = InvalidReport;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, path) = s
+            .split_once('=')
+            .ok_or_else(|| InvalidReport::Syntax(s.to_owned()))?;
+        let kind = match kind.trim().to_lowercase().as_str() {
+            "html" => ReportKind::Html,
+            other => return Err(InvalidReport::Kind(other.to_owned())),
+        };
+        if path.is_empty() {
+            return Err(InvalidReport::Syntax(s.to_owned()));
+        }
+        Ok(Self {
+            kind,
+            path: PathBuf::from(path),
+        })
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum InvalidReport {
+    #[error("Expected `<kind>=<path>`: {0}")]
+    Syntax(String),
+    #[error("Unknown report kind: {0}")]
+    Kind(String),
+}
+
+/// Grouping of the findings per file in the log of a CI provider.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum CiAnnotations {
+    /// Detect the CI provider from the environment.
+    #[default]
+    Auto,
+    /// Never emit grouping markers.
+    Off,
+    /// GitHub Actions `::group::` workflow commands.
+    Github,
+    /// GitLab CI collapsible sections.
+    Gitlab,
+}
+
+impl CiAnnotations {
+    /// Converts the variant to its static str representation.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Off => "off",
+            Self::Github => "github",
+            Self::Gitlab => "gitlab",
+        }
+    }
+
+    /// Resolve the CI provider to emit grouping markers for, if any.
+    pub fn provider(&self) -> Option<CiProvider> {
+        match self {
+            Self::Auto => CiProvider::detect(),
+            Self::Off => None,
+            Self::Github => Some(CiProvider::Github),
+            Self::Gitlab => Some(CiProvider::Gitlab),
+        }
+    }
+}
+
+impl std::fmt::Display for CiAnnotations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CiAnnotations {
+    type Err = UnknownCiAnnotationsVariant;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        Ok(match s.as_str() {
+            "auto" => Self::Auto,
+            "off" | "none" => Self::Off,
+            "github" | "gh" => Self::Github,
+            "gitlab" => Self::Gitlab,
+            _other => return Err(UnknownCiAnnotationsVariant(s)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Unknown CI annotations variant: {0}")]
+pub struct UnknownCiAnnotationsVariant(String);
+
 #[derive(clap::Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 #[clap(rename_all = "kebab-case")]
@@ -96,9 +450,17 @@ pub struct Args {
     /// Provide a configuration.
     pub cfg: Option<PathBuf>,
 
+    #[clap(long, global(true))]
+    /// Ignore the per user configuration file, i.e. for hermetic CI runs.
+    pub no_user_config: bool,
+
     #[clap(flatten)]
     pub verbosity: clap_verbosity_flag::Verbosity,
 
+    #[clap(long, global(true), default_value_t)]
+    /// Format of the log messages, either `text` or `json`.
+    pub log_format: LogFormat,
+
     // is required, but we use `subcommand_negates_reqs`, so it's not
     // when a command exists
     #[clap(flatten)]
@@ -109,6 +471,11 @@ pub struct Args {
     /// Alt for `cargo spellcheck fix` [deprecated].
     pub fix: bool,
 
+    #[clap(long, value_name = "ID")]
+    /// Explain the findings with the given identifier, i.e.
+    /// `hunspell/unknown-word`, and exit.
+    pub explain: Option<String>,
+
     #[clap(subcommand)]
     /// Available sub-commands.
     pub command: Option<Sub>,
@@ -124,8 +491,10 @@ pub struct Common {
 
     // with fallback from config, so it has to be tri-state
     #[clap(long)]
-    /// Execute the given subset of checkers.
-    pub checkers: Option<MultipleCheckerTypes>,
+    /// Execute the given subset of checkers. Prefix a checker with `+` to add
+    /// it to or `-` to remove it from the default set, i.e.
+    /// `--checkers=-hunspell,+nlprules`.
+    pub checkers: Option<CheckerSelection>,
 
     #[clap(short, long)]
     /// Do not check the referenced key `readme=` or default `README.md`.
@@ -135,13 +504,116 @@ pub struct Common {
     /// Also check developer comments besides documentation comments.
     pub dev_comments: bool,
 
+    #[clap(long)]
+    /// Check developer comments only of files matching the glob, relative to
+    /// the current directory, i.e. `src/lowlevel/**`. Can be repeated.
+    pub dev_comments_for: Vec<String>,
+
+    #[clap(long)]
+    /// Also check the messages of `panic!`, `assert!` and `log` macros.
+    pub check_strings: bool,
+
+    #[clap(long)]
+    /// Also check the help texts of `clap` derived command line interfaces,
+    /// given as `about = ".."` or `help = ".."` of `#[command(..)]`,
+    /// `#[arg(..)]` and alike attributes.
+    pub check_help: bool,
+
+    #[clap(long)]
+    /// Only follow modules gated by `#[cfg(..)]` if the predicate holds for
+    /// the given cfgs, i.e. `unix` or `feature="serde"`. Can be repeated, all
+    /// modules are followed if none is given.
+    pub enable_cfg: Vec<String>,
+
+    #[clap(long)]
+    /// Do not look up dictionaries in the OS specific search directories, as
+    /// if `skip_os_lookups` was set for all dictionary based checkers.
+    pub no_os_dictionaries: bool,
+
+    #[clap(long)]
+    /// Additional dictionary for all dictionary based checkers, relative to
+    /// the current directory. Can be repeated.
+    pub extra_dict: Vec<PathBuf>,
+
+    #[clap(long)]
+    /// Only check and report files matching the glob, relative to the current
+    /// directory, i.e. `src/**`. Applied after the traversal, so modules are
+    /// still followed through files which are not included. Can be repeated.
+    pub include: Vec<String>,
+
+    #[clap(long)]
+    /// Do not check and report files matching the glob, relative to the
+    /// current directory. Takes precedence over `--include`. Can be repeated.
+    pub exclude: Vec<String>,
+
+    #[clap(long)]
+    /// Also check generated files, which are skipped by default, same as
+    /// unsetting `skip_generated` in the configuration.
+    pub include_generated: bool,
+
+    #[clap(long)]
+    /// Also check files exceeding `max_file_size` and chunks exceeding
+    /// `max_chunk_chars`, which are otherwise skipped.
+    pub no_size_limits: bool,
+
     #[clap(short, long)]
     /// The number of worker threads to spawn for the actual processing text.
+    /// When checking, as many threads extract the documentation of the files
+    /// ahead of the checking.
     pub jobs: Option<usize>,
 
-    #[clap(short = 'm', long, default_value_t = 0_u8)]
-    /// Return code of the application if spelling mistakes were found.
-    pub code: u8,
+    #[clap(short = 'm', long)]
+    /// Return code of the application if mistakes were found. Can be given
+    /// per detector category as `spelling=2` or `grammar=3` and repeated, the
+    /// highest code of the categories with mistakes is returned.
+    pub code: Vec<ExitCodeRule>,
+
+    #[clap(long, default_value_t)]
+    /// Output format of the found mistakes, either `human`, `github` or
+    /// `rustfix`.
+    pub format: OutputFormat,
+
+    #[clap(long, default_value_t)]
+    /// Group the findings per file in CI logs, either `auto`, `off`, `github`
+    /// or `gitlab`.
+    pub ci_annotations: CiAnnotations,
+
+    #[clap(long)]
+    /// Additionally write a report of the findings to a file, given as
+    /// `<kind>=<path>`, i.e. `html=target/spellcheck.html`. Can be repeated.
+    pub report: Vec<Report>,
+
+    #[clap(long)]
+    /// Also show the rule identifier of each finding and where the
+    /// configuration of its detector came from. Ignored with `--quiet`, which
+    /// only prints the summary.
+    pub verbose_suggestions: bool,
+
+    #[clap(long)]
+    /// Do not acquire the workspace lock before modifying files.
+    pub no_lock: bool,
+
+    #[clap(long)]
+    /// Do not display a progress bar while checking, which is otherwise shown
+    /// if `stderr` is a terminal.
+    pub no_progress: bool,
+
+    #[clap(long)]
+    /// Do not report the mistakes recorded in this baseline file, see the
+    /// `baseline` sub-command. Overrides `baseline` of the configuration.
+    pub baseline: Option<PathBuf>,
+
+    #[clap(long)]
+    /// Stop checking as soon as this many mistakes are found, `0` stops at the
+    /// first one. Any mistake fails with the code given by `--code`, at least
+    /// `1`.
+    pub max_issues: Option<usize>,
+
+    #[clap(long)]
+    /// Read the files to check from `stdin`, one per line, in addition to the
+    /// given paths. Nothing is checked if none are given, i.e. in a pre-commit
+    /// hook without any staged files.
+    pub paths_from_stdin: bool,
 
     /// A list of files and directories to check. See `--recursive`.
     pub paths: Vec<PathBuf>,
@@ -153,18 +625,84 @@ pub enum Sub {
     /// Only show check errors, but do not request user input.
     // `cargo spellcheck` is short for checking.
     Check {
+        #[clap(long)]
+        /// Keep running and check again whenever a checked file or one of
+        /// the extra dictionaries changes, changed dictionaries are reloaded
+        /// without a restart.
+        watch: bool,
+
         #[clap(flatten)]
         common: Common,
     },
 
     /// Interactively choose from checker provided suggestions.
     Fix {
+        #[clap(long, alias = "fix-safe")]
+        /// Apply unambiguous corrections without asking, a sole replacement
+        /// one edit away with the same case pattern, i.e. `speling` to
+        /// `spelling`.
+        /// Only the remaining ones are presented interactively.
+        safe: bool,
+
+        #[clap(long, require_equals = true, value_name = "DIR")]
+        /// Save the original content of each modified file before writing,
+        /// as `<file>.orig` beside it or, if given, mirrored into `DIR`.
+        backup: Option<Option<PathBuf>>,
+
+        #[clap(long)]
+        /// Verify that the code of each modified file is untouched before
+        /// writing, refuse to write it otherwise.
+        verify: bool,
+
         #[clap(flatten)]
         common: Common,
     },
 
     /// Reflow doc comments, so they adhere to a given maximum column width.
     Reflow {
+        #[clap(long)]
+        /// Do not write any changes, print the diff of what would change
+        /// instead and fail if there is any, like `cargo fmt --check`.
+        check: bool,
+
+        #[clap(
+            long,
+            require_equals = true,
+            value_name = "DIR",
+            conflicts_with = "check"
+        )]
+        /// Save the original content of each modified file before writing,
+        /// as `<file>.orig` beside it or, if given, mirrored into `DIR`.
+        backup: Option<Option<PathBuf>>,
+
+        #[clap(long, conflicts_with = "check")]
+        /// Verify that the code of each modified file is untouched before
+        /// writing, refuse to write it otherwise.
+        verify: bool,
+
+        #[clap(flatten)]
+        common: Common,
+    },
+
+    /// Record all current mistakes, so subsequent checks with `--baseline`
+    /// only report new ones.
+    Baseline {
+        #[clap(long, default_value = ".spellcheck-baseline.json")]
+        /// Path of the baseline file to write.
+        write: PathBuf,
+
+        #[clap(flatten)]
+        common: Common,
+    },
+
+    /// Print the words unknown to the dictionaries, the most frequent ones
+    /// first, to bootstrap the allowlist of a project.
+    Words {
+        #[clap(long, value_name = "FILE")]
+        /// Add the words to the hunspell dictionary `FILE` instead of printing
+        /// them, it is created if it does not exist yet.
+        write: Option<PathBuf>,
+
         #[clap(flatten)]
         common: Common,
     },
@@ -183,6 +721,11 @@ pub enum Sub {
         /// Write to `stdout`.
         stdout: bool,
 
+        #[clap(long, conflicts_with_all = ["user", "stdout"])]
+        /// Check the configuration in use for unknown keys, unresolvable
+        /// dictionaries and checkers failing to initialize.
+        validate: bool,
+
         #[clap(long)]
         // Deprecated alias, will be removed in the future.
         #[clap(alias = "checkers")]
@@ -205,6 +748,36 @@ pub enum Sub {
         paths: Vec<PathBuf>,
     },
 
+    /// Dictionary curation helpers.
+    Dict {
+        #[clap(subcommand)]
+        command: DictSub,
+    },
+
+    /// Download a hunspell dictionary and affix file pair into the user cache
+    /// directory and register it in the configuration.
+    FetchDictionaries {
+        #[clap(long, default_value = "en_US")]
+        /// Language and country code of the dictionary, i.e. `en_US`.
+        lang: Lang5,
+
+        #[clap(long, default_value = crate::action::fetch::DEFAULT_DICTIONARY_MIRROR)]
+        /// Url template of the mirror, `{language}`, `{lang}` and `{ext}` are
+        /// replaced by i.e. `en`, `en_US` and `dic` or `aff` respectively.
+        mirror: String,
+
+        #[clap(long)]
+        /// Only download, do not register the dictionary in the user
+        /// configuration or the one given by `--cfg`.
+        no_register: bool,
+    },
+
+    /// Git hook integration.
+    Hook {
+        #[clap(subcommand)]
+        command: HookSub,
+    },
+
     /// Print completions.
     Completions {
         #[clap(long, env="SHELL", value_parser = load_shell_name)]
@@ -213,6 +786,58 @@ pub enum Sub {
     },
 }
 
+#[derive(Debug, PartialEq, Eq, clap::Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum DictSub {
+    /// Report how many of the current findings would disappear, if the given
+    /// words were added to the dictionary.
+    Simulate {
+        #[clap(short, long)]
+        /// Recurse down directories and module declaration derived paths.
+        recursive: bool,
+
+        #[clap(short, long)]
+        /// Do not check the referenced key `readme=` or default `README.md`.
+        skip_readme: bool,
+
+        #[clap(short, long)]
+        /// Also check developer comments besides documentation comments.
+        dev_comments: bool,
+
+        #[clap(required = true)]
+        /// Words to hypothetically add to the dictionary.
+        words: Vec<String>,
+
+        #[clap(last = true)]
+        /// A list of files and directories to check, separated by `--`.
+        paths: Vec<PathBuf>,
+    },
+
+    /// Validate hunspell dictionaries, the configured extra dictionaries if
+    /// none are given.
+    Lint {
+        #[clap(long)]
+        /// The affix file the dictionaries are used with, defaults to the
+        /// builtin `en_US` affix file.
+        aff: Option<PathBuf>,
+
+        /// The `.dic` files to validate.
+        dictionaries: Vec<PathBuf>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, clap::Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum HookSub {
+    /// Write a git pre-commit hook, which checks the staged files and aborts
+    /// the commit if there are any mistakes.
+    Install {
+        #[clap(short, long)]
+        /// Force overwrite an existing pre-commit hook.
+        force: bool,
+    },
+}
+
 #[derive(thiserror::Error, Debug, Clone)]
 enum ShellErr {
     #[error("Unknown shell: {shell:?}")]
@@ -245,19 +870,67 @@ pub fn generate_completions<G: clap_complete::Generator, W: std::io::Write>(
 }
 
 impl Args {
+    /// Level of detail of the reported findings, `--quiet` reduces the output
+    /// to the summary.
+    pub fn report_verbosity(&self) -> ReportVerbosity {
+        if self.verbosity.is_silent() {
+            ReportVerbosity::Quiet
+        } else if self
+            .common()
+            .map_or(false, |common| common.verbose_suggestions)
+        {
+            ReportVerbosity::Verbose
+        } else {
+            ReportVerbosity::Normal
+        }
+    }
+
     pub fn common(&self) -> Option<&Common> {
         match &self.command {
             Some(
-                Sub::Check { common, .. } | Sub::Fix { common, .. } | Sub::Reflow { common, .. },
+                Sub::Check { common, .. }
+                | Sub::Fix { common, .. }
+                | Sub::Reflow { common, .. }
+                | Sub::Baseline { common, .. }
+                | Sub::Words { common, .. },
             ) => Some(common),
             None => Some(&self.common),
-            Some(Sub::Completions { .. } | Sub::ListFiles { .. } | Sub::Config { .. }) => None,
+            Some(
+                Sub::Completions { .. }
+                | Sub::ListFiles { .. }
+                | Sub::Config { .. }
+                | Sub::Dict { .. }
+                | Sub::FetchDictionaries { .. }
+                | Sub::Hook { .. },
+            ) => None,
         }
     }
 
+    /// Where to save the original content of modified files, if at all.
+    pub fn backup(&self) -> Option<Backup> {
+        match &self.command {
+            Some(Sub::Fix { backup, .. } | Sub::Reflow { backup, .. }) => {
+                backup.as_ref().map(|dir| match dir {
+                    Some(dir) => Backup::Dir(dir.clone()),
+                    None => Backup::Beside,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Verify the modified files before writing them.
+    pub fn verify(&self) -> bool {
+        matches!(
+            self.command,
+            Some(Sub::Fix { verify: true, .. } | Sub::Reflow { verify: true, .. })
+        )
+    }
+
     pub fn checkers(&self) -> Option<Vec<CheckerType>> {
         self.common()
-            .and_then(|common| common.checkers.as_ref().map(|checkers| checkers.0.clone()))
+            .and_then(|common| common.checkers.as_ref())
+            .map(|selection| selection.resolve(DEFAULT_CHECKERS))
     }
 
     pub fn job_count(&self) -> usize {
@@ -269,16 +942,28 @@ impl Args {
         self.verbosity.log_level_filter()
     }
 
+    pub fn log_format(&self) -> LogFormat {
+        self.log_format
+    }
+
     /// Extract the required action.
     pub fn action(&self) -> Action {
         // extract operation mode
         let action = if let Some(sub) = &self.command {
             match sub {
+                Sub::Check { watch: true, .. } => Action::Watch,
                 Sub::Check { .. } => Action::Check,
+                Sub::Fix { safe: true, .. } => Action::FixSafe,
                 Sub::Fix { .. } => Action::Fix,
+                Sub::Reflow { check: true, .. } => Action::ReflowCheck,
                 Sub::Reflow { .. } => Action::Reflow,
+                Sub::Baseline { .. } => Action::Baseline,
                 Sub::ListFiles { .. } => Action::ListFiles,
+                Sub::Words { .. } => unreachable!(),
                 Sub::Config { .. } => unreachable!(),
+                Sub::Dict { .. } => unreachable!(),
+                Sub::FetchDictionaries { .. } => unreachable!(),
+                Sub::Hook { .. } => unreachable!(),
                 Sub::Completions { .. } => unreachable!(),
             }
         } else if self.fix {
@@ -340,7 +1025,8 @@ impl Args {
     }
 
     /// Overrides the enablement status of checkers in the configuration based
-    /// on the checkers enabled by argument, if it is set.
+    /// on the checkers enabled by argument, if it is set. Checkers which are
+    /// enabled but not configured use their default configuration.
     ///
     /// Errors of no checkers are left.
     pub fn checker_selection_override(
@@ -348,26 +1034,82 @@ impl Args {
         config: &mut Config,
     ) -> Result<()> {
         // overwrite checkers
-        if let Some(checkers) = filter_set {
-            #[cfg(feature = "hunspell")]
-            if !checkers.contains(&CheckerType::Hunspell) && config.hunspell.take().is_none() {
-                log::warn!("Hunspell was never configured.")
+        if let Some(filter_set) = filter_set {
+            if filter_set.contains(&CheckerType::Reflow) {
+                log::warn!("Reflow is a separate sub command.")
             }
-            #[cfg(feature = "nlprules")]
-            if !checkers.contains(&CheckerType::NlpRules) && config.nlprules.take().is_none() {
-                log::warn!("Nlprules checker was never configured.")
+            if filter_set
+                .iter()
+                .all(|checker| *checker == CheckerType::Reflow)
+            {
+                bail!("Argument override for checkers disabled all checkers")
             }
 
-            if !checkers.contains(&CheckerType::Reflow) {
-                log::warn!("Reflow is a separate sub command.")
+            if filter_set.contains(&CheckerType::Hunspell) {
+                if config.hunspell.is_none() {
+                    config.hunspell = Some(crate::config::HunspellConfig::default());
+                }
+            } else {
+                config.hunspell = None;
             }
-
-            const EXPECTED_COUNT: usize =
-                1_usize + cfg!(feature = "nlprules") as usize + cfg!(feature = "hunspell") as usize;
-
-            if checkers.iter().unique().count() == EXPECTED_COUNT {
-                bail!("Argument override for checkers disabled all checkers")
+            if filter_set.contains(&CheckerType::ZSpell) {
+                if config.zet.is_none() {
+                    config.zet = Some(crate::config::ZetConfig::default());
+                }
+            } else {
+                config.zet = None;
+            }
+            if filter_set.contains(&CheckerType::Spellbook) {
+                if config.spellbook.is_none() {
+                    config.spellbook = Some(crate::config::SpellbookConfig::default());
+                }
+            } else {
+                config.spellbook = None;
+            }
+            if filter_set.contains(&CheckerType::NlpRules) {
+                if config.nlprules.is_none() {
+                    config.nlprules = Some(crate::config::NlpRulesConfig::default());
+                }
+            } else {
+                config.nlprules = None;
             }
+            if filter_set.contains(&CheckerType::Identifiers) {
+                if config.identifiers.is_none() {
+                    config.identifiers = Some(crate::config::IdentifiersConfig::default());
+                }
+            } else {
+                config.identifiers = None;
+            }
+            if filter_set.contains(&CheckerType::Redundancy) {
+                if config.redundancy.is_none() {
+                    config.redundancy = Some(crate::config::RedundancyConfig::default());
+                }
+            } else {
+                config.redundancy = None;
+            }
+            if filter_set.contains(&CheckerType::Headings) {
+                if config.headings.is_none() {
+                    config.headings = Some(crate::config::HeadingsConfig::default());
+                }
+            } else {
+                config.headings = None;
+            }
+            // enabled by configuration as well, like the missing docs check
+            if filter_set.contains(&CheckerType::LineLength) {
+                config.line_length = true;
+            }
+            if filter_set.contains(&CheckerType::Anchors) {
+                config.anchors = true;
+            }
+            if filter_set.contains(&CheckerType::IntraDocLinks) {
+                config.intra_doc_links = true;
+            }
+            // external tools run commands of the configuration, so they are
+            // only spawned if selected, there is no default one to enable
+            if !filter_set.contains(&CheckerType::External) {
+                config.checker.external.clear();
+            }
+            // reflow is a different subcommand, not relevant
         }
         Ok(())
     }
@@ -382,32 +1124,35 @@ impl Args {
     /// 1. explicitly specified cli flag, error if it does not exist or parse
     /// 2. `Cargo.toml` metadata (unimplemented), error if it does not exist or parse
     /// 3. find a `Cargo.toml` and try to find `.config/spellcheck.toml` error if it does not parse
-    /// 4. Fallback to per-user config, error if it does not parse
+    /// 4. Fallback to per-user config unless `--no-user-config` is given, error if it does not parse
     /// 5. Default config, error if it does not parse
     ///
     // TODO split the IO operations and lookup dirs.
     fn load_config_inner(&self) -> Result<(Config, Option<PathBuf>)> {
-        log::debug!("Attempting to load configuration by priority.");
+        let Some(config_path) = self.config_file()? else {
+            log::debug!("Using configuration default, builtin configuration (5)");
+            return Ok((Config::default(), None));
+        };
+        let config =
+            Config::load_from(&config_path)?.ok_or_else(|| eyre!("File does not exist."))?;
+        Ok((config, Some(config_path)))
+    }
+
+    /// Locate the configuration file by priority, `None` if the builtin
+    /// configuration is used.
+    pub fn config_file(&self) -> Result<Option<PathBuf>> {
+        log::debug!("Attempting to locate configuration by priority.");
         let cwd = crate::traverse::cwd()?;
         // 1. explicitly specified
-        let explicit_cfg = self.cfg.as_ref().map(|config_path| {
-            if config_path.is_absolute() {
-                config_path.to_owned()
-            } else {
-                // TODO make sure this is sane behavior
-                // to use `cwd`.
-                cwd.join(config_path)
-            }
-        });
-
-        if let Some(config_path) = explicit_cfg {
+        if let Some(ref config_path) = self.cfg {
+            // TODO make sure this is sane behavior
+            // to use `cwd`.
+            let config_path = cwd.join(config_path);
             log::debug!(
                 "Using configuration file provided by flag (1) {}",
                 config_path.display()
             );
-            let config =
-                Config::load_from(&config_path)?.ok_or_else(|| eyre!("File does not exist."))?;
-            return Ok((config, Some(config_path)));
+            return Ok(Some(config_path));
         }
 
         log::debug!("No cfg flag present");
@@ -428,41 +1173,83 @@ impl Args {
             None
         };
         if let Some(manifest_path) = &manifest_path_in_target_dir {
-            if let Some((config, config_path)) = load_from_manifest_metadata(manifest_path)? {
-                return Ok((config, Some(config_path)));
+            if let Some(config_path) = config_path_from_manifest(manifest_path)? {
+                log::debug!("Using configuration file (2) {}", config_path.display());
+                return Ok(Some(config_path));
             }
         };
 
         // 3. manifest meta in current working dir
         if let Some(manifest_path) = look_for_cargo_manifest(&cwd)? {
-            if let Some((config, config_path)) = load_from_manifest_metadata(&manifest_path)? {
-                return Ok((config, Some(config_path)));
+            if let Some(config_path) = config_path_from_manifest(&manifest_path)? {
+                log::debug!("Using configuration file (3) {}", config_path.display());
+                return Ok(Some(config_path));
             }
         };
 
         // 4. load from `.config/spellcheck.toml` from the current working directory.
         let config_path = cwd.join(".config").join("spellcheck.toml");
-        if let Some(cfg) = Config::load_from(&config_path)? {
+        if config_path.is_file() {
             log::debug!("Using configuration file (4) {}", config_path.display());
-            return Ok((cfg, Some(config_path)));
+            return Ok(Some(config_path));
         }
 
-        let default_config_path = Config::default_path()?;
-        if let Some(cfg) = Config::load_from(&default_config_path)? {
-            log::debug!(
-                "Using configuration file (5) {}",
-                default_config_path.display()
-            );
-            return Ok((cfg, Some(default_config_path)));
+        // 5. per user configuration, unless disabled or unavailable
+        if self.no_user_config {
+            log::debug!("Skipping user config (5) as requested");
+        } else {
+            match Config::default_path() {
+                Ok(default_config_path) => {
+                    if default_config_path.is_file() {
+                        log::debug!(
+                            "Using configuration file (5) {}",
+                            default_config_path.display()
+                        );
+                        return Ok(Some(default_config_path));
+                    }
+                    log::debug!("No user config present {}", default_config_path.display());
+                }
+                Err(e) => log::warn!("Skipping user config (5): {e}"),
+            }
         }
-
-        log::debug!("No user config present {}", default_config_path.display());
-        log::debug!("Using configuration default, builtin configuration (5)");
-        Ok((Config::default(), None))
+        Ok(None)
     }
 
     fn load_config(&self) -> Result<(Config, Option<PathBuf>)> {
         let (mut config, config_path) = self.load_config_inner()?;
+        if let Some(common) = self.common().filter(|common| !common.enable_cfg.is_empty()) {
+            config.enable_cfg = common.enable_cfg.clone();
+        }
+        if let Some(common) = self.common().filter(|common| !common.include.is_empty()) {
+            config.include_paths = common.include.clone();
+        }
+        if let Some(common) = self.common().filter(|common| !common.exclude.is_empty()) {
+            config.exclude_paths = common.exclude.clone();
+        }
+        if self.common().filter(|common| common.check_help).is_some() {
+            config.check_help = true;
+        }
+        if self
+            .common()
+            .filter(|common| common.include_generated)
+            .is_some()
+        {
+            config.skip_generated = false;
+        }
+        if self
+            .common()
+            .filter(|common| common.no_size_limits)
+            .is_some()
+        {
+            config.max_file_size = 0;
+            config.max_chunk_chars = 0;
+        }
+        if let Some(Sub::Baseline { ref write, .. }) = self.command {
+            // the destination, which is not consulted while recording
+            config.baseline = Some(write.clone());
+        } else if let Some(baseline) = self.common().and_then(|common| common.baseline.as_ref()) {
+            config.baseline = Some(baseline.clone());
+        }
         // mask all disabled checkers, use the default config
         // for those which have one if not enabled already.
 
@@ -470,39 +1257,35 @@ impl Args {
         // causes friction for users, especially in presence of inline codes which are
         // elided, and cause even worse suggestions.
         // ISSUE: https://github.com/drahnr/cargo-spellcheck/issues/242
-        let filter_set = self
-            .checkers()
-            .unwrap_or_else(|| vec![CheckerType::Hunspell]);
-        {
-            if filter_set.contains(&CheckerType::Hunspell) {
-                if config.hunspell.is_none() {
-                    config.hunspell = Some(crate::config::HunspellConfig::default());
-                }
-            } else {
-                config.hunspell = None;
-            }
-            if filter_set.contains(&CheckerType::ZSpell) {
-                if config.zet.is_none() {
-                    config.zet = Some(crate::config::ZetConfig::default());
-                }
-            } else {
-                config.zet = None;
-            }
-            if filter_set.contains(&CheckerType::Spellbook) {
-                if config.spellbook.is_none() {
-                    config.spellbook = Some(crate::config::SpellbookConfig::default());
-                }
-            } else {
-                config.spellbook = None;
-            }
-            if filter_set.contains(&CheckerType::NlpRules) {
-                if config.nlprules.is_none() {
-                    config.nlprules = Some(crate::config::NlpRulesConfig::default());
+        let filter_set = self.checkers().unwrap_or_else(|| DEFAULT_CHECKERS.to_vec());
+        Self::checker_selection_override(Some(&filter_set), &mut config)?;
+
+        if let Some(ref mut reflow) = config.reflow {
+            reflow.derive_from_rustfmt(&crate::traverse::cwd()?)?;
+        }
+
+        // after the selection, which might add the default configurations
+        if let Some(common) = self.common() {
+            let cwd = crate::traverse::cwd()?;
+            let extra_dictionaries =
+                Result::<Vec<_>>::from_iter(common.extra_dict.iter().map(|path| {
+                    let path = cwd.join(path);
+                    if !path.is_file() {
+                        bail!("Extra dictionary {} does not exist", path.display());
+                    }
+                    Ok(path)
+                }))?;
+            for dictionary_config in [&mut config.hunspell, &mut config.zet, &mut config.spellbook]
+                .into_iter()
+                .flatten()
+            {
+                if common.no_os_dictionaries {
+                    dictionary_config.disable_os_lookups();
                 }
-            } else {
-                config.nlprules = None;
+                dictionary_config
+                    .extra_dictionaries
+                    .extend(extra_dictionaries.iter().cloned());
             }
-            // reflow is a different subcommand, not relevant
         }
 
         Ok((config, config_path))
@@ -512,12 +1295,14 @@ impl Args {
     /// provide a new, unified config struct.
     pub fn unified(self) -> Result<(UnifiedArgs, Config)> {
         let (config, config_path) = self.load_config()?;
+        let report_verbosity = self.report_verbosity();
         let unified = match self.command {
             Some(Sub::Config {
                 stdout,
                 user,
                 overwrite,
                 filter: checkers,
+                ..
             }) => {
                 let dest_config = match self.cfg {
                     None if stdout => ConfigWriteDestination::Stdout,
@@ -540,44 +1325,164 @@ impl Args {
             }) => UnifiedArgs::Operate {
                 action: self.action(),
                 config_path,
-                dev_comments: false, // not relevant
-                skip_readme,
+                dev_comments: false,          // not relevant
+                dev_comments_for: Vec::new(), // not relevant
+                check_strings: false,         // not relevant
+                skip_readme: skip_readme || config.skip_readme,
                 recursive,
                 paths: paths.clone(),
-                exit_code_override: 1,
+                exit_code_override: ExitCodes::from(1),
+                output_format: OutputFormat::Human, // not relevant
+                report_verbosity: ReportVerbosity::Normal, // not relevant
+                ci_annotations: CiAnnotations::Off, // not relevant
+                reports: Vec::new(),                // not relevant
+                max_issues: None,                   // not relevant
+                paths_from_stdin: false,            // not relevant
+                no_lock: false,                     // not relevant
+                progress: false,                    // not relevant
+                backup: None,                       // not relevant
+                verify: false,                      // not relevant
             },
+            Some(Sub::Dict {
+                command:
+                    DictSub::Simulate {
+                        recursive,
+                        skip_readme,
+                        dev_comments,
+                        ref words,
+                        ref paths,
+                    },
+            }) => UnifiedArgs::DictSimulate {
+                words: words.clone(),
+                dev_comments: dev_comments || config.dev_comments,
+                skip_readme: skip_readme || config.skip_readme,
+                recursive,
+                paths: paths.clone(),
+            },
+            Some(Sub::Words {
+                ref write,
+                ref common,
+            }) => UnifiedArgs::Words {
+                write: write.clone(),
+                dev_comments: common.dev_comments || config.dev_comments,
+                dev_comments_for: dev_comments_for(common, &config),
+                check_strings: common.check_strings || config.check_strings,
+                skip_readme: common.skip_readme || config.skip_readme,
+                recursive: common.recursive,
+                paths: common.paths.clone(),
+            },
+            Some(Sub::Dict {
+                command:
+                    DictSub::Lint {
+                        ref aff,
+                        ref dictionaries,
+                    },
+            }) => {
+                let dictionaries = if dictionaries.is_empty() {
+                    Vec::from_iter(
+                        config
+                            .hunspell
+                            .iter()
+                            .flat_map(|hunspell| hunspell.extra_dictionaries())
+                            .cloned(),
+                    )
+                } else {
+                    dictionaries.clone()
+                };
+                UnifiedArgs::DictLint {
+                    aff: aff.clone(),
+                    dictionaries,
+                }
+            }
+            Some(Sub::FetchDictionaries {
+                lang,
+                ref mirror,
+                no_register,
+            }) => {
+                let register = if no_register {
+                    None
+                } else if let Some(ref path) = self.cfg {
+                    Some(path.clone())
+                } else {
+                    Some(Config::default_path()?)
+                };
+                UnifiedArgs::FetchDictionaries {
+                    lang,
+                    mirror: mirror.clone(),
+                    register,
+                }
+            }
             None => {
                 let common = &self.common;
                 UnifiedArgs::Operate {
                     action: Action::Check,
                     config_path,
                     dev_comments: common.dev_comments || config.dev_comments,
+                    dev_comments_for: dev_comments_for(common, &config),
+                    check_strings: common.check_strings || config.check_strings,
                     skip_readme: common.skip_readme || config.skip_readme,
                     recursive: common.recursive,
                     paths: common.paths.clone(),
-                    exit_code_override: common.code,
+                    exit_code_override: ExitCodes(common.code.clone()),
+                    output_format: common.format,
+                    report_verbosity,
+                    ci_annotations: common.ci_annotations,
+                    reports: common.report.clone(),
+                    max_issues: common.max_issues,
+                    paths_from_stdin: common.paths_from_stdin,
+                    no_lock: common.no_lock,
+                    progress: !common.no_progress && report_verbosity != ReportVerbosity::Quiet,
+                    backup: None,
+                    verify: false,
                 }
             }
             Some(
                 Sub::Reflow { ref common, .. }
+                | Sub::Baseline { ref common, .. }
                 | Sub::Fix { ref common, .. }
                 | Sub::Check { ref common, .. },
             ) => UnifiedArgs::Operate {
                 action: self.action(),
                 config_path,
                 dev_comments: common.dev_comments || config.dev_comments,
+                dev_comments_for: dev_comments_for(common, &config),
+                check_strings: common.check_strings || config.check_strings,
                 skip_readme: common.skip_readme || config.skip_readme,
                 recursive: common.recursive,
                 paths: common.paths.clone(),
-                exit_code_override: common.code,
+                exit_code_override: ExitCodes(common.code.clone()),
+                output_format: common.format,
+                report_verbosity,
+                ci_annotations: common.ci_annotations,
+                reports: common.report.clone(),
+                max_issues: common.max_issues,
+                paths_from_stdin: common.paths_from_stdin,
+                no_lock: common.no_lock,
+                progress: !common.no_progress && report_verbosity != ReportVerbosity::Quiet,
+                backup: self.backup(),
+                verify: self.verify(),
             },
-            Some(Sub::Completions { .. }) => unreachable!("Was handled earlier. qed"),
+            Some(Sub::Completions { .. } | Sub::Hook { .. }) => {
+                unreachable!("Was handled earlier. qed")
+            }
         };
 
         Ok((unified, config))
     }
 }
 
+/// Globs of files to check developer comments for, the ones from the
+/// arguments add to the configured ones.
+fn dev_comments_for(common: &Common, config: &Config) -> Vec<String> {
+    Vec::from_iter(
+        config
+            .dev_comments_for
+            .iter()
+            .chain(common.dev_comments_for.iter())
+            .cloned(),
+    )
+}
+
 #[derive(Debug, Clone)]
 pub enum ConfigWriteDestination {
     Stdout,
@@ -598,10 +1503,49 @@ pub enum UnifiedArgs {
         action: Action,
         config_path: Option<PathBuf>,
         dev_comments: bool,
+        dev_comments_for: Vec<String>,
+        check_strings: bool,
+        skip_readme: bool,
+        recursive: bool,
+        paths: Vec<PathBuf>,
+        exit_code_override: ExitCodes,
+        output_format: OutputFormat,
+        report_verbosity: ReportVerbosity,
+        ci_annotations: CiAnnotations,
+        reports: Vec<Report>,
+        max_issues: Option<usize>,
+        paths_from_stdin: bool,
+        no_lock: bool,
+        progress: bool,
+        backup: Option<Backup>,
+        verify: bool,
+    },
+    Words {
+        /// The dictionary to add the words to, printed if `None`.
+        write: Option<PathBuf>,
+        dev_comments: bool,
+        dev_comments_for: Vec<String>,
+        check_strings: bool,
+        skip_readme: bool,
+        recursive: bool,
+        paths: Vec<PathBuf>,
+    },
+    DictSimulate {
+        words: Vec<String>,
+        dev_comments: bool,
         skip_readme: bool,
         recursive: bool,
         paths: Vec<PathBuf>,
-        exit_code_override: u8,
+    },
+    DictLint {
+        aff: Option<PathBuf>,
+        dictionaries: Vec<PathBuf>,
+    },
+    FetchDictionaries {
+        lang: Lang5,
+        mirror: String,
+        /// Configuration file to register the dictionary in.
+        register: Option<PathBuf>,
     },
 }
 
@@ -646,30 +1590,21 @@ fn look_for_cargo_manifest(base: &Path) -> Result<Option<PathBuf>> {
     })
 }
 
-fn extract_config_path_from_metadata(
-    manifest_path: &Path,
-    metadata: ManifestMetadata,
-    ident: &str,
-) -> Result<Option<(Config, PathBuf)>> {
-    if let Some(spellcheck) = metadata.spellcheck {
-        let config_path = &spellcheck.config;
-        let config_path = if config_path.is_absolute() {
-            config_path.to_owned()
-        } else {
-            let manifest_dir = manifest_path.parent().expect("File resides in a dir. qed");
-            manifest_dir.join(config_path)
-        };
-        log::debug!(
-            "Using configuration ({}) file {}",
-            ident,
-            config_path.display()
-        );
-        return Ok(Config::load_from(&config_path)?.map(|config| (config, config_path)));
-    }
-    Ok(None)
+/// The path of the configuration file referenced by the manifest metadata,
+/// relative paths are relative to the manifest.
+fn config_path_from_metadata(manifest_path: &Path, metadata: ManifestMetadata) -> Option<PathBuf> {
+    let config_path = metadata.spellcheck?.config;
+    Some(if config_path.is_absolute() {
+        config_path
+    } else {
+        let manifest_dir = manifest_path.parent().expect("File resides in a dir. qed");
+        manifest_dir.join(config_path)
+    })
 }
 
-fn load_from_manifest_metadata(manifest_path: &Path) -> Result<Option<(Config, PathBuf)>> {
+/// The path of the existing configuration file referenced by the package or
+/// workspace metadata of the manifest, without loading it.
+fn config_path_from_manifest(manifest_path: &Path) -> Result<Option<PathBuf>> {
     let manifest = fs::read_to_string(manifest_path)?;
     let manifest =
         cargo_toml::Manifest::<ManifestMetadata>::from_slice_with_metadata(manifest.as_bytes())
@@ -677,17 +1612,18 @@ fn load_from_manifest_metadata(manifest_path: &Path) -> Result<Option<(Config, P
                 "Failed to parse cargo manifest: {}",
                 manifest_path.display()
             ))?;
-    if let Some(metadata) = manifest.package.and_then(|package| package.metadata) {
-        if let Some(x) = extract_config_path_from_metadata(manifest_path, metadata, "package")? {
-            return Ok(Some(x));
-        }
-    }
-    if let Some(metadata) = manifest.workspace.and_then(|workspace| workspace.metadata) {
-        if let Some(x) = extract_config_path_from_metadata(manifest_path, metadata, "workspace")? {
-            return Ok(Some(x));
-        }
-    }
-    Ok(None)
+    Ok(manifest
+        .package
+        .and_then(|package| package.metadata)
+        .and_then(|metadata| config_path_from_metadata(manifest_path, metadata))
+        .filter(|path| path.is_file())
+        .or_else(|| {
+            manifest
+                .workspace
+                .and_then(|workspace| workspace.metadata)
+                .and_then(|metadata| config_path_from_metadata(manifest_path, metadata))
+                .filter(|path| path.is_file())
+        }))
 }
 
 /// Set the worker pool job/thread count.
@@ -743,10 +1679,14 @@ mod tests {
             "cargo-spellcheck -vvvv" => Action::Check,
             // check (explicit)
             "cargo spellcheck check -m 11" => Action::Check,
+            "cargo spellcheck check --no-user-config" => Action::Check,
             "cargo-spellcheck check -m 9" => Action::Check,
+            "cargo spellcheck check --watch" => Action::Watch,
             // reflow
             "cargo spellcheck reflow" => Action::Reflow,
+            "cargo spellcheck baseline --write baseline.json" => Action::Baseline,
             "cargo-spellcheck reflow" => Action::Reflow,
+            "cargo spellcheck reflow --check" => Action::ReflowCheck,
             // fix (deprecated)
             "cargo spellcheck --fix" => Action::Fix,
             "cargo-spellcheck --fix" => Action::Fix,
@@ -754,6 +1694,8 @@ mod tests {
             "cargo spellcheck fix" => Action::Fix,
             "cargo-spellcheck fix" => Action::Fix,
             "cargo-spellcheck fix -r file.rs" => Action::Fix,
+            "cargo spellcheck fix --safe" => Action::FixSafe,
+            "cargo-spellcheck fix --fix-safe -r file.rs" => Action::FixSafe,
             "cargo-spellcheck -q fix Cargo.toml" => Action::Fix,
             "cargo spellcheck -v fix Cargo.toml" => Action::Fix,
 
@@ -774,6 +1716,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn backup() {
+        let backup = |command| {
+            Args::parse(commandline_to_iter(command))
+                .expect("Parsing works. qed")
+                .backup()
+        };
+        assert_eq!(backup("cargo spellcheck fix"), None);
+        assert_eq!(
+            backup("cargo spellcheck fix --backup src"),
+            Some(Backup::Beside)
+        );
+        assert_eq!(
+            backup("cargo spellcheck reflow --backup=.backup"),
+            Some(Backup::Dir(PathBuf::from(".backup")))
+        );
+        assert!(Args::parse(commandline_to_iter(
+            "cargo spellcheck reflow --check --backup"
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn verify() {
+        let verify = |command| {
+            Args::parse(commandline_to_iter(command))
+                .expect("Parsing works. qed")
+                .verify()
+        };
+        assert!(!verify("cargo spellcheck fix"));
+        assert!(verify("cargo spellcheck fix --verify"));
+        assert!(verify("cargo spellcheck reflow --verify src"));
+        assert!(Args::parse(commandline_to_iter(
+            "cargo spellcheck reflow --check --verify"
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn words() {
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck words --write .config/allow.dic -r --checkers=hunspell",
+        ))
+        .expect("Parsing works. qed");
+        assert_matches!(args.command, Some(Sub::Words { ref write, .. }) => {
+            assert_eq!(write.as_deref(), Some(Path::new(".config/allow.dic")));
+        });
+        assert_eq!(args.checkers(), Some(vec![CheckerType::Hunspell]));
+        assert!(args.common().map_or(false, |common| common.recursive));
+    }
+
     #[test]
     fn deserialize_multiple_checkers() {
         let args = Args::parse(commandline_to_iter(
@@ -786,6 +1779,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn checker_selection() {
+        let selection = CheckerSelection::from_str("-hunspell,+nlprules,+headings").unwrap();
+        assert_eq!(
+            selection.resolve(DEFAULT_CHECKERS),
+            vec![CheckerType::NlpRules, CheckerType::Headings]
+        );
+        let selection = CheckerSelection::from_str("hunspell,-nlprules,reflow").unwrap();
+        assert_eq!(
+            selection.resolve(DEFAULT_CHECKERS),
+            vec![CheckerType::Hunspell, CheckerType::Reflow]
+        );
+        assert!(CheckerSelection::from_str("+grammarly").is_err());
+
+        let mut config = Config::default();
+        Args::checker_selection_override(Some(&[CheckerType::Redundancy]), &mut config).unwrap();
+        assert!(config.hunspell.is_none());
+        assert!(config.redundancy.is_some());
+        assert!(!config.line_length);
+        Args::checker_selection_override(Some(&[CheckerType::LineLength]), &mut config).unwrap();
+        assert!(config.line_length);
+        assert!(config.redundancy.is_none());
+        assert!(!config.anchors);
+        Args::checker_selection_override(Some(&[CheckerType::Anchors]), &mut config).unwrap();
+        assert!(config.anchors);
+        assert!(!config.intra_doc_links);
+        Args::checker_selection_override(Some(&[CheckerType::IntraDocLinks]), &mut config).unwrap();
+        assert!(config.intra_doc_links);
+
+        let external = crate::config::ExternalCheckerConfig {
+            name: "house-style".to_owned(),
+            command: "house-style-check".to_owned(),
+            args: Vec::new(),
+        };
+        config.checker.external = vec![external.clone()];
+        let selection = CheckerSelection::from_str("+external").unwrap();
+        Args::checker_selection_override(Some(&selection.resolve(DEFAULT_CHECKERS)), &mut config)
+            .unwrap();
+        assert!(config.is_enabled(Detector::External));
+        let selection = CheckerSelection::from_str("-external").unwrap();
+        Args::checker_selection_override(Some(&selection.resolve(DEFAULT_CHECKERS)), &mut config)
+            .unwrap();
+        assert!(!config.is_enabled(Detector::External));
+        assert!(
+            Args::checker_selection_override(Some(&[CheckerType::Reflow]), &mut config).is_err()
+        );
+    }
+
     #[test]
     fn alt_fix_works() {
         let args_sub = Args::parse(commandline_to_iter("cargo spellcheck fix")).unwrap();
@@ -794,6 +1835,72 @@ mod tests {
         assert_eq!(args_sub.action(), args_alt.action());
     }
 
+    #[test]
+    fn explain() {
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck --explain nlprules/EN_A_VS_AN",
+        ))
+        .expect("Parsing works. qed");
+        assert_eq!(args.explain.as_deref(), Some("nlprules/EN_A_VS_AN"));
+        let explanation = crate::suggestion::explain("nlprules/EN_A_VS_AN").unwrap();
+        assert!(explanation.starts_with("nlprules/EN_A_VS_AN: "));
+        assert!(crate::suggestion::explain("reflow/line-too-long").is_some());
+        assert!(crate::suggestion::explain("hunspell/line-too-long").is_none());
+        assert!(crate::suggestion::explain("unknown").is_none());
+    }
+
+    #[test]
+    fn exit_codes_per_category() {
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck check --code spelling=2 -m grammar=3 --code 1",
+        ))
+        .expect("Parsing works. qed");
+        let codes = ExitCodes(args.common().unwrap().code.clone());
+        assert_eq!(codes.fallback(), 1);
+
+        let mut per_detector = indexmap::IndexMap::new();
+        assert_eq!(codes.resolve(&per_detector), 1);
+        per_detector.insert(Detector::NlpRules, 4);
+        assert_eq!(codes.resolve(&per_detector), 3);
+        // the highest code, not the first flag
+        per_detector.insert(Detector::Spellbook, 1);
+        assert_eq!(codes.resolve(&per_detector), 3);
+        per_detector.swap_remove(&Detector::NlpRules);
+        assert_eq!(codes.resolve(&per_detector), 2);
+        per_detector.clear();
+        per_detector.insert(Detector::Reflow, 1);
+        assert_eq!(codes.resolve(&per_detector), 1);
+
+        assert_matches!(
+            ExitCodeRule::from_str("reflow=9"),
+            Ok(ExitCodeRule { detectors, code: 9 }) => assert_eq!(detectors, vec![Detector::Reflow])
+        );
+        assert_matches!(
+            ExitCodeRule::from_str("typos=1"),
+            Err(InvalidExitCodeRule::Category(_))
+        );
+        assert_matches!(
+            ExitCodeRule::from_str("256"),
+            Err(InvalidExitCodeRule::Code(_))
+        );
+    }
+
+    #[test]
+    fn fetch_dictionaries() {
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck fetch-dictionaries --lang de_AT --no-register",
+        ))
+        .expect("Parsing works. qed");
+        assert_matches!(args.command, Some(Sub::FetchDictionaries { lang, mirror, no_register: true }) => {
+            assert_eq!(lang, "de_AT");
+            assert_eq!(mirror, crate::action::fetch::DEFAULT_DICTIONARY_MIRROR);
+        });
+        assert!(Args::parse(commandline_to_iter(
+            "cargo spellcheck fetch-dictionaries --lang german"
+        ))
+        .is_err());
+    }
+
     #[test]
     fn unify_ops_check() {
         let args = Args::parse(
@@ -803,8 +1910,15 @@ mod tests {
                 "-vvvvv",
                 "check",
                 "--code=77",
+                "--format=github",
+                "--ci-annotations=off",
                 "--dev-comments",
+                "--dev-comments-for=src/lowlevel/**",
+                "--check-strings",
                 "--skip-readme",
+                "--no-progress",
+                "--report=html=target/spellcheck.html",
+                "--max-issues=0",
             ]
             .iter()
             .map(ToOwned::to_owned)
@@ -817,14 +1931,44 @@ mod tests {
                 action,
                 config_path: _,
                 dev_comments,
+                dev_comments_for,
+                check_strings,
                 skip_readme,
                 recursive,
                 paths,
                 exit_code_override,
+                output_format,
+                report_verbosity,
+                ci_annotations,
+                reports,
+                max_issues,
+                paths_from_stdin,
+                no_lock,
+                progress,
+                backup,
+                verify,
             } => {
                 assert_eq!(Action::Check, action);
-                assert_eq!(exit_code_override, 77);
+                assert_eq!(backup, None);
+                assert_eq!(verify, false);
+                assert_eq!(progress, false);
+                assert_eq!(report_verbosity, ReportVerbosity::Normal);
+                assert_eq!(no_lock, false);
+                assert_eq!(exit_code_override.fallback(), 77);
+                assert_eq!(output_format, OutputFormat::Github);
+                assert_eq!(ci_annotations, CiAnnotations::Off);
+                assert_eq!(
+                    reports,
+                    vec![Report {
+                        kind: ReportKind::Html,
+                        path: PathBuf::from("target/spellcheck.html"),
+                    }]
+                );
+                assert_eq!(max_issues, Some(0));
+                assert_eq!(paths_from_stdin, false);
                 assert_eq!(dev_comments, true);
+                assert_eq!(dev_comments_for, vec!["src/lowlevel/**".to_owned()]);
+                assert_eq!(check_strings, true);
                 assert_eq!(skip_readme, true);
                 assert_eq!(recursive, false);
                 assert_eq!(paths, Vec::<PathBuf>::new());
@@ -861,6 +2005,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unify_dictionary_overrides() {
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck check --no-user-config --checkers=hunspell,spellbook --no-os-dictionaries --extra-dict=.config/lingo.dic",
+        ))
+        .unwrap();
+        let (_unified, config) = args.unified().unwrap();
+        let lingo = crate::traverse::cwd().unwrap().join(".config/lingo.dic");
+        for dictionary_config in [config.hunspell.unwrap(), config.spellbook.unwrap()] {
+            assert!(dictionary_config.skip_os_lookups);
+            assert!(dictionary_config.extra_dictionaries.contains(&lingo));
+        }
+
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck check --no-user-config --extra-dict=does/not/exist.dic",
+        ))
+        .unwrap();
+        assert!(args.unified().is_err());
+    }
+
+    #[test]
+    fn unify_include_generated() {
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck check --no-user-config",
+        ))
+        .unwrap();
+        let (_unified, config) = args.unified().unwrap();
+        assert!(config.skip_generated);
+
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck check --no-user-config --include-generated",
+        ))
+        .unwrap();
+        let (_unified, config) = args.unified().unwrap();
+        assert!(!config.skip_generated);
+    }
+
+    #[test]
+    fn unify_dict_simulate() {
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck dict simulate foo Bar -- src/lib.rs",
+        ))
+        .unwrap();
+        let (unified, _config) = args.unified().unwrap();
+        assert_matches!(unified,
+            UnifiedArgs::DictSimulate {
+                words,
+                recursive,
+                paths,
+                ..
+            } => {
+                assert_eq!(words, vec!["foo".to_owned(), "Bar".to_owned()]);
+                assert_eq!(recursive, false);
+                assert_eq!(paths, vec![PathBuf::from("src/lib.rs")]);
+            }
+        );
+    }
+
     #[test]
     fn shell_check_env() {
         assert_matches!(load_shell_name("/usr/bin/zsh"), Ok(Shell::Zsh));
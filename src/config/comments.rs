@@ -0,0 +1,83 @@
+//! Comments of source files in languages other than rust configuration.
+use crate::documentation::CommentSyntax;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The comment syntax of files of another language, i.e. C headers shipped
+/// along with a `-sys` crate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CommentsConfig {
+    /// File extensions of the language, i.e. `["h", "c"]`, for files passed
+    /// explicitly or found within directories without a manifest.
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// Globs relative to the current working directory, of the files checked
+    /// with the whole project, i.e. `"include/**/*.h"`.
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Prefixes of line comments.
+    #[serde(default = "default_line")]
+    pub line: Vec<String>,
+    /// Opening and closing delimiters of block comments.
+    #[serde(default = "default_block")]
+    pub block: Vec<(String, String)>,
+}
+
+fn default_line() -> Vec<String> {
+    CommentSyntax::default().line
+}
+
+fn default_block() -> Vec<(String, String)> {
+    CommentSyntax::default().block
+}
+
+impl CommentsConfig {
+    /// Check if the file at `path` is written in the language, with `base`
+    /// as the directory the `paths` globs are relative to.
+    pub fn matches(&self, path: &Path, base: &Path) -> bool {
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        if self
+            .extensions
+            .iter()
+            .any(|candidate| Some(candidate.trim_start_matches('.')) == extension)
+        {
+            return true;
+        }
+        self.paths
+            .iter()
+            .filter_map(|glob| glob::Pattern::new(&base.join(glob).to_string_lossy()).ok())
+            .any(|pattern| pattern.matches_path(path))
+    }
+
+    /// The comment markers to extract the comments with.
+    pub fn syntax(&self) -> CommentSyntax {
+        CommentSyntax {
+            line: self.line.clone(),
+            block: self.block.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches() {
+        let config = toml::from_str::<CommentsConfig>(
+            r#"
+extensions = [".h", "c"]
+paths = ["include/**/*.hpp"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.syntax(), CommentSyntax::default());
+        let base = Path::new("/project");
+        assert!(config.matches(Path::new("/project/ffi.h"), base));
+        assert!(config.matches(Path::new("/elsewhere/ffi.c"), base));
+        assert!(config.matches(Path::new("/project/include/nested/ffi.hpp"), base));
+        assert!(!config.matches(Path::new("/project/src/ffi.hpp"), base));
+        assert!(!config.matches(Path::new("/project/src/lib.rs"), base));
+    }
+}
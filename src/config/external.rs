@@ -0,0 +1,25 @@
+//! External checker configuration.
+use serde::{Deserialize, Serialize};
+
+/// Generic checker settings, which are not specific to one of the builtin
+/// checkers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckerConfig {
+    /// External tools speaking the JSON protocol on `stdin` and `stdout`.
+    #[serde(default)]
+    pub external: Vec<ExternalCheckerConfig>,
+}
+
+/// An external tool, invoked once per checked file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExternalCheckerConfig {
+    /// Name used to prefix the descriptions of its suggestions.
+    pub name: String,
+    /// The executable, looked up in `PATH` if not a path.
+    pub command: String,
+    /// Additional arguments passed to the executable.
+    #[serde(default)]
+    pub args: Vec<String>,
+}
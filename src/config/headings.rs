@@ -0,0 +1,30 @@
+//! Heading capitalization configuration.
+use serde::{Deserialize, Serialize};
+
+/// Capitalization of headings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HeadingStyle {
+    /// All words capitalized, except for articles, conjunctions and short
+    /// prepositions, i.e. "Check for Spelling Mistakes".
+    #[default]
+    #[serde(alias = "title")]
+    #[serde(alias = "title-case")]
+    Title,
+    /// Only the first word capitalized, i.e. "Check for spelling mistakes".
+    #[serde(alias = "sentence")]
+    #[serde(alias = "sentence-case")]
+    Sentence,
+}
+
+/// Parameters of the heading capitalization check.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HeadingsConfig {
+    /// The capitalization style to enforce.
+    #[serde(default)]
+    pub style: HeadingStyle,
+    /// Words which are kept as given, i.e. proper nouns like `Rust` for
+    /// sentence case.
+    #[serde(default)]
+    pub allow: Vec<String>,
+}
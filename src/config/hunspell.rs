@@ -1,6 +1,6 @@
 //! Hunspell checker configuration.
 
-use super::{Lang5, SearchDirs, WrappedRegex};
+use super::{Lang5, SearchDirs, TokenizerConfig, WrappedRegex};
 use std::path::{Path, PathBuf};
 
 use crate::errors::*;
@@ -12,6 +12,7 @@ const fn yes() -> bool {
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
 pub struct Quirks {
     /// A regular expression, whose capture groups will be checked, instead of
     /// the initial token. Only the first one that matches will be used to split
@@ -26,6 +27,7 @@ pub struct Quirks {
     /// The counterpart of `allow_concatenation`. Accepts words which have
     /// replacement suggestions that contain additional dashes.
     #[serde(default)]
+    #[serde(alias = "allow_dashed")]
     pub allow_dashes: bool,
     /// Treats sequences of emojis as OK.
     #[serde(default = "yes")]
@@ -39,6 +41,43 @@ pub struct Quirks {
     /// reference altogether and will only check the word `hello`.
     #[serde(default = "yes")]
     pub check_footnote_references: bool,
+    /// Skip tokens which look like code, even without backticks.
+    #[serde(default)]
+    pub code_like: CodeLike,
+    /// Split compound identifiers such as `LineColumn` or `load_from_str` at
+    /// case boundaries and `_`, and accept them if all sub-words are known.
+    #[serde(default)]
+    pub split_compounds: bool,
+    /// Skip words starting with an uppercase letter in the middle of a
+    /// sentence, which are likely proper nouns or product names. Words at the
+    /// beginning of a sentence are still checked.
+    #[serde(default)]
+    pub ignore_capitalized: bool,
+}
+
+/// Heuristics to detect tokens which are code rather than prose.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct CodeLike {
+    /// Skip `snake_case` tokens.
+    #[serde(default)]
+    pub snake_case: bool,
+    /// Skip `SCREAMING_CASE` tokens.
+    #[serde(default)]
+    pub screaming_case: bool,
+    /// Skip all segments of `path::like::tokens`.
+    #[serde(default)]
+    pub paths: bool,
+    /// Skip identifiers which are used in the code of the same file.
+    #[serde(default)]
+    pub file_identifiers: bool,
+}
+
+impl CodeLike {
+    /// Any of the heuristics is enabled.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.snake_case || self.screaming_case || self.paths || self.file_identifiers
+    }
 }
 
 impl Default for Quirks {
@@ -49,6 +88,9 @@ impl Default for Quirks {
             allow_dashes: false,
             allow_emojis: true,
             check_footnote_references: true,
+            code_like: CodeLike::default(),
+            split_compounds: false,
+            ignore_capitalized: false,
         }
     }
 }
@@ -73,6 +115,18 @@ impl Quirks {
     pub(crate) fn check_footnote_references(&self) -> bool {
         self.check_footnote_references
     }
+
+    pub(crate) fn code_like(&self) -> &CodeLike {
+        &self.code_like
+    }
+
+    pub(crate) const fn split_compounds(&self) -> bool {
+        self.split_compounds
+    }
+
+    pub(crate) const fn ignore_capitalized(&self) -> bool {
+        self.ignore_capitalized
+    }
 }
 
 fn default_tokenization_splitchars() -> String {
@@ -105,9 +159,18 @@ pub struct HunspellConfig {
     #[serde(default)]
     pub use_builtin: bool,
 
+    /// Maximum number of replacements per suggestion, overrides the global
+    /// `max_replacements`.
+    #[serde(default)]
+    pub max_replacements: Option<usize>,
+
     #[serde(default = "default_tokenization_splitchars")]
     pub tokenization_splitchars: String,
 
+    /// Segmentation of the text into words.
+    #[serde(default)]
+    pub tokenizer: TokenizerConfig,
+
     /// Additional dictionaries for topic specific lingo.
     #[serde(default)]
     pub extra_dictionaries: Vec<PathBuf>,
@@ -124,8 +187,10 @@ impl Default for HunspellConfig {
             extra_dictionaries: Vec::default(),
             quirks: Quirks::default(),
             tokenization_splitchars: default_tokenization_splitchars(),
+            tokenizer: TokenizerConfig::default(),
             skip_os_lookups: false,
             use_builtin: true,
+            max_replacements: None,
         }
     }
 }
@@ -143,7 +208,16 @@ impl HunspellConfig {
         self.extra_dictionaries.iter()
     }
 
+    /// Avoid the OS provided dictionaries, also if the paths were sanitized
+    /// already.
+    pub fn disable_os_lookups(&mut self) {
+        self.skip_os_lookups = true;
+        self.search_dirs.remove_os_specific();
+    }
+
     pub fn sanitize_paths(&mut self, base: &Path) -> Result<()> {
+        self.tokenizer.sanitize_paths(base);
+
         self.search_dirs = self
             .search_dirs
             .iter(!self.skip_os_lookups)
@@ -0,0 +1,39 @@
+//! Identifier cross check configuration.
+use serde::{Deserialize, Serialize};
+
+/// Parameters for matching backticked code references in documentation
+/// against the identifiers of the checked sources.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IdentifiersConfig {
+    /// Upper bound of the edit distance between a code reference and an
+    /// identifier to consider it a typo. Only `1` and `2` are sensible.
+    #[serde(default = "default_max_distance")]
+    pub max_distance: usize,
+}
+
+fn default_max_distance() -> usize {
+    2
+}
+
+impl Default for IdentifiersConfig {
+    fn default() -> Self {
+        Self {
+            max_distance: default_max_distance(),
+        }
+    }
+}
+
+/// Accept the names of the items defined in the checked rust sources as
+/// words.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SymbolsConfig {
+    /// Accept words matching the name of a struct, function, module, etc., or
+    /// their plural.
+    #[serde(default)]
+    pub accept: bool,
+    /// Also accept the parts of the names, split at `_` and case boundaries.
+    #[serde(default)]
+    pub split_case: bool,
+}
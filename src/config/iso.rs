@@ -125,6 +125,14 @@ impl<'de> de::Visitor<'de> for Lang5Visitor {
     }
 }
 
+impl FromStr for Lang5 {
+    type Err = de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        de::Visitor::visit_str(Lang5Visitor, s)
+    }
+}
+
 impl<'de> Deserialize<'de> for Lang5 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -161,6 +169,12 @@ mod tests {
         assert_matches!(serde_plain::from_str::<Lang5>(S), Ok(x) => assert_eq!(EXPECTED, x));
     }
 
+    #[test]
+    fn from_str() {
+        assert_matches!(Lang5::from_str(S), Ok(x) => assert_eq!(EXPECTED, x));
+        assert!(Lang5::from_str("de-AU").is_err());
+    }
+
     #[test]
     fn cmp_variants() {
         assert!(EXPECTED == S);
@@ -0,0 +1,26 @@
+//! Project terminology configuration.
+use serde::{Deserialize, Serialize};
+
+/// Parameters for detecting project terminology by the frequency of unknown
+/// words.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JargonConfig {
+    /// Number of occurrences across all checked files from which on an unknown
+    /// word is reported once as possible project terminology, rather than at
+    /// every occurrence.
+    #[serde(default = "default_threshold")]
+    pub threshold: usize,
+}
+
+fn default_threshold() -> usize {
+    10
+}
+
+impl Default for JargonConfig {
+    fn default() -> Self {
+        Self {
+            threshold: default_threshold(),
+        }
+    }
+}
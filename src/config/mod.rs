@@ -16,6 +16,18 @@ pub use self::regex::*;
 mod reflow;
 pub use self::reflow::*;
 
+mod identifiers;
+pub use self::identifiers::*;
+
+mod redundancy;
+pub use self::redundancy::*;
+
+mod headings;
+pub use self::headings::*;
+
+mod external;
+pub use self::external::*;
+
 mod hunspell;
 pub use self::hunspell::*;
 
@@ -28,6 +40,27 @@ pub use search_dirs::*;
 mod iso;
 pub use iso::*;
 
+mod suppress;
+pub use suppress::*;
+
+mod severity;
+pub use severity::*;
+
+mod tokenizer;
+pub use tokenizer::*;
+
+mod workspace;
+pub use workspace::*;
+
+mod jargon;
+pub use jargon::*;
+
+mod overrides;
+pub use overrides::*;
+
+mod comments;
+pub use comments::*;
+
 use crate::errors::*;
 use crate::Detector;
 use fancy_regex::Regex;
@@ -48,11 +81,96 @@ pub struct Config {
     #[serde(alias = "devcomments")]
     pub dev_comments: bool,
 
+    #[serde(default)]
+    #[serde(alias = "dev-comments-for")]
+    pub dev_comments_for: Vec<String>,
+
+    #[serde(default)]
+    #[serde(alias = "check-strings")]
+    pub check_strings: bool,
+
+    #[serde(default)]
+    #[serde(alias = "check-help")]
+    pub check_help: bool,
+
+    #[serde(default)]
+    #[serde(alias = "public-only")]
+    pub public_only: bool,
+
+    #[serde(default)]
+    #[serde(alias = "missing-docs")]
+    pub missing_docs: bool,
+
+    #[serde(default)]
+    #[serde(alias = "line-length")]
+    pub line_length: bool,
+
+    #[serde(default)]
+    pub anchors: bool,
+
+    #[serde(default)]
+    #[serde(alias = "intra-doc-links")]
+    pub intra_doc_links: bool,
+
+    #[serde(default)]
+    #[serde(alias = "enable-cfg")]
+    pub enable_cfg: Vec<String>,
+
+    #[serde(default)]
+    #[serde(alias = "include-paths")]
+    pub include_paths: Vec<String>,
+
+    #[serde(default)]
+    #[serde(alias = "exclude-paths")]
+    pub exclude_paths: Vec<String>,
+
+    #[serde(default = "default_skip_generated")]
+    #[serde(alias = "skip-generated")]
+    pub skip_generated: bool,
+
+    #[serde(default = "default_generated_markers")]
+    #[serde(alias = "generated-markers")]
+    pub generated_markers: Vec<String>,
+
+    #[serde(default = "default_max_file_size")]
+    #[serde(alias = "max-file-size")]
+    pub max_file_size: u64,
+
+    #[serde(default = "default_max_chunk_chars")]
+    #[serde(alias = "max-chunk-chars")]
+    pub max_chunk_chars: usize,
+
     #[serde(default)]
     #[serde(alias = "skip-readme")]
     #[serde(alias = "skipreadme")]
     pub skip_readme: bool,
 
+    #[serde(default = "default_front_matter_fields")]
+    #[serde(alias = "front-matter-fields")]
+    pub front_matter_fields: Vec<String>,
+
+    #[serde(default)]
+    #[serde(alias = "manifest-fields")]
+    pub manifest_fields: Vec<String>,
+
+    #[serde(default)]
+    pub markdown: Vec<String>,
+
+    #[serde(default)]
+    #[serde(alias = "Workspace")]
+    pub workspace: WorkspaceConfig,
+
+    #[serde(default)]
+    #[serde(alias = "max-replacements")]
+    pub max_replacements: Option<usize>,
+
+    #[serde(default = "default_hyperlink")]
+    pub hyperlink: String,
+
+    #[serde(default)]
+    #[serde(alias = "context-lines")]
+    pub context_lines: usize,
+
     #[serde(alias = "Hunspell")]
     #[serde(default = "default_hunspell")]
     pub hunspell: Option<HunspellConfig>,
@@ -77,6 +195,49 @@ pub struct Config {
     #[serde(alias = "ReFlow")]
     #[serde(alias = "Reflow")]
     pub reflow: Option<ReflowConfig>,
+
+    #[serde(default)]
+    #[serde(alias = "Identifiers")]
+    pub identifiers: Option<IdentifiersConfig>,
+
+    #[serde(default)]
+    #[serde(alias = "Redundancy")]
+    pub redundancy: Option<RedundancyConfig>,
+
+    #[serde(default)]
+    #[serde(alias = "Headings")]
+    pub headings: Option<HeadingsConfig>,
+
+    #[serde(default)]
+    #[serde(alias = "Symbols")]
+    pub symbols: SymbolsConfig,
+
+    #[serde(default)]
+    #[serde(alias = "Jargon")]
+    pub jargon: Option<JargonConfig>,
+
+    #[serde(default)]
+    #[serde(alias = "Checker")]
+    pub checker: CheckerConfig,
+
+    #[serde(default)]
+    #[serde(alias = "Suppress")]
+    pub suppress: SuppressConfig,
+
+    #[serde(default)]
+    #[serde(alias = "Severity")]
+    pub severity: SeverityConfig,
+
+    #[serde(default)]
+    #[serde(alias = "Overrides")]
+    pub overrides: OverridesConfig,
+
+    #[serde(default)]
+    #[serde(alias = "Comments")]
+    pub comments: Vec<CommentsConfig>,
+
+    #[serde(default)]
+    pub baseline: Option<PathBuf>,
 }
 
 impl Config {
@@ -95,9 +256,29 @@ impl Config {
         if let Some(ref mut spellbook) = self.spellbook {
             spellbook.sanitize_paths(base)?;
         }
+        if let Some(ref mut nlprules) = self.nlprules {
+            nlprules.sanitize_paths(base);
+        }
+        if let Some(ref mut baseline) = self.baseline {
+            if !baseline.is_absolute() {
+                *baseline = base.join(&baseline);
+            }
+        }
         Ok(())
     }
 
+    /// Let the dictionary based checkers inherit the global
+    /// `max_replacements`, unless they override it.
+    pub(crate) fn inherit_max_replacements(&mut self) {
+        let max_replacements = self.max_replacements;
+        for checker in [&mut self.hunspell, &mut self.zet, &mut self.spellbook]
+            .into_iter()
+            .flatten()
+        {
+            checker.max_replacements = checker.max_replacements.or(max_replacements);
+        }
+    }
+
     pub fn parse<S: AsRef<str>>(s: S) -> Result<Self> {
         Ok(toml::from_str(s.as_ref())?)
     }
@@ -142,7 +323,8 @@ impl Config {
                     .join("config.toml"),
             )
         } else {
-            bail!("No idea where your config directory is located. XDG compliance would be nice.")
+            log::warn!("No idea where your config directory is located, skipping the user config.");
+            Ok(None)
         }
     }
 
@@ -229,6 +411,14 @@ impl Config {
             Detector::Spellbook => self.spellbook.is_some(),
             Detector::NlpRules => self.nlprules.is_some(),
             Detector::Reflow => self.reflow.is_some(),
+            Detector::LineLength => self.line_length,
+            Detector::Identifiers => self.identifiers.is_some(),
+            Detector::Redundancy => self.redundancy.is_some(),
+            Detector::Headings => self.headings.is_some(),
+            Detector::External => !self.checker.external.is_empty(),
+            Detector::MissingDocs => self.missing_docs,
+            Detector::Anchors => self.anchors,
+            Detector::IntraDocLinks => self.intra_doc_links,
             #[cfg(test)]
             Detector::Dummy => true,
         }
@@ -237,6 +427,67 @@ impl Config {
     pub fn full() -> Self {
         Default::default()
     }
+
+    /// The markers of generated files to skip, none if `skip_generated` is
+    /// unset.
+    pub(crate) fn skipped_generated_markers(&self) -> &[String] {
+        if self.skip_generated {
+            &self.generated_markers
+        } else {
+            &[]
+        }
+    }
+
+    /// The reflow configuration, without a `[Reflow]` section the default one
+    /// with the maximum line length derived from the `rustfmt` configuration.
+    pub(crate) fn reflow_or_default(&self) -> Result<ReflowConfig> {
+        if let Some(ref reflow) = self.reflow {
+            return Ok(reflow.clone());
+        }
+        let mut reflow = ReflowConfig::default();
+        reflow.derive_from_rustfmt(&crate::traverse::cwd()?)?;
+        Ok(reflow)
+    }
+
+    /// Describe where the configuration of each enabled detector came from,
+    /// `path` being the configuration file in use, if any.
+    pub fn provenance(&self, path: Option<&Path>) -> indexmap::IndexMap<Detector, String> {
+        let source = path
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "the defaults".to_owned());
+        let dictionary = |config: &HunspellConfig| {
+            format!(
+                ", lang {} with {} extra dictionaries",
+                config.lang(),
+                config.extra_dictionaries().count()
+            )
+        };
+        let details = [
+            (Detector::Hunspell, self.hunspell.as_ref().map(dictionary)),
+            (Detector::ZSpell, self.zet.as_ref().map(dictionary)),
+            (Detector::Spellbook, self.spellbook.as_ref().map(dictionary)),
+            (
+                Detector::External,
+                Some(format!(", {} tools", self.checker.external.len())),
+            ),
+        ];
+        Detector::ALL
+            .iter()
+            .copied()
+            .filter(|detector| self.is_enabled(*detector))
+            .map(|detector| {
+                let details = details
+                    .iter()
+                    .find(|(candidate, _)| *candidate == detector)
+                    .and_then(|(_, details)| details.clone())
+                    .unwrap_or_default();
+                (
+                    detector,
+                    format!("{detector} configured by {source}{details}"),
+                )
+            })
+            .collect()
+    }
 }
 
 fn default_nlprules() -> Option<NlpRulesConfig> {
@@ -248,6 +499,38 @@ fn default_nlprules() -> Option<NlpRulesConfig> {
     }
 }
 
+fn default_front_matter_fields() -> Vec<String> {
+    Vec::from_iter(
+        crate::documentation::DEFAULT_FRONT_MATTER_FIELDS
+            .iter()
+            .map(|field| field.to_string()),
+    )
+}
+
+fn default_skip_generated() -> bool {
+    true
+}
+
+fn default_generated_markers() -> Vec<String> {
+    Vec::from_iter(
+        ["@generated", "DO NOT EDIT", "automatically generated"]
+            .iter()
+            .map(|marker| marker.to_string()),
+    )
+}
+
+fn default_max_file_size() -> u64 {
+    4 * 1024 * 1024
+}
+
+fn default_hyperlink() -> String {
+    "file://{path}".to_owned()
+}
+
+fn default_max_chunk_chars() -> usize {
+    100_000
+}
+
 fn default_hunspell() -> Option<HunspellConfig> {
     Some(HunspellConfig::default())
 }
@@ -262,12 +545,45 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             dev_comments: false,
+            dev_comments_for: Vec::new(),
+            check_strings: false,
+            check_help: false,
+            public_only: false,
+            missing_docs: false,
+            line_length: false,
+            anchors: false,
+            intra_doc_links: false,
+            enable_cfg: Vec::new(),
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            skip_generated: default_skip_generated(),
+            generated_markers: default_generated_markers(),
+            max_file_size: default_max_file_size(),
+            max_chunk_chars: default_max_chunk_chars(),
             skip_readme: false,
+            front_matter_fields: default_front_matter_fields(),
+            manifest_fields: Vec::new(),
+            markdown: Vec::new(),
+            workspace: WorkspaceConfig::default(),
+            max_replacements: None,
+            hyperlink: default_hyperlink(),
+            context_lines: 0,
             hunspell: default_hunspell(),
             zet: default_zspell(),
             spellbook: default_spellbook(),
             nlprules: default_nlprules(),
             reflow: Some(ReflowConfig::default()),
+            identifiers: None,
+            redundancy: None,
+            headings: None,
+            symbols: SymbolsConfig::default(),
+            jargon: None,
+            checker: CheckerConfig::default(),
+            suppress: SuppressConfig::default(),
+            severity: SeverityConfig::default(),
+            overrides: OverridesConfig::default(),
+            comments: Vec::new(),
+            baseline: None,
         }
     }
 }
@@ -431,7 +747,128 @@ max_line_length = 42
         .unwrap();
         assert_eq!(
             cfg.reflow.expect("Must contain reflow cfg").max_line_length,
-            42
+            Some(42)
+        );
+    }
+
+    #[test]
+    fn partial_10() {
+        let cfg = Config::parse(
+            r#"
+[Suppress]
+words = ["^0x[0-9a-fA-F]+$"]
+descriptions = ["repeated word"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.suppress.words.len(), 1);
+        assert_eq!(cfg.suppress.descriptions.len(), 1);
+    }
+
+    #[test]
+    fn severity() {
+        let cfg = Config::parse(
+            r#"
+[Severity]
+"reflow/line-too-long" = "warning"
+"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.severity.of("reflow/line-too-long"), Severity::Warning);
+        assert_eq!(cfg.severity.of("hunspell/unknown-word"), Severity::Error);
+    }
+
+    #[test]
+    fn partial_11() {
+        let cfg = Config::parse(
+            r#"
+[overrides."demo/**"]
+lines = ["1-10"]
+detectors = ["nlprules"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.overrides.0.len(), 1);
+    }
+
+    #[test]
+    fn nlprules_categories() {
+        let nlprules = NlpRulesConfig::default();
+        assert!(nlprules.is_category_enabled(Some("grammar")));
+        assert!(nlprules.is_category_enabled(None));
+        assert!(!nlprules.is_category_enabled(Some("Typographical")));
+
+        let cfg = Config::parse(
+            r#"
+[NlpRules]
+categories = ["grammar", "style"]
+"#,
+        )
+        .unwrap();
+        let nlprules = cfg.nlprules.expect("Must contain nlprules cfg");
+        assert!(nlprules.is_category_enabled(Some("Grammar")));
+        assert!(!nlprules.is_category_enabled(Some("typographical")));
+        assert!(!nlprules.is_category_enabled(None));
+
+        let cfg = Config::parse(
+            r#"
+[NlpRules]
+disabled_categories = ["misspelling"]
+"#,
+        )
+        .unwrap();
+        let nlprules = cfg.nlprules.expect("Must contain nlprules cfg");
+        assert!(nlprules.is_category_enabled(Some("typographical")));
+        assert!(!nlprules.is_category_enabled(Some("misspelling")));
+    }
+
+    #[test]
+    fn nlprules_lang() {
+        let mut cfg = Config::parse(
+            r#"
+[NlpRules]
+lang = "de"
+tokenizer_path = "/opt/nlprule/de_tokenizer.bin"
+"#,
+        )
+        .unwrap();
+        cfg.sanitize_paths(Path::new("/project/.config")).unwrap();
+        let nlprules = cfg.nlprules.expect("Must contain nlprules cfg");
+        assert_eq!(nlprules.lang(), "de");
+        assert_eq!(
+            nlprules.override_rules,
+            Some(PathBuf::from("/project/.config/de_rules.bin"))
+        );
+        assert_eq!(
+            nlprules.override_tokenizer,
+            Some(PathBuf::from("/opt/nlprule/de_tokenizer.bin"))
+        );
+
+        let mut nlprules = NlpRulesConfig::default();
+        nlprules.sanitize_paths(Path::new("/project/.config"));
+        assert_eq!(nlprules.lang(), "en");
+        assert_eq!(nlprules.override_rules, None);
+        assert_eq!(nlprules.override_tokenizer, None);
+
+        // the builtin english, regardless of region and case
+        for lang in ["en_US", "en-GB", "EN"] {
+            let mut nlprules = NlpRulesConfig {
+                lang: Some(lang.to_owned()),
+                ..NlpRulesConfig::default()
+            };
+            nlprules.sanitize_paths(Path::new("/project/.config"));
+            assert_eq!(nlprules.lang(), "en");
+            assert_eq!(nlprules.override_rules, None);
+        }
+
+        let mut nlprules = NlpRulesConfig {
+            lang: Some("de-AT".to_owned()),
+            ..NlpRulesConfig::default()
+        };
+        nlprules.sanitize_paths(Path::new("/project/.config"));
+        assert_eq!(
+            nlprules.override_tokenizer,
+            Some(PathBuf::from("/project/.config/de_tokenizer.bin"))
         );
     }
 }
@@ -1,6 +1,9 @@
 //! NlpRules checker configuration.
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Language of the builtin tokenizer and rules.
+const BUILTIN_LANG: &str = "en";
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
@@ -13,11 +16,96 @@ impl LanguageToolConfig {
         &self.url
     }
 }
-#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct NlpRulesConfig {
     /// Location to use for an initial lookup of alternate tokenizer and rules
-    /// data.
+    /// data, relative to the configuration file.
+    #[serde(default)]
+    #[serde(alias = "rules_path")]
     pub override_rules: Option<PathBuf>,
+    #[serde(default)]
+    #[serde(alias = "tokenizer_path")]
     pub override_tokenizer: Option<PathBuf>,
+    /// The language of the documentation as language tag, i.e. `de`. Unless
+    /// overridden, the `nlprule` binaries `{lang}_tokenizer.bin` and
+    /// `{lang}_rules.bin` next to the configuration file are used for any but
+    /// the builtin english. Only the primary subtag matters, so `en_US` is the
+    /// builtin english and `de-AT` uses the `de` binaries.
+    #[serde(default)]
+    pub lang: Option<String>,
+    /// Only check the rules of these categories, i.e. `grammar` or `style`.
+    /// All but the `disabled_categories` are checked if empty.
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Skip the rules of these categories, ignored if `categories` is given.
+    #[serde(default = "default_disabled_categories")]
+    #[serde(alias = "disabled-categories")]
+    pub disabled_categories: Vec<String>,
+}
+
+fn default_disabled_categories() -> Vec<String> {
+    // The hunspell backend is aware of custom lingo, which this one is not, so
+    // there would be a lot of false positives. Anything quotes related is not
+    // relevant for code documentation.
+    vec!["misspelling".to_owned(), "typographical".to_owned()]
+}
+
+impl Default for NlpRulesConfig {
+    fn default() -> Self {
+        Self {
+            override_rules: None,
+            override_tokenizer: None,
+            lang: None,
+            categories: Vec::new(),
+            disabled_categories: default_disabled_categories(),
+        }
+    }
+}
+
+impl NlpRulesConfig {
+    /// The language of the tokenizer and rules in use, the lowercase primary
+    /// language subtag, so `en_US` and `en-us` are both `en`.
+    pub fn lang(&self) -> String {
+        let lang = self.lang.as_deref().unwrap_or(BUILTIN_LANG);
+        let primary = lang.split(['-', '_']).next().unwrap_or_default();
+        primary.trim().to_ascii_lowercase()
+    }
+
+    /// Derive the binaries of a non builtin `lang` unless overridden, and
+    /// resolve relative paths relative to `base`.
+    pub fn sanitize_paths(&mut self, base: &Path) {
+        let lang = self.lang();
+        if lang != BUILTIN_LANG {
+            let rules = PathBuf::from(format!("{lang}_rules.bin"));
+            let tokenizer = PathBuf::from(format!("{lang}_tokenizer.bin"));
+            self.override_rules.get_or_insert(rules);
+            self.override_tokenizer.get_or_insert(tokenizer);
+        }
+        for path in [&mut self.override_rules, &mut self.override_tokenizer]
+            .into_iter()
+            .flatten()
+        {
+            if !path.is_absolute() {
+                *path = base.join(&path);
+            }
+        }
+    }
+
+    /// Check if the rules of `category` are checked, rules without a category
+    /// are only skipped if `categories` are given.
+    pub fn is_category_enabled(&self, category: Option<&str>) -> bool {
+        let contains = |categories: &[String]| {
+            category.is_some_and(|category| {
+                categories
+                    .iter()
+                    .any(|enabled| enabled.eq_ignore_ascii_case(category))
+            })
+        };
+        if self.categories.is_empty() {
+            !contains(&self.disabled_categories)
+        } else {
+            contains(&self.categories)
+        }
+    }
 }
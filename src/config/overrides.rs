@@ -0,0 +1,162 @@
+//! Per file exemptions from checks.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A range of lines, `1`-based and inclusive, i.e. `"10-20"` or `42`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "LineRangeRepr", into = "LineRangeRepr")]
+pub struct LineRange(RangeInclusive<usize>);
+
+impl LineRange {
+    /// Check if `line` is part of the range.
+    pub fn contains(&self, line: usize) -> bool {
+        self.0.contains(&line)
+    }
+}
+
+impl fmt::Display for LineRange {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.start() == self.0.end() {
+            write!(formatter, "{}", self.0.start())
+        } else {
+            write!(formatter, "{}-{}", self.0.start(), self.0.end())
+        }
+    }
+}
+
+impl FromStr for LineRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |s: &str| {
+            s.trim()
+                .parse::<usize>()
+                .map_err(|e| format!("Invalid line number >{s}<: {e}"))
+        };
+        let (start, end) = if let Some((start, end)) = s.split_once('-') {
+            (parse(start)?, parse(end)?)
+        } else {
+            let line = parse(s)?;
+            (line, line)
+        };
+        if start == 0 || start > end {
+            return Err(format!(
+                "Line range >{s}< must be 1-based and must not be reversed"
+            ));
+        }
+        Ok(Self(start..=end))
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum LineRangeRepr {
+    Line(usize),
+    Range(String),
+}
+
+impl TryFrom<LineRangeRepr> for LineRange {
+    type Error = String;
+
+    fn try_from(repr: LineRangeRepr) -> Result<Self, Self::Error> {
+        match repr {
+            LineRangeRepr::Line(line) => Self::from_str(line.to_string().as_str()),
+            LineRangeRepr::Range(range) => Self::from_str(range.as_str()),
+        }
+    }
+}
+
+impl From<LineRange> for LineRangeRepr {
+    fn from(range: LineRange) -> Self {
+        Self::Range(range.to_string())
+    }
+}
+
+/// Exemptions for all files matching a glob.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileOverride {
+    /// Suggestions starting within these lines are dropped.
+    #[serde(default)]
+    pub lines: Vec<LineRange>,
+    /// Suggestions of these detectors are dropped, given by their namespace,
+    /// i.e. `hunspell` or `nlprules`.
+    #[serde(default)]
+    pub detectors: Vec<String>,
+}
+
+/// Exemptions per file, keyed by a glob matched against the path relative to
+/// the current working directory, i.e. `"tests/fixtures/*.rs"`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OverridesConfig(pub BTreeMap<String, FileOverride>);
+
+impl OverridesConfig {
+    /// No overrides are configured.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// All overrides applicable to the file at `path`.
+    pub fn matching<'a>(&'a self, path: &'a Path) -> impl Iterator<Item = &'a FileOverride> + 'a {
+        let relative = std::env::current_dir()
+            .ok()
+            .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf))
+            .unwrap_or_else(|| path.to_path_buf());
+        self.0
+            .iter()
+            .filter(move |(glob, _)| match glob::Pattern::new(glob) {
+                Ok(pattern) => pattern.matches_path(&relative) || pattern.matches_path(path),
+                Err(e) => {
+                    log::warn!("Invalid override glob >{glob}<: {e}");
+                    false
+                }
+            })
+            .map(|(_, file_override)| file_override)
+    }
+
+    /// Check if a suggestion of detector `namespace`, starting at `line`
+    /// of the file at `path`, shall be dropped.
+    pub fn exempts(&self, path: &Path, namespace: &str, line: usize) -> bool {
+        self.matching(path).any(|file_override| {
+            file_override.lines.iter().any(|range| range.contains(line))
+                || file_override
+                    .detectors
+                    .iter()
+                    .any(|detector| detector.eq_ignore_ascii_case(namespace))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exemptions() {
+        let config: OverridesConfig = toml::from_str(
+            r#"
+"tests/fixtures/*.rs" = { lines = ["3-5", 42] }
+"demo/**/*.rs" = { detectors = ["Hunspell"] }
+"#,
+        )
+        .unwrap();
+        let fixture = Path::new("tests/fixtures/typos.rs");
+        assert!(config.exempts(fixture, "hunspell", 3));
+        assert!(config.exempts(fixture, "nlprules", 5));
+        assert!(config.exempts(fixture, "hunspell", 42));
+        assert!(!config.exempts(fixture, "hunspell", 6));
+
+        let demo = Path::new("demo/src/nested/justone.rs");
+        assert!(config.exempts(demo, "hunspell", 1));
+        assert!(!config.exempts(demo, "nlprules", 1));
+
+        assert!(toml::from_str::<OverridesConfig>(r#""x" = { lines = ["5-3"] }"#).is_err());
+        assert!(toml::from_str::<OverridesConfig>(r#""x" = { lines = [0] }"#).is_err());
+    }
+}
@@ -0,0 +1,33 @@
+//! Repeated words configuration.
+use serde::{Deserialize, Serialize};
+
+/// Parameters of the check for repeated words.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RedundancyConfig {
+    /// Words which may be repeated, i.e. "had had", compared ignoring the
+    /// case.
+    #[serde(default = "default_allow")]
+    pub allow: Vec<String>,
+    /// Also flag consecutive sentences starting with the same word.
+    #[serde(default = "default_sentence_starts")]
+    #[serde(alias = "sentence-starts")]
+    pub sentence_starts: bool,
+}
+
+fn default_allow() -> Vec<String> {
+    vec!["had".to_owned(), "that".to_owned()]
+}
+
+fn default_sentence_starts() -> bool {
+    true
+}
+
+impl Default for RedundancyConfig {
+    fn default() -> Self {
+        Self {
+            allow: default_allow(),
+            sentence_starts: default_sentence_starts(),
+        }
+    }
+}
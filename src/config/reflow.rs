@@ -1,19 +1,280 @@
 //! Reflow configuration.
+use crate::errors::*;
+use crate::CommentVariant;
+use fs_err as fs;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Maximum line length if neither configured nor given by a `rustfmt`
+/// configuration.
+const DEFAULT_MAX_LINE_LENGTH: usize = 80;
+
+/// Configuration file names of `rustfmt`, in the order of precedence.
+const RUSTFMT_CONFIG_FILES: &[&str] = &["rustfmt.toml", ".rustfmt.toml"];
 
 /// Parameters for wrapping doc comments
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ReflowConfig {
-    /// Hard limit for absolute length of lines.
-    #[serde(default)]
+    /// Hard limit for absolute length of lines. Derived from the `rustfmt`
+    /// configuration of the project if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[serde(alias = "max_line_width")]
-    pub(crate) max_line_length: usize,
+    pub(crate) max_line_length: Option<usize>,
+    /// Overrides per comment variant, keyed by its prefix, i.e. `"//!"`.
+    /// Either a maximum line length of its own or `false` to not reflow the
+    /// variant at all.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) variants: BTreeMap<ReflowVariant, VariantReflow>,
+    /// Re-align the columns of markdown tables, which are never reflown.
+    #[serde(default)]
+    #[serde(alias = "align-tables")]
+    pub(crate) align_tables: bool,
 }
 
-impl Default for ReflowConfig {
-    fn default() -> Self {
-        Self {
-            max_line_length: 80,
+impl ReflowConfig {
+    /// The effective maximum line length.
+    pub(crate) fn max_line_length(&self) -> usize {
+        self.max_line_length.unwrap_or(DEFAULT_MAX_LINE_LENGTH)
+    }
+
+    /// The effective maximum line length of chunks of the comment `variant`,
+    /// `None` if the variant is not reflown.
+    pub(crate) fn max_line_length_of(&self, variant: &CommentVariant) -> Option<usize> {
+        let configured = ReflowVariant::of(variant).and_then(|variant| self.variants.get(&variant));
+        match configured {
+            None | Some(VariantReflow::Enabled(true)) => Some(self.max_line_length()),
+            Some(VariantReflow::Enabled(false)) => None,
+            Some(VariantReflow::MaxLineLength(max_line_length)) => Some(*max_line_length),
         }
     }
+
+    /// Derive the maximum line length from the closest `rustfmt.toml` or
+    /// `.rustfmt.toml` in `dir` or its parents, unless set explicitly.
+    pub(crate) fn derive_from_rustfmt(&mut self, dir: &Path) -> Result<()> {
+        if self.max_line_length.is_some() {
+            return Ok(());
+        }
+        let Some(path) = dir
+            .ancestors()
+            .flat_map(|dir| RUSTFMT_CONFIG_FILES.iter().map(move |name| dir.join(name)))
+            .find(|path| path.is_file())
+        else {
+            return Ok(());
+        };
+        let content = fs::read_to_string(&path)?;
+        self.max_line_length = rustfmt_width(&content)
+            .wrap_err_with(|| eyre!("Failed to parse rustfmt config {}", path.display()))?;
+        if let Some(width) = self.max_line_length {
+            log::debug!("Using max line length {width} of {}", path.display());
+        }
+        Ok(())
+    }
+}
+
+/// The comment variants which can be configured individually, named by their
+/// prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ReflowVariant {
+    /// `///`
+    TripleSlash,
+    /// `//!`
+    DoubleSlashEM,
+    /// `/**`
+    SlashAsteriskAsterisk,
+    /// `/*!`
+    SlashAsteriskEM,
+    /// `#[doc = ".."]`
+    MacroDoc,
+    /// `//`
+    DoubleSlash,
+    /// `/*`
+    SlashAsterisk,
+    /// Markdown files
+    CommonMark,
+    /// Manifest entries, i.e. the `description`
+    TomlEntry,
+}
+
+impl ReflowVariant {
+    const ALL: [Self; 9] = [
+        Self::TripleSlash,
+        Self::DoubleSlashEM,
+        Self::SlashAsteriskAsterisk,
+        Self::SlashAsteriskEM,
+        Self::MacroDoc,
+        Self::DoubleSlash,
+        Self::SlashAsterisk,
+        Self::CommonMark,
+        Self::TomlEntry,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::TripleSlash => "///",
+            Self::DoubleSlashEM => "//!",
+            Self::SlashAsteriskAsterisk => "/**",
+            Self::SlashAsteriskEM => "/*!",
+            Self::MacroDoc => "#[doc]",
+            Self::DoubleSlash => "//",
+            Self::SlashAsterisk => "/*",
+            Self::CommonMark => "markdown",
+            Self::TomlEntry => "Cargo.toml",
+        }
+    }
+
+    /// The configurable variant a comment `variant` belongs to, if any.
+    fn of(variant: &CommentVariant) -> Option<Self> {
+        Some(match variant {
+            CommentVariant::TripleSlash => Self::TripleSlash,
+            CommentVariant::DoubleSlashEM => Self::DoubleSlashEM,
+            CommentVariant::SlashAsteriskAsterisk => Self::SlashAsteriskAsterisk,
+            CommentVariant::SlashAsteriskEM => Self::SlashAsteriskEM,
+            CommentVariant::MacroDocEqStr(_, _) | CommentVariant::MacroDocEqMacro => Self::MacroDoc,
+            CommentVariant::DoubleSlash => Self::DoubleSlash,
+            CommentVariant::SlashAsterisk | CommentVariant::SlashStar => Self::SlashAsterisk,
+            CommentVariant::CommonMark => Self::CommonMark,
+            CommentVariant::TomlEntry => Self::TomlEntry,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for ReflowVariant {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ReflowVariant {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|variant| variant.as_str() == s)
+            .ok_or_else(|| {
+                format!(
+                    "Unknown comment variant >{s}<, expected one of `{}`",
+                    Self::ALL.map(|variant| variant.as_str()).join("`, `")
+                )
+            })
+    }
+}
+
+impl TryFrom<String> for ReflowVariant {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_str(s.as_str())
+    }
+}
+
+impl From<ReflowVariant> for String {
+    fn from(variant: ReflowVariant) -> Self {
+        variant.to_string()
+    }
+}
+
+/// The reflow of a comment variant, either enabled or disabled, or enabled
+/// with a maximum line length of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum VariantReflow {
+    Enabled(bool),
+    MaxLineLength(usize),
+}
+
+/// Extract the width `rustfmt` wraps comments at, `comment_width` if comments
+/// are wrapped at all, `max_width` otherwise.
+fn rustfmt_width(content: &str) -> Result<Option<usize>> {
+    let table = toml::from_str::<toml::Table>(content)?;
+    let integer = |key: &str| {
+        table
+            .get(key)
+            .and_then(toml::Value::as_integer)
+            .and_then(|width| usize::try_from(width).ok())
+    };
+    let wrap_comments = table
+        .get("wrap_comments")
+        .and_then(toml::Value::as_bool)
+        .unwrap_or_default();
+    Ok(if wrap_comments {
+        // the `rustfmt` default
+        Some(integer("comment_width").unwrap_or(80))
+    } else {
+        integer("max_width")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rustfmt() {
+        assert_eq!(rustfmt_width("").unwrap(), None);
+        assert_eq!(rustfmt_width("max_width = 100").unwrap(), Some(100));
+        assert_eq!(
+            rustfmt_width("max_width = 100\nwrap_comments = true").unwrap(),
+            Some(80)
+        );
+        assert_eq!(
+            rustfmt_width("max_width = 100\ncomment_width = 90\nwrap_comments = true").unwrap(),
+            Some(90)
+        );
+        assert!(rustfmt_width("max_width = ").is_err());
+
+        let mut config = ReflowConfig {
+            max_line_length: Some(42),
+            ..Default::default()
+        };
+        config
+            .derive_from_rustfmt(Path::new(env!("CARGO_MANIFEST_DIR")))
+            .unwrap();
+        assert_eq!(config.max_line_length(), 42);
+    }
+
+    #[test]
+    fn variants() {
+        let config = toml::from_str::<ReflowConfig>(
+            r##"
+max_line_length = 80
+[variants]
+"//!" = 100
+"#[doc]" = false
+"//" = true
+"##,
+        )
+        .unwrap();
+        assert_eq!(
+            config.max_line_length_of(&CommentVariant::TripleSlash),
+            Some(80)
+        );
+        assert_eq!(
+            config.max_line_length_of(&CommentVariant::DoubleSlashEM),
+            Some(100)
+        );
+        assert_eq!(
+            config.max_line_length_of(&CommentVariant::MacroDocEqStr("#[doc=".to_owned(), 0)),
+            None
+        );
+        assert_eq!(
+            config.max_line_length_of(&CommentVariant::DoubleSlash),
+            Some(80)
+        );
+        assert_eq!(
+            toml::from_str::<ReflowConfig>(&toml::to_string(&config).unwrap())
+                .unwrap()
+                .variants,
+            config.variants
+        );
+
+        let e = toml::from_str::<ReflowConfig>("[variants]\n\"#\" = 80").unwrap_err();
+        assert!(e.to_string().contains("Unknown comment variant >#<"));
+    }
 }
@@ -11,6 +11,14 @@ impl Clone for WrappedRegex {
     }
 }
 
+impl PartialEq for WrappedRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for WrappedRegex {}
+
 impl std::ops::Deref for WrappedRegex {
     type Target = Regex;
     fn deref(&self) -> &Self::Target {
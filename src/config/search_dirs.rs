@@ -44,6 +44,18 @@ impl SearchDirs {
         };
         self.0.iter().chain(chained)
     }
+
+    /// Remove the OS specific search directories, which are part of the
+    /// collection once the paths were sanitized.
+    pub fn remove_os_specific(&mut self) {
+        let os_specific = Vec::from_iter(
+            os_specific_search_dirs()
+                .iter()
+                .flat_map(|dir| [Some(dir.clone()), dir.canonicalize().ok()])
+                .flatten(),
+        );
+        self.0.retain(|dir| !os_specific.contains(dir));
+    }
 }
 
 impl std::convert::AsRef<Vec<PathBuf>> for SearchDirs {
@@ -0,0 +1,69 @@
+//! Severity overrides per finding identifier.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// How a finding is treated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Reported and counted as mistake, which fails the run.
+    #[default]
+    Error,
+    /// Reported only, the run does not fail because of it.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        })
+    }
+}
+
+/// Severities keyed by finding identifiers, i.e. `nlprules/EN_A_VS_AN`, or
+/// whole namespaces such as `reflow`. The most specific one applies, all other
+/// findings are errors.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SeverityConfig(pub BTreeMap<String, Severity>);
+
+impl SeverityConfig {
+    /// The severity of a finding identified by `id`.
+    pub fn of(&self, id: &str) -> Severity {
+        self.0
+            .iter()
+            .filter(|(prefix, _severity)| {
+                id.strip_prefix(prefix.as_str())
+                    .map_or(false, |rest| rest.is_empty() || rest.starts_with('/'))
+            })
+            .max_by_key(|(prefix, _severity)| prefix.len())
+            .map(|(_prefix, severity)| *severity)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn most_specific() {
+        let severity: SeverityConfig = toml::from_str(
+            r#"
+nlprules = "warning"
+"nlprules/EN_A_VS_AN" = "error"
+"reflow/line-too-long" = "warning"
+"#,
+        )
+        .unwrap();
+        assert_eq!(severity.of("nlprules/COMMA_RULE"), Severity::Warning);
+        assert_eq!(severity.of("nlprules/EN_A_VS_AN"), Severity::Error);
+        assert_eq!(severity.of("reflow/line-too-long"), Severity::Warning);
+        assert_eq!(severity.of("reflowing/line-too-long"), Severity::Error);
+        assert_eq!(severity.of("hunspell/unknown-word"), Severity::Error);
+    }
+}
@@ -0,0 +1,96 @@
+//! Project wide suppression of suggestions.
+
+use super::WrappedRegex;
+use serde::{Deserialize, Serialize};
+
+/// Regular expressions to drop suggestions of any checker.
+///
+/// Unlike the `transform_regex` quirk, which only affects the tokens fed to
+/// the dictionary based checkers, these apply to the final suggestions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SuppressConfig {
+    /// Matched against the flagged word, i.e. hex literals or API names.
+    #[serde(default)]
+    pub words: Vec<WrappedRegex>,
+    /// Matched against the description of a suggestion, i.e. to silence a
+    /// particular grammar rule.
+    #[serde(default)]
+    #[serde(alias = "description")]
+    pub descriptions: Vec<WrappedRegex>,
+    /// Finding identifiers, i.e. `nlprules/EN_A_VS_AN`, or whole namespaces
+    /// such as `reflow`.
+    #[serde(default)]
+    pub ids: Vec<String>,
+}
+
+impl SuppressConfig {
+    /// No patterns are configured, nothing will ever be suppressed.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty() && self.descriptions.is_empty() && self.ids.is_empty()
+    }
+
+    /// Check if a finding identified by `id` shall be dropped.
+    pub fn suppresses_id(&self, id: &str) -> bool {
+        self.ids.iter().any(|suppressed| {
+            id.strip_prefix(suppressed.as_str())
+                .map_or(false, |rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+
+    /// Check if a suggestion for `word` with `description` shall be dropped.
+    pub fn suppresses(&self, word: &str, description: Option<&str>) -> bool {
+        fn any_match(patterns: &[WrappedRegex], s: &str) -> bool {
+            patterns.iter().any(|regex| match regex.is_match(s) {
+                Ok(found) => found,
+                Err(e) => {
+                    log::warn!(
+                        "Matching suppression regex >{}< errored: {}",
+                        regex.as_str(),
+                        e
+                    );
+                    false
+                }
+            })
+        }
+        any_match(&self.words, word)
+            || description
+                .map(|description| any_match(&self.descriptions, description))
+                .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suppresses() {
+        let suppress: SuppressConfig = toml::from_str(
+            r#"
+words = ["^0x[0-9a-fA-F]+$", "^[A-Z][a-z]+[A-Z][A-Za-z]*$"]
+descriptions = ["(?i)comma"]
+"#,
+        )
+        .unwrap();
+        assert!(suppress.suppresses("0xDEADBEEF", None));
+        assert!(suppress.suppresses("HashMap", None));
+        assert!(!suppress.suppresses("Hashmap", None));
+        assert!(suppress.suppresses("foo", Some("Use a Comma here.")));
+        assert!(!suppress.suppresses("foo", Some("Possible spelling mistake found.")));
+    }
+
+    #[test]
+    fn suppresses_id() {
+        let suppress: SuppressConfig = toml::from_str(
+            r#"
+ids = ["reflow", "nlprules/EN_A_VS_AN"]
+"#,
+        )
+        .unwrap();
+        assert!(suppress.suppresses_id("reflow/line-too-long"));
+        assert!(suppress.suppresses_id("nlprules/EN_A_VS_AN"));
+        assert!(!suppress.suppresses_id("nlprules/EN_A_VS_AND"));
+        assert!(!suppress.suppresses_id("hunspell/unknown-word"));
+    }
+}
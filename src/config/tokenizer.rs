@@ -0,0 +1,69 @@
+//! Segmentation of the text into words for the spelling checkers.
+
+use super::WrappedRegex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+fn default_apostrophes() -> String {
+    "'".to_owned()
+}
+
+const fn yes() -> bool {
+    true
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TokenizerConfig {
+    /// An `nlprule` tokenizer binary for the language of the documentation,
+    /// i.e. `de_tokenizer.bin`. The builtin english one is used if omitted.
+    #[serde(default)]
+    #[serde(alias = "override-tokenizer")]
+    pub override_tokenizer: Option<PathBuf>,
+    /// Characters which join the surrounding letters into one word, i.e. to
+    /// add the typographic `’` besides `'`.
+    #[serde(default = "default_apostrophes")]
+    pub apostrophes: String,
+    /// Split elided articles and pronouns off the following word, i.e.
+    /// `l'homme` into `l'` and `homme` as common in french or italian.
+    #[serde(default)]
+    pub elision: bool,
+    /// Skip URLs, emails, file paths, version strings and hashes before the
+    /// dictionary lookup.
+    #[serde(default = "yes")]
+    #[serde(alias = "skip-non-prose")]
+    pub skip_non_prose: bool,
+    /// Additional patterns of text to skip, matched against the whole text
+    /// rather than individual tokens, so they may span multiple words.
+    #[serde(default)]
+    #[serde(alias = "skip-patterns")]
+    pub skip_patterns: Vec<WrappedRegex>,
+}
+
+impl Default for TokenizerConfig {
+    fn default() -> Self {
+        Self {
+            override_tokenizer: None,
+            apostrophes: default_apostrophes(),
+            elision: false,
+            skip_non_prose: true,
+            skip_patterns: Vec::new(),
+        }
+    }
+}
+
+impl TokenizerConfig {
+    pub(crate) fn is_apostrophe(&self, s: &str) -> bool {
+        let mut chars = s.chars();
+        matches!((chars.next(), chars.next()), (Some(c), None) if self.apostrophes.contains(c))
+    }
+
+    /// Resolve a relative tokenizer path relative to `base`.
+    pub fn sanitize_paths(&mut self, base: &Path) {
+        if let Some(ref mut path) = self.override_tokenizer {
+            if !path.is_absolute() {
+                *path = base.join(&path);
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Selection of workspace members.
+
+use serde::{Deserialize, Serialize};
+
+/// Select which members of a workspace are checked.
+///
+/// By default all members listed in the workspace manifest are checked.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceConfig {
+    /// Only check members whose package name matches one of these globs.
+    /// All members are checked if empty.
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Skip members whose package name matches one of these globs, i.e. fuzz
+    /// targets. Takes precedence over `include`.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Skip members which are marked `publish = false`.
+    #[serde(default)]
+    #[serde(alias = "skip-unpublished")]
+    pub skip_unpublished: bool,
+}
+
+impl WorkspaceConfig {
+    /// Check if the member with package name `name` is to be checked.
+    pub fn is_selected(&self, name: &str) -> bool {
+        fn any_match(globs: &[String], name: &str) -> bool {
+            globs.iter().any(|glob| match glob::Pattern::new(glob) {
+                Ok(pattern) => pattern.matches(name),
+                Err(e) => {
+                    log::warn!("Invalid workspace member glob >{glob}<: {e}");
+                    false
+                }
+            })
+        }
+        if any_match(&self.exclude, name) {
+            return false;
+        }
+        self.include.is_empty() || any_match(&self.include, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection() {
+        let config = WorkspaceConfig::default();
+        assert!(config.is_selected("anything"));
+
+        let config = WorkspaceConfig {
+            include: vec!["doc-*".to_owned(), "cargo-spellcheck".to_owned()],
+            exclude: vec!["*-fuzz".to_owned()],
+            skip_unpublished: false,
+        };
+        assert!(config.is_selected("doc-chunks"));
+        assert!(config.is_selected("cargo-spellcheck"));
+        assert!(!config.is_selected("doc-chunks-fuzz"));
+        assert!(!config.is_selected("xyz"));
+    }
+}
@@ -26,6 +26,7 @@ pub mod action;
 mod checker;
 mod config;
 pub mod errors;
+mod logging;
 mod reflow;
 mod suggestion;
 mod tinhat;
@@ -45,7 +46,7 @@ pub use self::tinhat::*;
 
 use self::errors::{bail, Result};
 
-use std::io::Write;
+use std::io::{BufRead, Write};
 
 #[cfg(target_os = "windows")]
 use signal_hook as _;
@@ -85,11 +86,7 @@ pub fn run(args: Args) -> Result<ExitCode> {
         .num_threads(args.job_count())
         .build_global();
 
-    env_logger::Builder::from_env(env_logger::Env::new().filter_or("CARGO_SPELLCHECK", "warn"))
-        .filter_level(args.verbosity())
-        .filter_module("nlprule", log::LevelFilter::Error)
-        .filter_module("mio", log::LevelFilter::Error)
-        .init();
+    logging::init(args.log_format(), args.verbosity());
 
     #[cfg(not(target_os = "windows"))]
     signal_handler(move || {
@@ -98,6 +95,14 @@ pub fn run(args: Args) -> Result<ExitCode> {
         }
     });
 
+    if let Some(id) = &args.explain {
+        let Some(explanation) = suggestion::explain(id) else {
+            bail!("Unknown finding identifier {id}, expected i.e. `hunspell/unknown-word`")
+        };
+        println!("{explanation}");
+        return Ok(ExitCode::Success);
+    }
+
     let (unified, config) = match &args.command {
         Some(Sub::Completions { shell }) => {
             let sink = &mut std::io::stdout();
@@ -105,6 +110,38 @@ pub fn run(args: Args) -> Result<ExitCode> {
             let _ = sink.flush();
             return Ok(ExitCode::Success);
         }
+        Some(Sub::Hook {
+            command: HookSub::Install { force },
+        }) => {
+            let path = action::hook::install_pre_commit(&traverse::cwd()?, *force)?;
+            println!("Installed the pre-commit hook {}", path.display());
+            return Ok(ExitCode::Success);
+        }
+        Some(Sub::Config { validate: true, .. }) => {
+            let Some(path) = args.config_file()? else {
+                println!("No configuration file found, the builtin defaults are used");
+                return Ok(ExitCode::Success);
+            };
+            let findings = action::validate::validate(&path)?;
+            for finding in findings.iter() {
+                println!("{finding}");
+            }
+            let errors = findings
+                .iter()
+                .filter(|finding| finding.severity == action::validate::Severity::Error)
+                .count();
+            println!(
+                "Validated {}: {} error(s), {} warning(s)",
+                path.display(),
+                errors,
+                findings.len() - errors
+            );
+            return Ok(if errors > 0 {
+                ExitCode::Custom(1)
+            } else {
+                ExitCode::Success
+            });
+        }
         _ => args.unified()?,
     };
 
@@ -140,26 +177,184 @@ pub fn run(args: Args) -> Result<ExitCode> {
             }
             Ok(ExitCode::Success)
         }
+        UnifiedArgs::Words {
+            write,
+            dev_comments,
+            dev_comments_for,
+            check_strings,
+            skip_readme,
+            recursive,
+            paths,
+        } => {
+            let dev_comments = traverse::DevComments::new(dev_comments, &dev_comments_for)?;
+            let documents = traverse::extract(
+                paths,
+                recursive,
+                skip_readme,
+                &dev_comments,
+                check_strings,
+                &config,
+            )?;
+            let words = action::words::collect_unknown_words(documents, config)?;
+            match write {
+                Some(path) => {
+                    let added = words.write_to_dictionary(&path)?;
+                    println!("Added {added} words to {}", path.display());
+                }
+                None if words.is_empty() => log::info!("No unknown words found"),
+                None => print!("{words}"),
+            }
+            Ok(ExitCode::Success)
+        }
+        UnifiedArgs::DictSimulate {
+            words,
+            dev_comments,
+            skip_readme,
+            recursive,
+            paths,
+        } => {
+            let dev_comments = traverse::DevComments::new(dev_comments, &config.dev_comments_for)?;
+            let documents = traverse::extract(
+                paths,
+                recursive,
+                skip_readme,
+                &dev_comments,
+                config.check_strings,
+                &config,
+            )?;
+            let simulation =
+                action::simulate::simulate_dictionary_additions(documents, config, &words)?;
+            println!("{simulation}");
+            Ok(ExitCode::Success)
+        }
+        UnifiedArgs::DictLint { aff, dictionaries } => {
+            if dictionaries.is_empty() {
+                bail!("No dictionaries given and no extra dictionaries configured.");
+            }
+            let broken = checker::lint_dictionaries(aff.as_deref(), &dictionaries)?;
+            if broken > 0 {
+                Ok(ExitCode::Custom(1))
+            } else {
+                Ok(ExitCode::Success)
+            }
+        }
+        UnifiedArgs::FetchDictionaries {
+            lang,
+            mirror,
+            register,
+        } => {
+            let dest = action::fetch::dictionary_dir()?;
+            let (dic, aff) = action::fetch::fetch_dictionary(lang, &mirror, &dest)?;
+            println!("Fetched {} and {}", dic.display(), aff.display());
+            if let Some(path) = register {
+                let mut target = Config::load_from(&path)?.unwrap_or_default();
+                action::fetch::register_dictionary(&mut target, lang, &dest);
+                target.write_values_to_path(&path)?;
+                println!("Registered {lang} in {}", path.display());
+            }
+            Ok(ExitCode::Success)
+        }
         UnifiedArgs::Operate {
             action,
-            paths,
+            mut paths,
             recursive,
             skip_readme,
             config_path,
             dev_comments,
+            dev_comments_for,
+            check_strings,
             exit_code_override,
+            output_format,
+            report_verbosity,
+            ci_annotations,
+            reports,
+            max_issues,
+            paths_from_stdin,
+            no_lock,
+            progress,
+            backup,
+            verify,
         } => {
             log::debug!("Executing: {action:?} with {config:?} from {config_path:?}");
 
-            let documents =
-                traverse::extract(paths, recursive, skip_readme, dev_comments, &config)?;
+            if paths_from_stdin {
+                for line in std::io::stdin().lock().lines() {
+                    let line = line?;
+                    let line = line.trim();
+                    if !line.is_empty() {
+                        paths.push(std::path::PathBuf::from(line));
+                    }
+                }
+                // i.e. a pre-commit hook without any relevant staged files
+                if paths.is_empty() {
+                    log::info!("No paths given via stdin, nothing to check");
+                    return Ok(ExitCode::Success);
+                }
+            }
+
+            // acquire before the documents are loaded, so the spans are not
+            // invalidated by another instance writing changes in between
+            let _lock = if action.modifies_files() && !no_lock {
+                let root = traverse::workspace_root(&traverse::cwd()?);
+                Some(action::lock::WorkspaceLock::acquire(root)?)
+            } else {
+                None
+            };
+
+            let dev_comments = traverse::DevComments::new(dev_comments, &dev_comments_for)?;
+            let extraction = traverse::Extraction::new(
+                paths,
+                recursive,
+                skip_readme,
+                dev_comments,
+                check_strings,
+                &config,
+            )?;
+
+            let render = RenderOptions {
+                verbosity: report_verbosity,
+                provenance: config.provenance(config_path.as_deref()),
+                hyperlink: (!config.hyperlink.is_empty()
+                    && supports_hyperlinks::on(supports_hyperlinks::Stream::Stdout))
+                .then(|| config.hyperlink.clone()),
+                context_lines: config.context_lines,
+                severities: config.severity.clone(),
+            };
+
+            // like `cargo fmt --check`, pending changes always fail
+            let reflow_check = action == Action::ReflowCheck;
 
             let rt = tokio::runtime::Runtime::new()?;
-            let finish = rt.block_on(async move { action.run(documents, config).await })?;
+            let finish = rt.block_on(async move {
+                action
+                    .run(
+                        extraction,
+                        config,
+                        output_format,
+                        render,
+                        ci_annotations.provider(),
+                        reports,
+                        max_issues,
+                        progress,
+                        backup,
+                        verify,
+                    )
+                    .await
+            })?;
 
             match finish {
                 Finish::Success | Finish::MistakeCount(0) => Ok(ExitCode::Success),
-                Finish::MistakeCount(_n) => Ok(ExitCode::Custom(exit_code_override)),
+                Finish::Checked(summary) if summary.mistakes() == 0 => Ok(ExitCode::Success),
+                Finish::Checked(summary) if max_issues.is_some() => Ok(ExitCode::Custom(
+                    exit_code_override.resolve(&summary.per_detector).max(1),
+                )),
+                Finish::MistakeCount(_n) if reflow_check => {
+                    Ok(ExitCode::Custom(exit_code_override.fallback().max(1)))
+                }
+                Finish::MistakeCount(_n) => Ok(ExitCode::Custom(exit_code_override.fallback())),
+                Finish::Checked(summary) => Ok(ExitCode::Custom(
+                    exit_code_override.resolve(&summary.per_detector),
+                )),
                 Finish::Abort => Ok(ExitCode::Signal),
             }
         }
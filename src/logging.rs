@@ -0,0 +1,62 @@
+//! Setup of the logger, emitting either free form text or JSON lines.
+
+use crate::LogFormat;
+
+use std::io::Write;
+
+/// Serialize a single log record as JSON object on a single line.
+fn json_line(timestamp: &str, level: log::Level, module: &str, message: &str) -> String {
+    serde_json::json!({
+        "timestamp": timestamp,
+        "level": level.as_str(),
+        "module": module,
+        "message": message,
+    })
+    .to_string()
+}
+
+/// Initialize the global logger, `CARGO_SPELLCHECK` refines the given `level`.
+pub(crate) fn init(format: LogFormat, level: log::LevelFilter) {
+    let mut builder =
+        env_logger::Builder::from_env(env_logger::Env::new().filter_or("CARGO_SPELLCHECK", "warn"));
+    builder
+        .filter_level(level)
+        .filter_module("nlprule", log::LevelFilter::Error)
+        .filter_module("mio", log::LevelFilter::Error);
+    match format {
+        LogFormat::Text => {}
+        LogFormat::Json => {
+            builder.format(|buf, record| {
+                let line = json_line(
+                    &buf.timestamp_millis().to_string(),
+                    record.level(),
+                    record.module_path().unwrap_or_else(|| record.target()),
+                    &record.args().to_string(),
+                );
+                writeln!(buf, "{line}")
+            });
+        }
+    }
+    builder.init();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_lines() {
+        let line = json_line(
+            "2024-01-01T00:00:00.000Z",
+            log::Level::Warn,
+            "cargo_spellcheck::traverse",
+            "Failed to \"read\"\nfoo.rs",
+        );
+        assert!(!line.contains('\n'));
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["timestamp"], "2024-01-01T00:00:00.000Z");
+        assert_eq!(value["level"], "WARN");
+        assert_eq!(value["module"], "cargo_spellcheck::traverse");
+        assert_eq!(value["message"], "Failed to \"read\"\nfoo.rs");
+    }
+}
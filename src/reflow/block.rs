@@ -0,0 +1,232 @@
+//! Reflow of block comments, `/* .. */` and their documentation variants.
+//!
+//! Block comments commonly come with a gutter of leading `*`, which the
+//! commonmark parser would mistake for list items. Paragraphs are hence
+//! determined line by line after stripping the gutter, and continuation lines
+//! re-use the gutter of the paragraph.
+
+use super::{Gluon, Indentation};
+use crate::documentation::CheckableChunk;
+use crate::errors::Result;
+use crate::util::{byte_range_to_char_range, byte_range_to_char_range_many, extract_delimiter};
+use crate::{ContentOrigin, Detector, Range, Span, Suggestion};
+
+use pulldown_cmark::{Event, Options, Parser, Tag};
+
+/// A line of a block comment paragraph.
+#[derive(Debug, Clone)]
+struct Line<'s> {
+    /// Leading whitespace and gutter, empty for the first line of the comment.
+    gutter: &'s str,
+    /// Content of the line without gutter and trailing whitespace.
+    content: &'s str,
+    /// Byte range of `content` within the chunk.
+    bytes: Range,
+}
+
+/// Split the leading gutter off a line, the first line of a block comment has
+/// none, it directly follows `/*`.
+fn split_gutter(raw: &str, first: bool, guttered: bool) -> (&str, &str) {
+    let trimmed = raw.trim_start();
+    let mut gutter_len = raw.len() - trimmed.len();
+    if !first && guttered {
+        if let Some(rest) = trimmed.strip_prefix('*') {
+            gutter_len += 1;
+            if rest.starts_with(' ') {
+                gutter_len += 1;
+            }
+        }
+    }
+    raw.split_at(gutter_len)
+}
+
+/// Lines which start a commonmark block that must not be merged with
+/// surrounding lines.
+fn is_block_marker(content: &str) -> bool {
+    const MARKERS: &[&str] = &["#", ">", "|", "<", "- ", "+ ", "* "];
+    if content == "-" || content == "*" || content == "+" {
+        return true;
+    }
+    // link reference definitions
+    if content.starts_with('[') && content.contains("]:") {
+        return true;
+    }
+    if MARKERS.iter().any(|marker| content.starts_with(marker)) {
+        return true;
+    }
+    // ordered list items, `1.` or `1)`
+    let digits = content.chars().take_while(char::is_ascii_digit).count();
+    digits > 0
+        && content[digits..]
+            .chars()
+            .next()
+            .map(|c| c == '.' || c == ')')
+            .unwrap_or_default()
+}
+
+/// Split the chunk of a block comment into paragraphs which can be reflown.
+fn split_paragraphs(s: &str) -> Vec<Vec<Line<'_>>> {
+    let raw_lines = s
+        .split('\n')
+        .map(|raw| raw.strip_suffix('\r').unwrap_or(raw))
+        .collect::<Vec<_>>();
+
+    // the gutter style is only assumed if it is used consistently
+    let mut continuation = raw_lines
+        .iter()
+        .skip(1)
+        .filter(|raw| !raw.trim().is_empty())
+        .peekable();
+    let guttered =
+        continuation.peek().is_some() && continuation.all(|raw| raw.trim_start().starts_with('*'));
+
+    let mut acc = Vec::new();
+    let mut current = Vec::new();
+    let mut fenced = false;
+    let mut offset = 0_usize;
+    for (idx, (full, raw)) in s.split('\n').zip(raw_lines).enumerate() {
+        let line_offset = offset;
+        offset += full.len() + 1;
+
+        let (gutter, content) = split_gutter(raw, idx == 0, guttered);
+        // an indented code block, only detectable with a gutter
+        let indented = guttered && idx > 0 && content.starts_with("    ");
+        let trimmed = content.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fenced = !fenced;
+        }
+        if fenced
+            || indented
+            || trimmed.is_empty()
+            || trimmed.starts_with("```")
+            || trimmed.starts_with("~~~")
+            || is_block_marker(trimmed)
+        {
+            if !current.is_empty() {
+                acc.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let start = line_offset + gutter.len() + (content.len() - content.trim_start().len());
+        current.push(Line {
+            gutter: if idx == 0 { "" } else { gutter },
+            content: trimmed,
+            bytes: start..(start + trimmed.len()),
+        });
+        // hard line breaks end a paragraph
+        if content.ends_with("  ") || content.ends_with('\\') {
+            acc.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        acc.push(current);
+    }
+    acc
+}
+
+/// Byte ranges of inline content which must not be broken up.
+fn unbreakables(s: &str) -> Vec<Range> {
+    let mut acc: Vec<Range> = Vec::new();
+    for (event, cover) in Parser::new_ext(s, Options::all()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Link { .. })
+            | Event::Start(Tag::Image { .. })
+            | Event::Code(_)
+            | Event::InlineHtml(_)
+            | Event::Html(_)
+            | Event::FootnoteReference(_) => {}
+            _ => continue,
+        }
+        // nested ones are covered by their parent already
+        match acc.last() {
+            Some(parent) if parent.end >= cover.end => {}
+            _ => acc.push(cover),
+        }
+    }
+    acc
+}
+
+/// Reflow all paragraphs of a block comment chunk.
+pub(crate) fn reflow_block<'s>(
+    origin: &ContentOrigin,
+    chunk: &'s CheckableChunk,
+    max_line_width: usize,
+) -> Result<Vec<Suggestion<'s>>> {
+    let s = chunk.as_str();
+    let line_delimiter = extract_delimiter(s).unwrap_or("\n");
+    let paragraphs = split_paragraphs(s);
+
+    // used for paragraphs that start right after `/*` and have no gutter to
+    // follow
+    let fallback_gutter = paragraphs
+        .iter()
+        .flatten()
+        .map(|line| line.gutter)
+        .find(|gutter| gutter.contains('*'));
+
+    let mut acc = Vec::with_capacity(paragraphs.len());
+    for paragraph in paragraphs {
+        let (first, last) = match (paragraph.first(), paragraph.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => continue,
+        };
+        let range = byte_range_to_char_range(s, first.bytes.start..last.bytes.end)
+            .expect("Must have alignment to byte boundaries. qed");
+        let spans = chunk.find_spans(range.clone());
+        let span = match (spans.values().next(), spans.values().last()) {
+            (Some(start), Some(end)) => Span {
+                start: start.start,
+                end: end.end,
+            },
+            _ => continue,
+        };
+
+        let gutter = paragraph
+            .get(1)
+            .or_else(|| paragraph.first().filter(|line| !line.gutter.is_empty()))
+            .map(|line| line.gutter.to_owned())
+            .or_else(|| fallback_gutter.map(ToOwned::to_owned))
+            .unwrap_or_else(|| " ".repeat(span.start.column));
+
+        let joined = paragraph
+            .iter()
+            .map(|line| line.content)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let indentations = vec![
+            Indentation::new(span.start.column),
+            Indentation::new(gutter.chars().count()),
+        ];
+        let mut gluon = Gluon::new(joined.as_str(), max_line_width, &indentations);
+        gluon.add_unbreakables(byte_range_to_char_range_many(
+            joined.as_str(),
+            &unbreakables(joined.as_str()),
+        ));
+        let reflown = gluon
+            .map(|(_lineno, content, _range)| content)
+            .collect::<Vec<_>>();
+
+        if reflown
+            .iter()
+            .map(String::as_str)
+            .eq(paragraph.iter().map(|line| line.content))
+        {
+            continue;
+        }
+
+        let replacement = reflown.join(&(line_delimiter.to_owned() + &gutter));
+        acc.push(Suggestion {
+            chunk,
+            detector: Detector::Reflow,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
+            origin: origin.clone(),
+            description: None,
+            range,
+            replacements: vec![replacement],
+            span,
+        });
+    }
+    Ok(acc)
+}
@@ -0,0 +1,254 @@
+//! Reflow of string values of a `Cargo.toml` manifest, i.e. `description`.
+//!
+//! A value exceeding the line width is turned into a multi-line basic string,
+//! where each line but the last one ends with a line ending backslash, so the
+//! value itself remains the same.
+
+use super::{Gluon, Indentation};
+use crate::documentation::CheckableChunk;
+use crate::errors::Result;
+use crate::util::extract_delimiter;
+use crate::{ContentOrigin, Detector, LineColumn, Range, Span, Suggestion};
+
+use fs_err as fs;
+
+/// String delimiters of toml, the longer ones first.
+const DELIMITERS: &[&str] = &["\"\"\"", "'''", "\"", "'"];
+
+/// A toml string within the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TomlString<'a> {
+    /// Byte range of the string including its delimiters.
+    bytes: Range,
+    delimiter: &'static str,
+    /// The content between the delimiters.
+    raw: &'a str,
+}
+
+/// Locate the toml string whose content starts at byte `offset`.
+///
+/// The content either starts after the opening delimiter, possibly after a
+/// line break, or at the opening delimiter itself.
+fn locate_string(content: &str, offset: usize) -> Option<TomlString<'_>> {
+    let before = content[..offset]
+        .trim_end()
+        .trim_end_matches('\\')
+        .trim_end();
+    let (start, delimiter) = if let Some(delimiter) = DELIMITERS
+        .iter()
+        .find(|delimiter| before.ends_with(**delimiter))
+    {
+        (before.len() - delimiter.len(), *delimiter)
+    } else {
+        let delimiter = DELIMITERS
+            .iter()
+            .find(|delimiter| content[offset..].starts_with(**delimiter))?;
+        (offset, *delimiter)
+    };
+    let content_start = start + delimiter.len();
+    let literal = delimiter.starts_with('\'');
+
+    let mut end = None;
+    let mut escaped = false;
+    for (idx, c) in content[content_start..].char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        if c == '\\' && !literal {
+            escaped = true;
+            continue;
+        }
+        let rest = &content[(content_start + idx)..];
+        if rest.starts_with(delimiter) {
+            // up to two quotes are allowed right before the closing delimiter
+            let surplus = if delimiter.len() == 3 {
+                rest[3..].chars().take_while(|&q| q == c).take(2).count()
+            } else {
+                0
+            };
+            end = Some(content_start + idx + surplus);
+            break;
+        }
+    }
+    let end = end?;
+    Some(TomlString {
+        bytes: start..(end + delimiter.len()),
+        delimiter,
+        raw: &content[content_start..end],
+    })
+}
+
+/// The value of a toml string escaped for use within a basic string, `None`
+/// if it contains line breaks, which would not survive the reflow.
+fn single_line_value(s: &TomlString<'_>) -> Option<String> {
+    let raw = if s.delimiter.len() == 3 {
+        // a line break right after the opening delimiter is trimmed
+        s.raw
+            .strip_prefix("\r\n")
+            .or_else(|| s.raw.strip_prefix('\n'))
+            .unwrap_or(s.raw)
+    } else {
+        s.raw
+    };
+    let value = if s.delimiter.starts_with('\'') {
+        raw.replace('\\', "\\\\").replace('"', "\\\"")
+    } else {
+        let mut acc = String::with_capacity(raw.len());
+        let mut rest = raw;
+        while let Some(idx) = rest.find('\\') {
+            acc.push_str(&rest[..idx]);
+            let after = &rest[(idx + 1)..];
+            let trimmed = after.trim_start_matches([' ', '\t']);
+            if trimmed.starts_with('\n') || trimmed.starts_with("\r\n") {
+                // line ending backslash, all whitespace up to the next
+                // content is trimmed
+                rest = after.trim_start();
+            } else {
+                // an escape sequence, which is retained as is
+                let mut chars = after.chars();
+                acc.push('\\');
+                acc.extend(chars.next());
+                rest = chars.as_str();
+            }
+        }
+        acc.push_str(rest);
+        acc
+    };
+    if value.contains('\n') || value.contains('\r') {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Convert a byte offset into a `LineColumn`, with 1-based lines and 0-based
+/// columns in characters.
+fn line_column(content: &str, offset: usize) -> LineColumn {
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or_default();
+    LineColumn {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count(),
+    }
+}
+
+/// Convert a `LineColumn` into a byte offset.
+fn byte_offset(content: &str, lc: LineColumn) -> Option<usize> {
+    let line_start = if lc.line <= 1 {
+        0
+    } else {
+        content.match_indices('\n').nth(lc.line - 2)?.0 + 1
+    };
+    let column = content[line_start..]
+        .char_indices()
+        .nth(lc.column)
+        .map(|(idx, _c)| idx)?;
+    Some(line_start + column)
+}
+
+/// Reflow a string value of a `Cargo.toml` manifest.
+///
+/// Values which fit into `max_line_width` or contain line breaks are left as
+/// they are.
+pub(crate) fn reflow_manifest_entry<'s>(
+    origin: &ContentOrigin,
+    chunk: &'s CheckableChunk,
+    max_line_width: usize,
+) -> Result<Vec<Suggestion<'s>>> {
+    let first = match chunk.find_spans(0..chunk.len_in_chars()).values().next() {
+        Some(span) => *span,
+        None => return Ok(Vec::new()),
+    };
+    let content = fs::read_to_string(origin.as_path())?;
+    let string = match byte_offset(&content, first.start)
+        .and_then(|offset| locate_string(&content, offset))
+    {
+        Some(string) => string,
+        None => {
+            log::debug!(
+                "Failed to locate the toml string of {:?} in {}",
+                first,
+                origin
+            );
+            return Ok(Vec::new());
+        }
+    };
+    let value = match single_line_value(&string) {
+        Some(value) => value,
+        None => return Ok(Vec::new()),
+    };
+
+    let start = line_column(&content, string.bytes.start);
+    let end = line_column(&content, string.bytes.end - 1);
+    let fits = if start.line == end.line {
+        end.column < max_line_width
+    } else {
+        start.column + value.chars().count() + 2 <= max_line_width
+    };
+    if fits {
+        return Ok(Vec::new());
+    }
+
+    let line_delimiter = extract_delimiter(&content).unwrap_or("\n");
+    // leave room for the line ending backslash or the closing delimiter
+    let indentations = [Indentation::new(0)];
+    let gluon = Gluon::new(
+        value.as_str(),
+        max_line_width.saturating_sub(3).max(1),
+        &indentations,
+    );
+    let reflown = gluon
+        .map(|(_lineno, line, _range)| line)
+        .collect::<Vec<_>>();
+    let replacement = format!(
+        "\"\"\"{line_delimiter}{}\"\"\"",
+        reflown.join(&format!(" \\{line_delimiter}"))
+    );
+    if replacement == content[string.bytes.clone()] {
+        return Ok(Vec::new());
+    }
+
+    Ok(vec![Suggestion {
+        chunk,
+        detector: Detector::Reflow,
+        agreeing: Vec::new(),
+        rule: None,
+        category: None,
+        origin: origin.clone(),
+        description: None,
+        range: 0..chunk.len_in_chars(),
+        replacements: vec![replacement],
+        span: Span { start, end },
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values() {
+        const CONTENT: &str = r#"[package]
+a = "plain \"quoted\""
+b = 'C:\path'
+c = """
+first \
+  second"""
+d = """""quoted"""""
+"#;
+        let values = Vec::from_iter(["a = ", "b = ", "c = ", "d = "].into_iter().map(|key| {
+            let offset = CONTENT.find(key).unwrap() + key.len();
+            single_line_value(&locate_string(CONTENT, offset).unwrap()).unwrap()
+        }));
+        assert_eq!(
+            values,
+            vec![
+                r#"plain \"quoted\""#,
+                r#"C:\\path"#,
+                "first second",
+                r#"""quoted"""#
+            ]
+        );
+    }
+}
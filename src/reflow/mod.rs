@@ -17,7 +17,10 @@ use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
 
 pub use crate::config::ReflowConfig;
 
+mod block;
 mod iter;
+mod manifest;
+mod table;
 pub use iter::Gluon;
 
 #[derive(Debug)]
@@ -48,13 +51,33 @@ impl Checker for Reflow {
     {
         let mut acc = Vec::with_capacity(chunks.len());
         for chunk in chunks {
-            match chunk.variant() {
+            let variant = chunk.variant();
+            let Some(max_line_length) = self.config.max_line_length_of(&variant) else {
+                continue;
+            };
+            let suggestions = match variant {
+                CommentVariant::StringLiteral
+                | CommentVariant::TomlComment
+                | CommentVariant::YamlEntry
+                | CommentVariant::Synthetic
+                | CommentVariant::PlainText
+                | CommentVariant::AsciiDoc
+                | CommentVariant::ForeignComment => continue,
                 CommentVariant::SlashAsterisk
                 | CommentVariant::SlashAsteriskAsterisk
-                | CommentVariant::SlashAsteriskEM => continue,
-                _ => {}
-            }
-            let suggestions = reflow(origin, chunk, &self.config)?;
+                | CommentVariant::SlashAsteriskEM
+                | CommentVariant::SlashStar => block::reflow_block(origin, chunk, max_line_length)?,
+                CommentVariant::TomlEntry => {
+                    manifest::reflow_manifest_entry(origin, chunk, max_line_length)?
+                }
+                _ => {
+                    let mut suggestions = reflow(origin, chunk, max_line_length)?;
+                    if self.config.align_tables {
+                        suggestions.extend(table::align_tables(origin, chunk)?);
+                    }
+                    suggestions
+                }
+            };
             acc.extend(suggestions);
         }
         Ok(acc)
@@ -70,12 +93,15 @@ impl Checker for Reflow {
 /// `CheckableChunk`. `unbreakable_ranges` contains all ranges of
 /// words/sequences which must not be split during the reflow. They are relative
 /// to the top-level `CheckableChunk` similar to `range`. The indentation vector
-/// contains the indentation for each line in `s`.
+/// contains the indentation for each line in `s`, including the `hanging`
+/// indentation which is inserted after the comment prefix of all but the first
+/// line, i.e. to align continuation lines of list items.
 fn reflow_inner<'s>(
     s: &'s str,
     range: Range,
     unbreakable_ranges: &[Range],
     indentations: &[Indentation<'s>],
+    hanging: usize,
     max_line_width: usize,
     variant: &CommentVariant,
 ) -> Result<Option<String>> {
@@ -139,7 +165,9 @@ fn reflow_inner<'s>(
         // which is being removed by the transformation `s` to `s_absolute`
         // that removes the leading space.
         let (indentation_skip_n, extra_space) = match variant {
-            CommentVariant::TripleSlash | CommentVariant::DoubleSlashEM => {
+            CommentVariant::TripleSlash
+            | CommentVariant::DoubleSlashEM
+            | CommentVariant::DoubleSlash => {
                 let n = variant.prefix_len();
                 (n + 1, " ")
             }
@@ -150,13 +178,14 @@ fn reflow_inner<'s>(
         } else {
             &last_indent
         }
-        .to_string_but_skip_n(indentation_skip_n);
+        .to_string_but_skip_n(indentation_skip_n + hanging);
 
-        log::trace!(target: "glue", "glue[shift={}]: acc = {:?} + {:?} + {:?} + {:?} + {:?} + {:?}",
+        log::trace!(target: "glue", "glue[shift={}]: acc = {:?} + {:?} + {:?} + {:?} + {:?} + {:?} + {:?}",
                 indentation_skip_n,
                 pre,
                 variant.prefix_string(),
                 extra_space,
+                hanging,
                 content,
                 variant.suffix_string(),
                 line_delimiter
@@ -164,6 +193,7 @@ fn reflow_inner<'s>(
         acc.push_str(&pre);
         acc.push_str(&variant.prefix_string());
         acc.push_str(extra_space);
+        acc.push_str(&" ".repeat(hanging));
         acc.push_str(&content);
         acc.push_str(&variant.suffix_string());
         acc.push_str(line_delimiter);
@@ -183,7 +213,12 @@ fn reflow_inner<'s>(
             .strip_suffix(&variant.suffix_string())
             .map(|content| content.to_owned())
             .unwrap_or_else(|| content);
-        if &CommentVariant::CommonMark == variant && last_char_is_newline && !content.is_empty() {
+        // list items do not cover the trailing newline, unlike paragraphs
+        if &CommentVariant::CommonMark == variant
+            && last_char_is_newline
+            && s_absolute.ends_with(line_delimiter)
+            && !content.is_empty()
+        {
             content.push_str(line_delimiter)
         }
 
@@ -244,6 +279,32 @@ impl<'s> Indentation<'s> {
     }
 }
 
+/// Find the first line of `paragraph` which is a link reference definition,
+/// i.e. `[ff]: https://..`, returns its byte offset.
+///
+/// A reference definition can not interrupt a paragraph, so commonmark treats
+/// it as part of the preceding paragraph. It must neither be wrapped nor
+/// merged with it though, since that breaks the link.
+fn reference_definition(paragraph: &str) -> Option<usize> {
+    let mut offset = 0;
+    for line in paragraph.split_inclusive('\n') {
+        let definition = line
+            .trim_start()
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once("]:"))
+            .map_or(false, |(label, target)| {
+                !label.trim().is_empty()
+                    && !label.contains(['[', ']'])
+                    && (target.is_empty() || target.starts_with(char::is_whitespace))
+            });
+        if definition {
+            return Some(offset);
+        }
+        offset += line.len();
+    }
+    None
+}
+
 /// Collect reflown Paragraphs in a `Vec` of `Suggestions`.
 ///
 /// Note: Leading spaces are skipped by the CommonMark parser, which implies for
@@ -251,6 +312,9 @@ impl<'s> Indentation<'s> {
 /// ` (note the space here).
 ///
 ///
+/// For list items, `list_item` must be set, such that continuation lines are
+/// aligned with the first character after the list marker.
+///
 /// Returns: end of processed range and Suggestion, if reflow happened.
 fn store_suggestion<'s>(
     chunk: &'s CheckableChunk,
@@ -258,13 +322,30 @@ fn store_suggestion<'s>(
     bytes_paragraph: usize,
     bytes_end: usize,
     bytes_unbreakable_ranges: &[Range],
+    list_item: bool,
     max_line_width: usize,
 ) -> Result<(usize, Option<Suggestion<'s>>)> {
+    let s = chunk.as_str();
+    // only the lines before a reference definition are reflown
+    let processed = bytes_end;
+    let bytes_end = match reference_definition(&s[bytes_paragraph..bytes_end.max(bytes_paragraph)])
+    {
+        Some(offset) => {
+            let end = bytes_paragraph
+                + s[bytes_paragraph..(bytes_paragraph + offset)]
+                    .trim_end()
+                    .len();
+            if end <= bytes_paragraph {
+                return Ok((processed, None));
+            }
+            end
+        }
+        None => bytes_end,
+    };
     let bytes_range = Range {
         start: bytes_paragraph,
         end: bytes_end,
     };
-    let s = chunk.as_str();
     #[cfg(debug_assertions)]
     let sb = s.as_bytes();
 
@@ -285,12 +366,29 @@ fn store_suggestion<'s>(
     // might be shifted, so the start in those cases must be shifted back
     // to right after `///`, which is done by substracting one.
     let adjustment = match chunk.variant() {
-        CommentVariant::DoubleSlashEM | CommentVariant::TripleSlash => 1usize,
+        CommentVariant::DoubleSlashEM
+        | CommentVariant::TripleSlash
+        | CommentVariant::DoubleSlash => 1usize,
         _ => 0usize,
     };
 
     debug_assert_eq!(&s[bytes_range], sub_char_range(s, range.clone()));
 
+    // the hanging indentation is the distance of the paragraph start to the
+    // start of its line, excluding the space that follows `///` and `//!`
+    let hanging = if list_item {
+        let line_start = s[..bytes_paragraph]
+            .rfind('\n')
+            .map(|idx| idx + 1)
+            .unwrap_or_default();
+        s[line_start..bytes_paragraph]
+            .chars()
+            .count()
+            .saturating_sub(adjustment)
+    } else {
+        0
+    };
+
     let range2span = chunk.find_spans(range.clone());
     let mut spans_iter = range2span.iter().map(|(_range, span)| *span);
 
@@ -335,11 +433,14 @@ fn store_suggestion<'s>(
             // but the following leading whitespaces of literals in the same
             // chunk are still present, yet they are not part of the prefix
             // as defined by the `CommentVariant` for `///` and `//!`.
+            // The first line already starts after the list marker, all
+            // following ones are shifted by the hanging indentation.
             let col = span
                 .start
                 .column
                 .saturating_sub(adjustment * (first as usize))
-                + adjustment;
+                + adjustment
+                + hanging * (!first as usize);
             let indentation = Indentation::new(col);
             first = false;
             vec![indentation; span.end.line.saturating_sub(span.start.line) + 1]
@@ -347,18 +448,22 @@ fn store_suggestion<'s>(
         .collect::<Vec<Indentation>>();
 
     Ok((
-        bytes_end,
+        processed,
         reflow_inner(
             chunk.as_str(),
             range.clone(),
             unbreakable_ranges,
             &indentations,
+            hanging,
             max_line_width,
             &chunk.variant(),
         )?
         .map(|replacement| Suggestion {
             chunk,
             detector: Detector::Reflow,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
             origin: origin.clone(),
             description: None,
             range,
@@ -373,7 +478,7 @@ fn store_suggestion<'s>(
 fn reflow<'s>(
     origin: &ContentOrigin,
     chunk: &'s CheckableChunk,
-    cfg: &ReflowConfig,
+    max_line_length: usize,
 ) -> Result<Vec<Suggestion<'s>>> {
     log::debug!("Reflowing {origin:?}");
     let parser = Parser::new_ext(chunk.as_str(), Options::all());
@@ -388,6 +493,16 @@ fn reflow<'s>(
     let mut acc = Vec::with_capacity(128);
 
     let mut within_quote = false;
+    // tables and footnote definitions are kept as they are, no matter how
+    // wide, until the end of the block
+    let mut untouched = None::<TagEnd>;
+
+    // nesting depth of list items
+    let mut items = 0_usize;
+    // the text of a tight list item is not wrapped in a paragraph, so track it
+    // until the item ends or a nested block starts
+    let mut item_text = false;
+    let mut inline_end = 0_usize;
 
     for (event, cover) in parser.into_offset_iter() {
         #[cfg(debug_assertions)]
@@ -398,8 +513,17 @@ fn reflow<'s>(
                 &chunk.as_str()[cover.clone()]
             );
         }
+        if let Some(end) = untouched {
+            if event == Event::End(end) {
+                untouched = None;
+                paragraph = cover.end;
+            }
+            continue;
+        }
         match event {
-            Event::InlineHtml(_html) => {}
+            Event::InlineHtml(_html) => {
+                inline_end = cover.end;
+            }
             Event::Start(tag) => {
                 if within_quote {
                     continue;
@@ -410,25 +534,49 @@ fn reflow<'s>(
                     | Tag::Strong
                     | Tag::Emphasis
                     | Tag::Strikethrough
-                    | Tag::BlockQuote
-                    | Tag::Table(..) => {
+                    | Tag::BlockQuote => {
                         unbreakable_stack.push(cover);
                         if tag == Tag::BlockQuote {
                             within_quote = true;
                         }
                     }
                     Tag::Paragraph => {
+                        // a loose list item
+                        item_text = false;
                         paragraph = cover.start;
                     }
-                    _ => {
-                        // all of these break a reflow-able chunk
+                    Tag::Item => {
+                        // the item content starts after the list marker, the
+                        // item may include the space following `///`
+                        let item = &chunk.as_str()[cover.start..];
+                        let marker = item.trim_start_matches([' ', '\t']);
+                        let marker_start = item.len() - marker.len();
+                        let marker_len = marker.find(char::is_whitespace).unwrap_or(marker.len());
+                        let whitespace_len = marker[marker_len..].len()
+                            - marker[marker_len..].trim_start_matches([' ', '\t']).len();
+                        paragraph = cover.start + marker_start + marker_len + whitespace_len;
+                        inline_end = paragraph;
+                        item_text = true;
+                        items += 1;
+                    }
+                    tag => {
+                        // all of these break a reflow-able chunk, including the
+                        // text of a tight list item preceding a nested list
+                        untouched = match tag {
+                            Tag::Table(..) => Some(TagEnd::Table),
+                            Tag::FootnoteDefinition(..) => Some(TagEnd::FootnoteDefinition),
+                            _ => None,
+                        };
+                        let end = if item_text { inline_end } else { paragraph };
+                        item_text = false;
                         let (p, suggestion) = store_suggestion(
                             chunk,
                             origin,
                             paragraph,
-                            paragraph,
+                            end.max(paragraph),
                             unbreakables.as_slice(),
-                            cfg.max_line_length,
+                            items > 0,
+                            max_line_length,
                         )?;
                         paragraph = p;
                         if let Some(suggestion) = suggestion {
@@ -448,12 +596,11 @@ fn reflow<'s>(
                     | TagEnd::Strong
                     | TagEnd::Emphasis
                     | TagEnd::Strikethrough
-                    | TagEnd::BlockQuote
-                    | TagEnd::Table => {
+                    | TagEnd::BlockQuote => {
                         // technically we only need the bottom-most range, since all others - by def - are contained in there
                         // so there
                         if unbreakable_stack.len() == 1 {
-                            unbreakables.push(cover);
+                            unbreakables.push(cover.clone());
                         } else if let Some(parent) = unbreakable_stack.last() {
                             debug_assert!(parent.contains(&cover.start));
                             debug_assert!(parent.contains(&(cover.end - 1)));
@@ -462,6 +609,7 @@ fn reflow<'s>(
                         if tag == TagEnd::BlockQuote {
                             within_quote = false;
                         }
+                        inline_end = cover.end;
                     }
                     TagEnd::Paragraph => {
                         // regular end of paragraph
@@ -471,7 +619,8 @@ fn reflow<'s>(
                             paragraph,
                             cover.end,
                             unbreakables.as_slice(),
-                            cfg.max_line_length,
+                            items > 0,
+                            max_line_length,
                         )?;
                         paragraph = p;
                         if let Some(suggestion) = suggestion {
@@ -479,14 +628,38 @@ fn reflow<'s>(
                         }
                         unbreakable_stack.clear();
                     }
+                    TagEnd::Item => {
+                        if item_text && inline_end > paragraph {
+                            // end of a tight list item
+                            let (_p, suggestion) = store_suggestion(
+                                chunk,
+                                origin,
+                                paragraph,
+                                inline_end,
+                                unbreakables.as_slice(),
+                                true,
+                                max_line_length,
+                            )?;
+                            if let Some(suggestion) = suggestion {
+                                acc.push(suggestion);
+                            }
+                            unbreakable_stack.clear();
+                        }
+                        item_text = false;
+                        items = items.saturating_sub(1);
+                        paragraph = cover.end;
+                    }
                     _ => {
                         paragraph = cover.end;
                     }
                 }
             }
-            Event::Text(_s) => {}
+            Event::Text(_s) => {
+                inline_end = cover.end;
+            }
             Event::Code(_s) => {
                 // always make code unbreakable
+                inline_end = cover.end;
                 unbreakables.push(cover);
             }
             Event::Html(_s) => {
@@ -494,6 +667,7 @@ fn reflow<'s>(
                 // TODO verify this does not interfere with paragraphs
             }
             Event::FootnoteReference(_s) => {
+                inline_end = cover.end;
                 unbreakables.push(cover);
             }
             Event::SoftBreak => {
@@ -506,7 +680,8 @@ fn reflow<'s>(
                     paragraph,
                     cover.end,
                     unbreakables.as_slice(),
-                    cfg.max_line_length,
+                    items > 0,
+                    max_line_length,
                 )?;
                 paragraph = p;
                 if let Some(suggestion) = suggestion {
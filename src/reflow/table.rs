@@ -0,0 +1,217 @@
+//! Re-alignment of the columns of commonmark tables.
+//!
+//! Tables are never reflown, but editing a cell, i.e. fixing a spelling
+//! mistake, leaves the pipes of the affected row misaligned. Each row is padded
+//! to the widest cell of each column, keeping the style of the outer pipes.
+
+use crate::documentation::CheckableChunk;
+use crate::errors::Result;
+use crate::util::byte_range_to_char_range;
+use crate::{ContentOrigin, Detector, Suggestion};
+
+use pulldown_cmark::{Alignment, Event, Options, Parser, Tag};
+
+/// Split a table row into its trimmed cells, an escaped `\|` does not
+/// separate cells. Returns if the row starts and ends with a pipe as well.
+fn split_row(row: &str) -> (Vec<&str>, bool, bool) {
+    let leading = row.starts_with('|');
+    let inner = if leading { &row[1..] } else { row };
+    let trailing = inner.ends_with('|') && !inner.ends_with("\\|");
+    let inner = if trailing {
+        &inner[..(inner.len() - 1)]
+    } else {
+        inner
+    };
+    let mut cells = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (idx, c) in inner.char_indices() {
+        match c {
+            '|' if !escaped => {
+                cells.push(inner[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    cells.push(inner[start..].trim());
+    (cells, leading, trailing)
+}
+
+/// Align the `rows` of a table, the second one being the delimiter row,
+/// according to the column `alignments`.
+fn align_rows(rows: &[&str], alignments: &[Alignment]) -> Vec<String> {
+    let rows = Vec::from_iter(rows.iter().map(|row| split_row(row)));
+    let Some((_cells, leading, trailing)) = rows.first() else {
+        return Vec::new();
+    };
+    let (leading, trailing) = (*leading, *trailing);
+    let columns = alignments.len();
+    let mut widths = vec![1usize; columns];
+    for (idx, (cells, _leading, _trailing)) in rows.iter().enumerate() {
+        if idx == 1 {
+            continue;
+        }
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let last = columns.saturating_sub(1);
+    Vec::from_iter(rows.iter().enumerate().map(|(idx, (cells, _, _))| {
+        let mut row = String::new();
+        if idx == 1 {
+            // the dashes span the padding around the cells as well
+            if leading {
+                row.push('|');
+            }
+            for (column, (width, alignment)) in widths.iter().zip(alignments).enumerate() {
+                let padding =
+                    usize::from(leading || column > 0) + usize::from(trailing || column < last);
+                let dashes = width + padding;
+                let (left, right) = match alignment {
+                    Alignment::None => (false, false),
+                    Alignment::Left => (true, false),
+                    Alignment::Center => (true, true),
+                    Alignment::Right => (false, true),
+                };
+                let colons = usize::from(left) + usize::from(right);
+                if left {
+                    row.push(':');
+                }
+                row.push_str(&"-".repeat(dashes.saturating_sub(colons).max(1)));
+                if right {
+                    row.push(':');
+                }
+                if column < last || trailing {
+                    row.push('|');
+                }
+            }
+            return row;
+        }
+        if leading {
+            row.push_str("| ");
+        }
+        for (column, (width, alignment)) in widths.iter().zip(alignments).enumerate() {
+            let cell = cells.get(column).copied().unwrap_or_default();
+            let fill = width.saturating_sub(cell.chars().count());
+            let (before, after) = match alignment {
+                Alignment::Right => (fill, 0),
+                Alignment::Center => (fill / 2, fill - fill / 2),
+                Alignment::None | Alignment::Left => (0, fill),
+            };
+            row.push_str(&" ".repeat(before));
+            row.push_str(cell);
+            row.push_str(&" ".repeat(after));
+            if column < last {
+                row.push_str(" | ");
+            } else if trailing {
+                row.push_str(" |");
+            }
+        }
+        // cells beyond the columns of the header are kept as they are
+        for cell in cells.iter().skip(columns) {
+            row.push_str(" | ");
+            row.push_str(cell);
+        }
+        row.truncate(row.trim_end().len());
+        row
+    }))
+}
+
+/// Suggest re-aligned rows for all misaligned ones of the tables within
+/// `chunk`, one suggestion per row.
+pub(crate) fn align_tables<'s>(
+    origin: &ContentOrigin,
+    chunk: &'s CheckableChunk,
+) -> Result<Vec<Suggestion<'s>>> {
+    let s = chunk.as_str();
+    let mut acc = Vec::new();
+    let parser = Parser::new_ext(s, Options::all());
+    for (event, cover) in parser.into_offset_iter() {
+        let Event::Start(Tag::Table(alignments)) = event else {
+            continue;
+        };
+        // the rows, without leading and trailing whitespace
+        let mut rows = Vec::new();
+        let mut offset = cover.start;
+        for line in s[cover.clone()].split('\n') {
+            let content = line.trim();
+            if !content.is_empty() {
+                let start = offset + (line.len() - line.trim_start().len());
+                rows.push((start..(start + content.len()), content));
+            }
+            offset += line.len() + 1;
+        }
+        let aligned = align_rows(
+            &Vec::from_iter(rows.iter().map(|(_bytes, row)| *row)),
+            &alignments,
+        );
+        for ((bytes, row), aligned) in rows.into_iter().zip(aligned) {
+            if row == aligned {
+                continue;
+            }
+            let Some(range) = byte_range_to_char_range(s, bytes) else {
+                continue;
+            };
+            let Some(span) = chunk.find_spans(range.clone()).values().next().copied() else {
+                continue;
+            };
+            acc.push(Suggestion {
+                chunk,
+                detector: Detector::Reflow,
+                agreeing: Vec::new(),
+                rule: None,
+                category: None,
+                origin: origin.clone(),
+                description: Some("Misaligned table row".to_owned()),
+                range,
+                replacements: vec![aligned],
+                span,
+            });
+        }
+    }
+    Ok(acc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aligned_stays() {
+        // the LTO table of the demo
+        const ROWS: &[&str] = &[
+            "Variant            | Flag                   | Object Code | Bitcode",
+            "-------------------|------------------------|-------------|--------",
+            "`Run`              | `-C lto=foo`           | n/a         | n/a",
+            "`Off`              | `-C lto=off`           | n/a         | n/a",
+            "`OnlyBitcode`      | `-C linker-plugin-lto` |             | ✓",
+            "`ObjectAndBitcode` |                        | ✓           | ✓",
+            "`OnlyObject`       | `-C embed-bitcode=no`  | ✓           |",
+        ];
+        assert_eq!(align_rows(ROWS, &[Alignment::None; 4]), ROWS);
+    }
+
+    #[test]
+    fn realign() {
+        assert_eq!(
+            align_rows(
+                &[
+                    "| Name | Value |",
+                    "|:-|-:|",
+                    "| Fixed spelling | 1 |",
+                    "| a \\| b | 42 |"
+                ],
+                &[Alignment::Left, Alignment::Right]
+            ),
+            vec![
+                "| Name           | Value |",
+                "|:---------------|------:|",
+                "| Fixed spelling |     1 |",
+                "| a \\| b         |    42 |",
+            ]
+        );
+    }
+}
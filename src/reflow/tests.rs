@@ -1,7 +1,10 @@
 use super::*;
+use crate::config::{ReflowVariant, VariantReflow};
+use crate::tests::TempDir;
 use crate::util::load_span_from;
 use crate::{chyrp_up, fluff_up};
 use crate::{Documentation, LineColumn, Span};
+use std::collections::BTreeMap;
 
 macro_rules! verify_reflow_inner {
     ($n:literal break [ $( $line:literal ),+ $(,)?] => $expected:literal) => {
@@ -29,6 +32,7 @@ macro_rules! verify_reflow_inner {
             range,
             &unbreakables,
             &indentation,
+            0,
             $n,
             &chunk.variant()
         );
@@ -78,7 +82,9 @@ fn reflow_inner_not_required() {
 macro_rules! reflow_content {
     ($max_line_width:literal break $content_type:expr, $content:expr => applied $expected:literal) => {
         const CFG: ReflowConfig = ReflowConfig {
-            max_line_length: $max_line_width,
+            max_line_length: Some($max_line_width),
+            variants: BTreeMap::new(),
+            align_tables: false,
         };
 
         let _ = env_logger::Builder::new()
@@ -93,7 +99,7 @@ macro_rules! reflow_content {
         assert_eq!(dbg!(chunks).len(), 1);
         let chunk = &chunks[0];
         let _plain = chunk.erase_cmark(&Default::default());
-        let suggestions = reflow(&$content_type, chunk, &CFG).expect("Reflow is working. qed");
+        let suggestions = reflow(&$content_type, chunk, CFG.max_line_length()).expect("Reflow is working. qed");
 
         let patches = suggestions
             .into_iter()
@@ -122,7 +128,9 @@ macro_rules! reflow_content {
     };
     ($max_line_width:literal break $content_type:expr, $content:expr => ok) => {
         const CFG: ReflowConfig = ReflowConfig {
-            max_line_length: $max_line_width,
+            max_line_length: Some($max_line_width),
+            variants: BTreeMap::new(),
+            align_tables: false,
         };
 
         let _ = env_logger::Builder::new()
@@ -137,7 +145,7 @@ macro_rules! reflow_content {
         assert_eq!(dbg!(chunks).len(), 1);
         let chunk = &chunks[0];
         let _plain = chunk.erase_cmark(&Default::default());
-        let suggestions = reflow(&$content_type, chunk, &CFG).expect("Reflow is working. qed");
+        let suggestions = reflow(&$content_type, chunk, CFG.max_line_length()).expect("Reflow is working. qed");
 
         assert_eq!(
             dbg!(suggestions).len(),
@@ -147,7 +155,9 @@ macro_rules! reflow_content {
     };
     ($max_line_width:literal break $content_type:expr, $content:expr => patches [ $( $expected:literal ),+ $(,)?]) => {
         const CFG: ReflowConfig = ReflowConfig {
-            max_line_length: $max_line_width,
+            max_line_length: Some($max_line_width),
+            variants: BTreeMap::new(),
+            align_tables: false,
         };
 
         let _ = env_logger::Builder::new()
@@ -163,7 +173,7 @@ macro_rules! reflow_content {
         let chunk = &chunks[0];
         let _plain = chunk.erase_cmark(&Default::default());
         println!("reflow content:\n {:?}", $content);
-        let suggestions = reflow(&$content_type, chunk, &CFG).expect("Reflow is working. qed");
+        let suggestions = reflow(&$content_type, chunk, CFG.max_line_length()).expect("Reflow is working. qed");
         let patches = suggestions
             .into_iter()
             .filter_map(|suggestion| {
@@ -285,7 +295,9 @@ fn reflow_indentations() {
     /// 🤔"#;
 
     const CONFIG: ReflowConfig = ReflowConfig {
-        max_line_length: 10,
+        max_line_length: Some(10),
+        variants: BTreeMap::new(),
+        align_tables: false,
     };
 
     let docs = Documentation::load_from_str(ContentOrigin::TestEntityRust, CONTENT, true, false);
@@ -296,8 +308,12 @@ fn reflow_indentations() {
     assert_eq!(dbg!(chunks).len(), 1);
     let chunk = &chunks[0];
 
-    let suggestion_set =
-        reflow(&ContentOrigin::TestEntityRust, chunk, &CONFIG).expect("Reflow is wokring. qed");
+    let suggestion_set = reflow(
+        &ContentOrigin::TestEntityRust,
+        chunk,
+        CONFIG.max_line_length(),
+    )
+    .expect("Reflow is wokring. qed");
 
     let suggestion = suggestion_set
         .first()
@@ -333,10 +349,11 @@ fn reflow_doc_indentations() {
     let chunk = &chunks[0];
 
     let cfg = ReflowConfig {
-        max_line_length: 45,
+        max_line_length: Some(45),
+        ..Default::default()
     };
-    let suggestion_set =
-        reflow(&ContentOrigin::TestEntityRust, chunk, &cfg).expect("Reflow is working. qed");
+    let suggestion_set = reflow(&ContentOrigin::TestEntityRust, chunk, cfg.max_line_length())
+        .expect("Reflow is working. qed");
 
     let suggestions = suggestion_set
         .first()
@@ -403,11 +420,12 @@ fn reflow_markdown_two_paragraphs() {
     let chunk = &chunks[0];
 
     let cfg = ReflowConfig {
-        max_line_length: 60,
+        max_line_length: Some(60),
+        ..Default::default()
     };
 
-    let suggestion_set =
-        reflow(&ContentOrigin::TestEntityRust, chunk, &cfg).expect("Reflow is working. qed");
+    let suggestion_set = reflow(&ContentOrigin::TestEntityRust, chunk, cfg.max_line_length())
+        .expect("Reflow is working. qed");
 
     for (sug, expected) in suggestion_set.iter().zip(expected) {
         assert_eq!(sug.replacements.len(), 1);
@@ -442,12 +460,13 @@ With a second part that is fine"#
         .expect("Contains test data. qed");
 
     let cfg = ReflowConfig {
-        max_line_length: 45,
+        max_line_length: Some(45),
+        ..Default::default()
     };
 
     for (chunk, expect) in chunks.iter().zip(expected) {
-        let suggestion_set =
-            reflow(&ContentOrigin::TestEntityRust, chunk, &cfg).expect("Reflow is working. qed");
+        let suggestion_set = reflow(&ContentOrigin::TestEntityRust, chunk, cfg.max_line_length())
+            .expect("Reflow is working. qed");
         let sug = suggestion_set.first().expect("Contains a suggestion. qed");
         let replacement = sug
             .replacements
@@ -477,10 +496,31 @@ fn reflow_doc_long() {
 #[doc=r#"another line."##);
 }
 
+#[test]
+fn reflow_list_item() {
+    reflow_fluff!(30 break ["- A list item which is too long for one line"]
+        => "A list item which is too\n///   long for one line");
+}
+
+#[test]
+fn reflow_nested_list_items() {
+    const CONTENT: &str =
+        "- A list item which is way too long to fit into a single line of the configured width.
+  - A nested item which is also rather long and needs a reflow.";
+
+    reflow_content!(40 break ContentOrigin::TestEntityCommonMark, CONTENT => applied "- A list item which is way too long to
+  fit into a single line of the
+  configured width.
+  - A nested item which is also rather
+    long and needs a reflow.");
+}
+
 #[test]
 fn reflow_sole_markdown() {
     const CONFIG: ReflowConfig = ReflowConfig {
-        max_line_length: 60,
+        max_line_length: Some(60),
+        variants: BTreeMap::new(),
+        align_tables: false,
     };
 
     const CONTENT: &str = "# Possible __ways__ to run __rustc__ and request various parts of LTO.
@@ -538,8 +578,12 @@ multiline. Fullstop."#,
     assert_eq!(dbg!(chunks).len(), 1);
     let chunk = chunks.first().unwrap();
 
-    let suggestion_set = reflow(&ContentOrigin::TestEntityCommonMark, &chunk, &CONFIG)
-        .expect("Reflow is working. qed");
+    let suggestion_set = reflow(
+        &ContentOrigin::TestEntityCommonMark,
+        chunk,
+        CONFIG.max_line_length(),
+    )
+    .expect("Reflow is working. qed");
     assert_eq!(suggestion_set.len(), 2);
 
     for (sug, &(expected_content, expected_span)) in suggestion_set.iter().zip(EXPECTED.iter()) {
@@ -582,7 +626,9 @@ fn reflow_line_delimiters() {
 #[test]
 fn reflow_check_span() {
     const CONFIG: ReflowConfig = ReflowConfig {
-        max_line_length: 27,
+        max_line_length: Some(27),
+        variants: BTreeMap::new(),
+        align_tables: false,
     };
 
     const CONTENT: &str = "/// A comment as we have many here and we will always
@@ -606,8 +652,12 @@ struct Fff;
     assert_eq!(dbg!(chunks).len(), 1);
     let chunk = chunks.first().unwrap();
 
-    let suggestion_set =
-        reflow(&ContentOrigin::TestEntityRust, &chunk, &CONFIG).expect("Reflow is working. qed");
+    let suggestion_set = reflow(
+        &ContentOrigin::TestEntityRust,
+        chunk,
+        CONFIG.max_line_length(),
+    )
+    .expect("Reflow is working. qed");
     assert_eq!(suggestion_set.len(), 1);
     let suggestion = suggestion_set
         .first()
@@ -697,7 +747,9 @@ configured with `-m <code>` to
 return a non-zero return code
 if mistakes are found instead
 of `0`.
-"##
+"##,
+r##"[x] Parse doc comments from
+  arbitrary files"##
     ]);
 }
 
@@ -723,7 +775,8 @@ of `0`.
 
 ## Implemented Features + Roadmap
 
-* [x] Parse doc comments from arbitrary files
+* [x] Parse doc comments from
+  arbitrary files
 * [x] Decent error printing
 
 ## Footy
@@ -757,3 +810,227 @@ fn reflow_crlf() {
         "cargo spellcheck can be\r\n        /// configured with `-m <code>`\r\n        /// to return a non-zero return\r\n        /// code."
     ]);
 }
+
+/// Run the `Reflow` checker, which dispatches on the comment variant, and
+/// return the replacements of all suggestions.
+fn reflow_checked(content: &str, dev_comments: bool, max_line_length: usize) -> Vec<String> {
+    let docs = Documentation::load_from_str(
+        ContentOrigin::TestEntityRust,
+        content,
+        !dev_comments,
+        dev_comments,
+    );
+    let chunks = docs
+        .get(&ContentOrigin::TestEntityRust)
+        .expect("Contains test data. qed");
+    assert_eq!(dbg!(chunks).len(), 1);
+
+    let reflow = Reflow::new(ReflowConfig {
+        max_line_length: Some(max_line_length),
+        ..Default::default()
+    })
+    .expect("Reflow is working. qed");
+    reflow
+        .check(&ContentOrigin::TestEntityRust, chunks)
+        .expect("Reflow is working. qed")
+        .into_iter()
+        .map(|suggestion| {
+            suggestion
+                .replacements
+                .first()
+                .expect("Reflow always provides a replacement string. qed")
+                .to_owned()
+        })
+        .collect()
+}
+
+#[test]
+fn reflow_block_comment_gutter() {
+    const CONTENT: &str = "/**
+ * A block comment which is way too long to fit into a single line.
+ */
+struct Fluff;";
+
+    assert_eq!(
+        reflow_checked(CONTENT, false, 40),
+        vec!["A block comment which is way too long\n * to fit into a single line."]
+    );
+}
+
+#[test]
+fn reflow_block_comment_keeps_lists() {
+    const CONTENT: &str = "/**
+ * - A list item which is left alone, even if it is too long.
+ * - Another one.
+ */
+struct Fluff;";
+
+    assert!(reflow_checked(CONTENT, false, 40).is_empty());
+}
+
+#[test]
+fn reflow_dev_comment() {
+    const CONTENT: &str = "// A developer comment which is too long
+// for one line.
+struct Fluff;";
+
+    assert_eq!(
+        reflow_checked(CONTENT, true, 30),
+        vec!["A developer comment which\n// is too long for one line."]
+    );
+}
+
+#[test]
+fn reflow_manifest_description() {
+    const CONTENT: &str = r#"[package]
+name = "fluff"
+description = "A description which is \"way\" too long to fit into a single line."
+"#;
+    let dir = TempDir::new();
+    let path = dir.join("Cargo.toml");
+    std::fs::write(&path, CONTENT).unwrap();
+
+    let mut docs = Documentation::new();
+    docs.add_cargo_manifest_fields(path.clone(), CONTENT, &["package.description"])
+        .unwrap();
+    let origin = ContentOrigin::CargoManifestDescription(path);
+    let chunks = docs.get(&origin).expect("Contains the description. qed");
+
+    let reflow = Reflow::new(ReflowConfig {
+        max_line_length: Some(40),
+        ..Default::default()
+    })
+    .expect("Reflow is working. qed");
+    let suggestions = reflow
+        .check(&origin, chunks)
+        .expect("Reflow is working. qed");
+
+    assert_eq!(suggestions.len(), 1);
+    let suggestion = &suggestions[0];
+    assert_eq!(
+        suggestion.span,
+        Span {
+            start: LineColumn {
+                line: 3,
+                column: 14
+            },
+            end: LineColumn {
+                line: 3,
+                column: 81
+            },
+        }
+    );
+    assert_eq!(
+        suggestion.replacements,
+        vec![
+            r#""""
+A description which is \"way\" too \
+long to fit into a single line.""""#
+        ]
+    );
+}
+
+#[test]
+fn reflow_per_variant() {
+    const CONTENT: &str = r#"//! A crate level comment which is long enough to be reflown at forty.
+
+#![doc = "An attribute which is long enough to be reflown at forty as well."]
+
+/// An item comment which is long enough to be reflown at forty.
+struct Foo;
+"#;
+    let docs = Documentation::load_from_str(ContentOrigin::TestEntityRust, CONTENT, true, false);
+    let chunks = docs
+        .get(&ContentOrigin::TestEntityRust)
+        .expect("Contains test data. qed");
+
+    let mut config = ReflowConfig {
+        max_line_length: Some(40),
+        ..Default::default()
+    };
+    config.variants.insert(
+        ReflowVariant::DoubleSlashEM,
+        VariantReflow::MaxLineLength(100),
+    );
+    config
+        .variants
+        .insert(ReflowVariant::MacroDoc, VariantReflow::Enabled(false));
+    let reflow = Reflow::new(config).expect("Reflow is working. qed");
+    let suggestions = reflow
+        .check(&ContentOrigin::TestEntityRust, chunks)
+        .expect("Reflow is working. qed");
+    // only the `///` comment exceeds its maximum line length
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].span.start.line, 5);
+}
+
+#[test]
+fn reflow_keeps_tables() {
+    const CONTENT: &str = r#"/// A table which is way too wide for the maximum line length.
+///
+/// Variant | Flag | Description
+/// --------|------|------------------------------------------------------
+/// `Run` | `-C lto=foo` | Runs the whole thing, which is a rather long description.
+struct Foo;
+"#;
+    let docs = Documentation::load_from_str(ContentOrigin::TestEntityRust, CONTENT, true, false);
+    let chunks = docs
+        .get(&ContentOrigin::TestEntityRust)
+        .expect("Contains test data. qed");
+
+    let mut config = ReflowConfig {
+        max_line_length: Some(40),
+        ..Default::default()
+    };
+    let reflow = Reflow::new(config.clone()).expect("Reflow is working. qed");
+    let suggestions = reflow
+        .check(&ContentOrigin::TestEntityRust, chunks)
+        .expect("Reflow is working. qed");
+    // only the paragraph above the table
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].span.start.line, 1);
+    assert_eq!(suggestions[0].span.end.line, 1);
+
+    config.align_tables = true;
+    let reflow = Reflow::new(config).expect("Reflow is working. qed");
+    let suggestions = reflow
+        .check(&ContentOrigin::TestEntityRust, chunks)
+        .expect("Reflow is working. qed");
+    let rows = Vec::from_iter(
+        suggestions
+            .iter()
+            .filter(|suggestion| suggestion.span.start.line > 1)
+            .map(|suggestion| suggestion.replacements[0].as_str()),
+    );
+    assert_eq!(
+        rows,
+        vec![
+            "Variant | Flag         | Description",
+            "--------|--------------|----------------------------------------------------------",
+            "`Run`   | `-C lto=foo` | Runs the whole thing, which is a rather long description.",
+        ]
+    );
+}
+
+#[test]
+fn reflow_reference_definitions() {
+    // directly following a paragraph, the definition is part of it
+    const CONTENT: &str = r#"/// A paragraph referring to [ff] which is long enough to be reflown.
+/// [ff]: https://example.com/a/very/long/path/which/must/never/be/wrapped
+struct Foo;
+"#;
+    let replacements = reflow_checked(CONTENT, false, 40);
+    assert_eq!(replacements.len(), 1);
+    assert!(replacements[0].starts_with("A paragraph referring to [ff]"));
+    assert!(replacements[0].ends_with("be reflown."));
+
+    // on their own, neither definitions nor footnotes are touched
+    const SEPARATE: &str = r#"/// A paragraph referring to [ff] and [^note].
+///
+/// [ff]: https://example.com/a/very/long/path/which/must/never/be/wrapped
+///
+/// [^note]: A footnote which is long enough to be reflown, but is not.
+struct Foo;
+"#;
+    assert_eq!(reflow_checked(SEPARATE, false, 50), Vec::<String>::new());
+}
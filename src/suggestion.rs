@@ -11,17 +11,19 @@
 //!     |     - you can add it to your personal dictionary to prevent future alerts.
 //! ```
 
+use crate::config::{Severity, SeverityConfig};
 use crate::documentation::{CheckableChunk, ContentOrigin};
 
 use std::cmp;
 use std::convert::TryFrom;
+use std::path::PathBuf;
 
 use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 
 use crate::{Range, Span};
 
 /// Bitflag of available checkers by compilation / configuration.
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum Detector {
     /// Hunspell lib based detector.
     Hunspell,
@@ -33,12 +35,45 @@ pub enum Detector {
     NlpRules,
     /// Reflow according to a given max column.
     Reflow,
+    /// Lines exceeding the max column, reported without rewriting them.
+    LineLength,
+    /// Code references in documentation that do not match any identifier.
+    Identifiers,
+    /// Immediately repeated words and sentence starts.
+    Redundancy,
+    /// Capitalization of headings.
+    Headings,
+    /// An external tool, speaking JSON over `stdin` and `stdout`.
+    External,
+    /// Public items without documentation.
+    MissingDocs,
+    /// Duplicate headings and broken anchors of markdown files.
+    Anchors,
+    /// Intra-doc link targets that do not match any identifier.
+    IntraDocLinks,
     /// Detection of nothing, a test helper.
     #[cfg(test)]
     Dummy,
 }
 
 impl Detector {
+    /// All detectors.
+    pub const ALL: &'static [Detector] = &[
+        Self::Hunspell,
+        Self::ZSpell,
+        Self::Spellbook,
+        Self::NlpRules,
+        Self::Reflow,
+        Self::LineLength,
+        Self::Identifiers,
+        Self::Redundancy,
+        Self::Headings,
+        Self::External,
+        Self::MissingDocs,
+        Self::Anchors,
+        Self::IntraDocLinks,
+    ];
+
     /// Converts the detector to its static str representation.
     pub const fn as_str(&self) -> &'static str {
         match self {
@@ -47,10 +82,128 @@ impl Detector {
             Self::Spellbook => "Spellbook",
             Self::NlpRules => "NlpRules",
             Self::Reflow => "Reflow",
+            Self::LineLength => "LineLength",
+            Self::Identifiers => "Identifiers",
+            Self::Redundancy => "Redundancy",
+            Self::Headings => "Headings",
+            Self::External => "External",
+            Self::MissingDocs => "MissingDocs",
+            Self::Anchors => "Anchors",
+            Self::IntraDocLinks => "IntraDocLinks",
             #[cfg(test)]
             Self::Dummy => "Dummy",
         }
     }
+
+    /// The namespace of the finding identifiers of the detector.
+    pub const fn namespace(&self) -> &'static str {
+        match self {
+            Self::Hunspell => "hunspell",
+            Self::ZSpell => "zspell",
+            Self::Spellbook => "spellbook",
+            Self::NlpRules => "nlprules",
+            Self::Reflow => "reflow",
+            Self::LineLength => "linelength",
+            Self::Identifiers => "identifiers",
+            Self::Redundancy => "redundancy",
+            Self::Headings => "headings",
+            Self::External => "external",
+            Self::MissingDocs => "missingdocs",
+            Self::Anchors => "anchors",
+            Self::IntraDocLinks => "intradoclinks",
+            #[cfg(test)]
+            Self::Dummy => "dummy",
+        }
+    }
+
+    /// The rule of a finding, unless the finding provides a more specific one.
+    const fn default_rule(&self) -> &'static str {
+        match self {
+            Self::Hunspell | Self::ZSpell | Self::Spellbook => "unknown-word",
+            Self::NlpRules => "grammar",
+            Self::Reflow | Self::LineLength => "line-too-long",
+            Self::Identifiers => "unknown-identifier",
+            Self::Redundancy => "repeated-word",
+            Self::Headings => "heading-case",
+            Self::External => "finding",
+            Self::MissingDocs => "missing-docs",
+            Self::Anchors => "broken-anchor",
+            Self::IntraDocLinks => "unresolved-link",
+            #[cfg(test)]
+            Self::Dummy => "dummy",
+        }
+    }
+
+    /// Explain the findings of `rule`, `None` if the detector has no such
+    /// rule.
+    pub fn explain(&self, rule: &str) -> Option<&'static str> {
+        Some(match (self, rule) {
+            (Self::Hunspell | Self::ZSpell | Self::Spellbook, "unknown-word") => {
+                "The word is not part of any of the dictionaries. Fix its spelling, or \
+                 add it to an extra dictionary if it is correct, i.e. a project specific \
+                 term."
+            }
+            (Self::NlpRules, _) => {
+                "A grammar or style rule of `nlprule`, derived from LanguageTool, \
+                 identified by its rule id. The description of the finding explains the \
+                 particular rule."
+            }
+            (Self::Reflow | Self::LineLength, "line-too-long") => {
+                "A line of a comment exceeds the maximum line length of the `[Reflow]` \
+                 section, or of the `rustfmt` configuration. `cargo spellcheck reflow` \
+                 wraps such lines."
+            }
+            (Self::Identifiers, "unknown-identifier") => {
+                "Inline code refers to an identifier which does not exist in the checked \
+                 sources, but one which is close to it does."
+            }
+            (Self::Redundancy, "repeated-word") => {
+                "The same word appears twice in a row, i.e. `the the`."
+            }
+            (Self::Redundancy, "repeated-sentence-start") => {
+                "Consecutive sentences start with the same word."
+            }
+            (Self::Headings, "heading-case") => {
+                "A heading does not follow the configured capitalization style of the \
+                 `[Headings]` section."
+            }
+            (Self::External, _) => {
+                "A finding of an external tool configured in the `[Checker]` section, \
+                 identified by the rule the tool reports. See the documentation of the \
+                 tool."
+            }
+            (Self::MissingDocs, "missing-docs") => "A public item has no documentation.",
+            (Self::Anchors, "broken-anchor") => {
+                "A link points to an anchor which no heading or HTML element of the \
+                 document defines."
+            }
+            (Self::Anchors, "duplicate-heading") => {
+                "Multiple headings share the same text, so their anchor is ambiguous."
+            }
+            (Self::IntraDocLinks, "unresolved-link") => {
+                "The target of an intra-doc link, a single item or a path starting at \
+                 `crate::`, `self::`, `super::` or `Self::`, does not exist in the checked \
+                 sources, but one which is close to it does."
+            }
+            _ => return None,
+        })
+    }
+}
+
+/// Explain the findings identified by `id`, i.e. `hunspell/unknown-word`, as
+/// printed by `--explain`. `None` if there are no such findings.
+pub fn explain(id: &str) -> Option<String> {
+    let (namespace, rule) = id.split_once('/')?;
+    let detector = Detector::ALL
+        .iter()
+        .find(|detector| detector.namespace() == namespace)?;
+    let explanation = detector.explain(rule)?;
+    Some(format!(
+        "{id}: {explanation}\n\nReported by {detector}, the severity can be changed in the \
+         `[Severity]` section and the finding suppressed by adding `{id}` to the `ids` \
+         of the `[Suppress]` section.",
+        detector = detector.as_str(),
+    ))
 }
 
 /// Terminal size in characters.
@@ -303,6 +456,13 @@ pub fn condition_display_content(
 pub struct Suggestion<'s> {
     /// Which checker suggested the change.
     pub detector: Detector,
+    /// Further checkers which flagged the same word at an overlapping span.
+    pub agreeing: Vec<Detector>,
+    /// The checker specific rule which triggered, if more specific than the
+    /// detector, i.e. the id of a grammar rule.
+    pub rule: Option<String>,
+    /// The category of the rule, i.e. `grammar` or `style`.
+    pub category: Option<String>,
     /// Reference to the file location the `span` and `literal` relate to.
     pub origin: ContentOrigin,
     /// The suggestion is relative to a specific chunk.
@@ -339,48 +499,468 @@ impl<'s> Suggestion<'s> {
             self.span.start.column < other.span.end.column
         }
     }
+
+    /// Merge `other` into `self` if both flag the same word at overlapping
+    /// spans but stem from different detectors, returns `true` if merged.
+    ///
+    /// The detector of `other` is recorded as agreeing and its replacements
+    /// are appended, skipping duplicates.
+    pub fn merge(&mut self, other: &Self) -> bool {
+        if other.detector == self.detector
+            || self.agreeing.contains(&other.detector)
+            || !self.is_overlapped(other)
+        {
+            return false;
+        }
+        let word = crate::util::sub_chars(self.chunk.as_str(), self.range.clone());
+        let other_word = crate::util::sub_chars(other.chunk.as_str(), other.range.clone());
+        if word != other_word {
+            return false;
+        }
+        for detector in std::iter::once(&other.detector).chain(other.agreeing.iter()) {
+            if *detector != self.detector && !self.agreeing.contains(detector) {
+                self.agreeing.push(*detector);
+            }
+        }
+        for replacement in other.replacements.iter() {
+            if !self.replacements.contains(replacement) {
+                self.replacements.push(replacement.clone());
+            }
+        }
+        true
+    }
+
+    /// All detectors which reported the suggestion, comma separated.
+    pub fn detectors(&self) -> String {
+        std::iter::once(&self.detector)
+            .chain(self.agreeing.iter())
+            .map(|detector| detector.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Stable identifier of the kind of finding, namespaced by the detector,
+    /// i.e. `hunspell/unknown-word` or `nlprules/EN_A_VS_AN`.
+    pub fn id(&self) -> String {
+        format!(
+            "{}/{}",
+            self.detector.namespace(),
+            self.rule
+                .as_deref()
+                .unwrap_or_else(|| self.detector.default_rule())
+        )
+    }
+
+    /// Stable fingerprint of the suggestion, a hash of the path relative to
+    /// the workspace root, the whitespace normalized line and the flagged
+    /// word.
+    ///
+    /// Unlike the location, it remains the same if lines are inserted or
+    /// removed elsewhere in the file, so findings can be tracked across runs,
+    /// started from any directory and on any platform.
+    pub fn fingerprint(&self) -> String {
+        use sha2::Digest;
+
+        let path = self.origin.as_path();
+        let root = crate::traverse::workspace_root(path.parent().unwrap_or(path));
+        let origin = path.strip_prefix(&root).unwrap_or(path);
+        let origin = Vec::from_iter(
+            origin
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy()),
+        )
+        .join("/");
+        let word = crate::util::sub_chars(self.chunk.as_str(), self.range.clone());
+        let line = self
+            .chunk
+            .find_covered_lines(self.range.clone())
+            .first()
+            .map(|line| crate::util::sub_chars(self.chunk.as_str(), line.clone()))
+            .unwrap_or_default();
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut hasher = sha2::Sha256::new();
+        // separated, so the parts can not shift into each other
+        for part in [origin.as_str(), line.as_str(), word.as_str()] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        hex::encode(&hasher.finalize()[..8])
+    }
+
+    /// Path of the file and the line the suggestion starts at, as displayed to
+    /// the user.
+    pub(crate) fn location(&self) -> (String, usize) {
+        let x = self.span.start.line;
+        match self.origin {
+            ContentOrigin::RustDocTest(ref path, ref span) => {
+                (path.display().to_string(), x + span.start.line)
+            }
+            ref origin => (origin.as_path().display().to_string(), x),
+        }
+    }
+
+    /// Fill the `{path}`, `{line}` and `{column}` placeholders of a hyperlink
+    /// `template`, i.e. `vscode://file/{path}:{line}:{column}`, with the
+    /// absolute location of the suggestion.
+    pub fn hyperlink(&self, template: &str) -> String {
+        let (path, line) = self.location();
+        let path = PathBuf::from(path);
+        let path = if path.is_absolute() {
+            path
+        } else {
+            std::env::current_dir()
+                .map(|cwd| cwd.join(&path))
+                .unwrap_or(path)
+        };
+        template
+            .replace("{path}", &path.display().to_string().replace(' ', "%20"))
+            .replace("{line}", &line.to_string())
+            .replace("{column}", &(self.span.start.column + 1).to_string())
+    }
+
+    /// Display the suggestion for humans, with the level of detail given by
+    /// the `options`.
+    pub fn render<'a>(&'a self, options: &'a RenderOptions) -> RenderedSuggestion<'a, 's> {
+        RenderedSuggestion {
+            suggestion: self,
+            options,
+        }
+    }
+
+    /// Display the suggestion as GitHub Actions workflow command, which is
+    /// rendered as inline annotation by GitHub.
+    pub fn github_annotation<'a>(&'a self, severity: Severity) -> GithubAnnotation<'a, 's> {
+        GithubAnnotation(self, severity)
+    }
+
+    /// Display the suggestion as JSON diagnostic like `rustc
+    /// --error-format=json` emits, which can be applied by `rustfix`. The
+    /// `content` of the file is required to determine the byte offsets.
+    pub fn rustfix_diagnostic<'a>(&'a self, content: &'a str) -> RustfixDiagnostic<'a, 's> {
+        RustfixDiagnostic(self, content)
+    }
+}
+
+/// Level of detail of the reported findings.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum ReportVerbosity {
+    /// Only the summary, no individual findings.
+    Quiet,
+    /// The findings with their location, replacements and description.
+    #[default]
+    Normal,
+    /// Additionally the identifier of the rule and which configuration the
+    /// detector was set up with.
+    Verbose,
+}
+
+/// Options for rendering a [`Suggestion`] for humans.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// Level of detail.
+    pub verbosity: ReportVerbosity,
+    /// Where each detector's configuration came from, only shown when
+    /// `Verbose`.
+    pub provenance: indexmap::IndexMap<Detector, String>,
+    /// URL template to render the location as terminal hyperlink with, see
+    /// [`Suggestion::hyperlink`]. Plain text if `None`.
+    pub hyperlink: Option<String>,
+    /// Number of lines shown before and after the offending one.
+    pub context_lines: usize,
+    /// Severity of the findings per identifier.
+    pub severities: SeverityConfig,
+}
+
+/// Display adapter for a [`Suggestion`], rendering it `rustc`-like as
+/// configured by [`RenderOptions`].
+pub struct RenderedSuggestion<'a, 's> {
+    suggestion: &'a Suggestion<'s>,
+    options: &'a RenderOptions,
+}
+
+/// Display adapter for a [`Suggestion`], producing a single line GitHub
+/// Actions `::error` or `::warning` workflow command.
+///
+/// See <https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions#setting-an-error-message>.
+pub struct GithubAnnotation<'a, 's>(&'a Suggestion<'s>, Severity);
+
+impl<'a, 's> GithubAnnotation<'a, 's> {
+    /// Escape the message part of a workflow command.
+    fn escape_data(s: &str) -> String {
+        s.replace('%', "%25")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    }
+
+    /// Escape a property value of a workflow command.
+    fn escape_property(s: &str) -> String {
+        Self::escape_data(s).replace(':', "%3A").replace(',', "%2C")
+    }
+}
+
+impl<'a, 's> fmt::Display for GithubAnnotation<'a, 's> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(suggestion, severity) = self;
+        let (path, line) = suggestion.location();
+        // span columns are zero based and inclusive, annotations are one based
+        let col = suggestion.span.start.column + 1;
+        let end_line = line + suggestion.span.end.line - suggestion.span.start.line;
+        let end_col = suggestion.span.end.column + 1;
+
+        let mut message = suggestion
+            .description
+            .clone()
+            .unwrap_or_else(|| "Possible spelling mistake found.".to_owned());
+        if !suggestion.replacements.is_empty() {
+            message.push_str("\nSuggestions: ");
+            message.push_str(&suggestion.replacements.join(", "));
+        }
+
+        write!(
+            formatter,
+            "::{severity} file={path},line={line},endLine={end_line},col={col},endColumn={end_col},title={title}::{message}",
+            path = Self::escape_property(&path),
+            title = Self::escape_property(&format!("spellcheck({})", suggestion.detectors())),
+            message = Self::escape_data(&message),
+        )
+    }
+}
+
+/// Display adapter for a [`Suggestion`], producing a single line JSON
+/// diagnostic in the format `rustfix` consumes.
+///
+/// See <https://doc.rust-lang.org/rustc/json.html#diagnostics>.
+pub struct RustfixDiagnostic<'a, 's>(&'a Suggestion<'s>, &'a str);
+
+impl<'a, 's> RustfixDiagnostic<'a, 's> {
+    /// Byte offset of the character at the one based `line` and zero based
+    /// `column` in `content`. The end of the line is a valid position.
+    fn byte_offset(content: &str, line: usize, column: usize) -> Option<usize> {
+        let start = if line == 1 {
+            0
+        } else {
+            content
+                .match_indices('\n')
+                .nth(line.checked_sub(2)?)
+                .map(|(offset, _)| offset + 1)?
+        };
+        let text = content[start..].split('\n').next()?;
+        let offset = text
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain(std::iter::once(text.len()))
+            .nth(column)?;
+        Some(start + offset)
+    }
+}
+
+impl<'a, 's> fmt::Display for RustfixDiagnostic<'a, 's> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(suggestion, content) = self;
+        let (path, line_start) = suggestion.location();
+        let line_end = line_start + suggestion.span.end.line - suggestion.span.start.line;
+        // span columns are zero based and inclusive, diagnostics are one based
+        // and exclusive
+        let column_start = suggestion.span.start.column + 1;
+        let column_end = suggestion.span.end.column + 2;
+        let bytes = Self::byte_offset(content, line_start, column_start - 1)
+            .zip(Self::byte_offset(content, line_end, column_end - 1));
+        let (byte_start, byte_end) = bytes.unwrap_or_default();
+
+        let text = Vec::from_iter(
+            content
+                .lines()
+                .enumerate()
+                .skip(line_start.saturating_sub(1))
+                .take(line_end + 1 - line_start)
+                .map(|(idx, text)| {
+                    let highlight_start = if idx + 1 == line_start {
+                        column_start
+                    } else {
+                        1
+                    };
+                    let highlight_end = if idx + 1 == line_end {
+                        column_end
+                    } else {
+                        text.chars().count() + 1
+                    };
+                    serde_json::json!({
+                        "text": text,
+                        "highlight_start": highlight_start,
+                        "highlight_end": highlight_end,
+                    })
+                }),
+        );
+        let span = |replacement: Option<&String>| {
+            // a sole replacement is as good as certain
+            let applicability = replacement.map(|_| match suggestion.replacements.len() {
+                1 => "MachineApplicable",
+                _ => "MaybeIncorrect",
+            });
+            serde_json::json!({
+                "file_name": path,
+                "byte_start": byte_start,
+                "byte_end": byte_end,
+                "line_start": line_start,
+                "line_end": line_end,
+                "column_start": column_start,
+                "column_end": column_end,
+                "is_primary": true,
+                "text": text,
+                "label": null,
+                "suggested_replacement": replacement,
+                "suggestion_applicability": applicability,
+                "expansion": null,
+            })
+        };
+
+        let message = suggestion
+            .description
+            .clone()
+            .unwrap_or_else(|| "Possible spelling mistake found.".to_owned());
+        // without byte offsets, replacements could not be applied
+        let children = Vec::from_iter(
+            suggestion
+                .replacements
+                .iter()
+                .filter(|_| bytes.is_some())
+                .map(|replacement| {
+                    serde_json::json!({
+                        "message": format!("consider `{replacement}`"),
+                        "code": null,
+                        "level": "help",
+                        "spans": [span(Some(replacement))],
+                        "children": [],
+                        "rendered": null,
+                    })
+                }),
+        );
+        let diagnostic = serde_json::json!({
+            "$message_type": "diagnostic",
+            "message": message,
+            "code": {
+                "code": suggestion.id(),
+                "explanation": null,
+            },
+            "level": "warning",
+            "spans": [span(None)],
+            "children": children,
+            "rendered": format!("warning: {message}\n --> {path}:{line_start}:{column_start}\n"),
+        });
+        write!(formatter, "{diagnostic}")
+    }
 }
 
 impl<'s> fmt::Display for Suggestion<'s> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(&RenderOptions::default()).fmt(formatter)
+    }
+}
+
+impl<'a, 's> fmt::Display for RenderedSuggestion<'a, 's> {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         use console::Style;
 
+        let suggestion = self.suggestion;
+
         let highlight = Style::new().bold().white();
-        let error = Style::new().bold().red();
+        let severity = self.options.severities.of(&suggestion.id());
+        let level = match severity {
+            Severity::Error => Style::new().bold().red(),
+            Severity::Warning => Style::new().bold().yellow(),
+        };
         let arrow_marker = Style::new().blue();
         let context_marker = Style::new().bold().blue();
         let fix = Style::new().green();
         let help = Style::new().yellow().bold();
 
-        let line_number_digit_count = self.span.start.line.to_string().len();
+        // lines of the file around the offending one, which is the first
+        // line covered by the mistake, the lines of the chunk may well be
+        // interrupted by code
+        let (path, line) = suggestion.location();
+        let context_lines = self.options.context_lines;
+        let source = if context_lines > 0 {
+            std::fs::read_to_string(&path)
+                .map_err(|e| log::debug!("Failed to read {path} for context lines: {e}"))
+                .unwrap_or_default()
+        } else {
+            String::new()
+        };
+        let source_lines = Vec::from_iter(source.lines());
+        let (lines_before, lines_after) = if (1..=source_lines.len()).contains(&line) {
+            (
+                context_lines
+                    .min(line - 1)
+                    .min(suggestion.span.start.line.saturating_sub(1)),
+                context_lines.min(source_lines.len() - line),
+            )
+        } else {
+            (0, 0)
+        };
+
+        let line_number_digit_count = (suggestion.span.start.line + lines_after).to_string().len();
         let indent = 3 + line_number_digit_count;
 
-        error.apply_to("error").fmt(formatter)?;
+        level
+            .apply_to(format!("{severity}[{}]", suggestion.id()))
+            .fmt(formatter)?;
         highlight
-            .apply_to(format!(": spellcheck({})", self.detector))
+            .apply_to(format!(": spellcheck({})", suggestion.detectors()))
             .fmt(formatter)?;
+        if let Some(ref category) = suggestion.category {
+            write!(formatter, " [{category}]")?;
+        }
         formatter.write_str("\n")?;
 
         arrow_marker
             .apply_to(format!("{:>width$}", "-->", width = indent + 1))
             .fmt(formatter)?;
 
-        let x = self.span.start.line;
-        let (path, line) = match self.origin {
-            ContentOrigin::RustDocTest(ref path, ref span) => {
-                (path.display().to_string(), x + span.start.line)
-            }
-            ref origin => (origin.as_path().display().to_string(), x),
-        };
-        writeln!(formatter, " {path}:{line}", path = path, line = line)?;
+        match self.options.hyperlink {
+            // OSC 8 escape sequence, shown as plain text by terminals without
+            // support
+            Some(ref template) => writeln!(
+                formatter,
+                " \x1b]8;;{url}\x1b\\{path}:{line}\x1b]8;;\x1b\\",
+                url = suggestion.hyperlink(template),
+            )?,
+            None => writeln!(formatter, " {path}:{line}", path = path, line = line)?,
+        }
         context_marker
             .apply_to(format!("{:>width$}", "|", width = indent))
             .fmt(formatter)?;
         formatter.write_str("\n")?;
+
+        let terminal_size = get_terminal_size();
+
+        // this values is dynamically calculated for each line where the doc is.
+        // the line being analysed can affect how the indentation is done.
+        let padding_till_excerpt_start = indent + 2;
+
+        let context_line = |formatter: &mut fmt::Formatter<'_>, line: usize, idx: usize| {
+            context_marker
+                .apply_to(format!("{:>width$} |", line, width = indent - 2))
+                .fmt(formatter)?;
+            let text = source_lines.get(idx).copied().unwrap_or_default();
+            let text = text
+                .chars()
+                .take(terminal_size.saturating_sub(padding_till_excerpt_start))
+                .collect::<String>();
+            writeln!(formatter, " {text}")
+        };
+
+        for offset in (1..=lines_before).rev() {
+            context_line(
+                formatter,
+                suggestion.span.start.line - offset,
+                line - 1 - offset,
+            )?;
+        }
+
         context_marker
             .apply_to(format!(
                 "{:>width$} |",
-                self.span.start.line,
+                suggestion.span.start.line,
                 width = indent - 2,
             ))
             .fmt(formatter)?;
@@ -388,30 +968,36 @@ impl<'s> fmt::Display for Suggestion<'s> {
         // underline the relevant part with ^^^^^
 
         // TODO this needs some more thought once multiline comments pop up
-        let marker_size = self.span.one_line_len().unwrap_or_else(|| {
-            self.chunk
+        let marker_size = suggestion.span.one_line_len().unwrap_or_else(|| {
+            suggestion
+                .chunk
                 .len_in_chars()
-                .saturating_sub(self.span.start.column)
+                .saturating_sub(suggestion.span.start.column)
         });
 
         // assumes the _mistake_ is within one line
         // if not we chop it down to the first line
-        let mistake_lines = self.chunk.find_covered_lines(self.range.clone());
+        let mistake_lines = suggestion
+            .chunk
+            .find_covered_lines(suggestion.range.clone());
         let (line_range, start_of_line_offset) = mistake_lines
             .first()
             .map(|line_range| {
                 (
                     line_range,
-                    self.range.start.saturating_sub(line_range.start),
+                    suggestion.range.start.saturating_sub(line_range.start),
                 )
             })
             .expect("Lines covered must exist");
 
         let intra_line_mistake_range = Range {
             start: start_of_line_offset,
-            end: cmp::min(start_of_line_offset + self.range.len(), line_range.len()),
+            end: cmp::min(
+                start_of_line_offset + suggestion.range.len(),
+                line_range.len(),
+            ),
         };
-        let relevant_line = self
+        let relevant_line = suggestion
             .chunk
             .as_str()
             .chars()
@@ -421,12 +1007,6 @@ impl<'s> fmt::Display for Suggestion<'s> {
             .map(|(_, c)| c)
             .collect::<String>();
 
-        let terminal_size = get_terminal_size();
-
-        // this values is dynamically calculated for each line where the doc is.
-        // the line being analysed can affect how the indentation is done.
-        let padding_till_excerpt_start = indent + 2;
-
         let (formatted, offset, marker_size) = condition_display_content(
             terminal_size,
             indent,
@@ -450,35 +1030,43 @@ impl<'s> fmt::Display for Suggestion<'s> {
             log::trace!(
                 "marker_size={} span {{ {:?} .. {:?} }} >> {:?} <<",
                 marker_size,
-                self.span.start,
-                self.span.end,
-                self,
+                suggestion.span.start,
+                suggestion.span.end,
+                suggestion,
             );
         } else {
             log::warn!(
                 "marker_size={} span {{ {:?} .. {:?} }} >> {:?} <<",
                 marker_size,
-                self.span.start,
-                self.span.end,
-                self,
+                suggestion.span.start,
+                suggestion.span.end,
+                suggestion,
             );
         }
 
+        for offset in 1..=lines_after {
+            context_line(
+                formatter,
+                suggestion.span.start.line + offset,
+                line - 1 + offset,
+            )?;
+        }
+
         context_marker
             .apply_to(format!("{:>width$}", "|", width = indent))
             .fmt(formatter)?;
 
-        let replacement = match self.replacements.len() {
+        let replacement = match suggestion.replacements.len() {
             0 => String::new(),
-            1 => format!(" - {}", fix.apply_to(&self.replacements[0])),
+            1 => format!(" - {}", fix.apply_to(&suggestion.replacements[0])),
             2 => format!(
                 " - {} or {}",
-                fix.apply_to(&self.replacements[0]),
-                fix.apply_to(&self.replacements[1])
+                fix.apply_to(&suggestion.replacements[0]),
+                fix.apply_to(&suggestion.replacements[1])
             ),
             n if (n < 7) => {
-                let last = fix.apply_to(&self.replacements[n - 1]).to_string();
-                let joined = self.replacements[..n - 1]
+                let last = fix.apply_to(&suggestion.replacements[n - 1]).to_string();
+                let joined = suggestion.replacements[..n - 1]
                     .iter()
                     .map(|x| fix.apply_to(x.to_owned()).to_string())
                     .collect::<Vec<String>>()
@@ -487,22 +1075,22 @@ impl<'s> fmt::Display for Suggestion<'s> {
                 format!(" - {joined}, or {last}")
             }
             _n => {
-                let joined = self.replacements[..=6]
+                let joined = suggestion.replacements[..=6]
                     .iter()
                     .map(|x| fix.apply_to(x.to_owned()).to_string())
                     .collect::<Vec<String>>()
                     .as_slice()
                     .join(", ");
 
-                let remaining = self.replacements.len() - 6;
+                let remaining = suggestion.replacements.len() - 6;
                 let remaining = fix.apply_to(format!("{remaining}")).to_string();
                 format!(" - {joined}, or one of {remaining} others")
             }
         };
 
-        error.apply_to(replacement).fmt(formatter)?;
+        level.apply_to(replacement).fmt(formatter)?;
 
-        if !self.replacements.is_empty() {
+        if !suggestion.replacements.is_empty() {
             formatter.write_str("\n")?;
             context_marker
                 .apply_to(format!("{:>width$}", "|\n", width = indent + 1))
@@ -512,9 +1100,28 @@ impl<'s> fmt::Display for Suggestion<'s> {
                 .fmt(formatter)?;
         }
 
-        if let Some(ref description) = self.description {
+        if let Some(ref description) = suggestion.description {
             writeln!(formatter, "   {description}")?;
         }
+        if self.options.verbosity == ReportVerbosity::Verbose {
+            if suggestion.description.is_none() {
+                formatter.write_str("\n")?;
+            }
+            let note = |formatter: &mut fmt::Formatter<'_>, note: String| {
+                context_marker
+                    .apply_to(format!("{:>width$}", "=", width = indent))
+                    .fmt(formatter)?;
+                writeln!(formatter, " note: {note}")
+            };
+            note(formatter, format!("rule `{}`", suggestion.id()))?;
+            note(
+                formatter,
+                format!("fingerprint `{}`", suggestion.fingerprint()),
+            )?;
+            if let Some(provenance) = self.options.provenance.get(&suggestion.detector) {
+                note(formatter, provenance.to_owned())?;
+            }
+        }
         Ok(())
     }
 }
@@ -558,6 +1165,91 @@ impl<'s> PartialOrd for Suggestion<'s> {
     }
 }
 
+/// A [`Suggestion`] owning all its data, so it can outlive the checked chunks.
+///
+/// Used to pass suggestions across thread or process boundaries, to cache them
+/// or to emit them in a machine readable format.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OwnedSuggestion {
+    /// Which checker suggested the change.
+    pub detector: Detector,
+    /// Further checkers which flagged the same word at an overlapping span.
+    #[serde(default)]
+    pub agreeing: Vec<Detector>,
+    /// The checker specific rule which triggered, if more specific than the
+    /// detector.
+    pub rule: Option<String>,
+    /// The category of the rule, i.e. `grammar` or `style`.
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Reference to the file location the `span` relates to.
+    pub origin: ContentOrigin,
+    /// The span (absolute!) within the file or chunk (depends on `origin`).
+    pub span: Span,
+    /// Range relative to the chunk the suggestion was located in.
+    pub range: Range,
+    /// The content of the chunk covered by `range`.
+    pub flagged: String,
+    /// Fix suggestions, might be words or the full sentence together with
+    /// leading whitespaces for some `CommentVariant`s.
+    pub replacements: Vec<String>,
+    /// Descriptive reason for the suggestion.
+    pub description: Option<String>,
+    /// Stable fingerprint, see [`Suggestion::fingerprint`].
+    pub fingerprint: String,
+}
+
+impl OwnedSuggestion {
+    /// Stable identifier of the kind of finding, see [`Suggestion::id`].
+    pub fn id(&self) -> String {
+        format!(
+            "{}/{}",
+            self.detector.namespace(),
+            self.rule
+                .as_deref()
+                .unwrap_or_else(|| self.detector.default_rule())
+        )
+    }
+}
+
+impl<'a, 's> From<&'a Suggestion<'s>> for OwnedSuggestion {
+    fn from(suggestion: &'a Suggestion<'s>) -> Self {
+        Self {
+            detector: suggestion.detector,
+            agreeing: suggestion.agreeing.clone(),
+            rule: suggestion.rule.clone(),
+            category: suggestion.category.clone(),
+            origin: suggestion.origin.clone(),
+            span: suggestion.span,
+            range: suggestion.range.clone(),
+            flagged: crate::util::sub_chars(suggestion.chunk.as_str(), suggestion.range.clone()),
+            replacements: suggestion.replacements.clone(),
+            description: suggestion.description.clone(),
+            fingerprint: suggestion.fingerprint(),
+        }
+    }
+}
+
+impl<'s> From<Suggestion<'s>> for OwnedSuggestion {
+    fn from(suggestion: Suggestion<'s>) -> Self {
+        let flagged = crate::util::sub_chars(suggestion.chunk.as_str(), suggestion.range.clone());
+        let fingerprint = suggestion.fingerprint();
+        Self {
+            detector: suggestion.detector,
+            agreeing: suggestion.agreeing,
+            rule: suggestion.rule,
+            category: suggestion.category,
+            origin: suggestion.origin,
+            span: suggestion.span,
+            range: suggestion.range,
+            flagged,
+            replacements: suggestion.replacements,
+            description: suggestion.description,
+            fingerprint,
+        }
+    }
+}
+
 /// A set of suggestions across multiple files, clustered per file
 #[derive(Debug, Clone)]
 pub struct SuggestionSet<'s> {
@@ -712,6 +1404,7 @@ impl<'s> IntoIterator for &'s SuggestionSet<'s> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tests::TempDir;
     use crate::{CommentVariant, LineColumn};
     use console;
     use std::fmt;
@@ -751,6 +1444,9 @@ mod tests {
 
         let suggestion = Suggestion {
             detector: Detector::Dummy,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
             origin: ContentOrigin::TestEntityRust,
             chunk: &chunk,
             range: 7..12,
@@ -769,7 +1465,7 @@ mod tests {
             description: Some("Possible spelling mistake found.".to_owned()),
         };
 
-        const EXPECTED: &str = r#"error: spellcheck(Dummy)
+        const EXPECTED: &str = r#"error[dummy/dummy]: spellcheck(Dummy)
   --> /tmp/test/entity.rs:1
    |
  1 |  Is it dyrck again?
@@ -781,6 +1477,216 @@ mod tests {
         assert_display_eq(suggestion, EXPECTED);
     }
 
+    #[test]
+    fn fmt_hyperlink() {
+        let chunk = CheckableChunk::from_str(
+            " Is it dyrck again?",
+            indexmap::indexmap! { 0..18 => Span {
+                start: LineColumn { line: 1, column: 0 },
+                end: LineColumn { line: 1, column: 17 },
+            }},
+            CommentVariant::TripleSlash,
+        );
+        let suggestion = Suggestion {
+            detector: Detector::Dummy,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
+            origin: ContentOrigin::TestEntityRust,
+            chunk: &chunk,
+            range: 7..12,
+            span: Span {
+                start: LineColumn { line: 1, column: 6 },
+                end: LineColumn {
+                    line: 1,
+                    column: 10,
+                },
+            },
+            replacements: vec![],
+            description: None,
+        };
+        let options = RenderOptions {
+            hyperlink: Some("vscode://file/{path}:{line}:{column}".to_owned()),
+            ..Default::default()
+        };
+        let rendered = suggestion.render(&options).to_string();
+        assert!(rendered.contains(
+            "\x1b]8;;vscode://file//tmp/test/entity.rs:1:7\x1b\\/tmp/test/entity.rs:1\x1b]8;;\x1b\\\n"
+        ));
+    }
+
+    #[test]
+    fn fmt_context_lines() {
+        // the doc comment is interrupted by an attribute
+        const CONTENT: &str = "/// First line.
+#[inline]
+/// Is it dyrck again?
+fn x() {}
+";
+        let dir = TempDir::new();
+        let path = dir.join("context.rs");
+        std::fs::write(&path, CONTENT).unwrap();
+        let chunk = CheckableChunk::from_str(
+            " First line.\n Is it dyrck again?",
+            indexmap::indexmap! {
+                0..12 => Span {
+                    start: LineColumn { line: 1, column: 3 },
+                    end: LineColumn { line: 1, column: 14 },
+                },
+                13..32 => Span {
+                    start: LineColumn { line: 3, column: 3 },
+                    end: LineColumn { line: 3, column: 21 },
+                },
+            },
+            CommentVariant::TripleSlash,
+        );
+        let suggestion = Suggestion {
+            detector: Detector::Dummy,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
+            origin: ContentOrigin::RustSourceFile(path.clone()),
+            chunk: &chunk,
+            range: 20..25,
+            span: Span {
+                start: LineColumn { line: 3, column: 9 },
+                end: LineColumn {
+                    line: 3,
+                    column: 13,
+                },
+            },
+            replacements: vec!["dirk".to_owned()],
+            description: Some("Possible spelling mistake found.".to_owned()),
+        };
+        let options = RenderOptions {
+            context_lines: 2,
+            ..Default::default()
+        };
+        let rendered = suggestion.render(&options).to_string();
+
+        let expected = format!(
+            r#"error[dummy/dummy]: spellcheck(Dummy)
+  --> {}:3
+   |
+ 1 | /// First line.
+ 2 | #[inline]
+ 3 |  Is it dyrck again?
+   |        ^^^^^
+ 4 | fn x() {{}}
+   | - dirk
+   |
+   |   Possible spelling mistake found.
+"#,
+            path.display()
+        );
+        assert_display_eq(rendered, &expected);
+    }
+
+    #[test]
+    fn fmt_github_annotation() {
+        const CONTENT: &str = " Is it dyrck again?";
+        let chunk = CheckableChunk::from_str(
+            CONTENT,
+            indexmap::indexmap! { 0..18 => Span {
+                start: LineColumn {
+                    line: 1,
+                    column: 0,
+                },
+                end: LineColumn {
+                    line: 1,
+                    column: 17,
+                }
+            }
+            },
+            CommentVariant::TripleSlash,
+        );
+
+        let suggestion = Suggestion {
+            detector: Detector::Dummy,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
+            origin: ContentOrigin::TestEntityRust,
+            chunk: &chunk,
+            range: 7..12,
+            span: Span {
+                start: LineColumn { line: 1, column: 6 },
+                end: LineColumn {
+                    line: 1,
+                    column: 10,
+                },
+            },
+            replacements: vec!["dirk".to_owned(), "dyrk".to_owned()],
+            description: Some("Possible spelling mistake found.".to_owned()),
+        };
+
+        const EXPECTED: &str = "::error file=/tmp/test/entity.rs,line=1,endLine=1,col=7,endColumn=11,title=spellcheck(Dummy)::Possible spelling mistake found.%0ASuggestions: dirk, dyrk";
+        assert_eq!(
+            suggestion.github_annotation(Severity::Error).to_string(),
+            EXPECTED
+        );
+        assert!(suggestion
+            .github_annotation(Severity::Warning)
+            .to_string()
+            .starts_with("::warning file=/tmp/test/entity.rs,"));
+    }
+
+    #[test]
+    fn fmt_rustfix_diagnostic() {
+        const CONTENT: &str = "fn x() {}\n/// Is it dyrck again?\n";
+        let chunk = CheckableChunk::from_str(
+            " Is it dyrck again?",
+            indexmap::indexmap! { 0..18 => Span {
+                start: LineColumn {
+                    line: 2,
+                    column: 3,
+                },
+                end: LineColumn {
+                    line: 2,
+                    column: 20,
+                }
+            }
+            },
+            CommentVariant::TripleSlash,
+        );
+
+        let suggestion = Suggestion {
+            detector: Detector::Dummy,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
+            origin: ContentOrigin::TestEntityRust,
+            chunk: &chunk,
+            range: 7..12,
+            span: Span {
+                start: LineColumn {
+                    line: 2,
+                    column: 10,
+                },
+                end: LineColumn {
+                    line: 2,
+                    column: 14,
+                },
+            },
+            replacements: vec!["dirk".to_owned()],
+            description: Some("Possible spelling mistake found.".to_owned()),
+        };
+
+        let diagnostic = suggestion.rustfix_diagnostic(CONTENT).to_string();
+        assert!(!diagnostic.contains('\n'));
+        let diagnostic: serde_json::Value = serde_json::from_str(&diagnostic).unwrap();
+        let span = &diagnostic["spans"][0];
+        assert_eq!(span["byte_start"], 20);
+        assert_eq!(span["byte_end"], 25);
+        assert_eq!(&CONTENT[20..25], "dyrck");
+        assert_eq!(span["column_start"], 11);
+        assert_eq!(span["column_end"], 16);
+        assert_eq!(span["text"][0]["text"], "/// Is it dyrck again?");
+        let fix = &diagnostic["children"][0]["spans"][0];
+        assert_eq!(fix["suggested_replacement"], "dirk");
+        assert_eq!(fix["suggestion_applicability"], "MachineApplicable");
+    }
+
     #[test]
     fn fmt_0_no_suggestion() {
         const CONTENT: &str = " Is it dyrck again?";
@@ -802,6 +1708,9 @@ mod tests {
 
         let suggestion = Suggestion {
             detector: Detector::Dummy,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
             origin: ContentOrigin::TestEntityRust,
             chunk: &chunk,
             range: 7..12,
@@ -816,7 +1725,7 @@ mod tests {
             description: Some("Possible spelling mistake found.".to_owned()),
         };
 
-        const EXPECTED: &str = r#"error: spellcheck(Dummy)
+        const EXPECTED: &str = r#"error[dummy/dummy]: spellcheck(Dummy)
   --> /tmp/test/entity.rs:1
    |
  1 |  Is it dyrck again?
@@ -871,6 +1780,9 @@ mod tests {
 
         let suggestion = Suggestion {
             detector: Detector::Dummy,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
             origin: ContentOrigin::TestEntityRust,
             chunk: &chunk,
             range: 6..12,
@@ -892,7 +1804,7 @@ mod tests {
             description: Some("Possible spelling mistake found.".to_owned()),
         };
 
-        const EXPECTED: &str = r#"error: spellcheck(Dummy)
+        const EXPECTED: &str = r#"error[dummy/dummy]: spellcheck(Dummy)
   --> /tmp/test/entity.rs:1
    |
  1 |  Line mitake 1
@@ -940,6 +1852,9 @@ mod tests {
 
         let suggestion = Suggestion {
             detector: Detector::Dummy,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
             origin: ContentOrigin::TestEntityRust,
             chunk: &chunk,
             range: 66..94,
@@ -958,7 +1873,7 @@ mod tests {
             description: Some("Possible spelling mistake found.".to_owned()),
         };
 
-        const EXPECTED: &str = r#"error: spellcheck(Dummy)
+        const EXPECTED: &str = r#"error[dummy/dummy]: spellcheck(Dummy)
   --> /tmp/test/entity.rs:2
    |
  2 | ..uuuuuuuuuuuuuuuuuuuuuuuuuuuuuuuuuuuuuuuuuuper duuu...uper too long
@@ -998,6 +1913,9 @@ mod tests {
 
         let suggestion = Suggestion {
             detector: Detector::Dummy,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
             origin: ContentOrigin::TestEntityRust,
             chunk: &chunk,
             span: Span {
@@ -1041,6 +1959,9 @@ mod tests {
         );
         let suggestion = Suggestion {
             detector: Detector::Dummy,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
             origin: ContentOrigin::TestEntityRust,
             chunk: &chunk,
             span: Span {
@@ -1059,6 +1980,9 @@ mod tests {
         };
         let overlapped_smaller_suggestion = Suggestion {
             detector: Detector::Dummy,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
             origin: ContentOrigin::TestEntityRust,
             chunk: &chunk,
             span: Span {
@@ -1078,6 +2002,9 @@ mod tests {
 
         let overlapped_larger_suggestion = Suggestion {
             detector: Detector::Dummy,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
             origin: ContentOrigin::TestEntityRust,
             chunk: &chunk,
             span: Span {
@@ -1098,4 +2025,136 @@ mod tests {
         assert!(suggestion.is_overlapped(&overlapped_smaller_suggestion));
         assert!(suggestion.is_overlapped(&overlapped_larger_suggestion));
     }
+
+    #[test]
+    fn merge() {
+        let chunk = CheckableChunk::from_str(
+            " A speling mistake.",
+            indexmap::indexmap! { 0..19 => Span {
+                start: LineColumn { line: 1, column: 3 },
+                end: LineColumn { line: 1, column: 21 },
+            }},
+            CommentVariant::TripleSlash,
+        );
+        let suggestion = |detector, range: Range, replacements: &[&str]| Suggestion {
+            detector,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
+            origin: ContentOrigin::TestEntityRust,
+            chunk: &chunk,
+            span: Span {
+                start: LineColumn {
+                    line: 1,
+                    column: 3 + range.start,
+                },
+                end: LineColumn {
+                    line: 1,
+                    column: 2 + range.end,
+                },
+            },
+            range,
+            replacements: replacements.iter().map(|s| s.to_string()).collect(),
+            description: None,
+        };
+        let mut hunspell = suggestion(Detector::Hunspell, 3..10, &["spelling", "spieling"]);
+        let zspell = suggestion(Detector::ZSpell, 3..10, &["spelling", "spewing"]);
+        let other = suggestion(Detector::ZSpell, 3..18, &[]);
+        let same = suggestion(Detector::Hunspell, 3..10, &[]);
+
+        assert!(!hunspell.merge(&other));
+        assert!(!hunspell.merge(&same));
+        assert!(hunspell.merge(&zspell));
+        assert!(!hunspell.merge(&zspell));
+        assert_eq!(hunspell.agreeing, vec![Detector::ZSpell]);
+        assert_eq!(
+            hunspell.replacements,
+            vec!["spelling", "spieling", "spewing"]
+        );
+        assert_eq!(hunspell.detectors(), "Hunspell, ZSpell");
+    }
+
+    #[test]
+    fn fingerprint() {
+        let chunk = |line| {
+            CheckableChunk::from_str(
+                " Is it  dyrck again?",
+                indexmap::indexmap! { 0..20 => Span {
+                    start: LineColumn { line, column: 3 },
+                    end: LineColumn { line, column: 22 },
+                }},
+                CommentVariant::TripleSlash,
+            )
+        };
+        let suggestion = |chunk, line, range| Suggestion {
+            detector: Detector::Hunspell,
+            agreeing: Vec::new(),
+            rule: None,
+            category: None,
+            origin: ContentOrigin::RustSourceFile("src/lib.rs".into()),
+            chunk,
+            range,
+            span: Span {
+                start: LineColumn { line, column: 11 },
+                end: LineColumn { line, column: 15 },
+            },
+            replacements: vec![],
+            description: None,
+        };
+        let (first, moved) = (chunk(3), chunk(7));
+        let fingerprint = suggestion(&first, 3, 8..13).fingerprint();
+        assert_eq!(fingerprint.len(), 16);
+        assert_eq!(fingerprint, suggestion(&moved, 7, 8..13).fingerprint());
+        assert_ne!(fingerprint, suggestion(&first, 3, 1..3).fingerprint());
+    }
+
+    #[test]
+    fn owned_roundtrip() {
+        const CONTENT: &str = " Is it dyrck again?";
+        let chunk = CheckableChunk::from_str(
+            CONTENT,
+            indexmap::indexmap! { 0..18 => Span {
+                start: LineColumn {
+                    line: 1,
+                    column: 0,
+                },
+                end: LineColumn {
+                    line: 1,
+                    column: 17,
+                }
+            }
+            },
+            CommentVariant::TripleSlash,
+        );
+
+        let suggestion = Suggestion {
+            detector: Detector::Dummy,
+            agreeing: vec![Detector::ZSpell],
+            rule: Some("typo".to_owned()),
+            category: None,
+            origin: ContentOrigin::RustSourceFile("src/lib.rs".into()),
+            chunk: &chunk,
+            range: 7..12,
+            span: Span {
+                start: LineColumn { line: 1, column: 6 },
+                end: LineColumn {
+                    line: 1,
+                    column: 10,
+                },
+            },
+            replacements: vec!["dirk".to_owned()],
+            description: None,
+        };
+
+        let owned = OwnedSuggestion::from(&suggestion);
+        assert_eq!(owned.flagged, "dyrck");
+        assert_eq!(owned.agreeing, vec![Detector::ZSpell]);
+        assert_eq!(owned.id(), suggestion.id());
+        assert_eq!(owned.fingerprint, suggestion.fingerprint());
+
+        let json = serde_json::to_string(&owned).unwrap();
+        let deserialized: OwnedSuggestion = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, owned);
+        assert_eq!(deserialized, OwnedSuggestion::from(suggestion));
+    }
 }
@@ -7,6 +7,39 @@ use doc_chunks::{literalset::testhelper::gen_literal_set, Ignores};
 use crate::documentation::{testcase::annotated_literals, SourceRange};
 use indexmap::IndexMap;
 use std::convert::From;
+use std::path::{Path, PathBuf};
+
+/// A temporary directory of a test, which is removed once dropped, even if the
+/// test panics.
+pub(crate) struct TempDir(PathBuf);
+
+impl TempDir {
+    pub(crate) fn new() -> Self {
+        let path = std::env::temp_dir().join(format!("cargo-spellcheck-{}", uuid::Uuid::new_v4()));
+        fs_err::create_dir_all(&path).expect("Temporary directory can be created. qed");
+        let path = fs_err::canonicalize(path).expect("Temporary directory exists. qed");
+        Self(path)
+    }
+}
+
+impl std::ops::Deref for TempDir {
+    type Target = Path;
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
 
 #[test]
 fn parse_and_construct() {
@@ -198,6 +231,29 @@ struct X;
         );
     }
 
+    #[test]
+    fn ignore_capitalized() {
+        let cfg = crate::config::HunspellConfig {
+            quirks: crate::config::Quirks {
+                ignore_capitalized: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // only the sentence initial one is checked
+        end2end!(
+            r####"
+/// We depend on Zorgle. Zorgle is fast.
+struct X;
+            "####,
+            ContentOrigin::TestEntityRust,
+            1,
+            HunspellChecker,
+            cfg
+        );
+    }
+
     #[test]
     fn issue_227() {
         // The test
@@ -1278,6 +1334,46 @@ I like vars named `Yak<Turbo>` but not `Foo<Bar>`.
     );
 }
 
+#[test]
+fn reduce_w_inline_code_across_paragraphs() {
+    // a single fragment is rendered as is, the backticks are regular text
+    cmark_reduction_test("Use `Foo\n\nBar` here.", "Use `Foo\n\nBar` here.", 6);
+    // unpaired or escaped backticks are regular text
+    cmark_reduction_test("A ` tick.", "A ` tick.", 3);
+    cmark_reduction_test("Use \\`Foo\n\nBar\\` here.", "Use `Foo\n\nBar` here.", 6);
+}
+
+#[test]
+fn inline_code_split_across_doc_comment_lines() {
+    const TEST_SOURCE: &str = r#"/// Use `Foo
+///
+/// Bar` and `Baz
+/// Qux` here.
+struct Vikings;
+"#;
+    let origin = ContentOrigin::TestEntityRust;
+    let docs = Documentation::load_from_str(origin.clone(), TEST_SOURCE, true, false);
+    let chunks = docs.get(&origin).expect("Must contain dummy path");
+    assert_eq!(chunks.len(), 1);
+    let plain = chunks[0].erase_cmark(&Default::default());
+    assert_eq!(plain.as_str(), "Use FooBar and BazQux here.");
+}
+
+#[test]
+fn stray_tick_before_inline_code_of_next_paragraph() {
+    const TEST_SOURCE: &str = r#"/// A stray ` tick.
+///
+/// See `code` here.
+struct Vikings;
+"#;
+    let origin = ContentOrigin::TestEntityRust;
+    let docs = Documentation::load_from_str(origin.clone(), TEST_SOURCE, true, false);
+    let chunks = docs.get(&origin).expect("Must contain dummy path");
+    assert_eq!(chunks.len(), 1);
+    let plain = chunks[0].erase_cmark(&Default::default());
+    assert_eq!(plain.as_str(), "A stray ` tick.\n\nSee code here.");
+}
+
 #[test]
 fn reduce_w_link_footnote() {
     cmark_reduction_test(
@@ -0,0 +1,247 @@
+//! Evaluation of the `#[cfg(..)]` attributes of module declarations.
+//!
+//! By default all declared modules are followed, regardless of their cfgs, so
+//! platform specific modules are checked on any host. Once a set of cfgs is
+//! given, only modules whose predicates hold for that set are followed, like
+//! `rustc --cfg` would.
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use std::collections::HashSet;
+
+/// A single cfg, either a name like `unix` or a key value pair like
+/// `feature = "serde"`.
+type Cfg = (String, Option<String>);
+
+/// The set of enabled cfgs, all of them if none were given.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CfgSet(Option<HashSet<Cfg>>);
+
+impl CfgSet {
+    /// Create the set from cfgs in the `--cfg` notation, i.e. `unix` or
+    /// `feature="serde"`. An empty list enables all cfgs.
+    pub fn new<S: AsRef<str>>(cfgs: &[S]) -> Self {
+        if cfgs.is_empty() {
+            return Self(None);
+        }
+        Self(Some(HashSet::from_iter(cfgs.iter().map(
+            |cfg| match cfg.as_ref().split_once('=') {
+                Some((key, value)) => (
+                    key.trim().to_owned(),
+                    Some(value.trim().trim_matches('"').to_owned()),
+                ),
+                None => (cfg.as_ref().trim().to_owned(), None),
+            },
+        ))))
+    }
+
+    /// Check if an item with the given outer `attributes`, the content of
+    /// each `#[..]`, is enabled.
+    ///
+    /// Returns the first predicate which does not hold otherwise.
+    pub(crate) fn disabled_by(&self, attributes: &[TokenStream]) -> Option<String> {
+        let enabled = self.0.as_ref()?;
+        for attribute in attributes {
+            let trees = Vec::from_iter(attribute.clone());
+            let predicates = match trees.as_slice() {
+                [TokenTree::Ident(ident), TokenTree::Group(args)] if ident == "cfg" => {
+                    vec![args.stream()]
+                }
+                // `#[cfg_attr(predicate, cfg(..))]` only gates if the predicate holds
+                [TokenTree::Ident(ident), TokenTree::Group(args)] if ident == "cfg_attr" => {
+                    let mut args = split_args(args.stream()).into_iter();
+                    match args.next() {
+                        Some(predicate) if evaluate(enabled, &predicate) => args
+                            .filter_map(|attribute| match attribute.as_slice() {
+                                [TokenTree::Ident(ident), TokenTree::Group(inner)]
+                                    if ident == "cfg" =>
+                                {
+                                    Some(inner.stream())
+                                }
+                                _ => None,
+                            })
+                            .collect(),
+                        _ => continue,
+                    }
+                }
+                _ => continue,
+            };
+            for predicate in predicates {
+                if !evaluate(enabled, &Vec::from_iter(predicate.clone())) {
+                    return Some(format!("cfg({predicate})"));
+                }
+            }
+        }
+        None
+    }
+
+    /// Expand the `#[cfg_attr(predicate, ..)]` attributes among `attributes`
+    /// into the attributes they apply, if the predicate holds. All of them
+    /// apply if all cfgs are enabled.
+    pub(crate) fn expand_cfg_attr(&self, attributes: &[TokenStream]) -> Vec<Vec<TokenTree>> {
+        let mut acc = Vec::new();
+        for attribute in attributes {
+            let trees = Vec::from_iter(attribute.clone());
+            match trees.as_slice() {
+                [TokenTree::Ident(ident), TokenTree::Group(args)] if ident == "cfg_attr" => {
+                    let mut args = split_args(args.stream()).into_iter();
+                    let holds = match (args.next(), self.0.as_ref()) {
+                        (Some(predicate), Some(enabled)) => evaluate(enabled, &predicate),
+                        (Some(_), None) => true,
+                        (None, _) => false,
+                    };
+                    if holds {
+                        acc.extend(args);
+                    }
+                }
+                _ => acc.push(trees),
+            }
+        }
+        acc
+    }
+
+    /// Select the bodies of the branches of a `cfg_if! { if #[cfg(..)] { .. }
+    /// else { .. } }` invocation which are compiled, the first one whose
+    /// predicate holds or the trailing `else`. All of them are selected if all
+    /// cfgs are enabled.
+    pub(crate) fn select_cfg_if(&self, stream: TokenStream) -> Vec<TokenStream> {
+        let mut branches = Vec::new();
+        let mut attributes = Vec::new();
+        let mut trees = stream.into_iter().peekable();
+        while let Some(tree) = trees.next() {
+            match tree {
+                TokenTree::Punct(hash) if hash.as_char() == '#' => {
+                    if let Some(TokenTree::Group(attribute)) = trees.peek() {
+                        if attribute.delimiter() == Delimiter::Bracket {
+                            attributes.push(attribute.stream());
+                            let _ = trees.next();
+                        }
+                    }
+                }
+                TokenTree::Group(body) if body.delimiter() == Delimiter::Brace => {
+                    branches.push((std::mem::take(&mut attributes), body.stream()));
+                }
+                // `if` and `else`
+                _ => {}
+            }
+        }
+        if self.0.is_none() {
+            return Vec::from_iter(branches.into_iter().map(|(_attributes, body)| body));
+        }
+        Vec::from_iter(
+            branches
+                .into_iter()
+                .find(|(attributes, _body)| self.disabled_by(attributes).is_none())
+                .map(|(_attributes, body)| body),
+        )
+    }
+}
+
+/// Split the arguments of `all(..)` and alike at the top level commas.
+fn split_args(stream: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut acc = vec![Vec::new()];
+    for tree in stream {
+        match tree {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => acc.push(Vec::new()),
+            tree => acc
+                .last_mut()
+                .expect("Has at least one element. qed")
+                .push(tree),
+        }
+    }
+    acc.retain(|arg| !arg.is_empty());
+    acc
+}
+
+/// Evaluate a cfg `predicate` against the `enabled` cfgs.
+///
+/// Unknown syntax evaluates to `true`, to rather check too much.
+fn evaluate(enabled: &HashSet<Cfg>, predicate: &[TokenTree]) -> bool {
+    match predicate {
+        [TokenTree::Ident(op), TokenTree::Group(args)]
+            if args.delimiter() == Delimiter::Parenthesis =>
+        {
+            let args = split_args(args.stream());
+            match op.to_string().as_str() {
+                "all" => args.iter().all(|arg| evaluate(enabled, arg)),
+                "any" => args.iter().any(|arg| evaluate(enabled, arg)),
+                "not" => args
+                    .first()
+                    .map(|arg| !evaluate(enabled, arg))
+                    .unwrap_or(true),
+                _ => true,
+            }
+        }
+        [TokenTree::Ident(key), TokenTree::Punct(eq), TokenTree::Literal(value)]
+            if eq.as_char() == '=' =>
+        {
+            let value = value.to_string();
+            enabled.contains(&(key.to_string(), Some(value.trim_matches('"').to_owned())))
+        }
+        [TokenTree::Ident(key)] => enabled.contains(&(key.to_string(), None)),
+        _ => {
+            log::warn!(
+                "Unknown cfg predicate {}, assuming it holds",
+                TokenStream::from_iter(predicate.iter().cloned())
+            );
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attributes(source: &str) -> Vec<TokenStream> {
+        let stream = syn::parse_str::<TokenStream>(source).unwrap();
+        Vec::from_iter(stream.into_iter().filter_map(|tree| match tree {
+            TokenTree::Group(group) => Some(group.stream()),
+            _ => None,
+        }))
+    }
+
+    #[test]
+    fn predicates() {
+        let cfgs = CfgSet::new(&["unix", r#"feature="serde""#]);
+        assert_eq!(cfgs.disabled_by(&attributes("#[cfg(unix)]")), None);
+        assert_eq!(
+            cfgs.disabled_by(&attributes("#[cfg(windows)]")),
+            Some("cfg(windows)".to_owned())
+        );
+        assert_eq!(
+            cfgs.disabled_by(&attributes(
+                r#"#[cfg(all(unix, not(windows), feature = "serde"))]"#
+            )),
+            None
+        );
+        assert!(cfgs
+            .disabled_by(&attributes(r#"#[cfg(any(windows, feature = "json"))]"#))
+            .is_some());
+        assert!(cfgs
+            .disabled_by(&attributes("#[cfg_attr(unix, cfg(test))]"))
+            .is_some());
+        assert_eq!(
+            cfgs.disabled_by(&attributes("#[cfg_attr(windows, cfg(test))]")),
+            None
+        );
+        assert_eq!(
+            CfgSet::default().disabled_by(&attributes("#[cfg(windows)]")),
+            None
+        );
+    }
+
+    #[test]
+    fn cfg_attr() {
+        let attributes = attributes(
+            r#"#[cfg_attr(unix, path = "unix.rs")] #[cfg_attr(windows, path = "win.rs")] #[inline]"#,
+        );
+        let expanded = CfgSet::new(&["unix"]).expand_cfg_attr(&attributes);
+        let expanded = Vec::from_iter(
+            expanded
+                .into_iter()
+                .map(|trees| TokenStream::from_iter(trees).to_string()),
+        );
+        assert_eq!(expanded, vec![r#"path = "unix.rs""#, "inline"]);
+        assert_eq!(CfgSet::default().expand_cfg_attr(&attributes).len(), 3);
+    }
+}
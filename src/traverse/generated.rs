@@ -0,0 +1,75 @@
+//! Detection of generated sources.
+//!
+//! Code emitted by `bindgen`, `prost` and alike carries documentation copied
+//! from elsewhere, which can not be fixed in place. Such files are recognized
+//! by a marker in their leading comments and attributes, or by residing in the
+//! `OUT_DIR` of a build script.
+
+use std::path::{Component, Path};
+
+/// Check if the file at `path` with the given `content` is generated.
+///
+/// Returns the reason if so, nothing is considered generated if there are no
+/// `markers`.
+pub(crate) fn generated_by(path: &Path, content: &str, markers: &[String]) -> Option<String> {
+    if markers.is_empty() {
+        return None;
+    }
+    if is_build_script_output(path) {
+        return Some("located in the output directory of a build script".to_owned());
+    }
+    content
+        .lines()
+        .map(str::trim)
+        .take_while(|line| {
+            line.is_empty()
+                || line.starts_with("//")
+                || line.starts_with("/*")
+                || line.starts_with('#')
+        })
+        .find_map(|line| markers.iter().find(|marker| line.contains(marker.as_str())))
+        .map(|marker| format!("marked by `{marker}`"))
+}
+
+/// Check if `path` is within `target/../build/<package>-<hash>/out`, the
+/// `OUT_DIR` of a build script.
+fn is_build_script_output(path: &Path) -> bool {
+    let components = Vec::from_iter(path.components().filter_map(|component| match component {
+        Component::Normal(component) => component.to_str(),
+        _ => None,
+    }));
+    components
+        .windows(3)
+        .any(|window| matches!(window, ["build", _, "out"]))
+        && components.contains(&"target")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markers() {
+        let markers = vec!["@generated".to_owned(), "DO NOT EDIT".to_owned()];
+        let path = Path::new("src/lib.rs");
+        assert!(generated_by(
+            path,
+            "// This file is @generated by prost-build.\npub struct A;\n",
+            &markers
+        )
+        .is_some());
+        assert!(generated_by(
+            path,
+            "#![allow(dead_code)]\n\n/* DO NOT EDIT */\npub struct A;\n",
+            &markers
+        )
+        .is_some());
+        // only the header is considered
+        assert!(generated_by(path, "pub struct A;\n// @generated\n", &markers).is_none());
+        assert!(generated_by(path, "// @generated\n", &[]).is_none());
+
+        let out = Path::new("/x/target/debug/build/foo-0123abcd/out/bindings.rs");
+        assert!(generated_by(out, "", &markers).is_some());
+        assert!(generated_by(Path::new("/x/src/build/y/out/z.rs"), "", &markers).is_none());
+    }
+}
@@ -13,6 +13,8 @@ pub struct TraverseModulesIter {
     /// zero limits to the provided path, if it is a directory, all children are
     /// collected
     max_depth: usize,
+    /// modules gated by cfgs which are not enabled are not followed
+    cfgs: CfgSet,
 }
 
 impl Default for TraverseModulesIter {
@@ -20,6 +22,7 @@ impl Default for TraverseModulesIter {
         Self {
             max_depth: usize::MAX,
             queue: VecDeque::with_capacity(128),
+            cfgs: CfgSet::default(),
         }
     }
 }
@@ -76,9 +79,14 @@ impl TraverseModulesIter {
         Ok(me)
     }
 
-    pub fn with_depth_limit<P: AsRef<Path>>(path: P, max_depth: usize) -> Result<Self> {
+    pub fn with_depth_limit<P: AsRef<Path>>(
+        path: P,
+        max_depth: usize,
+        cfgs: CfgSet,
+    ) -> Result<Self> {
         let mut me = Self {
             max_depth,
+            cfgs,
             ..Default::default()
         };
         me.add_initial_path(path, 0)?;
@@ -88,14 +96,16 @@ impl TraverseModulesIter {
     /// Create a new path with (almost) infinite depth bounds
     #[allow(unused)]
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
-        Self::with_depth_limit(path, usize::MAX)
+        Self::with_depth_limit(path, usize::MAX, CfgSet::default())
     }
 
     pub fn collect_modules(&mut self, path: &Path, level: usize) -> Result<()> {
-        if path.is_file() {
+        if is_markdown(path) {
+            // included markdown does not declare any modules
+        } else if path.is_file() {
             log::trace!("🥞 collecting mods declared in file {}", path.display());
             self.queue.extend(
-                extract_modules_from_file(path)?
+                extract_modules_from_file(path, &self.cfgs)?
                     .into_iter()
                     .map(|item| (item, level)),
             );
@@ -122,6 +132,11 @@ impl Iterator for TraverseModulesIter {
     }
 }
 
+/// Markdown files are only reached through `include_str!`.
+pub(super) fn is_markdown(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("md")
+}
+
 /// traverse path with a depth limit, if the path is a directory all its
 /// children will be collected instead
 // TODO should not read the documentation, that is out of scope.
@@ -129,32 +144,92 @@ impl Iterator for TraverseModulesIter {
 pub(crate) fn traverse(
     path: &Path,
     doc_comments: bool,
-    dev_comments: bool,
+    public_only: bool,
+    dev_comments: DevComments,
+    check_strings: bool,
+    check_help: bool,
+    cfgs: CfgSet,
+    generated_markers: &[String],
 ) -> Result<impl Iterator<Item = Documentation>> {
-    traverse_with_depth_limit(path, usize::MAX, doc_comments, dev_comments)
+    traverse_with_depth_limit(
+        path,
+        usize::MAX,
+        doc_comments,
+        public_only,
+        dev_comments,
+        check_strings,
+        check_help,
+        cfgs,
+        generated_markers,
+    )
 }
 
 /// traverse path with a depth limit, if the path is a directory all its
-/// children will be collected as depth 0 instead
+/// children will be collected as depth 0 instead, generated files carrying
+/// one of the `generated_markers` are skipped
 pub(crate) fn traverse_with_depth_limit(
     path: &Path,
     max_depth: usize,
     doc_comments: bool,
-    dev_comments: bool,
+    public_only: bool,
+    dev_comments: DevComments,
+    check_strings: bool,
+    check_help: bool,
+    cfgs: CfgSet,
+    generated_markers: &[String],
 ) -> Result<impl Iterator<Item = Documentation>> {
-    let it = TraverseModulesIter::with_depth_limit(path, max_depth)?
+    let paths = Vec::from_iter(TraverseModulesIter::with_depth_limit(
+        path, max_depth, cfgs,
+    )?);
+    // reading and parsing dominates, so do it for all files at once
+    let docs = paths
+        .into_par_iter()
         .filter_map(move |path: PathBuf| -> Option<Documentation> {
-            fs::read_to_string(&path).ok().map(|content| {
+            let content = read_source(&path).ok()?;
+            if is_markdown(&path) {
+                let origin = ContentOrigin::CommonMarkFile(path);
+                let mut docs = Documentation::new();
+                if let Err(e) = docs.add_commonmark(origin.clone(), content.as_str()) {
+                    log::warn!("Failed to load content from {origin}: {e:?}");
+                }
+                return Some(docs);
+            }
+            if let Some(reason) = generated_by(&path, content.as_str(), generated_markers) {
+                log::info!("Skipping generated file {}, {reason}", path.display());
+                return None;
+            }
+            let dev_comments = dev_comments.applies_to(&path);
+            let origin = ContentOrigin::RustSourceFile(path);
+            let mut docs = if doc_comments && public_only {
+                let mut docs = Documentation::new();
+                if let Err(e) = docs.add_rust_public(origin.clone(), content.as_str(), dev_comments)
+                {
+                    log::warn!("Failed to load content from {origin}: {e:?}");
+                }
+                docs
+            } else {
                 Documentation::load_from_str(
-                    ContentOrigin::RustSourceFile(path),
+                    origin.clone(),
                     content.as_str(),
                     doc_comments,
                     dev_comments,
                 )
-            })
+            };
+            if check_strings {
+                if let Err(e) = docs.add_rust_string_literals(origin.clone(), content.as_str()) {
+                    log::warn!("Failed to load string literals from {origin}: {e:?}");
+                }
+            }
+            if check_help {
+                if let Err(e) = docs.add_rust_help_texts(origin.clone(), content.as_str()) {
+                    log::warn!("Failed to load help texts from {origin}: {e:?}");
+                }
+            }
+            Some(docs)
         })
-        .filter(|documentation| !documentation.is_empty());
-    Ok(it)
+        .filter(|documentation| !documentation.is_empty())
+        .collect::<Vec<_>>();
+    Ok(docs.into_iter())
 }
 
 #[cfg(test)]
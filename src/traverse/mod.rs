@@ -4,18 +4,122 @@
 //! `Origin`.
 
 use super::*;
+use crate::config::CommentsConfig;
+use crate::documentation::CommentSyntax;
 use crate::Documentation;
 
 use crate::errors::*;
 
 use fs_err as fs;
+use rayon::prelude::*;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::SyncSender;
+use std::sync::Mutex;
 
 pub(crate) fn cwd() -> Result<PathBuf> {
     std::env::current_dir().wrap_err_with(|| eyre!("Missing cwd!"))
 }
 
+/// The directory of the top-level `Cargo.toml` at or above `dir`, the root of
+/// the workspace, or `dir` itself if there is none.
+pub(crate) fn workspace_root(dir: &Path) -> PathBuf {
+    dir.ancestors()
+        .filter(|ancestor| ancestor.join("Cargo.toml").is_file())
+        .last()
+        .unwrap_or(dir)
+        .to_path_buf()
+}
+
+/// Which files developer comments are checked for, either all or those
+/// matching one of the globs.
+#[derive(Debug, Clone)]
+pub(crate) struct DevComments {
+    all: bool,
+    globs: Vec<glob::Pattern>,
+}
+
+impl DevComments {
+    pub(crate) fn new(all: bool, globs: &[String]) -> Result<Self> {
+        let globs = globs
+            .iter()
+            .map(|glob| {
+                glob::Pattern::new(glob)
+                    .wrap_err_with(|| eyre!("Invalid glob for developer comments: {glob}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { all, globs })
+    }
+
+    /// Check if developer comments of the file at `path` are checked, globs
+    /// are matched relative to the current working directory.
+    pub(crate) fn applies_to(&self, path: &Path) -> bool {
+        if self.all {
+            return true;
+        }
+        any_glob_matches(&self.globs, path)
+    }
+}
+
+/// Check if any of the `globs` matches `path`, either as is or relative to the
+/// current working directory.
+fn any_glob_matches(globs: &[glob::Pattern], path: &Path) -> bool {
+    if globs.is_empty() {
+        return false;
+    }
+    let relative = cwd()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| path.to_path_buf());
+    globs
+        .iter()
+        .any(|glob| glob.matches_path(&relative) || glob.matches_path(path))
+}
+
+/// Restrict the checked files to those matching the `include_paths` and not
+/// matching the `exclude_paths` globs of the configuration.
+///
+/// Applied to the extracted files, so the traversal still follows modules
+/// declared in files which are not checked themselves.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PathFilter {
+    include: Vec<glob::Pattern>,
+    exclude: Vec<glob::Pattern>,
+}
+
+impl PathFilter {
+    pub(crate) fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        let compile = |globs: &[String]| {
+            globs
+                .iter()
+                .map(|glob| {
+                    glob::Pattern::new(glob)
+                        .wrap_err_with(|| eyre!("Invalid glob for the checked paths: {glob}"))
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+        Ok(Self {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    /// Check if the file at `path` is checked.
+    pub(crate) fn selects(&self, path: &Path) -> bool {
+        (self.include.is_empty() || any_glob_matches(&self.include, path))
+            && !any_glob_matches(&self.exclude, path)
+    }
+}
+
+impl From<bool> for DevComments {
+    fn from(all: bool) -> Self {
+        Self {
+            all,
+            globs: Vec::new(),
+        }
+    }
+}
+
 #[cfg(test)]
 fn manifest_dir() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -26,6 +130,13 @@ use std::collections::VecDeque;
 mod iter;
 pub use iter::*;
 
+mod cfg;
+pub use cfg::CfgSet;
+
+mod generated;
+use generated::generated_by;
+
+use proc_macro2::Delimiter;
 use proc_macro2::Spacing;
 use proc_macro2::TokenStream;
 use proc_macro2::TokenTree;
@@ -83,9 +194,128 @@ fn extract_modules_recurse_collect<P: AsRef<Path>>(
     Ok(())
 }
 
+/// Collect the content of the outer attributes, `#[..]`, of the item whose
+/// keyword follows `trees`, skipping its visibility.
+fn outer_attributes(trees: &[TokenTree]) -> Vec<TokenStream> {
+    let mut trees = match trees {
+        [rest @ .., TokenTree::Ident(vis), TokenTree::Group(restriction)]
+            if vis == "pub" && restriction.delimiter() == Delimiter::Parenthesis =>
+        {
+            rest
+        }
+        [rest @ .., TokenTree::Ident(vis)] if vis == "pub" => rest,
+        trees => trees,
+    };
+    let mut acc = Vec::new();
+    while let [rest @ .., TokenTree::Punct(hash), TokenTree::Group(attribute)] = trees {
+        if hash.as_char() != '#' || attribute.delimiter() != Delimiter::Bracket {
+            break;
+        }
+        acc.push(attribute.stream());
+        trees = rest;
+    }
+    acc
+}
+
+/// Extract the values of the `#[path = ".."]` attributes of a module
+/// declaration, including those applied by `#[cfg_attr(..)]` if the predicate
+/// holds.
+fn path_attributes(attributes: &[TokenStream], cfgs: &CfgSet) -> Vec<String> {
+    Vec::from_iter(
+        cfgs.expand_cfg_attr(attributes)
+            .into_iter()
+            .filter_map(|attribute| match attribute.as_slice() {
+                [TokenTree::Ident(ident), TokenTree::Punct(eq), TokenTree::Literal(value)]
+                    if ident == "path" && eq.as_char() == '=' =>
+                {
+                    syn::parse_str::<syn::LitStr>(&value.to_string())
+                        .ok()
+                        .map(|value| value.value())
+                }
+                _ => None,
+            }),
+    )
+}
+
+/// Resolve the `#[path = ".."]` of a module declared in the file `path`, the
+/// custom path is relative to the directory of the declaring file.
+fn extract_modules_recurse_collect_path(
+    path: &Path,
+    acc: &mut HashSet<PathBuf>,
+    mod_name: &str,
+    custom: &str,
+) -> Result<()> {
+    let base = path
+        .parent()
+        .ok_or_else(|| eyre!("Must have a valid parent directory: {}", path.display()))?;
+    let custom = base.join(custom);
+    if custom.is_file() {
+        log::trace!(
+            "Module {mod_name} is located at custom path {}",
+            custom.display()
+        );
+        let _ = acc.insert(custom);
+    } else {
+        log::warn!(
+            "Custom path {} of module {mod_name} declared in {} does not exist",
+            custom.display(),
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Resolve the file included by an `include!("..")` or `include_str!("..")`
+/// invocation at the start of `trees`, relative to the directory of the
+/// including file `path`.
+///
+/// Only markdown files are followed for `include_str!`. Paths composed by
+/// other macros, i.e. `concat!(env!("OUT_DIR"), ..)`, can not be resolved and
+/// are skipped.
+fn extract_included_file(path: &Path, trees: &[TokenTree]) -> Option<PathBuf> {
+    let (mac, args) = match trees {
+        [TokenTree::Ident(mac), TokenTree::Punct(bang), TokenTree::Group(args), ..]
+            if bang.as_char() == '!' && (mac == "include" || mac == "include_str") =>
+        {
+            (mac.to_string(), Vec::from_iter(args.stream()))
+        }
+        _ => return None,
+    };
+    let included = match args.as_slice() {
+        [TokenTree::Literal(literal)] | [TokenTree::Literal(literal), TokenTree::Punct(_)] => {
+            syn::parse_str::<syn::LitStr>(&literal.to_string())
+                .ok()?
+                .value()
+        }
+        _ => {
+            log::debug!(
+                "Skipping {mac}! in {}, the path is not a plain string literal",
+                path.display()
+            );
+            return None;
+        }
+    };
+    let included = path.parent()?.join(included);
+    if mac == "include_str" && included.extension().and_then(|ext| ext.to_str()) != Some("md") {
+        return None;
+    }
+    if !included.is_file() {
+        log::warn!(
+            "File {} included by {} does not exist",
+            included.display(),
+            path.display()
+        );
+        return None;
+    }
+    log::trace!("📎 Found included file {}", included.display());
+    // includes commonly step up, i.e. `../README.md`, which is checked already
+    fs::canonicalize(&included).ok()
+}
+
 fn extract_modules_recurse<P: AsRef<Path>>(
     path: P,
     stream: TokenStream,
+    cfgs: &CfgSet,
 ) -> Result<HashSet<PathBuf>> {
     let path: &Path = path.as_ref();
 
@@ -102,60 +332,97 @@ fn extract_modules_recurse<P: AsRef<Path>>(
 
     let mut acc = HashSet::with_capacity(16);
 
-    #[derive(Debug, Clone)]
-    enum SeekingFor {
-        ModulKeyword,
-        ModulName,
-        ModulFin(String),
-    }
-
-    let mut state = SeekingFor::ModulKeyword;
-    for tt in stream {
+    let trees = Vec::from_iter(stream);
+    for (idx, tt) in trees.iter().enumerate() {
         match tt {
-            TokenTree::Ident(ident) => match state {
-                SeekingFor::ModulKeyword => {
-                    if ident == "mod" {
-                        state = SeekingFor::ModulName;
+            TokenTree::Ident(ident) if ident == "mod" => {
+                let mod_name = match trees.get(idx + 1) {
+                    Some(TokenTree::Ident(mod_name)) => {
+                        let mod_name = mod_name.to_string();
+                        // raw identifiers, i.e. `mod r#async;`, live in `async.rs`
+                        mod_name
+                            .strip_prefix("r#")
+                            .map(ToOwned::to_owned)
+                            .unwrap_or(mod_name)
                     }
+                    _ => continue,
+                };
+                log::trace!("✨ Found a module: {mod_name}");
+                let attributes = outer_attributes(&trees[..idx]);
+                if let Some(predicate) = cfgs.disabled_by(&attributes) {
+                    log::info!(
+                        "Skipping module {mod_name} of {}, {predicate} does not hold",
+                        path.display()
+                    );
+                    continue;
                 }
-                SeekingFor::ModulName => {
-                    state = SeekingFor::ModulFin(ident.to_string());
-                }
-                _x => {
-                    state = SeekingFor::ModulKeyword;
-                }
-            },
-            TokenTree::Punct(punct) => {
-                if let SeekingFor::ModulFin(ref mod_name) = state {
-                    log::trace!("✨ Found a module: {mod_name}");
-                    if punct.as_char() == ';' && punct.spacing() == Spacing::Alone {
-                        extract_modules_recurse_collect(path, &mut acc, mod_name)?;
-                    } else {
+                match trees.get(idx + 2) {
+                    Some(TokenTree::Punct(punct))
+                        if punct.as_char() == ';' && punct.spacing() == Spacing::Alone =>
+                    {
+                        let custom = path_attributes(&attributes, cfgs);
+                        if custom.is_empty() {
+                            extract_modules_recurse_collect(path, &mut acc, &mod_name)?;
+                        }
+                        for custom in custom {
+                            extract_modules_recurse_collect_path(
+                                path, &mut acc, &mod_name, &custom,
+                            )?;
+                        }
+                    }
+                    Some(TokenTree::Group(grp)) => {
+                        acc.extend(extract_modules_recurse(path, grp.stream(), cfgs)?);
+                    }
+                    punct => {
                         log::trace!("🍂 Either not alone or not a semi colon {punct:?} - incomplete mod {mod_name}");
                     }
                 }
-                state = SeekingFor::ModulKeyword;
             }
             TokenTree::Group(grp) => {
-                state = SeekingFor::ModulKeyword;
-                acc.extend(extract_modules_recurse(path, grp.stream())?.into_iter());
+                // inline modules and `cfg_if!` invocations are handled along
+                // with their leading identifier
+                let handled = match idx.checked_sub(2).map(|idx| (&trees[idx], &trees[idx + 1])) {
+                    Some((TokenTree::Ident(ident), _)) if ident == "mod" => true,
+                    Some((TokenTree::Ident(ident), TokenTree::Punct(bang))) => {
+                        ident == "cfg_if" && bang.as_char() == '!'
+                    }
+                    _ => false,
+                };
+                if handled {
+                    continue;
+                }
+                acc.extend(extract_modules_recurse(path, grp.stream(), cfgs)?);
+            }
+            TokenTree::Ident(ident) if ident == "cfg_if" => {
+                if let [TokenTree::Punct(bang), TokenTree::Group(grp), ..] = &trees[(idx + 1)..] {
+                    if bang.as_char() == '!' {
+                        for branch in cfgs.select_cfg_if(grp.stream()) {
+                            acc.extend(extract_modules_recurse(path, branch, cfgs)?);
+                        }
+                    }
+                }
             }
-            _y => {
-                state = SeekingFor::ModulKeyword;
+            TokenTree::Ident(_) => {
+                acc.extend(extract_included_file(path, &trees[idx..]));
             }
+            _ => {}
         };
     }
     Ok(acc)
 }
 
-/// Read all `mod x;` declarations from a source file.
-pub(crate) fn extract_modules_from_file<P: AsRef<Path>>(path: P) -> Result<HashSet<PathBuf>> {
+/// Read all `mod x;` declarations and included files from a source file,
+/// modules gated by cfgs which are not enabled in `cfgs` are skipped.
+pub(crate) fn extract_modules_from_file<P: AsRef<Path>>(
+    path: P,
+    cfgs: &CfgSet,
+) -> Result<HashSet<PathBuf>> {
     let path: &Path = path.as_ref();
     if let Some(path_str) = path.to_str() {
         let s = fs::read_to_string(path_str)?;
         let stream = syn::parse_str::<proc_macro2::TokenStream>(s.as_str())
             .wrap_err_with(|| eyre!("File {path_str} has syntax errors"))?;
-        let acc = extract_modules_recurse(path, stream)?;
+        let acc = extract_modules_recurse(path, stream, cfgs)?;
         log::debug!(
             "🥞 Recursed into {} modules from {}",
             acc.len(),
@@ -179,8 +446,13 @@ pub(crate) fn extract_modules_from_file<P: AsRef<Path>>(path: P) -> Result<HashS
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CheckEntity {
     Markdown(PathBuf),
+    AsciiDoc(PathBuf),
+    ForeignSource(PathBuf),
+    PlainText(PathBuf),
     Source(PathBuf, bool), // recurse is the bool
     ManifestDescription(PathBuf, String),
+    ManifestFeatures(PathBuf, String),
+    ManifestFields(PathBuf, String),
 }
 
 impl CheckEntity {
@@ -188,8 +460,13 @@ impl CheckEntity {
     pub fn as_path(&self) -> &Path {
         match self {
             Self::Markdown(ref path) => path,
+            Self::AsciiDoc(ref path) => path,
+            Self::ForeignSource(ref path) => path,
+            Self::PlainText(ref path) => path,
             Self::Source(ref path, _) => path,
             Self::ManifestDescription(ref path, _) => path,
+            Self::ManifestFeatures(ref path, _) => path,
+            Self::ManifestFields(ref path, _) => path,
         }
         .as_path()
     }
@@ -200,7 +477,7 @@ fn load_manifest<P: AsRef<Path>>(manifest_dir: P) -> Result<(cargo_toml::Manifes
     let manifest_dir = manifest_dir.as_ref();
     let manifest_file = manifest_dir.join("Cargo.toml");
     // read to str first to provide better error messages
-    let manifest_content = fs::read_to_string(&manifest_file)?;
+    let manifest_content = read_source(&manifest_file)?;
     let mut manifest = cargo_toml::Manifest::from_str(manifest_content.as_str())
         .wrap_err_with(|| eyre!("Failed to parse manifest file {}", manifest_file.display()))?;
 
@@ -244,11 +521,21 @@ fn to_manifest_dir<P: AsRef<Path>>(manifest_dir: P) -> Result<PathBuf> {
 }
 
 /// Extract all cargo manifest products / build targets.
+///
+/// Includes tests, benches and examples, explicitly listed ones as well as
+/// the ones discovered in their default directories.
 fn extract_products(
     manifest: &cargo_toml::Manifest,
     manifest_dir: &Path,
 ) -> Result<HashSet<CheckEntity>> {
-    let iter = manifest.bin.clone().into_iter().chain(manifest.lib.clone());
+    let iter = manifest
+        .bin
+        .clone()
+        .into_iter()
+        .chain(manifest.lib.clone())
+        .chain(manifest.test.clone())
+        .chain(manifest.bench.clone())
+        .chain(manifest.example.clone());
 
     let items = iter
         .filter_map(|product| {
@@ -285,9 +572,16 @@ fn extract_readme(
         .as_ref()
         .and_then(|package| package.readme.get().ok().and_then(|x| x.as_path()))
         .and_then(|readme| {
-            let readme = PathBuf::from(readme);
+            let readme = manifest_dir.join(readme);
             if readme.is_file() {
-                Some(CheckEntity::Markdown(manifest_dir.join(readme)))
+                // the same file might be included as crate documentation, which
+                // is only detected as such with the canonical path
+                let readme = fs::canonicalize(&readme).unwrap_or(readme);
+                Some(if is_asciidoc(&readme) {
+                    CheckEntity::AsciiDoc(readme)
+                } else {
+                    CheckEntity::Markdown(readme)
+                })
             } else {
                 log::warn!(
                     "📜 read-me file declared in Cargo.toml {} is not a file",
@@ -315,10 +609,79 @@ fn extract_description(
         }))
 }
 
+fn extract_feature_docs(
+    manifest: &cargo_toml::Manifest,
+    manifest_dir: &Path,
+    manifest_content: &str,
+) -> Result<Option<CheckEntity>> {
+    if manifest.features.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(CheckEntity::ManifestFeatures(
+        manifest_dir.join("Cargo.toml"),
+        manifest_content.to_owned(),
+    )))
+}
+
+/// The configured `fields` of the manifest, if any.
+fn extract_fields(
+    manifest_dir: &Path,
+    manifest_content: &str,
+    fields: &[String],
+) -> Option<CheckEntity> {
+    if fields.is_empty() {
+        return None;
+    }
+    Some(CheckEntity::ManifestFields(
+        manifest_dir.join("Cargo.toml"),
+        manifest_content.to_owned(),
+    ))
+}
+
+/// Check if a workspace member is selected by the workspace configuration.
+///
+/// `workspace` is the workspace section of the root manifest, used to resolve
+/// an inherited `publish` flag.
+fn is_selected_member(
+    member_manifest: &cargo_toml::Manifest,
+    workspace: &cargo_toml::Workspace,
+    config: &crate::config::WorkspaceConfig,
+) -> bool {
+    let package = if let Some(package) = member_manifest.package.as_ref() {
+        package
+    } else {
+        return true;
+    };
+    if !config.is_selected(package.name.as_str()) {
+        log::debug!("🪆 Skipping deselected workspace member {}", package.name);
+        return false;
+    }
+    if config.skip_unpublished {
+        let publish = match package.publish {
+            cargo_toml::Inheritable::Set(ref publish) => Some(publish),
+            cargo_toml::Inheritable::Inherited { .. } => {
+                workspace.package.as_ref().map(|template| &template.publish)
+            }
+        };
+        let unpublished = match publish {
+            Some(cargo_toml::Publish::Flag(flag)) => !flag,
+            Some(cargo_toml::Publish::Registry(registries)) => registries.is_empty(),
+            None => false,
+        };
+        if unpublished {
+            log::debug!("🪆 Skipping unpublished workspace member {}", package.name);
+            return false;
+        }
+    }
+    true
+}
+
 fn handle_manifest<P: AsRef<Path>>(
     manifest_dir: P,
     skip_readme: bool,
+    config: &Config,
 ) -> Result<HashSet<CheckEntity>> {
+    let manifest_fields = config.manifest_fields.as_slice();
     let manifest_dir = to_manifest_dir(manifest_dir)?;
     log::trace!("📜 Handle manifest in dir: {}", manifest_dir.display());
 
@@ -360,11 +723,27 @@ fn handle_manifest<P: AsRef<Path>>(
         acc.extend(v);
     }
 
+    let v =
+        extract_feature_docs(&manifest, manifest_dir, &manifest_content).wrap_err_with(|| {
+            eyre!(
+                "Failed to extract feature documentation from manifest {}",
+                manifest_dir.display()
+            )
+        })?;
+    acc.extend(v);
+
+    // also covers virtual manifests, i.e. `workspace.package.description`
+    acc.extend(extract_fields(
+        manifest_dir,
+        &manifest_content,
+        manifest_fields,
+    ));
+
     if let Some(workspace) = manifest.workspace {
         log::trace!("🪆 Handling manifest workspace");
         workspace
             .members
-            .into_iter()
+            .iter()
             .try_for_each::<_, Result<()>>(|member_entry_glob| {
                 let member_dir_glob = manifest_dir.join(&member_entry_glob);
 
@@ -382,7 +761,7 @@ fn handle_manifest<P: AsRef<Path>>(
                         "🪆 Handling manifest member glob resolved: {}",
                         member_dir.display()
                     );
-                    if let Ok((member_manifest, _member_manifest_content)) =
+                    if let Ok((member_manifest, member_manifest_content)) =
                         load_manifest(&member_dir).wrap_err_with(|| {
                             eyre!(
                                 "Failed to load manifest from member directory {}",
@@ -390,8 +769,16 @@ fn handle_manifest<P: AsRef<Path>>(
                             )
                         })
                     {
+                        if !is_selected_member(&member_manifest, &workspace, &config.workspace) {
+                            continue;
+                        }
                         if let Ok(member) = extract_products(&member_manifest, &member_dir) {
                             acc.extend(member.into_iter());
+                            acc.extend(extract_fields(
+                                &member_dir,
+                                &member_manifest_content,
+                                manifest_fields,
+                            ));
                         } else {
                             bail!(
                                 "Workspace member {} product extraction failed",
@@ -411,185 +798,555 @@ fn handle_manifest<P: AsRef<Path>>(
     Ok(acc)
 }
 
-/// Extract all chunks from
-pub(crate) fn extract(
-    mut paths: Vec<PathBuf>,
-    mut recurse: bool,
-    skip_readme: bool,
-    dev_comments: bool,
-    _config: &Config,
-) -> Result<Documentation> {
-    let cwd = cwd()?;
-    // if there are no arguments, pretend to be told to check the whole project
-    if paths.is_empty() {
-        paths.push(cwd.clone());
-        recurse = true;
-    }
-
-    log::debug!("Running on inputs {paths:?} / recursive={recurse}");
-
-    #[derive(Debug, Clone)]
-    enum Extraction {
-        Manifest(PathBuf),
-        Missing(PathBuf),
-        Source(PathBuf),
-        Markdown(PathBuf),
-    }
-
-    // stage 1 - obtain canonical paths
-    let mut flow = VecDeque::<PathBuf>::with_capacity(32);
-    flow.extend(paths.into_iter().filter_map(|path_in| {
-        let path = if path_in.is_absolute() {
-            path_in.to_owned()
-        } else {
-            cwd.join(&path_in)
-        };
-        log::debug!("Processing {} -> {}", path_in.display(), path.display());
-        path.canonicalize().ok()
-    }));
-
-    log::debug!("Running on absolute dirs {flow:?}");
-
-    // stage 2 - check for manifest, .rs , .md files and directories
-    let mut files_to_check = Vec::with_capacity(64);
-    while let Some(path) = flow.pop_front() {
-        let x = if let Ok(meta) = path.metadata() {
-            if meta.is_file() {
-                match path.file_name().and_then(|x| x.to_str()) {
-                    Some(file_name) if file_name == "Cargo.toml" => Extraction::Manifest(path),
-                    Some(file_name) if file_name.ends_with(".md") => Extraction::Markdown(path),
-                    Some(file_name) if file_name.ends_with(".rs") => Extraction::Source(path),
-                    _ => {
-                        // This branch is commonly entered when ran on a non-cargo
-                        // path.
-                        // Potentially become mdbook aware
-                        // <https://github.com/drahnr/cargo-spellcheck/issues/273>
-                        log::debug!(
-                            "Unknown file type encountered, skipping path: {}",
-                            path.display()
-                        );
-                        continue;
-                    }
+/// Expand the configured `globs`, relative to `base`.
+fn expand_globs(base: &Path, globs: &[String]) -> Vec<PathBuf> {
+    let mut acc = Vec::new();
+    for glob in globs {
+        let pattern = base.join(glob);
+        let pattern = pattern.to_string_lossy();
+        match glob::glob(&pattern) {
+            Ok(paths) => {
+                let before = acc.len();
+                acc.extend(paths.filter_map(|path| path.ok()));
+                if acc.len() == before {
+                    log::debug!("Glob >{glob}< did not match any file");
                 }
-            } else if meta.is_dir() {
-                let cargo_toml = to_manifest_dir(&path).unwrap().join("Cargo.toml");
-                if cargo_toml.is_file() {
-                    Extraction::Manifest(cargo_toml)
-                } else if recurse {
-                    // keep walking directories and feed the path back
-                    // if recursing is wanted
-                    // and if it doesn't contain a manifest file
-                    match fs::read_dir(path) {
-                        Err(err) => log::warn!("Listing directory contents {err} failed"),
-                        Ok(entries) => {
-                            for entry in entries.flatten() {
-                                let path = entry.path();
-                                // let's try with that path again
-                                flow.push_back(path);
-                            }
+            }
+            Err(e) => log::warn!("Invalid glob >{glob}<: {e}"),
+        }
+    }
+    acc
+}
+
+/// Add the markdown files included as documentation by the rust source file at
+/// `path`, i.e. `#![doc = include_str!("../README.md")]`, unless present
+/// already.
+fn add_included_markdown(docs: &mut Documentation, path: &Path, config: &Config) -> Result<()> {
+    let included = match extract_modules_from_file(path, &CfgSet::new(&config.enable_cfg)) {
+        Ok(included) => included,
+        Err(e) => {
+            log::debug!("Failed to find included files of {}: {e:?}", path.display());
+            return Ok(());
+        }
+    };
+    for included in included.into_iter().filter(|path| is_markdown(path)) {
+        let origin = ContentOrigin::CommonMarkFile(included.clone());
+        if docs.contains_key(&origin) || is_oversized(&included, config) {
+            continue;
+        }
+        let content = read_source(&included)?;
+        if !content.is_empty() {
+            docs.add_commonmark_with_front_matter(
+                origin,
+                content.as_str(),
+                &config.front_matter_fields,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Check if the file at `path` is an AsciiDoc document.
+fn is_asciidoc(path: &Path) -> bool {
+    path.extension().map_or(false, |extension| {
+        extension == "adoc" || extension == "asciidoc"
+    })
+}
+
+/// The comment syntax of the first configured language the file at `path`
+/// is written in, if any.
+fn comment_syntax(path: &Path, config: &Config) -> Option<CommentSyntax> {
+    let cwd = cwd().ok()?;
+    config
+        .comments
+        .iter()
+        .find(|comments| comments.matches(path, &cwd))
+        .map(CommentsConfig::syntax)
+}
+
+/// Check if the file at `path` is checked as plain text, without any
+/// extension like `LICENSE` or with a `.txt` one.
+fn is_plain_text(path: &Path) -> bool {
+    path.extension()
+        .map_or(true, |extension| extension == "txt")
+}
+
+/// Read the file at `path` to check, without a leading byte order mark since
+/// spans do not account for it.
+fn read_source(path: &Path) -> std::io::Result<String> {
+    let mut content = fs::read_to_string(path)?;
+    if content.starts_with(BYTE_ORDER_MARK) {
+        content.drain(..BYTE_ORDER_MARK.len_utf8());
+    }
+    Ok(content)
+}
+
+/// Check if the file at `path` exceeds the configured `max_file_size`.
+fn is_oversized(path: &Path, config: &Config) -> bool {
+    if config.max_file_size == 0 {
+        return false;
+    }
+    match fs::metadata(path) {
+        Ok(meta) if meta.len() > config.max_file_size => {
+            log::warn!(
+                "Skipping {} of {} bytes, exceeding the limit of {} bytes, pass `--no-size-limits` to check it anyway",
+                path.display(),
+                meta.len(),
+                config.max_file_size
+            );
+            true
+        }
+        _ => false,
+    }
+}
+
+/// The entities to check, i.e. source and markdown files or manifests, along
+/// with the options to extract their documentation with.
+#[derive(Debug, Clone)]
+pub struct Extraction {
+    entities: Vec<CheckEntity>,
+    dev_comments: DevComments,
+    check_strings: bool,
+    path_filter: PathFilter,
+    config: Config,
+}
+
+impl Extraction {
+    /// Resolve the entities to check from the given `paths`, without
+    /// extracting their documentation yet.
+    pub(crate) fn new(
+        mut paths: Vec<PathBuf>,
+        mut recurse: bool,
+        skip_readme: bool,
+        dev_comments: DevComments,
+        check_strings: bool,
+        config: &Config,
+    ) -> Result<Self> {
+        let cwd = cwd()?;
+        // if there are no arguments, pretend to be told to check the whole project
+        if paths.is_empty() {
+            paths.push(cwd.clone());
+            recurse = true;
+        }
+
+        log::debug!("Running on inputs {paths:?} / recursive={recurse}");
+
+        #[derive(Debug, Clone)]
+        enum TaggedPath {
+            Manifest(PathBuf),
+            Missing(PathBuf),
+            Source(PathBuf),
+            Markdown(PathBuf),
+            AsciiDoc(PathBuf),
+            ForeignSource(PathBuf),
+            PlainText(PathBuf),
+        }
+
+        // stage 1 - obtain canonical paths
+        let mut flow = VecDeque::<PathBuf>::with_capacity(32);
+        flow.extend(paths.into_iter().filter_map(|path_in| {
+            let path = if path_in.is_absolute() {
+                path_in.to_owned()
+            } else {
+                cwd.join(&path_in)
+            };
+            log::debug!("Processing {} -> {}", path_in.display(), path.display());
+            path.canonicalize().ok()
+        }));
+        // plain text files are only checked if passed explicitly
+        let explicit = HashSet::<PathBuf>::from_iter(flow.iter().cloned());
+
+        // additional markdown and other language files, only when checking
+        // the whole project
+        if recurse {
+            let comments = config.comments.iter().flat_map(|comments| &comments.paths);
+            let globs = Vec::from_iter(config.markdown.iter().chain(comments).cloned());
+            flow.extend(
+                expand_globs(&cwd, &globs)
+                    .into_iter()
+                    .filter_map(|path| path.canonicalize().ok()),
+            );
+        }
+
+        log::debug!("Running on absolute dirs {flow:?}");
+
+        // stage 2 - check for manifest, .rs , .md files and directories
+        let mut files_to_check = Vec::with_capacity(64);
+        while let Some(path) = flow.pop_front() {
+            let x = if let Ok(meta) = path.metadata() {
+                if meta.is_file() {
+                    match path.file_name().and_then(|x| x.to_str()) {
+                        Some(file_name) if file_name == "Cargo.toml" => TaggedPath::Manifest(path),
+                        Some(file_name) if file_name.ends_with(".md") => TaggedPath::Markdown(path),
+                        Some(file_name) if file_name.ends_with(".rs") => TaggedPath::Source(path),
+                        _ if is_asciidoc(&path) => TaggedPath::AsciiDoc(path),
+                        _ if comment_syntax(&path, config).is_some() => {
+                            TaggedPath::ForeignSource(path)
+                        }
+                        _ if explicit.contains(&path) && is_plain_text(&path) => {
+                            TaggedPath::PlainText(path)
+                        }
+                        _ => {
+                            // This branch is commonly entered when ran on a non-cargo
+                            // path.
+                            // Potentially become mdbook aware
+                            // <https://github.com/drahnr/cargo-spellcheck/issues/273>
+                            log::debug!(
+                                "Unknown file type encountered, skipping path: {}",
+                                path.display()
+                            );
+                            continue;
                         }
                     }
-                    continue;
-                } else {
-                    match fs::read_dir(path) {
-                        Err(err) => log::warn!("Listing directory contents {err} failed"),
-                        Ok(entries) => {
-                            for entry in entries.flatten() {
-                                let path = entry.path();
-                                // let's try attempt with that .rs file
-                                // if we end up here, recursion is off already
-                                if path.is_file() {
+                } else if meta.is_dir() {
+                    let cargo_toml = to_manifest_dir(&path).unwrap().join("Cargo.toml");
+                    if cargo_toml.is_file() {
+                        TaggedPath::Manifest(cargo_toml)
+                    } else if recurse {
+                        // keep walking directories and feed the path back
+                        // if recursing is wanted
+                        // and if it doesn't contain a manifest file
+                        match fs::read_dir(path) {
+                            Err(err) => log::warn!("Listing directory contents {err} failed"),
+                            Ok(entries) => {
+                                for entry in entries.flatten() {
+                                    let path = entry.path();
+                                    // let's try with that path again
                                     flow.push_back(path);
                                 }
                             }
                         }
+                        continue;
+                    } else {
+                        match fs::read_dir(path) {
+                            Err(err) => log::warn!("Listing directory contents {err} failed"),
+                            Ok(entries) => {
+                                for entry in entries.flatten() {
+                                    let path = entry.path();
+                                    // let's try attempt with that .rs file
+                                    // if we end up here, recursion is off already
+                                    if path.is_file() {
+                                        flow.push_back(path);
+                                    }
+                                }
+                            }
+                        }
+                        continue;
                     }
-                    continue;
+                } else {
+                    TaggedPath::Missing(path)
                 }
             } else {
-                Extraction::Missing(path)
+                TaggedPath::Missing(path)
+            };
+            files_to_check.push(x);
+        }
+
+        log::debug!("Found a total of {} files to check ", files_to_check.len());
+
+        // stage 3 - resolve the manifest products and workspaces, warn about missing
+        let files_to_check = files_to_check
+            .into_par_iter()
+            .map(|tagged_path| -> Result<Vec<_>> {
+                let acc = match tagged_path {
+                    TaggedPath::Manifest(ref cargo_toml_path) => {
+                        Vec::from_iter(handle_manifest(cargo_toml_path, skip_readme, config)?)
+                    }
+                    TaggedPath::Missing(ref missing_path) => {
+                        log::warn!(
+                            "File passed as argument or listed in Cargo.toml manifest does not exist: {}",
+                            missing_path.display()
+                        );
+                        Vec::new()
+                    }
+                    TaggedPath::Source(path) => vec![CheckEntity::Source(path, recurse)],
+                    TaggedPath::Markdown(path) => vec![CheckEntity::Markdown(path)],
+                    TaggedPath::AsciiDoc(path) => vec![CheckEntity::AsciiDoc(path)],
+                    TaggedPath::ForeignSource(path) => vec![CheckEntity::ForeignSource(path)],
+                    TaggedPath::PlainText(path) => vec![CheckEntity::PlainText(path)],
+                };
+                Ok(acc)
+            })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        // a file might be passed explicitly, declared in a manifest and matched
+        // by a glob at the same time
+        let mut files_to_check = files_to_check;
+        let mut seen = HashSet::with_capacity(files_to_check.len());
+        files_to_check.retain(|check_entity| seen.insert(check_entity.clone()));
+
+        Ok(Self {
+            entities: files_to_check,
+            dev_comments,
+            check_strings,
+            path_filter: PathFilter::new(&config.include_paths, &config.exclude_paths)?,
+            config: config.clone(),
+        })
+    }
+
+    /// The paths the extraction reads from, the directory of the entry point
+    /// for sources which pull in their modules recursively.
+    pub(crate) fn paths(&self) -> Vec<PathBuf> {
+        let mut acc = Vec::from_iter(self.entities.iter().map(|entity| {
+            match entity {
+                CheckEntity::Source(path, true) => path
+                    .parent()
+                    .map_or_else(|| path.clone(), Path::to_path_buf),
+                entity => entity.as_path().to_path_buf(),
             }
-        } else {
-            Extraction::Missing(path)
-        };
-        files_to_check.push(x);
+        }));
+        acc.sort();
+        acc.dedup();
+        acc
     }
 
-    log::debug!("Found a total of {} files to check ", files_to_check.len());
+    /// Extract the documentation of all entities at once.
+    ///
+    /// Every entity, and hence every workspace member, is extracted on its
+    /// own and the results are joined in order afterwards.
+    pub(crate) fn extract(self) -> Result<Documentation> {
+        let Self {
+            entities,
+            dev_comments,
+            check_strings,
+            path_filter,
+            config,
+        } = self;
+        entities
+            .into_par_iter()
+            .map(|entity| extract_entity(entity, &dev_comments, check_strings, &config))
+            .try_reduce(Documentation::new, |mut acc, docs| {
+                // Files reached from multiple entry points are only checked once.
+                let docs = Vec::from_iter(
+                    docs.into_iter()
+                        .filter(|(origin, _chunks)| path_filter.selects(origin.as_path()))
+                        .filter(|(origin, _chunks)| !acc.contains_key(origin)),
+                );
+                acc.extend(docs);
+                Ok(acc)
+            })
+    }
 
-    // stage 3 - resolve the manifest products and workspaces, warn about missing
-    let files_to_check = files_to_check
-        .into_iter()
-        .try_fold::<Vec<_>, _, Result<_>>(Vec::with_capacity(64), |mut acc, tagged_path| {
-            match tagged_path {
-                Extraction::Manifest(ref cargo_toml_path) => {
-                    let manifest_list = handle_manifest(cargo_toml_path, skip_readme)?;
-                    acc.extend(manifest_list);
-                }
-                Extraction::Missing(ref missing_path) => log::warn!(
-                    "File passed as argument or listed in Cargo.toml manifest does not exist: {}",
-                    missing_path.display()
-                ),
-                Extraction::Source(path) => acc.push(CheckEntity::Source(path, recurse)),
-                Extraction::Markdown(path) => acc.push(CheckEntity::Markdown(path)),
-            }
-            Ok(acc)
-        })?;
+    /// Extract the documentation of the entities on a dedicated pool of
+    /// `jobs` threads and pass each file to `sink` as soon as it is
+    /// extracted, blocking while `sink` is full.
+    ///
+    /// The pool is separate from the global one, which checks the files
+    /// received, so a full `sink` can not stall the checking. Fails once the
+    /// receiving end is gone. The remaining entities are skipped once `stop`
+    /// holds, i.e. the outcome of the check is settled.
+    pub(crate) fn extract_into(
+        self,
+        sink: SyncSender<(ContentOrigin, Vec<CheckableChunk>)>,
+        jobs: usize,
+        stop: impl Fn() -> bool + Sync,
+    ) -> Result<()> {
+        let Self {
+            entities,
+            dev_comments,
+            check_strings,
+            path_filter,
+            config,
+        } = self;
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .thread_name(|idx| format!("extract-{idx}"))
+            .build()?;
+        // Files reached from multiple entry points are only checked once.
+        let seen = Mutex::new(HashSet::<ContentOrigin>::new());
+        pool.install(|| {
+            entities
+                .into_par_iter()
+                .try_for_each_with(sink, |sink, entity| -> Result<()> {
+                    if stop() {
+                        return Ok(());
+                    }
+                    let docs = extract_entity(entity, &dev_comments, check_strings, &config)?;
+                    for (origin, chunks) in docs {
+                        if !path_filter.selects(origin.as_path()) {
+                            continue;
+                        }
+                        let first = seen
+                            .lock()
+                            .expect("Extraction does not panic while holding the lock. qed")
+                            .insert(origin.clone());
+                        if first {
+                            sink.send((origin, chunks)).map_err(|_| {
+                                eyre!("Checking stopped before all files were extracted")
+                            })?;
+                        }
+                    }
+                    Ok(())
+                })
+        })
+    }
+}
+
+/// Extract all chunks from the given `paths`.
+pub(crate) fn extract(
+    paths: Vec<PathBuf>,
+    recurse: bool,
+    skip_readme: bool,
+    dev_comments: &DevComments,
+    check_strings: bool,
+    config: &Config,
+) -> Result<Documentation> {
+    Extraction::new(
+        paths,
+        recurse,
+        skip_readme,
+        dev_comments.clone(),
+        check_strings,
+        config,
+    )?
+    .extract()
+}
 
-    // stage 4 - expand from the passed source files, if recursive, recurse down the module train
-    let docs = files_to_check.into_iter().try_fold(
-        Documentation::new(),
-        |mut docs, check_entity| -> Result<_> {
-            match check_entity {
-                CheckEntity::Source(path, recurse) => {
-                    let content: String = fs::read_to_string(&path)?;
-                    docs.add_rust(
+/// Extract the documentation of a single entity, if recursive, down the
+/// module train.
+fn extract_entity(
+    check_entity: CheckEntity,
+    dev_comments: &DevComments,
+    check_strings: bool,
+    config: &Config,
+) -> Result<Documentation> {
+    let mut docs = Documentation::new();
+    match check_entity {
+        CheckEntity::Source(path, recurse) => {
+            let content = match is_oversized(&path, config) {
+                // the declared modules are still followed
+                true => None,
+                false => Some(read_source(&path)?),
+            };
+            let origin = ContentOrigin::RustSourceFile(path.clone());
+            let dev_comments_of_file = dev_comments.applies_to(&path);
+            if let Some(reason) = content.as_deref().and_then(|content| {
+                generated_by(&path, content, config.skipped_generated_markers())
+            }) {
+                log::info!("Skipping generated file {}, {reason}", path.display());
+            } else if let Some(content) = content {
+                if config.public_only {
+                    docs.add_rust_public(origin, content.as_str(), dev_comments_of_file)?;
+                } else {
+                    docs.add_rust(origin, content.as_str(), true, dev_comments_of_file)?;
+                }
+                if check_strings {
+                    docs.add_rust_string_literals(
                         ContentOrigin::RustSourceFile(path.clone()),
                         content.as_str(),
-                        true,
-                        dev_comments,
                     )?;
-
-                    if recurse {
-                        let iter =
-                            Vec::from_iter(traverse(path.as_path(), true, dev_comments)?.flat_map(
-                                |documentation| {
-                                    // Filter out duplicate _chunks_
-                                    // that `extend` would happily duplicate.
-                                    documentation
-                                        .into_iter()
-                                        .filter(|(origin, _chunks)| !docs.contains_key(origin))
-                                },
-                            ));
-                        docs.extend(iter);
-                    }
-                }
-                CheckEntity::Markdown(path) => {
-                    let content = fs::read_to_string(&path)
-                        .wrap_err_with(|| eyre!("Common mark / markdown file does not exist"))?;
-                    if content.is_empty() {
-                        bail!("Common mark / markdown file is empty")
-                    }
-                    docs.add_commonmark(ContentOrigin::CommonMarkFile(path), content.as_str())?;
                 }
-                CheckEntity::ManifestDescription(path, content) => {
-                    if content.is_empty() {
-                        bail!("Cargo.toml manifest description field is empty")
-                    }
-                    docs.add_cargo_manifest_description(path, content.as_str())?;
+                if config.check_help {
+                    docs.add_rust_help_texts(
+                        ContentOrigin::RustSourceFile(path.clone()),
+                        content.as_str(),
+                    )?;
                 }
             }
-            Result::Ok(docs)
-        },
-    )?;
 
-    Result::Ok(docs)
+            if recurse {
+                let iter = Vec::from_iter(
+                    traverse(
+                        path.as_path(),
+                        true,
+                        config.public_only,
+                        dev_comments.clone(),
+                        check_strings,
+                        config.check_help,
+                        CfgSet::new(&config.enable_cfg),
+                        config.skipped_generated_markers(),
+                    )?
+                    .flat_map(|documentation| {
+                        // Filter out duplicate _chunks_
+                        // that `extend` would happily duplicate.
+                        documentation
+                            .into_iter()
+                            .filter(|(origin, _chunks)| !docs.contains_key(origin))
+                            .filter(|(origin, _chunks)| !is_oversized(origin.as_path(), config))
+                    }),
+                );
+                docs.extend(iter);
+            } else {
+                // followed along with the modules otherwise
+                add_included_markdown(&mut docs, &path, config)?;
+            }
+        }
+        CheckEntity::Markdown(path) if is_oversized(&path, config) => {}
+        CheckEntity::Markdown(path) => {
+            let content = read_source(&path)
+                .wrap_err_with(|| eyre!("Common mark / markdown file does not exist"))?;
+            if content.is_empty() {
+                bail!("Common mark / markdown file is empty")
+            }
+            docs.add_commonmark_with_front_matter(
+                ContentOrigin::CommonMarkFile(path),
+                content.as_str(),
+                &config.front_matter_fields,
+            )?;
+        }
+        CheckEntity::AsciiDoc(path) if is_oversized(&path, config) => {}
+        CheckEntity::AsciiDoc(path) => {
+            let content = read_source(&path)
+                .wrap_err_with(|| eyre!("Failed to read AsciiDoc file {}", path.display()))?;
+            docs.add_asciidoc(ContentOrigin::AsciiDocFile(path), content.as_str())?;
+        }
+        CheckEntity::ForeignSource(path) if is_oversized(&path, config) => {}
+        CheckEntity::ForeignSource(path) => {
+            let syntax = comment_syntax(&path, config)
+                .ok_or_else(|| eyre!("No comment syntax configured for {}", path.display()))?;
+            let content = read_source(&path)
+                .wrap_err_with(|| eyre!("Failed to read source file {}", path.display()))?;
+            docs.add_foreign_comments(
+                ContentOrigin::ForeignSourceFile(path),
+                content.as_str(),
+                &syntax,
+            )?;
+        }
+        CheckEntity::PlainText(path) if is_oversized(&path, config) => {}
+        CheckEntity::PlainText(path) => {
+            let content = read_source(&path)
+                .wrap_err_with(|| eyre!("Failed to read plain text file {}", path.display()))?;
+            docs.add_plain_text(ContentOrigin::PlainTextFile(path), content.as_str())?;
+        }
+        CheckEntity::ManifestDescription(path, content) => {
+            if content.is_empty() {
+                bail!("Cargo.toml manifest description field is empty")
+            }
+            docs.add_cargo_manifest_description(path, content.as_str())?;
+        }
+        CheckEntity::ManifestFeatures(path, content) => {
+            docs.add_cargo_manifest_features(path, content.as_str())?;
+        }
+        CheckEntity::ManifestFields(path, content) => {
+            docs.add_cargo_manifest_fields(path, content.as_str(), &config.manifest_fields)?;
+        }
+    }
+
+    if config.max_chunk_chars > 0 {
+        docs.retain_chunks(|origin, chunk| {
+            let len = chunk.len_in_chars();
+            let keep = len <= config.max_chunk_chars;
+            if !keep {
+                log::warn!(
+                    "Skipping a chunk of {len} characters in {origin}, exceeding the limit of {} characters, pass `--no-size-limits` to check it anyway",
+                    config.max_chunk_chars
+                );
+            }
+            keep
+        });
+    }
+
+    Ok(docs)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use crate::tests::TempDir;
     use itertools::Itertools;
 
     const TEST_FILE_FRAGMENTS: &str = "src/nested/fragments.rs";
@@ -602,7 +1359,7 @@ mod tests {
             .try_init();
 
         assert_eq!(
-            extract_modules_from_file(demo_dir().join(TEST_FILE_FRAGMENTS))
+            extract_modules_from_file(demo_dir().join(TEST_FILE_FRAGMENTS), &CfgSet::default())
                 .expect("fragments.rs must exist"),
             maplit::hashset![
                 demo_dir()
@@ -615,6 +1372,123 @@ mod tests {
         );
     }
 
+    #[test]
+    fn obtain_modules_custom_path() {
+        let base = TempDir::new();
+        fs::create_dir_all(base.join("custom")).unwrap();
+        fs::write(
+            base.join("lib.rs"),
+            r#"
+#[path = "custom/location.rs"]
+mod foo;
+#[cfg_attr(unix, path = "custom/unix.rs")]
+#[cfg_attr(windows, path = "custom/windows.rs")]
+mod sys;
+#[cfg(windows)]
+mod win;
+"#,
+        )
+        .unwrap();
+        for file in [
+            "custom/location.rs",
+            "custom/unix.rs",
+            "custom/windows.rs",
+            "win.rs",
+        ] {
+            fs::write(base.join(file), "").unwrap();
+        }
+
+        assert_eq!(
+            extract_modules_from_file(base.join("lib.rs"), &CfgSet::default()).unwrap(),
+            maplit::hashset![
+                base.join("custom/location.rs"),
+                base.join("custom/unix.rs"),
+                base.join("custom/windows.rs"),
+                base.join("win.rs"),
+            ]
+        );
+        assert_eq!(
+            extract_modules_from_file(base.join("lib.rs"), &CfgSet::new(&["unix"])).unwrap(),
+            maplit::hashset![base.join("custom/location.rs"), base.join("custom/unix.rs"),]
+        );
+    }
+
+    #[test]
+    fn obtain_modules_cfg_if() {
+        let base = TempDir::new();
+        fs::write(
+            base.join("lib.rs"),
+            r#"
+cfg_if::cfg_if! {
+    if #[cfg(unix)] {
+        mod unix;
+    } else if #[cfg(windows)] {
+        pub(crate) mod r#windows;
+    } else {
+        mod fallback;
+    }
+}
+macro_rules! declare {
+    ($name:ident) => { mod $name; };
+    () => { mod literal; };
+}
+"#,
+        )
+        .unwrap();
+        for file in ["unix.rs", "windows.rs", "fallback.rs", "literal.rs"] {
+            fs::write(base.join(file), "").unwrap();
+        }
+
+        let modules = |cfgs: &[&str]| {
+            extract_modules_from_file(base.join("lib.rs"), &CfgSet::new(cfgs)).unwrap()
+        };
+        assert_eq!(
+            modules(&[]),
+            maplit::hashset![
+                base.join("unix.rs"),
+                base.join("windows.rs"),
+                base.join("fallback.rs"),
+                base.join("literal.rs"),
+            ]
+        );
+        assert_eq!(
+            modules(&["windows"]),
+            maplit::hashset![base.join("windows.rs"), base.join("literal.rs")]
+        );
+        assert_eq!(
+            modules(&["target_os = \"none\""]),
+            maplit::hashset![base.join("fallback.rs"), base.join("literal.rs")]
+        );
+    }
+
+    #[test]
+    fn obtain_included_files() {
+        let base = TempDir::new();
+        fs::create_dir_all(base.join("gen")).unwrap();
+        fs::write(
+            base.join("lib.rs"),
+            r#"
+include!("gen/tables.rs");
+#[doc = include_str!("gen/intro.md")]
+pub struct Intro;
+const RAW: &str = include_str!("gen/raw.txt");
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+"#,
+        )
+        .unwrap();
+        for file in ["gen/tables.rs", "gen/intro.md", "gen/raw.txt"] {
+            fs::write(base.join(file), "").unwrap();
+        }
+
+        assert_eq!(
+            extract_modules_from_file(base.join("lib.rs"), &CfgSet::default()).unwrap(),
+            maplit::hashset![
+                fs::canonicalize(base.join("gen/tables.rs")).unwrap(),
+                fs::canonicalize(base.join("gen/intro.md")).unwrap(),
+            ]
+        );
+    }
+
     #[test]
     fn manifest_entries() {
         let _ = env_logger::builder()
@@ -628,6 +1502,7 @@ mod tests {
             maplit::hashset![
                 CheckEntity::Source(demo_dir().join("src/main.rs"), true),
                 CheckEntity::Source(demo_dir().join("src/lib.rs"), true),
+                CheckEntity::Source(demo_dir().join("examples/showcase.rs"), true),
             ]
         );
         assert_eq!(
@@ -645,6 +1520,10 @@ mod tests {
                 assert_eq!(path, demo_dir().join("Cargo.toml"));
             }
         );
+        assert_matches::assert_matches!(
+            extract_feature_docs(&manifest, &dir, manifest_content.as_str()),
+            Ok(None)
+        );
     }
 
     fn demo_dir() -> PathBuf {
@@ -728,7 +1607,8 @@ mod tests {
                 ],
                 $recurse,
                 false,
-                true,
+                &DevComments::from(true),
+                false,
                 &Config::default(),
             )
             .expect("Must be able to extract demo dir");
@@ -767,6 +1647,7 @@ mod tests {
             "src/nested/justone.rs",
             "src/nested/justtwo.rs",
             "src/nested/mod.rs",
+            "examples/showcase.rs",
             "member/true/lib.rs",
             "member/procmacro/src/lib.rs",
         ]);
@@ -802,6 +1683,7 @@ mod tests {
         "src/nested/justone.rs",
         "src/nested/justtwo.rs",
         "src/nested/mod.rs",
+        "examples/showcase.rs",
         "member/true/lib.rs",
         "member/procmacro/src/lib.rs",
     ]);
@@ -819,6 +1701,7 @@ mod tests {
         "src/nested/justone.rs",
         "src/nested/justtwo.rs",
         "src/nested/mod.rs",
+        "examples/showcase.rs",
         "member/true/lib.rs",
         "member/procmacro/src/lib.rs",
     ]);
@@ -846,4 +1729,288 @@ mod tests {
         // "member/procmacro/Cargo.toml",
         "member/stray.rs",
     ]);
+
+    #[test]
+    fn traverse_markdown_globs() {
+        let config = Config {
+            markdown: vec![
+                "demo/member/*/README.md".to_owned(),
+                "demo/README.md".to_owned(),
+            ],
+            ..Config::default()
+        };
+        let docs = extract(
+            vec![demo_dir()],
+            true,
+            false,
+            &DevComments::from(true),
+            false,
+            &config,
+        )
+        .expect("Must be able to extract demo dir");
+        let paths = Vec::from_iter(docs.iter().map(|(origin, _)| {
+            origin
+                .as_path()
+                .strip_prefix(demo_dir())
+                .expect("Must have common prefix")
+                .to_owned()
+        }));
+        assert!(paths.contains(&PathBuf::from("member/true/README.md")));
+        // declared in the manifest and matched by the glob
+        assert_eq!(
+            paths
+                .iter()
+                .filter(|path| path.as_path() == Path::new("README.md"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn included_readme_once() {
+        let base = TempDir::new();
+        fs::create_dir_all(base.join("src")).unwrap();
+        fs::write(
+            base.join("Cargo.toml"),
+            "[package]\nname = \"included\"\nversion = \"0.1.0\"\nreadme = \"./README.md\"\n",
+        )
+        .unwrap();
+        fs::write(base.join("README.md"), "# Included\n\nOnly once.\n").unwrap();
+        fs::write(
+            base.join("src/lib.rs"),
+            "#![doc = include_str!(\"../README.md\")]\n\n/// Documented.\npub struct Foo;\n",
+        )
+        .unwrap();
+        let readme = ContentOrigin::CommonMarkFile(base.join("README.md"));
+
+        for (path, recurse) in [(base.to_path_buf(), true), (base.join("src/lib.rs"), false)] {
+            let docs = extract(
+                vec![path],
+                recurse,
+                false,
+                &DevComments::from(false),
+                false,
+                &Config::default(),
+            )
+            .expect("Must be able to extract");
+            assert_eq!(docs.get(&readme).map(|chunks| chunks.len()), Some(1));
+        }
+    }
+
+    #[test]
+    fn workspace_root_of_member() {
+        let base = TempDir::new();
+        fs::create_dir_all(base.join("member/src")).unwrap();
+        fs::write(
+            base.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"member\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            base.join("member/Cargo.toml"),
+            "[package]\nname = \"member\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        assert_eq!(workspace_root(&base.join("member/src")), base.to_path_buf());
+        assert_eq!(workspace_root(&base), base.to_path_buf());
+
+        let outside = TempDir::new();
+        assert_eq!(workspace_root(&outside), outside.to_path_buf());
+    }
+
+    #[test]
+    fn generated_skipped_by_default() {
+        let base = TempDir::new();
+        let path = base.join("bindings.rs");
+        fs::write(
+            &path,
+            "// @generated by prost-build\n\n/// Generaetd docs.\npub struct Foo;\n",
+        )
+        .unwrap();
+
+        let extract_with = |config: &Config| {
+            extract(
+                vec![path.clone()],
+                false,
+                false,
+                &DevComments::from(false),
+                false,
+                config,
+            )
+            .expect("Must be able to extract")
+        };
+        assert!(extract_with(&Config::default()).is_empty());
+        assert!(extract_with(&Config::parse("").unwrap()).is_empty());
+        let docs = extract_with(&Config {
+            skip_generated: false,
+            ..Config::default()
+        });
+        assert_eq!(docs.entry_count(), 1);
+    }
+
+    #[test]
+    fn size_limits() {
+        let base = TempDir::new();
+        let path = base.join("huge.md");
+        fs::write(&path, "# Huge\n\nA rather long paragraph.\n").unwrap();
+
+        let extract_with = |config: &Config| {
+            extract(
+                vec![path.clone()],
+                false,
+                false,
+                &DevComments::from(false),
+                false,
+                config,
+            )
+            .expect("Must be able to extract")
+        };
+        let chunks =
+            |docs: &Documentation| docs.iter().map(|(_, chunks)| chunks.len()).sum::<usize>();
+
+        let docs = extract_with(&Config::default());
+        assert_eq!(chunks(&docs), 1);
+        let docs = extract_with(&Config {
+            max_file_size: 16,
+            ..Config::default()
+        });
+        assert!(docs.is_empty());
+        let docs = extract_with(&Config {
+            max_chunk_chars: 16,
+            ..Config::default()
+        });
+        assert_eq!(chunks(&docs), 0);
+        let docs = extract_with(&Config {
+            max_file_size: 0,
+            max_chunk_chars: 0,
+            ..Config::default()
+        });
+        assert_eq!(chunks(&docs), 1);
+    }
+
+    #[test]
+    fn dev_comments_per_path() {
+        let dev_comments = DevComments::new(false, &["src/lowlevel/**".to_owned()]).unwrap();
+        assert!(dev_comments.applies_to(Path::new("src/lowlevel/ffi.rs")));
+        assert!(dev_comments.applies_to(&cwd().unwrap().join("src/lowlevel/sys/mod.rs")));
+        assert!(!dev_comments.applies_to(Path::new("src/lib.rs")));
+
+        assert!(DevComments::from(true).applies_to(Path::new("src/lib.rs")));
+        assert!(!DevComments::from(false).applies_to(Path::new("src/lib.rs")));
+        assert!(DevComments::new(false, &["[".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn path_filter() {
+        let path_filter =
+            PathFilter::new(&["src/**".to_owned()], &["src/generated/**".to_owned()]).unwrap();
+        assert!(path_filter.selects(Path::new("src/lib.rs")));
+        assert!(path_filter.selects(&cwd().unwrap().join("src/nested/mod.rs")));
+        assert!(!path_filter.selects(Path::new("src/generated/bindings.rs")));
+        assert!(!path_filter.selects(Path::new("README.md")));
+        assert!(PathFilter::default().selects(Path::new("README.md")));
+        assert!(PathFilter::new(&[], &["[".to_owned()]).is_err());
+
+        // the modules are still followed through the files which are skipped
+        let config = Config {
+            include_paths: vec!["**/nested/fragments/**".to_owned()],
+            exclude_paths: vec!["**/enumerate.rs".to_owned()],
+            ..Config::default()
+        };
+        let docs = extract(
+            vec![demo_dir().join("src/main.rs")],
+            true,
+            false,
+            &DevComments::from(false),
+            false,
+            &config,
+        )
+        .expect("Must be able to extract demo dir");
+        let paths = Vec::from_iter(docs.iter().map(|(origin, _)| origin.as_path().to_owned()));
+        assert_eq!(paths, vec![demo_dir().join(TEST_FILE_SIMPLE)]);
+    }
+
+    #[test]
+    fn plain_text_files() {
+        let base = TempDir::new();
+        fs::create_dir_all(base.join("dir")).unwrap();
+        for file in ["CHANGELOG", "notes.txt", "data.csv", "dir/LICENSE"] {
+            fs::write(base.join(file), "Some text.\n").unwrap();
+        }
+
+        let docs = extract(
+            vec![
+                base.join("CHANGELOG"),
+                base.join("notes.txt"),
+                base.join("data.csv"),
+                base.join("dir"),
+            ],
+            false,
+            false,
+            &DevComments::from(false),
+            false,
+            &Config::default(),
+        )
+        .expect("Must be able to extract plain text files");
+        let mut origins = Vec::from_iter(docs.iter().map(|(origin, _)| origin.clone()));
+        origins.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+        // only the ones passed explicitly
+        assert_eq!(
+            origins,
+            vec![
+                ContentOrigin::PlainTextFile(base.join("CHANGELOG")),
+                ContentOrigin::PlainTextFile(base.join("notes.txt")),
+            ]
+        );
+    }
+
+    #[test]
+    fn foreign_comments() {
+        let base = TempDir::new();
+        fs::write(base.join("ffi.h"), "/* Opaque handle. */\nstruct handle;\n").unwrap();
+        fs::write(base.join("build.py"), "x = 1 # The answer.\n").unwrap();
+        fs::write(base.join("other.c"), "// Not configured.\n").unwrap();
+
+        let config = Config::parse(
+            r##"
+[[comments]]
+extensions = ["h"]
+
+[[comments]]
+extensions = ["py"]
+line = ["#"]
+block = []
+"##,
+        )
+        .unwrap();
+        let docs = extract(
+            vec![base.to_path_buf()],
+            false,
+            false,
+            &DevComments::from(false),
+            false,
+            &config,
+        )
+        .expect("Must be able to extract comments");
+        let mut chunks = Vec::from_iter(docs.iter().map(|(origin, chunks)| {
+            (
+                origin.clone(),
+                Vec::from_iter(chunks.iter().map(|chunk| chunk.as_str().to_owned())),
+            )
+        }));
+        chunks.sort_by(|a, b| a.0.as_path().cmp(b.0.as_path()));
+        assert_eq!(
+            chunks,
+            vec![
+                (
+                    ContentOrigin::ForeignSourceFile(base.join("build.py")),
+                    vec!["The answer.".to_owned()]
+                ),
+                (
+                    ContentOrigin::ForeignSourceFile(base.join("ffi.h")),
+                    vec!["Opaque handle.".to_owned()]
+                ),
+            ]
+        );
+    }
 }
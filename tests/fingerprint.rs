@@ -0,0 +1,48 @@
+use cargo_spellcheck::{
+    CheckableChunk, CommentVariant, ContentOrigin, Detector, LineColumn, Span, Suggestion,
+};
+
+use std::path::Path;
+
+/// The only test of this binary, it changes the working directory of the
+/// whole process.
+#[test]
+fn fingerprint_independent_of_working_directory() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let chunk = CheckableChunk::from_str(
+        " Is it  dyrck again?",
+        indexmap::indexmap! { 0..20 => Span {
+            start: LineColumn { line: 3, column: 3 },
+            end: LineColumn { line: 3, column: 22 },
+        }},
+        CommentVariant::TripleSlash,
+    );
+    let suggestion = Suggestion {
+        detector: Detector::Hunspell,
+        agreeing: Vec::new(),
+        rule: None,
+        category: None,
+        origin: ContentOrigin::RustSourceFile(root.join("src").join("lib.rs")),
+        chunk: &chunk,
+        range: 8..13,
+        span: Span {
+            start: LineColumn {
+                line: 3,
+                column: 11,
+            },
+            end: LineColumn {
+                line: 3,
+                column: 15,
+            },
+        },
+        replacements: vec![],
+        description: None,
+    };
+
+    std::env::set_current_dir(root).unwrap();
+    let fingerprint = suggestion.fingerprint();
+    std::env::set_current_dir(root.join("src")).unwrap();
+    assert_eq!(suggestion.fingerprint(), fingerprint);
+    std::env::set_current_dir(std::env::temp_dir()).unwrap();
+    assert_eq!(suggestion.fingerprint(), fingerprint);
+}